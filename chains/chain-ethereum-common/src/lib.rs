@@ -0,0 +1,205 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Primitives of Ethereum-compatible Substrate chains (like Moonbeam and Moonriver).
+//!
+//! Accounts of such chains are 20-byte Ethereum addresses and transactions are signed using
+//! secp256k1 ECDSA over the Keccak-256 hash of the signed payload.
+
+#![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::Blake2_128Concat;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_core::{ecdsa, storage::StorageKey, H160};
+use sp_io::hashing::keccak_256;
+use sp_runtime::{
+	traits::{IdentifyAccount, Lazy, Verify},
+	RuntimeDebug,
+};
+
+/// Ethereum-compatible account identifier.
+///
+/// It is the last 20 bytes of the Keccak-256 hash of the uncompressed secp256k1 public key.
+#[derive(
+	Clone,
+	Copy,
+	Decode,
+	DecodeWithMemTracking,
+	Default,
+	Deserialize,
+	Encode,
+	Eq,
+	Hash,
+	MaxEncodedLen,
+	Ord,
+	PartialEq,
+	PartialOrd,
+	Serialize,
+	TypeInfo,
+)]
+pub struct AccountId20(pub [u8; 20]);
+
+impl AccountId20 {
+	/// Compute account identifier from the uncompressed public key (without `0x04` prefix).
+	pub fn from_uncompressed_public(public: &[u8; 64]) -> Self {
+		let hash = keccak_256(public);
+		let mut account = [0u8; 20];
+		account.copy_from_slice(&hash[12..]);
+		Self(account)
+	}
+}
+
+impl core::fmt::Display for AccountId20 {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "0x")?;
+		for byte in self.0 {
+			write!(f, "{byte:02x}")?;
+		}
+		Ok(())
+	}
+}
+
+impl core::fmt::Debug for AccountId20 {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		core::fmt::Display::fmt(self, f)
+	}
+}
+
+impl From<[u8; 20]> for AccountId20 {
+	fn from(bytes: [u8; 20]) -> Self {
+		Self(bytes)
+	}
+}
+
+impl From<AccountId20> for [u8; 20] {
+	fn from(account: AccountId20) -> Self {
+		account.0
+	}
+}
+
+impl From<H160> for AccountId20 {
+	fn from(address: H160) -> Self {
+		Self(address.0)
+	}
+}
+
+impl From<AccountId20> for H160 {
+	fn from(account: AccountId20) -> Self {
+		H160(account.0)
+	}
+}
+
+impl AsRef<[u8]> for AccountId20 {
+	fn as_ref(&self) -> &[u8] {
+		&self.0
+	}
+}
+
+impl From<ecdsa::Public> for AccountId20 {
+	fn from(public: ecdsa::Public) -> Self {
+		let public = k256::PublicKey::from_sec1_bytes(public.as_ref())
+			.expect("`ecdsa::Public` is always a valid compressed secp256k1 point; qed");
+		let uncompressed = public.to_encoded_point(false);
+		let mut raw = [0u8; 64];
+		raw.copy_from_slice(&uncompressed.as_bytes()[1..]);
+		Self::from_uncompressed_public(&raw)
+	}
+}
+
+/// Signer of Ethereum-compatible chain transactions.
+#[derive(
+	Clone, Decode, Encode, Eq, MaxEncodedLen, Ord, PartialEq, PartialOrd, RuntimeDebug, TypeInfo,
+)]
+pub struct EthereumSigner(AccountId20);
+
+impl IdentifyAccount for EthereumSigner {
+	type AccountId = AccountId20;
+
+	fn into_account(self) -> AccountId20 {
+		self.0
+	}
+}
+
+impl From<ecdsa::Public> for EthereumSigner {
+	fn from(public: ecdsa::Public) -> Self {
+		Self(public.into())
+	}
+}
+
+impl From<AccountId20> for EthereumSigner {
+	fn from(account: AccountId20) -> Self {
+		Self(account)
+	}
+}
+
+/// Signature of Ethereum-compatible chain transactions.
+///
+/// This is the recoverable secp256k1 signature of the Keccak-256 hash of the signed payload.
+#[derive(
+	Clone,
+	Decode,
+	DecodeWithMemTracking,
+	Encode,
+	Eq,
+	MaxEncodedLen,
+	PartialEq,
+	RuntimeDebug,
+	TypeInfo,
+)]
+pub struct EthereumSignature(pub ecdsa::Signature);
+
+impl From<ecdsa::Signature> for EthereumSignature {
+	fn from(signature: ecdsa::Signature) -> Self {
+		Self(signature)
+	}
+}
+
+impl Verify for EthereumSignature {
+	type Signer = EthereumSigner;
+
+	fn verify<L: Lazy<[u8]>>(&self, mut msg: L, signer: &AccountId20) -> bool {
+		let signature: &[u8] = self.0.as_ref();
+		let Ok(signature) = <&[u8; 65]>::try_from(signature) else { return false };
+		match sp_io::crypto::secp256k1_ecdsa_recover(signature, &keccak_256(msg.get())) {
+			Ok(public) => AccountId20::from_uncompressed_public(&public) == *signer,
+			Err(_) => false,
+		}
+	}
+}
+
+/// Provides storage key of the `frame_system::Account` map entry of Ethereum-compatible
+/// chains. The account balance is stored in the `data` field of this entry.
+pub struct AccountInfoStorageMapKeyProvider;
+
+impl AccountInfoStorageMapKeyProvider {
+	/// Name of the system pallet.
+	pub const PALLET_NAME: &'static str = "System";
+	/// Name of the account info map.
+	pub const MAP_NAME: &'static str = "Account";
+
+	/// Return storage key of the account info entry of the given account.
+	pub fn final_key(id: &AccountId20) -> StorageKey {
+		bp_runtime::storage_map_final_key::<Blake2_128Concat>(
+			Self::PALLET_NAME,
+			Self::MAP_NAME,
+			&id.encode(),
+		)
+	}
+}
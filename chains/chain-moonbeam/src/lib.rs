@@ -0,0 +1,100 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Module with configuration which reflects Moonbeam runtime setup (AccountId, Headers,
+//! Hashes...)
+
+#![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use bp_bridge_hub_cumulus::*;
+pub use bp_ethereum_common::{AccountId20, AccountInfoStorageMapKeyProvider, EthereumSignature};
+use bp_messages::*;
+use bp_runtime::{
+	decl_bridge_finality_runtime_apis, decl_bridge_messages_runtime_apis, Chain, ChainId, Parachain,
+};
+use frame_support::dispatch::DispatchClass;
+use sp_runtime::{generic, RuntimeDebug, StateVersion};
+
+/// Moonbeam parachain.
+#[derive(RuntimeDebug)]
+pub struct Moonbeam;
+
+impl Chain for Moonbeam {
+	const ID: ChainId = *b"mnbm";
+
+	type BlockNumber = BlockNumber;
+	type Hash = Hash;
+	type Hasher = Hasher;
+	type Header = Header;
+
+	type AccountId = AccountId;
+	type Balance = Balance;
+	type Nonce = Nonce;
+	type Signature = Signature;
+
+	const STATE_VERSION: StateVersion = StateVersion::V1;
+
+	fn max_extrinsic_size() -> u32 {
+		*BlockLength::get().max.get(DispatchClass::Normal)
+	}
+
+	fn max_extrinsic_weight() -> Weight {
+		BlockWeights::get()
+			.get(DispatchClass::Normal)
+			.max_extrinsic
+			.unwrap_or(Weight::MAX)
+	}
+}
+
+impl Parachain for Moonbeam {
+	const PARACHAIN_ID: u32 = MOONBEAM_PARACHAIN_ID;
+	const MAX_HEADER_SIZE: u32 = MAX_BRIDGE_HUB_HEADER_SIZE;
+}
+
+impl ChainWithMessages for Moonbeam {
+	const WITH_CHAIN_MESSAGES_PALLET_NAME: &'static str = WITH_MOONBEAM_MESSAGES_PALLET_NAME;
+	const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: MessageNonce =
+		MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
+	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce =
+		MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+}
+
+/// Moonbeam accounts are 20-byte Ethereum addresses.
+pub type AccountId = AccountId20;
+
+/// Moonbeam transactions are signed with Ethereum-compatible (secp256k1 + Keccak-256)
+/// signatures.
+pub type Signature = EthereumSignature;
+
+/// Public key of the chain account that may be used to verify signatures.
+pub type AccountSigner = bp_ethereum_common::EthereumSigner;
+
+/// The address format for describing accounts. Moonbeam is not using `MultiAddress`.
+pub type Address = AccountId;
+
+/// Unchecked extrinsic of the Moonbeam runtime.
+pub type UncheckedExtrinsic<Call, TransactionExt> =
+	generic::UncheckedExtrinsic<Address, Call, Signature, TransactionExt>;
+
+/// Identifier of Moonbeam in the Polkadot relay chain.
+pub const MOONBEAM_PARACHAIN_ID: u32 = 2004;
+
+/// Name of the With-Moonbeam messages pallet instance that is deployed at bridged chains.
+pub const WITH_MOONBEAM_MESSAGES_PALLET_NAME: &str = "BridgePolkadotMessages";
+
+decl_bridge_finality_runtime_apis!(moonbeam);
+decl_bridge_messages_runtime_apis!(moonbeam, HashedLaneId);
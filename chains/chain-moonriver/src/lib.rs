@@ -0,0 +1,100 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Module with configuration which reflects Moonriver runtime setup (AccountId, Headers,
+//! Hashes...)
+
+#![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use bp_bridge_hub_cumulus::*;
+pub use bp_ethereum_common::{AccountId20, AccountInfoStorageMapKeyProvider, EthereumSignature};
+use bp_messages::*;
+use bp_runtime::{
+	decl_bridge_finality_runtime_apis, decl_bridge_messages_runtime_apis, Chain, ChainId, Parachain,
+};
+use frame_support::dispatch::DispatchClass;
+use sp_runtime::{generic, RuntimeDebug, StateVersion};
+
+/// Moonriver parachain.
+#[derive(RuntimeDebug)]
+pub struct Moonriver;
+
+impl Chain for Moonriver {
+	const ID: ChainId = *b"mnrv";
+
+	type BlockNumber = BlockNumber;
+	type Hash = Hash;
+	type Hasher = Hasher;
+	type Header = Header;
+
+	type AccountId = AccountId;
+	type Balance = Balance;
+	type Nonce = Nonce;
+	type Signature = Signature;
+
+	const STATE_VERSION: StateVersion = StateVersion::V1;
+
+	fn max_extrinsic_size() -> u32 {
+		*BlockLength::get().max.get(DispatchClass::Normal)
+	}
+
+	fn max_extrinsic_weight() -> Weight {
+		BlockWeights::get()
+			.get(DispatchClass::Normal)
+			.max_extrinsic
+			.unwrap_or(Weight::MAX)
+	}
+}
+
+impl Parachain for Moonriver {
+	const PARACHAIN_ID: u32 = MOONRIVER_PARACHAIN_ID;
+	const MAX_HEADER_SIZE: u32 = MAX_BRIDGE_HUB_HEADER_SIZE;
+}
+
+impl ChainWithMessages for Moonriver {
+	const WITH_CHAIN_MESSAGES_PALLET_NAME: &'static str = WITH_MOONRIVER_MESSAGES_PALLET_NAME;
+	const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: MessageNonce =
+		MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
+	const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce =
+		MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+}
+
+/// Moonriver accounts are 20-byte Ethereum addresses.
+pub type AccountId = AccountId20;
+
+/// Moonriver transactions are signed with Ethereum-compatible (secp256k1 + Keccak-256)
+/// signatures.
+pub type Signature = EthereumSignature;
+
+/// Public key of the chain account that may be used to verify signatures.
+pub type AccountSigner = bp_ethereum_common::EthereumSigner;
+
+/// The address format for describing accounts. Moonriver is not using `MultiAddress`.
+pub type Address = AccountId;
+
+/// Unchecked extrinsic of the Moonriver runtime.
+pub type UncheckedExtrinsic<Call, TransactionExt> =
+	generic::UncheckedExtrinsic<Address, Call, Signature, TransactionExt>;
+
+/// Identifier of Moonriver in the Kusama relay chain.
+pub const MOONRIVER_PARACHAIN_ID: u32 = 2023;
+
+/// Name of the With-Moonriver messages pallet instance that is deployed at bridged chains.
+pub const WITH_MOONRIVER_MESSAGES_PALLET_NAME: &str = "BridgeKusamaMessages";
+
+decl_bridge_finality_runtime_apis!(moonriver);
+decl_bridge_messages_runtime_apis!(moonriver, HashedLaneId);
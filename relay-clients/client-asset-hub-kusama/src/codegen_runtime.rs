@@ -0,0 +1,568 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Autogenerated runtime API
+//! THIS FILE WAS AUTOGENERATED USING parity-bridges-common::runtime-codegen
+//! EXECUTED COMMAND: runtime-codegen --from-node-url wss://kusama-asset-hub-rpc.polkadot.io/
+//! --allow BridgePolkadotMessages
+
+#[allow(dead_code, unused_imports, non_camel_case_types, unreachable_patterns)]
+#[allow(clippy::all)]
+#[allow(rustdoc::broken_intra_doc_links)]
+pub mod api {
+	#[allow(unused_imports)]
+	mod root_mod {
+		pub use super::*;
+	}
+	pub mod runtime_types {
+		use super::runtime_types;
+		pub mod asset_hub_kusama_runtime {
+			use super::runtime_types;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct BridgeRejectObsoleteHeadersAndMessages;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct Runtime;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum RuntimeCall {
+				#[codec(index = 63)]
+				BridgePolkadotMessages(runtime_types::pallet_bridge_messages::pallet::Call),
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum RuntimeError {
+				#[codec(index = 63)]
+				BridgePolkadotMessages(runtime_types::pallet_bridge_messages::pallet::Error),
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum RuntimeEvent {
+				#[codec(index = 63)]
+				BridgePolkadotMessages(runtime_types::pallet_bridge_messages::pallet::Event),
+			}
+		}
+		pub mod bp_header_chain {
+			use super::runtime_types;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum HeaderChainError {
+				#[codec(index = 0)]
+				UnknownHeader,
+				#[codec(index = 1)]
+				StorageProof(runtime_types::bp_runtime::storage_proof::StorageProofError),
+			}
+		}
+		pub mod bp_messages {
+			use super::runtime_types;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct DeliveredMessages {
+				pub begin: ::core::primitive::u64,
+				pub end: ::core::primitive::u64,
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum MessagesOperatingMode {
+				#[codec(index = 0)]
+				Basic(runtime_types::bp_runtime::BasicOperatingMode),
+				#[codec(index = 1)]
+				RejectingOutboundMessages,
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct ReceivedMessages<_0, _1> {
+				pub lane: _1,
+				pub receive_results: ::subxt::ext::subxt_core::alloc::vec::Vec<(
+					::core::primitive::u64,
+					runtime_types::bp_messages::ReceptionResult<_0>,
+				)>,
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum ReceptionResult<_0> {
+				#[codec(index = 0)]
+				Dispatched(runtime_types::bp_runtime::messages::MessageDispatchResult<_0>),
+				#[codec(index = 1)]
+				InvalidNonce,
+				#[codec(index = 2)]
+				TooManyUnrewardedRelayers,
+				#[codec(index = 3)]
+				TooManyUnconfirmedMessages,
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum VerificationError {
+				#[codec(index = 0)]
+				EmptyMessageProof,
+				#[codec(index = 1)]
+				HeaderChain(runtime_types::bp_header_chain::HeaderChainError),
+				#[codec(index = 2)]
+				InboundLaneStorage(runtime_types::bp_runtime::storage_proof::StorageProofError),
+				#[codec(index = 3)]
+				InvalidMessageWeight,
+				#[codec(index = 4)]
+				MessagesCountMismatch,
+				#[codec(index = 5)]
+				MessageStorage(runtime_types::bp_runtime::storage_proof::StorageProofError),
+				#[codec(index = 6)]
+				MessageTooLarge,
+				#[codec(index = 7)]
+				OutboundLaneStorage(runtime_types::bp_runtime::storage_proof::StorageProofError),
+				#[codec(index = 8)]
+				StorageProof(runtime_types::bp_runtime::storage_proof::StorageProofError),
+				#[codec(index = 9)]
+				Other,
+			}
+		}
+		pub mod bp_runtime {
+			use super::runtime_types;
+			pub mod messages {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub struct MessageDispatchResult<_0> {
+					pub unspent_weight: ::sp_weights::Weight,
+					pub dispatch_level_result: _0,
+				}
+			}
+			pub mod storage_proof {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum StorageProofError {
+					#[codec(index = 0)]
+					UnableToGenerateTrieProof,
+					#[codec(index = 1)]
+					InvalidProof,
+					#[codec(index = 2)]
+					UnsortedEntries,
+					#[codec(index = 3)]
+					UnavailableKey,
+					#[codec(index = 4)]
+					EmptyVal,
+					#[codec(index = 5)]
+					DecodeError,
+					#[codec(index = 6)]
+					UnusedKey,
+					#[codec(index = 7)]
+					StorageRootMismatch,
+					#[codec(index = 8)]
+					StorageValueUnavailable,
+					#[codec(index = 9)]
+					DuplicateNodes,
+				}
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum BasicOperatingMode {
+				#[codec(index = 0)]
+				Normal,
+				#[codec(index = 1)]
+				Halted,
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum OwnedBridgeModuleError {
+				#[codec(index = 0)]
+				Halted,
+			}
+		}
+		pub mod cumulus_pallet_weight_reclaim {
+			use super::runtime_types;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct StorageWeightReclaim<_1>(pub _1);
+		}
+		pub mod frame_metadata_hash_extension {
+			use super::runtime_types;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct CheckMetadataHash {
+				pub mode: runtime_types::frame_metadata_hash_extension::Mode,
+			}
+			#[derive(
+				::codec::Decode,
+				::codec::DecodeWithMemTracking,
+				::codec::Encode,
+				Clone,
+				Debug,
+				Eq,
+				PartialEq,
+				scale_info::TypeInfo,
+			)]
+			pub enum Mode {
+				#[codec(index = 0)]
+				Disabled,
+				#[codec(index = 1)]
+				Enabled,
+			}
+		}
+		pub mod frame_system {
+			use super::runtime_types;
+			pub mod extensions {
+				use super::runtime_types;
+				pub mod check_genesis {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub struct CheckGenesis;
+				}
+				pub mod check_mortality {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub struct CheckMortality(pub ::sp_runtime::generic::Era);
+				}
+				pub mod check_non_zero_sender {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub struct CheckNonZeroSender;
+				}
+				pub mod check_nonce {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub struct CheckNonce(#[codec(compact)] pub ::core::primitive::u32);
+				}
+				pub mod check_spec_version {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub struct CheckSpecVersion;
+				}
+				pub mod check_tx_version {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub struct CheckTxVersion;
+				}
+				pub mod check_weight {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub struct CheckWeight;
+				}
+			}
+		}
+		pub mod pallet_asset_conversion_tx_payment {
+			use super::runtime_types;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct ChargeAssetTxPayment {
+				#[codec(compact)]
+				pub tip: ::core::primitive::u128,
+				pub asset_id:
+					::core::option::Option<runtime_types::staging_xcm::v5::location::Location>,
+			}
+		}
+		pub mod pallet_bridge_messages {
+			use super::runtime_types;
+			pub mod lanes_manager {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum LanesManagerError {
+					#[codec(index = 0)]
+					InboundLaneAlreadyExists,
+					#[codec(index = 1)]
+					OutboundLaneAlreadyExists,
+					#[codec(index = 2)]
+					UnknownInboundLane,
+					#[codec(index = 3)]
+					UnknownOutboundLane,
+					#[codec(index = 4)]
+					ClosedInboundLane,
+					#[codec(index = 5)]
+					ClosedOutboundLane,
+					#[codec(index = 6)]
+					LaneDispatcherInactive,
+				}
+			}
+			pub mod outbound_lane {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum ReceptionConfirmationError {
+					#[codec(index = 0)]
+					FailedToConfirmFutureMessages,
+					#[codec(index = 1)]
+					EmptyUnrewardedRelayerEntry,
+					#[codec(index = 2)]
+					NonConsecutiveUnrewardedRelayerEntries,
+					#[codec(index = 3)]
+					TryingToConfirmMoreMessagesThanExpected,
+				}
+			}
+			pub mod pallet {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum Call {
+					#[codec(index = 0)]
+					set_owner { new_owner: ::core::option::Option<::sp_core::crypto::AccountId32> },
+					#[codec(index = 1)]
+					set_operating_mode {
+						operating_mode: runtime_types::bp_messages::MessagesOperatingMode,
+					},
+					#[codec(index = 2)]
+					receive_messages_proof {
+						relayer_id_at_bridged_chain: ::sp_core::crypto::AccountId32,
+						proof: ::subxt::ext::subxt_core::alloc::boxed::Box<
+							::bp_messages::target_chain::FromBridgedChainMessagesProof<
+								::subxt::ext::subxt_core::utils::H256,
+								::bp_messages::HashedLaneId,
+							>,
+						>,
+						messages_count: ::core::primitive::u32,
+						dispatch_weight: ::sp_weights::Weight,
+					},
+					#[codec(index = 3)]
+					receive_messages_delivery_proof {
+						proof: ::bp_messages::source_chain::FromBridgedChainMessagesDeliveryProof<
+							::subxt::ext::subxt_core::utils::H256,
+							::bp_messages::HashedLaneId,
+						>,
+						relayers_state: ::bp_messages::UnrewardedRelayersState,
+					},
+				}
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum Error {
+					#[codec(index = 0)]
+                    NotOperatingNormally,
+                    #[codec(index = 1)]
+                    LanesManager(
+                        runtime_types::pallet_bridge_messages::lanes_manager::LanesManagerError,
+                    ),
+                    #[codec(index = 2)]
+                    MessageRejectedByPallet(
+                        runtime_types::bp_messages::VerificationError,
+                    ),
+                    #[codec(index = 3)]
+                    TooManyMessagesInTheProof,
+                    #[codec(index = 4)]
+                    InvalidMessagesProof,
+                    #[codec(index = 5)]
+                    InvalidMessagesDeliveryProof,
+                    #[codec(index = 6)]
+                    InvalidUnrewardedRelayersState,
+                    #[codec(index = 7)]
+                    InsufficientDispatchWeight,
+                    #[codec(index = 8)]
+                    ReceptionConfirmation(
+                        runtime_types::pallet_bridge_messages::outbound_lane::ReceptionConfirmationError,
+                    ),
+                    #[codec(index = 9)]
+                    BridgeModule(runtime_types::bp_runtime::OwnedBridgeModuleError),
+                }
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum Event {
+					#[codec(index = 0)]
+                    MessageAccepted {
+                        lane_id: ::bp_messages::HashedLaneId,
+                        nonce: ::core::primitive::u64,
+                    },
+                    #[codec(index = 1)]
+                    MessagesReceived(
+                        runtime_types::bp_messages::ReceivedMessages<
+                            runtime_types::pallet_xcm_bridge::dispatcher::XcmBlobMessageDispatchResult,
+                            ::bp_messages::HashedLaneId,
+                        >,
+                    ),
+                    #[codec(index = 2)]
+                    MessagesDelivered {
+                        lane_id: ::bp_messages::HashedLaneId,
+                        messages: runtime_types::bp_messages::DeliveredMessages,
+                    },
+                }
+			}
+		}
+		pub mod pallet_bridge_relayers {
+			use super::runtime_types;
+			pub mod extension {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub struct BridgeRelayersTransactionExtension;
+			}
+		}
+		pub mod pallet_xcm_bridge {
+			use super::runtime_types;
+			pub mod dispatcher {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum XcmBlobMessageDispatchResult {
+					#[codec(index = 0)]
+					InvalidPayload,
+					#[codec(index = 1)]
+					Dispatched,
+					#[codec(index = 2)]
+					NotDispatched,
+				}
+			}
+		}
+		pub mod staging_xcm {
+			use super::runtime_types;
+			pub mod v5 {
+				use super::runtime_types;
+				pub mod junction {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub enum Junction {
+						#[codec(index = 0)]
+						Parachain(#[codec(compact)] ::core::primitive::u32),
+						#[codec(index = 1)]
+						AccountId32 {
+							network: ::core::option::Option<
+								runtime_types::staging_xcm::v5::junction::NetworkId,
+							>,
+							id: [::core::primitive::u8; 32usize],
+						},
+						#[codec(index = 2)]
+						AccountIndex64 {
+							network: ::core::option::Option<
+								runtime_types::staging_xcm::v5::junction::NetworkId,
+							>,
+							#[codec(compact)]
+							index: ::core::primitive::u64,
+						},
+						#[codec(index = 3)]
+						AccountKey20 {
+							network: ::core::option::Option<
+								runtime_types::staging_xcm::v5::junction::NetworkId,
+							>,
+							key: [::core::primitive::u8; 20usize],
+						},
+						#[codec(index = 4)]
+						PalletInstance(::core::primitive::u8),
+						#[codec(index = 5)]
+						GeneralIndex(#[codec(compact)] ::core::primitive::u128),
+						#[codec(index = 6)]
+						GeneralKey {
+							length: ::core::primitive::u8,
+							data: [::core::primitive::u8; 32usize],
+						},
+						#[codec(index = 7)]
+						OnlyChild,
+						#[codec(index = 8)]
+						Plurality {
+							id: runtime_types::xcm::v3::junction::BodyId,
+							part: runtime_types::xcm::v3::junction::BodyPart,
+						},
+						#[codec(index = 9)]
+						GlobalConsensus(runtime_types::staging_xcm::v5::junction::NetworkId),
+					}
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub enum NetworkId {
+						#[codec(index = 0)]
+						ByGenesis([::core::primitive::u8; 32usize]),
+						#[codec(index = 1)]
+						ByFork {
+							block_number: ::core::primitive::u64,
+							block_hash: [::core::primitive::u8; 32usize],
+						},
+						#[codec(index = 2)]
+						Polkadot,
+						#[codec(index = 3)]
+						Kusama,
+						#[codec(index = 7)]
+						Ethereum {
+							#[codec(compact)]
+							chain_id: ::core::primitive::u64,
+						},
+						#[codec(index = 8)]
+						BitcoinCore,
+						#[codec(index = 9)]
+						BitcoinCash,
+						#[codec(index = 10)]
+						PolkadotBulletin,
+					}
+				}
+				pub mod junctions {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub enum Junctions {
+						#[codec(index = 0)]
+						Here,
+						#[codec(index = 1)]
+						X1([runtime_types::staging_xcm::v5::junction::Junction; 1usize]),
+						#[codec(index = 2)]
+						X2([runtime_types::staging_xcm::v5::junction::Junction; 2usize]),
+						#[codec(index = 3)]
+						X3([runtime_types::staging_xcm::v5::junction::Junction; 3usize]),
+						#[codec(index = 4)]
+						X4([runtime_types::staging_xcm::v5::junction::Junction; 4usize]),
+						#[codec(index = 5)]
+						X5([runtime_types::staging_xcm::v5::junction::Junction; 5usize]),
+						#[codec(index = 6)]
+						X6([runtime_types::staging_xcm::v5::junction::Junction; 6usize]),
+						#[codec(index = 7)]
+						X7([runtime_types::staging_xcm::v5::junction::Junction; 7usize]),
+						#[codec(index = 8)]
+						X8([runtime_types::staging_xcm::v5::junction::Junction; 8usize]),
+					}
+				}
+				pub mod location {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub struct Location {
+						pub parents: ::core::primitive::u8,
+						pub interior: runtime_types::staging_xcm::v5::junctions::Junctions,
+					}
+				}
+			}
+		}
+		pub mod xcm {
+			use super::runtime_types;
+			pub mod v3 {
+				use super::runtime_types;
+				pub mod junction {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub enum BodyId {
+						#[codec(index = 0)]
+						Unit,
+						#[codec(index = 1)]
+						Moniker([::core::primitive::u8; 4usize]),
+						#[codec(index = 2)]
+						Index(#[codec(compact)] ::core::primitive::u32),
+						#[codec(index = 3)]
+						Executive,
+						#[codec(index = 4)]
+						Technical,
+						#[codec(index = 5)]
+						Legislative,
+						#[codec(index = 6)]
+						Judicial,
+						#[codec(index = 7)]
+						Defense,
+						#[codec(index = 8)]
+						Administration,
+						#[codec(index = 9)]
+						Treasury,
+					}
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub enum BodyPart {
+						#[codec(index = 0)]
+						Voice,
+						#[codec(index = 1)]
+						Members {
+							#[codec(compact)]
+							count: ::core::primitive::u32,
+						},
+						#[codec(index = 2)]
+						Fraction {
+							#[codec(compact)]
+							nom: ::core::primitive::u32,
+							#[codec(compact)]
+							denom: ::core::primitive::u32,
+						},
+						#[codec(index = 3)]
+						AtLeastProportion {
+							#[codec(compact)]
+							nom: ::core::primitive::u32,
+							#[codec(compact)]
+							denom: ::core::primitive::u32,
+						},
+						#[codec(index = 4)]
+						MoreThanProportion {
+							#[codec(compact)]
+							nom: ::core::primitive::u32,
+							#[codec(compact)]
+							denom: ::core::primitive::u32,
+						},
+					}
+				}
+			}
+		}
+	}
+}
+/// `spec_version` of the runtime and the RFC-78 hash of its metadata, this code has been
+/// generated from. It is used by the `CheckMetadataHash` transaction extension.
+pub const METADATA_HASH: Option<(u32, [u8; 32])> = None;
@@ -0,0 +1,190 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types used to connect to the AssetHub-Polkadot-Substrate parachain.
+
+pub mod codegen_runtime;
+pub mod runtime_apis;
+
+use bp_asset_hub_polkadot::AVERAGE_BLOCK_INTERVAL;
+use bp_messages::{InboundMessageDetails, MessageNonce, MessagePayload, OutboundMessageDetails};
+use bp_polkadot_core::{
+	AccountId, SuffixedCommonTransactionExtension, SuffixedCommonTransactionExtensionExt,
+};
+use relay_common_client::{
+	ChainWithMetadataHash, ChainWithTransactionExtension, ExplicitParams, SupportedRuntime,
+	TransactionExtensionParams,
+};
+use relay_substrate_client::{
+	Chain, ChainWithBalances, ChainWithMessages, ChainWithRuntimeVersion, ChainWithTransactions,
+	Error as SubstrateError, SignParam, SimpleRuntimeVersion, UnderlyingChainProvider,
+	UnsignedTransaction,
+};
+use sp_core::storage::StorageKey;
+use sp_runtime::{MultiAddress, MultiSignature, OpaqueExtrinsic};
+use std::time::Duration;
+
+pub use codegen_runtime::api::runtime_types;
+use runtime_types::frame_metadata_hash_extension::Mode;
+
+use bp_runtime::extensions::{
+	BridgeRejectObsoleteHeadersAndMessages, GenericTransactionExtensionSchema,
+	RefundBridgedParachainMessagesSchema,
+};
+
+pub type CheckMetadataHash = GenericTransactionExtensionSchema<Mode, Option<[u8; 32]>>;
+
+pub type TransactionExtension = SuffixedCommonTransactionExtension<(
+	CheckMetadataHash,
+	BridgeRejectObsoleteHeadersAndMessages,
+	RefundBridgedParachainMessagesSchema,
+)>;
+
+pub type RuntimeCall = runtime_types::asset_hub_polkadot_runtime::RuntimeCall;
+pub type BridgeMessagesCall = runtime_types::pallet_bridge_messages::pallet::Call;
+
+/// AssetHubPolkadot chain definition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssetHubPolkadot;
+
+impl UnderlyingChainProvider for AssetHubPolkadot {
+	type Chain = bp_asset_hub_polkadot::AssetHubPolkadot;
+}
+
+impl Chain for AssetHubPolkadot {
+	const NAME: &'static str = "AssetHubPolkadot";
+	const BEST_FINALIZED_HEADER_ID_METHOD: &'static str =
+		runtime_apis::asset_hub_polkadot_finality_api::best_finalized::NAME;
+	const FREE_HEADERS_INTERVAL_METHOD: &'static str =
+		runtime_apis::asset_hub_polkadot_finality_api::free_headers_interval::NAME;
+	const AVERAGE_BLOCK_INTERVAL: Duration = AVERAGE_BLOCK_INTERVAL;
+
+	type SignedBlock = bp_asset_hub_polkadot::SignedBlock;
+	type Call = RuntimeCall;
+}
+
+impl ChainWithBalances for AssetHubPolkadot {
+	fn account_info_storage_key(account_id: &Self::AccountId) -> StorageKey {
+		bp_asset_hub_polkadot::AccountInfoStorageMapKeyProvider::final_key(account_id)
+	}
+}
+
+impl ChainWithTransactions for AssetHubPolkadot {
+	type AccountKeyPair = sp_core::sr25519::Pair;
+	type SignedTransaction = OpaqueExtrinsic;
+
+	fn sign_transaction(
+		param: SignParam<Self>,
+		unsigned: UnsignedTransaction<Self>,
+	) -> Result<Self::SignedTransaction, SubstrateError> {
+		relay_common_client::sign_transaction(SUPPORTED_RUNTIMES, param, unsigned)
+	}
+}
+
+impl ChainWithMetadataHash for AssetHubPolkadot {
+	const METADATA_HASH: Option<(u32, [u8; 32])> = codegen_runtime::METADATA_HASH;
+}
+
+const SUPPORTED_RUNTIMES: &[SupportedRuntime<AssetHubPolkadot>] = &[SupportedRuntime {
+	version: RUNTIME_VERSION,
+	sign: relay_common_client::sign_opaque_transaction,
+}];
+
+impl ChainWithTransactionExtension for AssetHubPolkadot {
+	type Address = MultiAddress<AccountId, ()>;
+	type Signature = MultiSignature;
+	type TransactionExtension = TransactionExtension;
+
+	fn transaction_extension(params: TransactionExtensionParams<Self>) -> TransactionExtension {
+		let metadata_hash_mode =
+			if params.metadata_hash.is_some() { Mode::Enabled } else { Mode::Disabled };
+		TransactionExtension::from_params(
+			params.spec_version,
+			params.transaction_version,
+			params.era,
+			params.genesis_hash,
+			params.nonce,
+			params.tip,
+			((metadata_hash_mode, (), ()), (params.metadata_hash, (), ())),
+		)
+	}
+
+	fn explicit_params(
+		extension: &TransactionExtension,
+	) -> Result<ExplicitParams<Self>, SubstrateError> {
+		let (
+			_,
+			_,
+			_,
+			_,
+			era,
+			nonce,
+			_,
+			tip,
+			(metadata_hash_mode, bridge_reject_obsolete, bridge_refund),
+		) = extension.payload.clone();
+		Ok(ExplicitParams {
+			era,
+			nonce: nonce.0,
+			tip: tip.0,
+			metadata_hash_check: Some(metadata_hash_mode == Mode::Enabled),
+			bridge_extensions: vec![
+				("BridgeRejectObsoleteHeadersAndMessages", format!("{bridge_reject_obsolete:?}")),
+				("BridgeRelayersTransactionExtension", format!("{bridge_refund:?}")),
+			],
+		})
+	}
+
+	fn signer_account(address: MultiAddress<AccountId, ()>) -> Option<AccountId> {
+		relay_common_client::multi_address_account(address)
+	}
+}
+
+impl ChainWithMessages for AssetHubPolkadot {
+	const TO_CHAIN_MESSAGE_DETAILS_METHOD: &'static str =
+		runtime_apis::to_asset_hub_polkadot_outbound_lane_api::message_details::NAME;
+	const FROM_CHAIN_MESSAGE_DETAILS_METHOD: &'static str =
+		runtime_apis::from_asset_hub_polkadot_inbound_lane_api::message_details::NAME;
+}
+
+// The relay calls bridge runtime APIs of the chain with arguments and decodes their outputs, using
+// types below. Make sure they match types of APIs, generated from the bridged runtime metadata.
+const _: fn(
+	runtime_apis::asset_hub_polkadot_finality_api::best_finalized::Output,
+) -> Option<bp_runtime::HeaderIdOf<bp_asset_hub_polkadot::AssetHubPolkadot>> = |output| output;
+const _: fn(
+	runtime_apis::asset_hub_polkadot_finality_api::free_headers_interval::Output,
+) -> Option<bp_runtime::BlockNumberOf<bp_asset_hub_polkadot::AssetHubPolkadot>> = |output| output;
+const _: fn(
+	(bp_messages::HashedLaneId, MessageNonce, MessageNonce),
+) -> runtime_apis::to_asset_hub_polkadot_outbound_lane_api::message_details::Input = |input| input;
+const _: fn(
+	runtime_apis::to_asset_hub_polkadot_outbound_lane_api::message_details::Output,
+) -> Vec<OutboundMessageDetails> = |output| output;
+const _: fn(
+	(bp_messages::HashedLaneId, Vec<(MessagePayload, OutboundMessageDetails)>),
+) -> runtime_apis::from_asset_hub_polkadot_inbound_lane_api::message_details::Input = |input| input;
+const _: fn(
+	runtime_apis::from_asset_hub_polkadot_inbound_lane_api::message_details::Output,
+) -> Vec<InboundMessageDetails> = |output| output;
+
+impl ChainWithRuntimeVersion for AssetHubPolkadot {
+	const RUNTIME_VERSION: Option<SimpleRuntimeVersion> = Some(RUNTIME_VERSION);
+}
+
+/// Version of the runtime that the `codegen_runtime` has been generated for.
+const RUNTIME_VERSION: SimpleRuntimeVersion =
+	SimpleRuntimeVersion { spec_version: 2_000_003, transaction_version: 15 };
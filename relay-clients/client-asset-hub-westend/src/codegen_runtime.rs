@@ -8548,3 +8548,6 @@ pub mod api {
 		}
 	}
 }
+/// `spec_version` of the runtime and the RFC-78 hash of its metadata, this code has been
+/// generated from. It is used by the `CheckMetadataHash` transaction extension.
+pub const METADATA_HASH: Option<(u32, [u8; 32])> = None;
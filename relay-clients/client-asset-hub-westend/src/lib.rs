@@ -17,20 +17,24 @@
 //! Types used to connect to the AssetHub-Westend-Substrate parachain.
 
 pub mod codegen_runtime;
+pub mod runtime_apis;
 
 use bp_asset_hub_westend::AVERAGE_BLOCK_INTERVAL;
-use bp_polkadot_core::{SuffixedCommonTransactionExtension, SuffixedCommonTransactionExtensionExt};
-use codec::Encode;
+use bp_messages::{InboundMessageDetails, MessageNonce, MessagePayload, OutboundMessageDetails};
+use bp_polkadot_core::{
+	AccountId, SuffixedCommonTransactionExtension, SuffixedCommonTransactionExtensionExt,
+};
+use relay_common_client::{
+	ChainWithMetadataHash, ChainWithTransactionExtension, ExplicitParams, SupportedRuntime,
+	TransactionExtensionParams,
+};
 use relay_substrate_client::{
 	Chain, ChainWithBalances, ChainWithMessages, ChainWithRuntimeVersion, ChainWithTransactions,
 	Error as SubstrateError, SignParam, SimpleRuntimeVersion, UnderlyingChainProvider,
 	UnsignedTransaction,
 };
-use sp_core::{storage::StorageKey, Pair};
-use sp_runtime::{
-	generic::SignedPayload,
-	traits::{FakeDispatchable, IdentifyAccount},
-};
+use sp_core::storage::StorageKey;
+use sp_runtime::{MultiAddress, MultiSignature, OpaqueExtrinsic};
 use std::time::Duration;
 
 pub use codegen_runtime::api::runtime_types;
@@ -51,8 +55,6 @@ pub type TransactionExtension = SuffixedCommonTransactionExtension<(
 
 pub type RuntimeCall = runtime_types::asset_hub_westend_runtime::RuntimeCall;
 pub type BridgeMessagesCall = runtime_types::pallet_bridge_messages::pallet::Call;
-type UncheckedExtrinsic =
-	bp_asset_hub_westend::UncheckedExtrinsic<RuntimeCall, TransactionExtension>;
 
 /// AssetHubWestend chain definition
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -65,9 +67,9 @@ impl UnderlyingChainProvider for AssetHubWestend {
 impl Chain for AssetHubWestend {
 	const NAME: &'static str = "AssetHubWestend";
 	const BEST_FINALIZED_HEADER_ID_METHOD: &'static str =
-		bp_asset_hub_westend::BEST_FINALIZED_ASSET_HUB_WESTEND_HEADER_METHOD;
+		runtime_apis::asset_hub_westend_finality_api::best_finalized::NAME;
 	const FREE_HEADERS_INTERVAL_METHOD: &'static str =
-		bp_asset_hub_westend::FREE_HEADERS_INTERVAL_FOR_ASSET_HUB_WESTEND_METHOD;
+		runtime_apis::asset_hub_westend_finality_api::free_headers_interval::NAME;
 	const AVERAGE_BLOCK_INTERVAL: Duration = AVERAGE_BLOCK_INTERVAL;
 
 	type SignedBlock = bp_asset_hub_westend::SignedBlock;
@@ -82,46 +84,107 @@ impl ChainWithBalances for AssetHubWestend {
 
 impl ChainWithTransactions for AssetHubWestend {
 	type AccountKeyPair = sp_core::sr25519::Pair;
-	type SignedTransaction = UncheckedExtrinsic;
+	type SignedTransaction = OpaqueExtrinsic;
 
 	fn sign_transaction(
 		param: SignParam<Self>,
 		unsigned: UnsignedTransaction<Self>,
 	) -> Result<Self::SignedTransaction, SubstrateError> {
-		let raw_payload = SignedPayload::new(
-			FakeDispatchable::from(unsigned.call),
-			TransactionExtension::from_params(
-				param.spec_version,
-				param.transaction_version,
-				unsigned.era,
-				param.genesis_hash,
-				unsigned.nonce,
-				unsigned.tip,
-				((Mode::Disabled, (), ()), (None, (), ())),
-			),
-		)?;
-
-		let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload));
-		let signer: sp_runtime::MultiSigner = param.signer.public().into();
-		let (call, extra, _) = raw_payload.deconstruct();
-
-		Ok(UncheckedExtrinsic::new_signed(
-			call.deconstruct(),
-			signer.into_account().into(),
-			signature.into(),
-			extra,
-		))
+		relay_common_client::sign_transaction(SUPPORTED_RUNTIMES, param, unsigned)
+	}
+}
+
+impl ChainWithMetadataHash for AssetHubWestend {
+	const METADATA_HASH: Option<(u32, [u8; 32])> = codegen_runtime::METADATA_HASH;
+}
+
+const SUPPORTED_RUNTIMES: &[SupportedRuntime<AssetHubWestend>] = &[SupportedRuntime {
+	version: RUNTIME_VERSION,
+	sign: relay_common_client::sign_opaque_transaction,
+}];
+
+impl ChainWithTransactionExtension for AssetHubWestend {
+	type Address = MultiAddress<AccountId, ()>;
+	type Signature = MultiSignature;
+	type TransactionExtension = TransactionExtension;
+
+	fn transaction_extension(params: TransactionExtensionParams<Self>) -> TransactionExtension {
+		let metadata_hash_mode =
+			if params.metadata_hash.is_some() { Mode::Enabled } else { Mode::Disabled };
+		TransactionExtension::from_params(
+			params.spec_version,
+			params.transaction_version,
+			params.era,
+			params.genesis_hash,
+			params.nonce,
+			params.tip,
+			((metadata_hash_mode, (), ()), (params.metadata_hash, (), ())),
+		)
+	}
+
+	fn explicit_params(
+		extension: &TransactionExtension,
+	) -> Result<ExplicitParams<Self>, SubstrateError> {
+		let (
+			_,
+			_,
+			_,
+			_,
+			era,
+			nonce,
+			_,
+			tip,
+			(metadata_hash_mode, bridge_reject_obsolete, bridge_refund),
+		) = extension.payload.clone();
+		Ok(ExplicitParams {
+			era,
+			nonce: nonce.0,
+			tip: tip.0,
+			metadata_hash_check: Some(metadata_hash_mode == Mode::Enabled),
+			bridge_extensions: vec![
+				("BridgeRejectObsoleteHeadersAndMessages", format!("{bridge_reject_obsolete:?}")),
+				("BridgeRelayersTransactionExtension", format!("{bridge_refund:?}")),
+			],
+		})
+	}
+
+	fn signer_account(address: MultiAddress<AccountId, ()>) -> Option<AccountId> {
+		relay_common_client::multi_address_account(address)
 	}
 }
 
 impl ChainWithMessages for AssetHubWestend {
 	const TO_CHAIN_MESSAGE_DETAILS_METHOD: &'static str =
-		bp_asset_hub_westend::TO_ASSET_HUB_WESTEND_MESSAGE_DETAILS_METHOD;
+		runtime_apis::to_asset_hub_westend_outbound_lane_api::message_details::NAME;
 	const FROM_CHAIN_MESSAGE_DETAILS_METHOD: &'static str =
-		bp_asset_hub_westend::FROM_ASSET_HUB_WESTEND_MESSAGE_DETAILS_METHOD;
+		runtime_apis::from_asset_hub_westend_inbound_lane_api::message_details::NAME;
 }
 
+// The relay calls bridge runtime APIs of the chain with arguments and decodes their outputs, using
+// types below. Make sure they match types of APIs, generated from the bridged runtime metadata.
+const _: fn(
+	runtime_apis::asset_hub_westend_finality_api::best_finalized::Output,
+) -> Option<bp_runtime::HeaderIdOf<bp_asset_hub_westend::AssetHubWestend>> = |output| output;
+const _: fn(
+	runtime_apis::asset_hub_westend_finality_api::free_headers_interval::Output,
+) -> Option<bp_runtime::BlockNumberOf<bp_asset_hub_westend::AssetHubWestend>> = |output| output;
+const _: fn(
+	(bp_messages::HashedLaneId, MessageNonce, MessageNonce),
+) -> runtime_apis::to_asset_hub_westend_outbound_lane_api::message_details::Input = |input| input;
+const _: fn(
+	runtime_apis::to_asset_hub_westend_outbound_lane_api::message_details::Output,
+) -> Vec<OutboundMessageDetails> = |output| output;
+const _: fn(
+	(bp_messages::HashedLaneId, Vec<(MessagePayload, OutboundMessageDetails)>),
+) -> runtime_apis::from_asset_hub_westend_inbound_lane_api::message_details::Input = |input| input;
+const _: fn(
+	runtime_apis::from_asset_hub_westend_inbound_lane_api::message_details::Output,
+) -> Vec<InboundMessageDetails> = |output| output;
+
 impl ChainWithRuntimeVersion for AssetHubWestend {
-	const RUNTIME_VERSION: Option<SimpleRuntimeVersion> =
-		Some(SimpleRuntimeVersion { spec_version: 1_017_007, transaction_version: 16 });
+	const RUNTIME_VERSION: Option<SimpleRuntimeVersion> = Some(RUNTIME_VERSION);
 }
+
+/// Version of the runtime that the `codegen_runtime` has been generated for.
+const RUNTIME_VERSION: SimpleRuntimeVersion =
+	SimpleRuntimeVersion { spec_version: 1_017_007, transaction_version: 16 };
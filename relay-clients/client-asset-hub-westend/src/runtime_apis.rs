@@ -0,0 +1,77 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Autogenerated runtime API
+//! THIS FILE WAS AUTOGENERATED USING parity-bridges-common::runtime-codegen
+//! EXECUTED COMMAND: runtime-codegen runtime-apis --from-node-url
+//! wss://rococo-asset-hub-rpc.polkadot.io:443 --chain AssetHubWestend
+
+/// `AssetHubWestendFinalityApi` runtime API.
+pub mod asset_hub_westend_finality_api {
+	/// `AssetHubWestendFinalityApi::best_finalized` runtime API method.
+	pub mod best_finalized {
+		/// Name of the method, used in the `state_call` RPC.
+		pub const NAME: &str = "AssetHubWestendFinalityApi_best_finalized";
+		/// Method arguments.
+		pub type Input = ();
+		/// Method output.
+		pub type Output = ::core::option::Option<
+			::bp_runtime::HeaderId<::subxt::ext::subxt_core::utils::H256, ::core::primitive::u32>,
+		>;
+	}
+	/// `AssetHubWestendFinalityApi::free_headers_interval` runtime API method.
+	pub mod free_headers_interval {
+		/// Name of the method, used in the `state_call` RPC.
+		pub const NAME: &str = "AssetHubWestendFinalityApi_free_headers_interval";
+		/// Method arguments.
+		pub type Input = ();
+		/// Method output.
+		pub type Output = ::core::option::Option<::core::primitive::u32>;
+	}
+}
+/// `FromAssetHubWestendInboundLaneApi` runtime API.
+pub mod from_asset_hub_westend_inbound_lane_api {
+	/// `FromAssetHubWestendInboundLaneApi::message_details` runtime API method.
+	pub mod message_details {
+		/// Name of the method, used in the `state_call` RPC.
+		pub const NAME: &str = "FromAssetHubWestendInboundLaneApi_message_details";
+		/// Method arguments.
+		pub type Input = (
+			::bp_messages::HashedLaneId,
+			::subxt::ext::subxt_core::alloc::vec::Vec<(
+				::subxt::ext::subxt_core::alloc::vec::Vec<::core::primitive::u8>,
+				::bp_messages::OutboundMessageDetails,
+			)>,
+		);
+		/// Method output.
+		pub type Output =
+			::subxt::ext::subxt_core::alloc::vec::Vec<::bp_messages::InboundMessageDetails>;
+	}
+}
+/// `ToAssetHubWestendOutboundLaneApi` runtime API.
+pub mod to_asset_hub_westend_outbound_lane_api {
+	/// `ToAssetHubWestendOutboundLaneApi::message_details` runtime API method.
+	pub mod message_details {
+		/// Name of the method, used in the `state_call` RPC.
+		pub const NAME: &str = "ToAssetHubWestendOutboundLaneApi_message_details";
+		/// Method arguments.
+		pub type Input =
+			(::bp_messages::HashedLaneId, ::core::primitive::u64, ::core::primitive::u64);
+		/// Method output.
+		pub type Output =
+			::subxt::ext::subxt_core::alloc::vec::Vec<::bp_messages::OutboundMessageDetails>;
+	}
+}
@@ -0,0 +1,77 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Autogenerated runtime API
+//! THIS FILE WAS AUTOGENERATED USING parity-bridges-common::runtime-codegen
+//! EXECUTED COMMAND: runtime-codegen runtime-apis --from-node-url
+//! wss://polkadot-bridge-hub-rpc.polkadot.io --chain BridgeHubKusama
+
+/// `BridgeHubKusamaFinalityApi` runtime API.
+pub mod bridge_hub_kusama_finality_api {
+	/// `BridgeHubKusamaFinalityApi::best_finalized` runtime API method.
+	pub mod best_finalized {
+		/// Name of the method, used in the `state_call` RPC.
+		pub const NAME: &str = "BridgeHubKusamaFinalityApi_best_finalized";
+		/// Method arguments.
+		pub type Input = ();
+		/// Method output.
+		pub type Output = ::core::option::Option<
+			::bp_runtime::HeaderId<::subxt::ext::subxt_core::utils::H256, ::core::primitive::u32>,
+		>;
+	}
+	/// `BridgeHubKusamaFinalityApi::free_headers_interval` runtime API method.
+	pub mod free_headers_interval {
+		/// Name of the method, used in the `state_call` RPC.
+		pub const NAME: &str = "BridgeHubKusamaFinalityApi_free_headers_interval";
+		/// Method arguments.
+		pub type Input = ();
+		/// Method output.
+		pub type Output = ::core::option::Option<::core::primitive::u32>;
+	}
+}
+/// `FromBridgeHubKusamaInboundLaneApi` runtime API.
+pub mod from_bridge_hub_kusama_inbound_lane_api {
+	/// `FromBridgeHubKusamaInboundLaneApi::message_details` runtime API method.
+	pub mod message_details {
+		/// Name of the method, used in the `state_call` RPC.
+		pub const NAME: &str = "FromBridgeHubKusamaInboundLaneApi_message_details";
+		/// Method arguments.
+		pub type Input = (
+			::bp_messages::LegacyLaneId,
+			::subxt::ext::subxt_core::alloc::vec::Vec<(
+				::subxt::ext::subxt_core::alloc::vec::Vec<::core::primitive::u8>,
+				::bp_messages::OutboundMessageDetails,
+			)>,
+		);
+		/// Method output.
+		pub type Output =
+			::subxt::ext::subxt_core::alloc::vec::Vec<::bp_messages::InboundMessageDetails>;
+	}
+}
+/// `ToBridgeHubKusamaOutboundLaneApi` runtime API.
+pub mod to_bridge_hub_kusama_outbound_lane_api {
+	/// `ToBridgeHubKusamaOutboundLaneApi::message_details` runtime API method.
+	pub mod message_details {
+		/// Name of the method, used in the `state_call` RPC.
+		pub const NAME: &str = "ToBridgeHubKusamaOutboundLaneApi_message_details";
+		/// Method arguments.
+		pub type Input =
+			(::bp_messages::LegacyLaneId, ::core::primitive::u64, ::core::primitive::u64);
+		/// Method output.
+		pub type Output =
+			::subxt::ext::subxt_core::alloc::vec::Vec<::bp_messages::OutboundMessageDetails>;
+	}
+}
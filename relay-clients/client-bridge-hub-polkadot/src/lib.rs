@@ -17,21 +17,25 @@
 //! Types used to connect to the BridgeHub-Polkadot-Substrate parachain.
 
 pub mod codegen_runtime;
+pub mod runtime_apis;
 
 use bp_bridge_hub_polkadot::AVERAGE_BLOCK_INTERVAL;
-use bp_polkadot_core::{SuffixedCommonTransactionExtension, SuffixedCommonTransactionExtensionExt};
-use codec::Encode;
+use bp_messages::{InboundMessageDetails, MessageNonce, MessagePayload, OutboundMessageDetails};
+use bp_polkadot_core::{
+	AccountId, SuffixedCommonTransactionExtension, SuffixedCommonTransactionExtensionExt,
+};
+use relay_common_client::{
+	ChainWithMetadataHash, ChainWithTransactionExtension, ExplicitParams, SupportedRuntime,
+	TransactionExtensionParams,
+};
 use relay_substrate_client::{
 	calls::UtilityCall as MockUtilityCall, Chain, ChainWithBalances, ChainWithMessages,
 	ChainWithRuntimeVersion, ChainWithTransactions, ChainWithUtilityPallet,
 	Error as SubstrateError, MockedRuntimeUtilityPallet, SignParam, SimpleRuntimeVersion,
 	UnderlyingChainProvider, UnsignedTransaction,
 };
-use sp_core::{storage::StorageKey, Pair};
-use sp_runtime::{
-	generic::SignedPayload,
-	traits::{FakeDispatchable, IdentifyAccount},
-};
+use sp_core::storage::StorageKey;
+use sp_runtime::{MultiAddress, MultiSignature, OpaqueExtrinsic};
 use std::time::Duration;
 
 pub use codegen_runtime::api::runtime_types;
@@ -58,8 +62,6 @@ pub type BridgeKusamaMessagesCall = runtime_types::pallet_bridge_messages::palle
 pub type BridgePolkadotBulletinGrandpaCall = runtime_types::pallet_bridge_grandpa::pallet::Call;
 pub type BridgeKusamaGrandpaCall = runtime_types::pallet_bridge_grandpa::pallet::Call;
 pub type BridgeParachainCall = runtime_types::pallet_bridge_parachains::pallet::Call;
-type UncheckedExtrinsic =
-	bp_bridge_hub_polkadot::UncheckedExtrinsic<RuntimeCall, TransactionExtension>;
 type UtilityCall = runtime_types::pallet_utility::pallet::Call;
 
 /// Polkadot chain definition
@@ -73,9 +75,9 @@ impl UnderlyingChainProvider for BridgeHubPolkadot {
 impl Chain for BridgeHubPolkadot {
 	const NAME: &'static str = "BridgeHubPolkadot";
 	const BEST_FINALIZED_HEADER_ID_METHOD: &'static str =
-		bp_bridge_hub_polkadot::BEST_FINALIZED_BRIDGE_HUB_POLKADOT_HEADER_METHOD;
+		runtime_apis::bridge_hub_polkadot_finality_api::best_finalized::NAME;
 	const FREE_HEADERS_INTERVAL_METHOD: &'static str =
-		bp_bridge_hub_polkadot::FREE_HEADERS_INTERVAL_FOR_BRIDGE_HUB_POLKADOT_METHOD;
+		runtime_apis::bridge_hub_polkadot_finality_api::free_headers_interval::NAME;
 	const AVERAGE_BLOCK_INTERVAL: Duration = AVERAGE_BLOCK_INTERVAL;
 
 	type SignedBlock = bp_bridge_hub_polkadot::SignedBlock;
@@ -103,46 +105,108 @@ impl ChainWithUtilityPallet for BridgeHubPolkadot {
 
 impl ChainWithTransactions for BridgeHubPolkadot {
 	type AccountKeyPair = sp_core::sr25519::Pair;
-	type SignedTransaction = UncheckedExtrinsic;
+	type SignedTransaction = OpaqueExtrinsic;
 
 	fn sign_transaction(
 		param: SignParam<Self>,
 		unsigned: UnsignedTransaction<Self>,
 	) -> Result<Self::SignedTransaction, SubstrateError> {
-		let raw_payload = SignedPayload::new(
-			FakeDispatchable::from(unsigned.call),
-			TransactionExtension::from_params(
-				param.spec_version,
-				param.transaction_version,
-				unsigned.era,
-				param.genesis_hash,
-				unsigned.nonce,
-				unsigned.tip,
-				(((), (), Mode::Disabled), ((), (), None)),
-			),
-		)?;
-
-		let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload));
-		let signer: sp_runtime::MultiSigner = param.signer.public().into();
-		let (call, extra, _) = raw_payload.deconstruct();
-
-		Ok(UncheckedExtrinsic::new_signed(
-			call.deconstruct(),
-			signer.into_account().into(),
-			signature.into(),
-			extra,
-		))
+		relay_common_client::sign_transaction(SUPPORTED_RUNTIMES, param, unsigned)
+	}
+}
+
+impl ChainWithMetadataHash for BridgeHubPolkadot {
+	const METADATA_HASH: Option<(u32, [u8; 32])> = codegen_runtime::METADATA_HASH;
+}
+
+const SUPPORTED_RUNTIMES: &[SupportedRuntime<BridgeHubPolkadot>] = &[SupportedRuntime {
+	version: RUNTIME_VERSION,
+	sign: relay_common_client::sign_opaque_transaction,
+}];
+
+impl ChainWithTransactionExtension for BridgeHubPolkadot {
+	type Address = MultiAddress<AccountId, ()>;
+	type Signature = MultiSignature;
+	type TransactionExtension = TransactionExtension;
+
+	fn transaction_extension(params: TransactionExtensionParams<Self>) -> TransactionExtension {
+		let metadata_hash_mode =
+			if params.metadata_hash.is_some() { Mode::Enabled } else { Mode::Disabled };
+		TransactionExtension::from_params(
+			params.spec_version,
+			params.transaction_version,
+			params.era,
+			params.genesis_hash,
+			params.nonce,
+			params.tip,
+			(((), (), metadata_hash_mode), ((), (), params.metadata_hash)),
+		)
+	}
+
+	fn explicit_params(
+		extension: &TransactionExtension,
+	) -> Result<ExplicitParams<Self>, SubstrateError> {
+		let (
+			_,
+			_,
+			_,
+			_,
+			era,
+			nonce,
+			_,
+			tip,
+			(bridge_reject_obsolete, bridge_refund, metadata_hash_mode),
+		) = extension.payload.clone();
+		Ok(ExplicitParams {
+			era,
+			nonce: nonce.0,
+			tip: tip.0,
+			metadata_hash_check: Some(metadata_hash_mode == Mode::Enabled),
+			bridge_extensions: vec![
+				("BridgeRejectObsoleteHeadersAndMessages", format!("{bridge_reject_obsolete:?}")),
+				("OnBridgeHubPolkadotRefundBridgeHubKusamaMessages", format!("{bridge_refund:?}")),
+			],
+		})
+	}
+
+	fn signer_account(address: MultiAddress<AccountId, ()>) -> Option<AccountId> {
+		relay_common_client::multi_address_account(address)
 	}
 }
 
 impl ChainWithMessages for BridgeHubPolkadot {
 	const TO_CHAIN_MESSAGE_DETAILS_METHOD: &'static str =
-		bp_bridge_hub_polkadot::TO_BRIDGE_HUB_POLKADOT_MESSAGE_DETAILS_METHOD;
+		runtime_apis::to_bridge_hub_polkadot_outbound_lane_api::message_details::NAME;
 	const FROM_CHAIN_MESSAGE_DETAILS_METHOD: &'static str =
-		bp_bridge_hub_polkadot::FROM_BRIDGE_HUB_POLKADOT_MESSAGE_DETAILS_METHOD;
+		runtime_apis::from_bridge_hub_polkadot_inbound_lane_api::message_details::NAME;
 }
 
+// The relay calls bridge runtime APIs of the chain with arguments and decodes their outputs, using
+// types below. Make sure they match types of APIs, generated from the bridged runtime metadata.
+const _: fn(
+	runtime_apis::bridge_hub_polkadot_finality_api::best_finalized::Output,
+) -> Option<bp_runtime::HeaderIdOf<bp_bridge_hub_polkadot::BridgeHubPolkadot>> = |output| output;
+const _: fn(
+	runtime_apis::bridge_hub_polkadot_finality_api::free_headers_interval::Output,
+) -> Option<bp_runtime::BlockNumberOf<bp_bridge_hub_polkadot::BridgeHubPolkadot>> = |output| output;
+const _: fn(
+	(bp_messages::LegacyLaneId, MessageNonce, MessageNonce),
+) -> runtime_apis::to_bridge_hub_polkadot_outbound_lane_api::message_details::Input = |input| input;
+const _: fn(
+	runtime_apis::to_bridge_hub_polkadot_outbound_lane_api::message_details::Output,
+) -> Vec<OutboundMessageDetails> = |output| output;
+const _: fn(
+	(bp_messages::LegacyLaneId, Vec<(MessagePayload, OutboundMessageDetails)>),
+) -> runtime_apis::from_bridge_hub_polkadot_inbound_lane_api::message_details::Input =
+	|input| input;
+const _: fn(
+	runtime_apis::from_bridge_hub_polkadot_inbound_lane_api::message_details::Output,
+) -> Vec<InboundMessageDetails> = |output| output;
+
 impl ChainWithRuntimeVersion for BridgeHubPolkadot {
-	const RUNTIME_VERSION: Option<SimpleRuntimeVersion> =
-		Some(SimpleRuntimeVersion { spec_version: 2_000_003, transaction_version: 4 });
+	const RUNTIME_VERSION: Option<SimpleRuntimeVersion> = Some(RUNTIME_VERSION);
 }
+
+/// Version of the runtime that the `codegen_runtime` has been generated for.
+const RUNTIME_VERSION: SimpleRuntimeVersion =
+	SimpleRuntimeVersion { spec_version: 2_000_003, transaction_version: 4 };
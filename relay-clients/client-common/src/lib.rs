@@ -0,0 +1,116 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types and helpers, shared by clients of all chains.
+
+mod metadata_hash;
+mod signing;
+mod transaction;
+
+pub use metadata_hash::{
+	compute_metadata_hash, metadata_hash, verify_metadata_hash, ChainWithMetadataHash, TokenInfo,
+	METADATA_HASH_VERSION,
+};
+pub use signing::{sign_transaction, SignTransaction, SupportedRuntime};
+pub use transaction::{
+	decode_transaction, multi_address_account, sign_opaque_transaction,
+	sign_transaction_with_extension, ChainWithTransactionExtension, ExplicitParams,
+	TransactionExtensionParams, UncheckedExtrinsicOf,
+};
+
+use bp_runtime::{HeaderId, TransactionEra};
+use relay_substrate_client::{AccountIdOf, ChainWithBalances, Error as SubstrateError};
+use sp_runtime::generic::Era;
+
+/// Returns true if the chain has balances.
+///
+/// Chains without balances return the empty `ChainWithBalances::account_info_storage_key`.
+/// Relayer balances can't be tracked at such chains, so balance metrics are not registered for
+/// them.
+pub fn has_balances<C: ChainWithBalances>(account_id: &AccountIdOf<C>) -> bool {
+	!C::account_info_storage_key(account_id).0.is_empty()
+}
+
+/// Parameters of the signed transaction payload that are not encoded into the transaction, but
+/// are required to verify its signature.
+#[derive(Clone, Copy, Debug)]
+pub struct VerifyParam<Hash> {
+	/// Spec version of the runtime that the transaction has been signed for.
+	pub spec_version: u32,
+	/// Transaction version of the runtime that the transaction has been signed for.
+	pub transaction_version: u32,
+	/// Hash of the chain genesis block.
+	pub genesis_hash: Hash,
+	/// Hash of the block where the transaction era starts. Only required for mortal
+	/// transactions.
+	pub era_block_hash: Option<Hash>,
+	/// Metadata hash of the runtime. Only required to verify signature of the transaction with
+	/// the `CheckMetadataHash` transaction extension enabled.
+	pub metadata_hash: Option<[u8; 32]>,
+}
+
+impl<Hash: Copy> VerifyParam<Hash> {
+	/// Returns era that the transaction has been signed with, given its encoded era.
+	///
+	/// The number of the era block is not encoded into the transaction, but any block number
+	/// with the same phase gives the same encoded era, so the phase is used instead.
+	pub fn transaction_era<BlockNumber: From<u16>>(
+		&self,
+		era: Era,
+	) -> Result<TransactionEra<BlockNumber, Hash>, SubstrateError> {
+		match era {
+			Era::Immortal => Ok(TransactionEra::Immortal),
+			Era::Mortal(period, phase) => {
+				let era_block_hash = self.era_block_hash.ok_or_else(|| {
+					SubstrateError::Custom(
+						"Transaction is mortal. Era block hash is required to verify its signature"
+							.into(),
+					)
+				})?;
+				// `Era::Mortal` period is at most `1 << 16` and phase is less than the period
+				let phase = u16::try_from(phase).map_err(|_| {
+					SubstrateError::Custom(format!("Invalid transaction era: {era:?}"))
+				})?;
+				Ok(TransactionEra::Mortal(HeaderId(phase.into(), era_block_hash), period as u32))
+			},
+		}
+	}
+}
+
+/// Signed transaction, decoded by the chain client.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedTransaction<AccountId, Call> {
+	/// Account that has signed the transaction.
+	pub signer: AccountId,
+	/// Encoded signature of the transaction.
+	pub signature: Vec<u8>,
+	/// True if the signature matches the payload, reconstructed from the transaction and
+	/// `VerifyParam`.
+	pub signature_valid: bool,
+	/// Transaction nonce.
+	pub nonce: u64,
+	/// Transaction era.
+	pub era: Era,
+	/// Transaction tip. It is always zero if the chain doesn't support tips.
+	pub tip: u128,
+	/// True if the `CheckMetadataHash` transaction extension is enabled. `None` if the chain
+	/// doesn't support the extension.
+	pub metadata_hash_check: Option<bool>,
+	/// Names and explicit parameters of the bridge transaction extensions.
+	pub bridge_extensions: Vec<(&'static str, String)>,
+	/// Transaction call.
+	pub call: Call,
+}
@@ -0,0 +1,178 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Support of the `CheckMetadataHash` transaction extension.
+//!
+//! The relay only signs transactions with the extension enabled if the
+//! [RFC-78](https://polkadot-fellows.github.io/RFCs/approved/0078-merkleized-metadata.html)
+//! hash of the metadata of the live runtime matches the hash, computed by the `runtime-codegen`.
+//! If the runtime code has been generated without the hash, the live runtime must be the runtime
+//! that the code has been generated for. Hashes are verified once, at startup, and used by all
+//! further `sign_transaction` calls.
+
+use codec::Decode;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use merkleized_metadata::{generate_metadata_digest, ExtraInfo};
+use relay_substrate_client::{Chain, ChainWithRuntimeVersion, Error as SubstrateError};
+use std::{collections::BTreeMap, sync::Mutex};
+
+/// Version of the runtime metadata that the metadata hash is computed over.
+pub const METADATA_HASH_VERSION: u32 = 15;
+
+/// Chain that supports the `CheckMetadataHash` transaction extension.
+pub trait ChainWithMetadataHash: ChainWithRuntimeVersion {
+	/// `spec_version` of the runtime and the RFC-78 hash of its metadata, computed by the
+	/// `runtime-codegen`.
+	const METADATA_HASH: Option<(u32, [u8; 32])>;
+}
+
+/// Native token of the chain. It is not a part of the metadata, but is required to compute
+/// the hash.
+#[derive(Clone, Debug)]
+pub struct TokenInfo {
+	/// Token symbol.
+	pub symbol: String,
+	/// Number of token decimals.
+	pub decimals: u8,
+}
+
+/// Metadata hashes that have been verified against live runtimes, by chain name.
+///
+/// `ChainWithTransactions::sign_transaction` has no room for any extra parameters, so verified
+/// hashes are kept here.
+static VERIFIED_METADATA_HASHES: Mutex<BTreeMap<&'static str, (u32, [u8; 32])>> =
+	Mutex::new(BTreeMap::new());
+
+/// Compute metadata hash of the live runtime, using its raw (SCALE-encoded
+/// `RuntimeMetadataPrefixed`) V15 metadata, and compare it with the hash, computed by the
+/// `runtime-codegen`. If the runtime code has been generated without the hash, the live runtime
+/// must have the `spec_version` that the code has been generated for.
+///
+/// If the check passes, all further transactions to the chain are signed with the
+/// `CheckMetadataHash` transaction extension enabled.
+pub fn verify_metadata_hash<C: ChainWithMetadataHash>(
+	raw_metadata: &[u8],
+	token: &TokenInfo,
+) -> Result<(), SubstrateError> {
+	let (spec_version, hash) = compute_metadata_hash(raw_metadata, token)?;
+	let (expected_spec_version, expected_hash) = match C::METADATA_HASH {
+		Some((expected_spec_version, expected_hash)) =>
+			(expected_spec_version, Some(expected_hash)),
+		None => (
+			C::RUNTIME_VERSION.map(|version| version.spec_version).ok_or_else(|| {
+				SubstrateError::Custom(format!(
+					"Neither metadata hash nor version of {} runtime is known. Regenerate the \
+					runtime code",
+					C::NAME,
+				))
+			})?,
+			None,
+		),
+	};
+	if spec_version != expected_spec_version {
+		return Err(SubstrateError::Custom(format!(
+			"Runtime code of {} has been generated for spec_version {}, but the chain runs \
+			spec_version {}. Regenerate the runtime code",
+			C::NAME,
+			expected_spec_version,
+			spec_version,
+		)))
+	}
+	if let Some(expected_hash) = expected_hash.filter(|expected_hash| *expected_hash != hash) {
+		return Err(SubstrateError::Custom(format!(
+			"Metadata hash of {} runtime 0x{} differs from the hash of the live runtime 0x{}. \
+			Regenerate the runtime code",
+			C::NAME,
+			hex::encode(expected_hash),
+			hex::encode(hash),
+		)))
+	}
+
+	VERIFIED_METADATA_HASHES
+		.lock()
+		.unwrap_or_else(|e| e.into_inner())
+		.insert(C::NAME, (spec_version, hash));
+	Ok(())
+}
+
+/// Returns metadata hash to sign the transaction for the runtime with given `spec_version`.
+///
+/// Returns `None` if the hash has not been verified, so the `CheckMetadataHash` transaction
+/// extension is disabled. Fails if the hash has been verified for the other runtime version, so
+/// the transaction would be rejected anyway.
+pub fn metadata_hash<C: Chain>(spec_version: u32) -> Result<Option<[u8; 32]>, SubstrateError> {
+	match VERIFIED_METADATA_HASHES.lock().unwrap_or_else(|e| e.into_inner()).get(C::NAME) {
+		Some((hash_spec_version, hash)) if *hash_spec_version == spec_version => Ok(Some(*hash)),
+		Some((hash_spec_version, _)) => Err(SubstrateError::Custom(format!(
+			"Metadata hash of {} runtime has been verified for spec_version {}, but the chain \
+			runs spec_version {}. Restart the relay",
+			C::NAME,
+			hash_spec_version,
+			spec_version,
+		))),
+		None => Ok(None),
+	}
+}
+
+/// Compute `spec_version` and metadata hash of the runtime, using its raw V15 metadata.
+///
+/// It is the same hash that the `substrate-wasm-builder` and the `runtime-codegen` compute.
+pub fn compute_metadata_hash(
+	raw_metadata: &[u8],
+	token: &TokenInfo,
+) -> Result<(u32, [u8; 32]), SubstrateError> {
+	let error = |e: String| SubstrateError::Custom(format!("Failed to compute metadata hash: {e}"));
+
+	let metadata = RuntimeMetadataPrefixed::decode(&mut &raw_metadata[..])
+		.map_err(|e| error(format!("error decoding metadata: {e:?}")))?;
+	let RuntimeMetadata::V15(ref metadata_v15) = metadata.1 else {
+		return Err(error(format!(
+			"metadata hash may only be computed from V{METADATA_HASH_VERSION} metadata. Got V{}",
+			metadata.1.version()
+		)))
+	};
+
+	let system_constant = |name: &str| {
+		metadata_v15
+			.pallets
+			.iter()
+			.filter(|pallet| pallet.name == "System")
+			.flat_map(|pallet| pallet.constants.iter())
+			.find(|constant| constant.name == name)
+			.map(|constant| constant.value.clone())
+			.ok_or_else(|| error(format!("missing System::{name} constant in the metadata")))
+	};
+	// `RuntimeVersion` starts with `spec_name`, `impl_name`, `authoring_version` and `spec_version`
+	let (spec_name, _impl_name, _authoring_version, spec_version) =
+		<(String, String, u32, u32)>::decode(&mut &system_constant("Version")?[..])
+			.map_err(|e| error(format!("error decoding System::Version constant: {e:?}")))?;
+	let base58_prefix = u16::decode(&mut &system_constant("SS58Prefix")?[..])
+		.map_err(|e| error(format!("error decoding System::SS58Prefix constant: {e:?}")))?;
+
+	let digest = generate_metadata_digest(
+		&metadata.1,
+		ExtraInfo {
+			spec_version,
+			spec_name,
+			base58_prefix,
+			decimals: token.decimals,
+			token_symbol: token.symbol.clone(),
+		},
+	)
+	.map_err(error)?;
+
+	Ok((spec_version, digest.hash()))
+}
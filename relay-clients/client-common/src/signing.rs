@@ -0,0 +1,83 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Signing transactions for one of runtimes, supported by the chain client.
+//!
+//! Before the runtime upgrade, the client may carry code for both the current and the next
+//! runtime versions (`codegen_runtime` and `codegen_runtime_v2` modules, generated by the
+//! `runtime-codegen`). The call encoding and the transaction extension are chosen at signing time,
+//! based on the live `spec_version`, so the relay keeps working across the runtime upgrade.
+
+use crate::metadata_hash::metadata_hash;
+
+use relay_substrate_client::{
+	ChainWithTransactions, Error as SubstrateError, SignParam, SimpleRuntimeVersion,
+	UnsignedTransaction,
+};
+
+const LOG_TARGET: &str = "bridge";
+
+/// Function that signs transaction for the runtime, given the metadata hash to sign it with.
+pub type SignTransaction<C> =
+	fn(
+		SignParam<C>,
+		UnsignedTransaction<C>,
+		Option<[u8; 32]>,
+	) -> Result<<C as ChainWithTransactions>::SignedTransaction, SubstrateError>;
+
+/// Runtime that the client is able to sign transactions for.
+pub struct SupportedRuntime<C: ChainWithTransactions> {
+	/// Version of the runtime.
+	pub version: SimpleRuntimeVersion,
+	/// Sign transaction for the runtime.
+	pub sign: SignTransaction<C>,
+}
+
+/// Sign transaction for the runtime with the live `spec_version`.
+///
+/// If the metadata hash of the chain has been verified, the transaction is signed with the
+/// `CheckMetadataHash` transaction extension enabled. If the runtime is not supported by the
+/// client, the transaction is signed for the latest supported runtime with the extension
+/// disabled - it is valid as long as the runtime upgrade hasn't changed the transaction format.
+pub fn sign_transaction<C: ChainWithTransactions>(
+	runtimes: &[SupportedRuntime<C>],
+	param: SignParam<C>,
+	unsigned: UnsignedTransaction<C>,
+) -> Result<C::SignedTransaction, SubstrateError> {
+	if let Some(runtime) = runtimes
+		.iter()
+		.find(|runtime| runtime.version.spec_version == param.spec_version)
+	{
+		let metadata_hash = metadata_hash::<C>(param.spec_version)?;
+		return (runtime.sign)(param, unsigned, metadata_hash)
+	}
+
+	let runtime =
+		runtimes
+			.iter()
+			.max_by_key(|runtime| runtime.version.spec_version)
+			.ok_or_else(|| {
+				SubstrateError::Custom(format!("{} client has no supported runtimes", C::NAME))
+			})?;
+	log::warn!(
+		target: LOG_TARGET,
+		"{} client has no code for spec_version {}. Signing transaction for spec_version {}",
+		C::NAME,
+		param.spec_version,
+		runtime.version.spec_version,
+	);
+	(runtime.sign)(param, unsigned, None)
+}
@@ -0,0 +1,354 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Signing and decoding transactions of chains with the known transaction extension.
+
+use crate::{DecodedTransaction, VerifyParam};
+
+use bp_runtime::{EncodedOrDecodedCall, TransactionEra};
+use codec::{DecodeAll, Encode};
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, CallOf, Chain, ChainWithTransactions, Error as SubstrateError,
+	SignParam, UnsignedTransaction,
+};
+use sp_core::Pair;
+use sp_runtime::{
+	generic::{Era, Preamble, SignedPayload, UncheckedExtrinsic},
+	traits::{
+		FakeDispatchable, IdentifyAccount, TransactionExtension, UniqueSaturatedInto, Verify,
+	},
+	MultiAddress, OpaqueExtrinsic,
+};
+
+/// Signed transaction of the chain.
+pub type UncheckedExtrinsicOf<C> = UncheckedExtrinsic<
+	<C as ChainWithTransactionExtension>::Address,
+	EncodedOrDecodedCall<CallOf<C>>,
+	<C as ChainWithTransactionExtension>::Signature,
+	<C as ChainWithTransactionExtension>::TransactionExtension,
+>;
+
+/// Chain with the transaction extension of the runtime that the chain client has been generated
+/// for.
+pub trait ChainWithTransactionExtension: ChainWithTransactions {
+	/// Address of the transaction signer.
+	type Address: From<AccountIdOf<Self>>;
+	/// Transaction signature.
+	type Signature: Encode
+		+ From<<AccountKeyPairOf<Self> as Pair>::Signature>
+		+ Verify<Signer: IdentifyAccount<AccountId = AccountIdOf<Self>>>;
+	/// Transaction extension of the runtime.
+	type TransactionExtension: TransactionExtension<
+		FakeDispatchable<EncodedOrDecodedCall<CallOf<Self>>>,
+	>;
+
+	/// Returns transaction extension with given parameters.
+	fn transaction_extension(
+		params: TransactionExtensionParams<Self>,
+	) -> Self::TransactionExtension;
+	/// Returns explicit parameters of the transaction extension.
+	fn explicit_params(
+		extension: &Self::TransactionExtension,
+	) -> Result<ExplicitParams<Self>, SubstrateError>;
+	/// Returns account of the transaction signer or `None` if the address is not an account.
+	fn signer_account(address: Self::Address) -> Option<AccountIdOf<Self>>;
+}
+
+/// Parameters of the transaction extension.
+pub struct TransactionExtensionParams<C: Chain> {
+	/// Spec version of the runtime.
+	pub spec_version: u32,
+	/// Transaction version of the runtime.
+	pub transaction_version: u32,
+	/// Transaction era.
+	pub era: TransactionEra<C::BlockNumber, C::Hash>,
+	/// Hash of the chain genesis block.
+	pub genesis_hash: C::Hash,
+	/// Transaction nonce.
+	pub nonce: C::Nonce,
+	/// Transaction tip. It is ignored if the chain doesn't support tips.
+	pub tip: C::Balance,
+	/// Metadata hash to sign the transaction with. If it is `None`, the `CheckMetadataHash`
+	/// transaction extension is disabled. It is ignored if the chain doesn't support the
+	/// extension.
+	pub metadata_hash: Option<[u8; 32]>,
+}
+
+/// Parameters of the transaction extension, that are encoded into the transaction.
+pub struct ExplicitParams<C: Chain> {
+	/// Transaction era.
+	pub era: Era,
+	/// Transaction nonce.
+	pub nonce: C::Nonce,
+	/// Transaction tip. It is always zero if the chain doesn't support tips.
+	pub tip: C::Balance,
+	/// True if the `CheckMetadataHash` transaction extension is enabled. `None` if the chain
+	/// doesn't support the extension.
+	pub metadata_hash_check: Option<bool>,
+	/// Names and explicit parameters of the bridge transaction extensions.
+	pub bridge_extensions: Vec<(&'static str, String)>,
+}
+
+/// Sign transaction with the transaction extension of the chain.
+pub fn sign_transaction_with_extension<C: ChainWithTransactionExtension>(
+	param: SignParam<C>,
+	unsigned: UnsignedTransaction<C>,
+	metadata_hash: Option<[u8; 32]>,
+) -> Result<UncheckedExtrinsicOf<C>, SubstrateError>
+where
+	AccountIdOf<C>: From<<AccountKeyPairOf<C> as Pair>::Public>,
+{
+	let raw_payload = SignedPayload::new(
+		FakeDispatchable::from(unsigned.call),
+		C::transaction_extension(TransactionExtensionParams {
+			spec_version: param.spec_version,
+			transaction_version: param.transaction_version,
+			era: unsigned.era,
+			genesis_hash: param.genesis_hash,
+			nonce: unsigned.nonce,
+			tip: unsigned.tip,
+			metadata_hash,
+		}),
+	)?;
+
+	let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload));
+	let signer = AccountIdOf::<C>::from(param.signer.public());
+	let (call, extension, _) = raw_payload.deconstruct();
+
+	Ok(UncheckedExtrinsic::new_signed(
+		call.deconstruct(),
+		signer.into(),
+		signature.into(),
+		extension,
+	))
+}
+
+/// Sign transaction with the transaction extension of the chain and return it as
+/// `OpaqueExtrinsic`.
+pub fn sign_opaque_transaction<C: ChainWithTransactionExtension>(
+	param: SignParam<C>,
+	unsigned: UnsignedTransaction<C>,
+	metadata_hash: Option<[u8; 32]>,
+) -> Result<OpaqueExtrinsic, SubstrateError>
+where
+	AccountIdOf<C>: From<<AccountKeyPairOf<C> as Pair>::Public>,
+	UncheckedExtrinsicOf<C>: Encode,
+{
+	let transaction = sign_transaction_with_extension(param, unsigned, metadata_hash)?;
+	OpaqueExtrinsic::from_bytes(&transaction.encode()).map_err(|e| {
+		SubstrateError::Custom(format!("Failed to encode {} transaction: {e:?}", C::NAME))
+	})
+}
+
+/// Decode transaction, signed with the transaction extension of the chain, and verify its
+/// signature.
+///
+/// The transaction extension is reconstructed from its explicit parameters and implicit
+/// parameters from `param`, so the signature is only valid if both are correct.
+pub fn decode_transaction<C: ChainWithTransactionExtension>(
+	encoded: &[u8],
+	param: VerifyParam<C::Hash>,
+) -> Result<DecodedTransaction<AccountIdOf<C>, CallOf<C>>, SubstrateError>
+where
+	UncheckedExtrinsicOf<C>: DecodeAll,
+{
+	let transaction = UncheckedExtrinsicOf::<C>::decode_all(&mut &encoded[..]).map_err(|e| {
+		SubstrateError::Custom(format!("Failed to decode {} transaction: {e:?}", C::NAME))
+	})?;
+	let unsigned_transaction_error =
+		|| SubstrateError::Custom(format!("{} transaction is not signed by an account", C::NAME));
+	let Preamble::Signed(address, signature, extension) = transaction.preamble else {
+		return Err(unsigned_transaction_error())
+	};
+	let signer = C::signer_account(address).ok_or_else(unsigned_transaction_error)?;
+	let explicit_params = C::explicit_params(&extension)?;
+
+	let expected_extension = C::transaction_extension(TransactionExtensionParams {
+		spec_version: param.spec_version,
+		transaction_version: param.transaction_version,
+		era: param.transaction_era(explicit_params.era)?,
+		genesis_hash: param.genesis_hash,
+		nonce: explicit_params.nonce,
+		tip: explicit_params.tip,
+		metadata_hash: param
+			.metadata_hash
+			.filter(|_| explicit_params.metadata_hash_check == Some(true)),
+	});
+	let signature_valid = expected_extension.encode() == extension.encode() &&
+		SignedPayload::new(
+			FakeDispatchable::from(transaction.function.clone()),
+			expected_extension,
+		)?
+		.using_encoded(|payload| signature.verify(payload, &signer));
+	let call = transaction.function.into_decoded().map_err(|e| {
+		SubstrateError::Custom(format!("Failed to decode {} transaction call: {e:?}", C::NAME))
+	})?;
+
+	Ok(DecodedTransaction {
+		signer,
+		signature: signature.encode(),
+		signature_valid,
+		nonce: explicit_params.nonce.unique_saturated_into(),
+		era: explicit_params.era,
+		tip: explicit_params.tip.unique_saturated_into(),
+		metadata_hash_check: explicit_params.metadata_hash_check,
+		bridge_extensions: explicit_params.bridge_extensions,
+		call,
+	})
+}
+
+/// Returns account of the `MultiAddress::Id` address or `None` if the address is not an account.
+pub fn multi_address_account<AccountId, AccountIndex>(
+	address: MultiAddress<AccountId, AccountIndex>,
+) -> Option<AccountId> {
+	match address {
+		MultiAddress::Id(account_id) => Some(account_id),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{sign_transaction, SupportedRuntime};
+	use bp_polkadot_core::SuffixedCommonTransactionExtensionExt;
+	use bp_runtime::HeaderId;
+	use relay_substrate_client::{SimpleRuntimeVersion, UnderlyingChainProvider};
+	use sp_core::H256;
+	use std::time::Duration;
+
+	const RUNTIME_VERSION: SimpleRuntimeVersion =
+		SimpleRuntimeVersion { spec_version: 1, transaction_version: 2 };
+
+	#[derive(Debug, Clone, Copy)]
+	struct TestChain;
+
+	impl UnderlyingChainProvider for TestChain {
+		type Chain = bp_kusama::Kusama;
+	}
+
+	impl Chain for TestChain {
+		const NAME: &'static str = "Test";
+		const BEST_FINALIZED_HEADER_ID_METHOD: &'static str = "TestFinalityApi_best_finalized";
+		const FREE_HEADERS_INTERVAL_METHOD: &'static str = "TestFinalityApi_free_headers_interval";
+		const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_secs(6);
+
+		type SignedBlock = bp_kusama::SignedBlock;
+		type Call = Vec<u8>;
+	}
+
+	impl ChainWithTransactions for TestChain {
+		type AccountKeyPair = sp_core::sr25519::Pair;
+		type SignedTransaction = OpaqueExtrinsic;
+
+		fn sign_transaction(
+			param: SignParam<Self>,
+			unsigned: UnsignedTransaction<Self>,
+		) -> Result<Self::SignedTransaction, SubstrateError> {
+			sign_transaction(
+				&[SupportedRuntime { version: RUNTIME_VERSION, sign: sign_opaque_transaction }],
+				param,
+				unsigned,
+			)
+		}
+	}
+
+	impl ChainWithTransactionExtension for TestChain {
+		type Address = MultiAddress<bp_kusama::AccountId, ()>;
+		type Signature = sp_runtime::MultiSignature;
+		type TransactionExtension = bp_kusama::TransactionExtension;
+
+		fn transaction_extension(
+			params: TransactionExtensionParams<Self>,
+		) -> Self::TransactionExtension {
+			bp_kusama::TransactionExtension::from_params(
+				params.spec_version,
+				params.transaction_version,
+				params.era,
+				params.genesis_hash,
+				params.nonce,
+				params.tip,
+				((), ()),
+			)
+		}
+
+		fn explicit_params(
+			extension: &Self::TransactionExtension,
+		) -> Result<ExplicitParams<Self>, SubstrateError> {
+			let (_, _, _, _, era, nonce, _, tip, _) = extension.payload.clone();
+			Ok(ExplicitParams {
+				era,
+				nonce: nonce.0,
+				tip: tip.0,
+				metadata_hash_check: None,
+				bridge_extensions: Vec::new(),
+			})
+		}
+
+		fn signer_account(address: Self::Address) -> Option<AccountIdOf<Self>> {
+			multi_address_account(address)
+		}
+	}
+
+	#[test]
+	fn signed_transaction_is_decoded() {
+		let signer = sp_core::sr25519::Pair::from_seed(&[1; 32]);
+		let call = vec![42];
+		let genesis_hash = H256::repeat_byte(1);
+		let era_block_hash = H256::repeat_byte(2);
+
+		// transactions are signed for the latest supported runtime if the live one is unknown
+		for spec_version in [RUNTIME_VERSION.spec_version, RUNTIME_VERSION.spec_version + 1] {
+			let param = VerifyParam {
+				spec_version,
+				transaction_version: RUNTIME_VERSION.transaction_version,
+				genesis_hash,
+				era_block_hash: Some(era_block_hash),
+				metadata_hash: None,
+			};
+			let transaction = TestChain::sign_transaction(
+				SignParam {
+					spec_version,
+					transaction_version: param.transaction_version,
+					genesis_hash,
+					signer: signer.clone(),
+				},
+				UnsignedTransaction::new(call.clone().into(), 3)
+					.tip(4)
+					.era(TransactionEra::Mortal(HeaderId(100, era_block_hash), 64)),
+			)
+			.unwrap()
+			.encode();
+
+			let decoded = decode_transaction::<TestChain>(&transaction, param).unwrap();
+			assert_eq!(decoded.signer, signer.public().into());
+			assert!(decoded.signature_valid);
+			assert_eq!(decoded.nonce, 3);
+			assert_eq!(decoded.era, Era::mortal(64, 100));
+			assert_eq!(decoded.tip, 4);
+			assert_eq!(decoded.metadata_hash_check, None);
+			assert_eq!(decoded.call, call);
+
+			// signature is not valid if implicit parameters are different
+			let decoded = decode_transaction::<TestChain>(
+				&transaction,
+				VerifyParam { genesis_hash: H256::repeat_byte(3), ..param },
+			)
+			.unwrap();
+			assert!(!decoded.signature_valid);
+		}
+	}
+}
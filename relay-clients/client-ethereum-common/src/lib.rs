@@ -0,0 +1,378 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types and helpers, used to connect to Ethereum-compatible Substrate chains (like Moonbeam and
+//! Moonriver), which are using 20-byte accounts and secp256k1 signatures over Keccak-256 hashes.
+
+pub use bp_ethereum_common::{
+	AccountId20, AccountInfoStorageMapKeyProvider, EthereumSignature, EthereumSigner,
+};
+
+use bip39::{Language, Mnemonic};
+use hmac::{Hmac, Mac};
+use k256::{elliptic_curve::PrimeField, Scalar};
+use sha2::Sha512;
+use sp_core::{
+	crypto::{CryptoType, DeriveError, DeriveJunction, SecretStringError},
+	ecdsa, Pair,
+};
+use sp_crypto_hashing::keccak_256;
+
+/// Default BIP-44 derivation path of Ethereum accounts.
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/60'/0'/0/0";
+
+/// BIP-32 index offset of hardened keys.
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// Ethereum-compatible key pair.
+///
+/// It is a secp256k1 key pair that signs Keccak-256 hash of the message (instead of the
+/// Blake2-256 hash, used by the regular `ecdsa::Pair`). Key pair may be created from:
+///
+/// - `0x`-prefixed hex-encoded 32-byte private key;
+/// - BIP-39 mnemonic, using the default Ethereum derivation path (`m/44'/60'/0'/0/0`);
+/// - BIP-39 mnemonic, followed by the BIP-32 derivation path (e.g. `<mnemonic>/m/44'/60'/0'/0/1`).
+///
+/// Mnemonic may be followed by `///<password>`, which is used as the BIP-39 password.
+#[derive(Clone)]
+pub struct EthereumPair(ecdsa::Pair);
+
+impl EthereumPair {
+	/// Create key pair from the BIP-39 mnemonic, using given BIP-32 derivation path.
+	pub fn from_mnemonic(
+		phrase: &str,
+		password: Option<&str>,
+		path: &str,
+	) -> Result<(Self, [u8; 32]), SecretStringError> {
+		let mnemonic = Mnemonic::parse_in(Language::English, phrase)
+			.map_err(|_| SecretStringError::InvalidPhrase)?;
+		let secret = derive_secret(&mnemonic.to_seed(password.unwrap_or("")), path)?;
+		Self::from_seed_slice(&secret).map(|pair| (pair, secret))
+	}
+
+	/// Return account identifier of this key pair.
+	pub fn account_id(&self) -> AccountId20 {
+		self.public().into()
+	}
+}
+
+impl CryptoType for EthereumPair {
+	type Pair = Self;
+}
+
+impl Pair for EthereumPair {
+	type Public = ecdsa::Public;
+	type Seed = [u8; 32];
+	type Signature = ecdsa::Signature;
+	type ProofOfPossession = <ecdsa::Pair as Pair>::ProofOfPossession;
+
+	fn from_phrase(
+		phrase: &str,
+		password: Option<&str>,
+	) -> Result<(Self, Self::Seed), SecretStringError> {
+		Self::from_mnemonic(phrase, password, DEFAULT_DERIVATION_PATH)
+	}
+
+	fn derive<Iter: Iterator<Item = DeriveJunction>>(
+		&self,
+		mut path: Iter,
+		seed: Option<Self::Seed>,
+	) -> Result<(Self, Option<Self::Seed>), DeriveError> {
+		// Ethereum keys are derived using BIP-32 paths, which are not compatible with Substrate
+		// junctions
+		match path.next() {
+			Some(_) => Err(DeriveError::SoftKeyInPath),
+			None => Ok((self.clone(), seed)),
+		}
+	}
+
+	fn from_seed_slice(seed: &[u8]) -> Result<Self, SecretStringError> {
+		ecdsa::Pair::from_seed_slice(seed).map(Self)
+	}
+
+	fn sign(&self, message: &[u8]) -> Self::Signature {
+		self.0.sign_prehashed(&keccak_256(message))
+	}
+
+	fn verify<M: AsRef<[u8]>>(sig: &Self::Signature, message: M, pubkey: &Self::Public) -> bool {
+		ecdsa::Pair::verify_prehashed(sig, &keccak_256(message.as_ref()), pubkey)
+	}
+
+	fn public(&self) -> Self::Public {
+		self.0.public()
+	}
+
+	fn from_string_with_seed(
+		s: &str,
+		password_override: Option<&str>,
+	) -> Result<(Self, Option<Self::Seed>), SecretStringError> {
+		let (secret_uri, password) = match s.split_once("///") {
+			Some((secret_uri, password)) => (secret_uri, Some(password)),
+			None => (s, None),
+		};
+		let password = password_override.or(password);
+
+		if let Some(private_key) = secret_uri.strip_prefix("0x") {
+			let seed: Self::Seed = hex::decode(private_key)
+				.map_err(|_| SecretStringError::InvalidSeed)?
+				.try_into()
+				.map_err(|_| SecretStringError::InvalidSeedLength)?;
+			ensure_valid_secret(&seed)?;
+			return Self::from_seed_slice(&seed).map(|pair| (pair, Some(seed)))
+		}
+
+		let (phrase, path) = match secret_uri.split_once('/') {
+			Some((phrase, path)) => (phrase, path),
+			None => (secret_uri, DEFAULT_DERIVATION_PATH),
+		};
+		Self::from_mnemonic(phrase, password, path).map(|(pair, seed)| (pair, Some(seed)))
+	}
+
+	fn to_raw_vec(&self) -> Vec<u8> {
+		self.0.to_raw_vec()
+	}
+}
+
+/// Derive secp256k1 private key from the BIP-39 seed, using BIP-32 derivation path.
+///
+/// Path must start with `m` and may contain hardened (`'` or `h` suffix) and normal indices.
+pub fn derive_secret(seed: &[u8], path: &str) -> Result<[u8; 32], SecretStringError> {
+	let mut indices = path.split('/');
+	if indices.next() != Some("m") {
+		return Err(SecretStringError::InvalidPath)
+	}
+
+	let (mut secret, mut chain_code) = hmac_sha512(b"Bitcoin seed", &[seed]);
+	ensure_valid_secret(&secret)?;
+	for index in indices {
+		let index = parse_index(index)?;
+		let (tweak, child_chain_code) = if index >= HARDENED_OFFSET {
+			hmac_sha512(&chain_code, &[&[0], &secret, &index.to_be_bytes()])
+		} else {
+			let public = ecdsa::Pair::from_seed_slice(&secret)?.public();
+			hmac_sha512(&chain_code, &[public.as_ref(), &index.to_be_bytes()])
+		};
+
+		let child = ensure_valid_secret(&tweak)? + ensure_valid_secret(&secret)?;
+		secret = child.to_bytes().into();
+		ensure_valid_secret(&secret)?;
+		chain_code = child_chain_code;
+	}
+
+	Ok(secret)
+}
+
+/// Compute HMAC-SHA512 of the concatenated `data`, split into two 32-byte halves.
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+	let mut mac =
+		Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length; qed");
+	data.iter().for_each(|data| mac.update(data));
+	let output = mac.finalize().into_bytes();
+
+	let (mut left, mut right) = ([0u8; 32], [0u8; 32]);
+	left.copy_from_slice(&output[..32]);
+	right.copy_from_slice(&output[32..]);
+	(left, right)
+}
+
+/// Parse single BIP-32 path index.
+fn parse_index(index: &str) -> Result<u32, SecretStringError> {
+	let (index, is_hardened) = match index.strip_suffix(['\'', 'h']) {
+		Some(index) => (index, true),
+		None => (index, false),
+	};
+	let index: u32 = index.parse().map_err(|_| SecretStringError::InvalidPath)?;
+	if index >= HARDENED_OFFSET {
+		return Err(SecretStringError::InvalidPath)
+	}
+
+	Ok(if is_hardened { index + HARDENED_OFFSET } else { index })
+}
+
+/// Ensure that the given bytes represent a valid (non-zero and less than the curve order)
+/// secp256k1 private key and return it as a scalar.
+fn ensure_valid_secret(secret: &[u8; 32]) -> Result<Scalar, SecretStringError> {
+	Option::<Scalar>::from(Scalar::from_repr((*secret).into()))
+		.filter(|scalar| !bool::from(scalar.is_zero()))
+		.ok_or(SecretStringError::InvalidSeed)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex_literal::hex;
+	use sp_runtime::traits::{IdentifyAccount, Verify};
+
+	const DEV_PHRASE: &str =
+		"bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+
+	const ALITH_PRIVATE_KEY: [u8; 32] =
+		hex!("5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d45af5bdc4da702133");
+	const ALITH_ADDRESS: [u8; 20] = hex!("f24FF3a9CF04c71Dbc94D0b566f7A27B94566cac");
+	const BALTATHAR_PRIVATE_KEY: [u8; 32] =
+		hex!("8075991ce870b93a8870eca0c0f91913d12f47948ca0fd25b49c6fa7cdbeee8b");
+	const BALTATHAR_ADDRESS: [u8; 20] = hex!("3Cd0A705a2DC65e5b1E1205896BaA2be8A07c6e0");
+
+	#[test]
+	fn key_pair_is_created_from_private_key() {
+		let pair =
+			EthereumPair::from_string(&format!("0x{}", hex::encode(ALITH_PRIVATE_KEY)), None)
+				.unwrap();
+		assert_eq!(pair.account_id(), AccountId20(ALITH_ADDRESS));
+		assert_eq!(pair.account_id().to_string(), "0xf24ff3a9cf04c71dbc94d0b566f7a27b94566cac");
+
+		assert_eq!(
+			EthereumPair::from_string("0x5fb92d6e98884f76de468fa3f6278f8807c48bebc13595d4", None)
+				.err(),
+			Some(SecretStringError::InvalidSeedLength),
+		);
+		assert_eq!(
+			EthereumPair::from_string(&format!("0x{}", "0".repeat(64)), None).err(),
+			Some(SecretStringError::InvalidSeed),
+		);
+	}
+
+	#[test]
+	fn key_pair_is_derived_from_mnemonic() {
+		// default derivation path
+		let (pair, seed) = EthereumPair::from_string_with_seed(DEV_PHRASE, None).unwrap();
+		assert_eq!(seed, Some(ALITH_PRIVATE_KEY));
+		assert_eq!(pair.account_id(), AccountId20(ALITH_ADDRESS));
+
+		// explicit derivation path
+		let (pair, seed) =
+			EthereumPair::from_string_with_seed(&format!("{DEV_PHRASE}/m/44'/60'/0'/0/1"), None)
+				.unwrap();
+		assert_eq!(seed, Some(BALTATHAR_PRIVATE_KEY));
+		assert_eq!(pair.account_id(), AccountId20(BALTATHAR_ADDRESS));
+
+		// `h` may be used to mark hardened indices
+		let pair =
+			EthereumPair::from_string(&format!("{DEV_PHRASE}/m/44h/60h/0h/0/1"), None).unwrap();
+		assert_eq!(pair.account_id(), AccountId20(BALTATHAR_ADDRESS));
+
+		// well-known Hardhat/Anvil development account
+		let pair = EthereumPair::from_string(
+			"test test test test test test test test test test test junk",
+			None,
+		)
+		.unwrap();
+		assert_eq!(
+			pair.account_id(),
+			AccountId20(hex!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266"))
+		);
+		assert_eq!(
+			pair.to_raw_vec(),
+			hex!("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80").to_vec(),
+		);
+	}
+
+	#[test]
+	fn password_changes_derived_key() {
+		let with_password = EthereumPair::from_string(&format!("{DEV_PHRASE}///secret"), None)
+			.unwrap()
+			.account_id();
+		assert_ne!(with_password, AccountId20(ALITH_ADDRESS));
+		assert_eq!(
+			EthereumPair::from_string(DEV_PHRASE, Some("secret")).unwrap().account_id(),
+			with_password,
+		);
+	}
+
+	#[test]
+	fn invalid_secret_uris_are_rejected() {
+		assert_eq!(
+			EthereumPair::from_string("not a valid mnemonic", None).err(),
+			Some(SecretStringError::InvalidPhrase),
+		);
+		assert_eq!(
+			EthereumPair::from_string(&format!("{DEV_PHRASE}/44'/60'/0'/0/0"), None).err(),
+			Some(SecretStringError::InvalidPath),
+		);
+		assert_eq!(
+			EthereumPair::from_string(&format!("{DEV_PHRASE}/m/44'/60'/x"), None).err(),
+			Some(SecretStringError::InvalidPath),
+		);
+		assert_eq!(
+			EthereumPair::from_string(&format!("{DEV_PHRASE}/m/2147483648"), None).err(),
+			Some(SecretStringError::InvalidPath),
+		);
+	}
+
+	#[test]
+	fn substrate_junctions_are_not_supported() {
+		let pair = EthereumPair::from_seed(&ALITH_PRIVATE_KEY);
+		assert!(pair.derive(std::iter::empty(), None).is_ok());
+		assert_eq!(
+			pair.derive(std::iter::once(DeriveJunction::hard("Alice")), None).err(),
+			Some(DeriveError::SoftKeyInPath),
+		);
+	}
+
+	#[test]
+	fn signature_matches_known_vector() {
+		// EIP-155 example transaction
+		let pair = EthereumPair::from_seed(&[0x46; 32]);
+		let signing_data = hex!(
+			"ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080"
+		);
+		assert_eq!(
+			keccak_256(&signing_data),
+			hex!("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"),
+		);
+
+		let signature = pair.sign(&signing_data);
+		assert_eq!(
+			signature.as_ref(),
+			hex!(
+				"28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276"
+				"67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+				"00"
+			),
+		);
+		assert!(EthereumPair::verify(&signature, signing_data, &pair.public()));
+		assert!(!EthereumPair::verify(&signature, b"other data", &pair.public()));
+
+		// regular `ecdsa::Pair` is using Blake2-256 hash, so it must fail
+		assert!(!ecdsa::Pair::verify(&signature, signing_data, &pair.public()));
+	}
+
+	#[test]
+	fn signature_is_verified_against_account_id() {
+		let sender = AccountId20(hex!("9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"));
+		let pair = EthereumPair::from_seed(&[0x46; 32]);
+		assert_eq!(pair.account_id(), sender);
+		assert_eq!(EthereumSigner::from(pair.public()).into_account(), sender);
+
+		let signature = EthereumSignature::from(pair.sign(b"payload"));
+		assert!(signature.verify(&b"payload"[..], &sender));
+		assert!(!signature.verify(&b"other payload"[..], &sender));
+		assert!(!signature.verify(&b"payload"[..], &AccountId20(ALITH_ADDRESS)));
+	}
+
+	#[test]
+	fn account_info_storage_key_is_computed_correctly() {
+		let key = AccountInfoStorageMapKeyProvider::final_key(&AccountId20(ALITH_ADDRESS));
+		// `twox_128("System") ++ twox_128("Account")`
+		assert_eq!(
+			key.0[..32],
+			hex!("26aa394eea5630e07c48ae0c9558cef7b99d880ec681799c0cf30e8886371da9"),
+		);
+		// `Blake2_128Concat` hasher appends the encoded key to its 16-byte hash
+		assert_eq!(key.0.len(), 32 + 16 + 20);
+		assert_eq!(key.0[48..], ALITH_ADDRESS);
+	}
+}
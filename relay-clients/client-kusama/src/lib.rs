@@ -17,21 +17,20 @@
 //! Types used to connect to the Kusama chain.
 
 pub mod codegen_runtime;
+pub mod runtime_apis;
 
-use bp_kusama::{AccountInfoStorageMapKeyProvider, KUSAMA_SYNCED_HEADERS_GRANDPA_INFO_METHOD};
+use bp_kusama::AccountInfoStorageMapKeyProvider;
 use bp_polkadot_core::SuffixedCommonTransactionExtensionExt;
-use codec::Encode;
+use relay_common_client::{
+	ChainWithTransactionExtension, ExplicitParams, TransactionExtensionParams,
+};
 use relay_substrate_client::{
 	Chain, ChainWithBalances, ChainWithGrandpa, ChainWithRuntimeVersion, ChainWithTransactions,
 	Error as SubstrateError, RelayChain, SignParam, SimpleRuntimeVersion, UnderlyingChainProvider,
 	UnsignedTransaction,
 };
-use sp_core::{storage::StorageKey, Pair};
-use sp_runtime::{
-	generic::SignedPayload,
-	traits::{FakeDispatchable, IdentifyAccount},
-	MultiAddress,
-};
+use sp_core::storage::StorageKey;
+use sp_runtime::{MultiAddress, MultiSignature};
 use sp_session::MembershipProof;
 use std::time::Duration;
 
@@ -61,9 +60,9 @@ impl UnderlyingChainProvider for Kusama {
 impl Chain for Kusama {
 	const NAME: &'static str = "Kusama";
 	const BEST_FINALIZED_HEADER_ID_METHOD: &'static str =
-		bp_kusama::BEST_FINALIZED_KUSAMA_HEADER_METHOD;
+		runtime_apis::kusama_finality_api::best_finalized::NAME;
 	const FREE_HEADERS_INTERVAL_METHOD: &'static str =
-		bp_kusama::FREE_HEADERS_INTERVAL_FOR_KUSAMA_METHOD;
+		runtime_apis::kusama_finality_api::free_headers_interval::NAME;
 	const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_secs(6);
 
 	type SignedBlock = bp_kusama::SignedBlock;
@@ -72,11 +71,25 @@ impl Chain for Kusama {
 
 impl ChainWithGrandpa for Kusama {
 	const SYNCED_HEADERS_GRANDPA_INFO_METHOD: &'static str =
-		KUSAMA_SYNCED_HEADERS_GRANDPA_INFO_METHOD;
+		runtime_apis::kusama_finality_api::synced_headers_grandpa_info::NAME;
 
 	type KeyOwnerProof = MembershipProof;
 }
 
+// The relay calls bridge runtime APIs of the chain with arguments and decodes their outputs, using
+// types below. Make sure they match types of APIs, generated from the bridged runtime metadata.
+const _: fn(
+	runtime_apis::kusama_finality_api::best_finalized::Output,
+) -> Option<bp_runtime::HeaderIdOf<bp_kusama::Kusama>> = |output| output;
+const _: fn(
+	runtime_apis::kusama_finality_api::free_headers_interval::Output,
+) -> Option<bp_runtime::BlockNumberOf<bp_kusama::Kusama>> = |output| output;
+const _: fn(
+	runtime_apis::kusama_finality_api::synced_headers_grandpa_info::Output,
+) -> Vec<
+	bp_header_chain::StoredHeaderGrandpaInfo<bp_runtime::HeaderOf<bp_kusama::Kusama>>,
+> = |output| output;
+
 impl ChainWithBalances for Kusama {
 	fn account_info_storage_key(account_id: &Self::AccountId) -> StorageKey {
 		AccountInfoStorageMapKeyProvider::final_key(account_id)
@@ -98,29 +111,44 @@ impl ChainWithTransactions for Kusama {
 		param: SignParam<Self>,
 		unsigned: UnsignedTransaction<Self>,
 	) -> Result<Self::SignedTransaction, SubstrateError> {
-		let raw_payload = SignedPayload::new(
-			FakeDispatchable::from(unsigned.call),
-			bp_kusama::TransactionExtension::from_params(
-				param.spec_version,
-				param.transaction_version,
-				unsigned.era,
-				param.genesis_hash,
-				unsigned.nonce,
-				unsigned.tip,
-				((), ()),
-			),
-		)?;
-
-		let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload));
-		let signer: sp_runtime::MultiSigner = param.signer.public().into();
-		let (call, extra, _) = raw_payload.deconstruct();
-
-		Ok(Self::SignedTransaction::new_signed(
-			call.deconstruct(),
-			signer.into_account().into(),
-			signature.into(),
-			extra,
-		))
+		relay_common_client::sign_transaction_with_extension(param, unsigned, None)
+	}
+}
+
+impl ChainWithTransactionExtension for Kusama {
+	type Address = Address;
+	type Signature = MultiSignature;
+	type TransactionExtension = bp_kusama::TransactionExtension;
+
+	fn transaction_extension(
+		params: TransactionExtensionParams<Self>,
+	) -> bp_kusama::TransactionExtension {
+		bp_kusama::TransactionExtension::from_params(
+			params.spec_version,
+			params.transaction_version,
+			params.era,
+			params.genesis_hash,
+			params.nonce,
+			params.tip,
+			((), ()),
+		)
+	}
+
+	fn explicit_params(
+		extension: &bp_kusama::TransactionExtension,
+	) -> Result<ExplicitParams<Self>, SubstrateError> {
+		let (_, _, _, _, era, nonce, _, tip, _) = extension.payload.clone();
+		Ok(ExplicitParams {
+			era,
+			nonce: nonce.0,
+			tip: tip.0,
+			metadata_hash_check: None,
+			bridge_extensions: Vec::new(),
+		})
+	}
+
+	fn signer_account(address: Address) -> Option<bp_kusama::AccountId> {
+		relay_common_client::multi_address_account(address)
 	}
 }
 
@@ -0,0 +1,773 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Autogenerated runtime API
+//! THIS FILE WAS AUTOGENERATED USING parity-bridges-common::runtime-codegen
+//! EXECUTED COMMAND: runtime-codegen --from-node-url wss://wss.api.moonbeam.network/
+//! --allow Utility::batch_all --allow BridgeKusama*
+
+#[allow(dead_code, unused_imports, non_camel_case_types, unreachable_patterns)]
+#[allow(clippy::all)]
+#[allow(rustdoc::broken_intra_doc_links)]
+pub mod api {
+	#[allow(unused_imports)]
+	mod root_mod {
+		pub use super::*;
+	}
+	pub mod runtime_types {
+		use super::runtime_types;
+		pub mod bp_header_chain {
+			use super::runtime_types;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct AuthoritySet {
+				pub authorities: ::subxt::ext::subxt_core::alloc::vec::Vec<(
+					runtime_types::sp_consensus_grandpa::app::Public,
+					::core::primitive::u64,
+				)>,
+				pub set_id: ::core::primitive::u64,
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum HeaderChainError {
+				#[codec(index = 0)]
+				UnknownHeader,
+				#[codec(index = 1)]
+				StorageProof(runtime_types::bp_runtime::storage_proof::StorageProofError),
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct HeaderFinalityInfo<_0, _1> {
+				pub finality_proof: _0,
+				pub new_verification_context: ::core::option::Option<_1>,
+			}
+		}
+		pub mod bp_messages {
+			use super::runtime_types;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct DeliveredMessages {
+				pub begin: ::core::primitive::u64,
+				pub end: ::core::primitive::u64,
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum MessagesOperatingMode {
+				#[codec(index = 0)]
+				Basic(runtime_types::bp_runtime::BasicOperatingMode),
+				#[codec(index = 1)]
+				RejectingOutboundMessages,
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct ReceivedMessages<_0, _1> {
+				pub lane: _1,
+				pub receive_results: ::subxt::ext::subxt_core::alloc::vec::Vec<(
+					::core::primitive::u64,
+					runtime_types::bp_messages::ReceptionResult<_0>,
+				)>,
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum ReceptionResult<_0> {
+				#[codec(index = 0)]
+				Dispatched(runtime_types::bp_runtime::messages::MessageDispatchResult<_0>),
+				#[codec(index = 1)]
+				InvalidNonce,
+				#[codec(index = 2)]
+				TooManyUnrewardedRelayers,
+				#[codec(index = 3)]
+				TooManyUnconfirmedMessages,
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum VerificationError {
+				#[codec(index = 0)]
+				EmptyMessageProof,
+				#[codec(index = 1)]
+				HeaderChain(runtime_types::bp_header_chain::HeaderChainError),
+				#[codec(index = 2)]
+				InboundLaneStorage(runtime_types::bp_runtime::storage_proof::StorageProofError),
+				#[codec(index = 3)]
+				InvalidMessageWeight,
+				#[codec(index = 4)]
+				MessagesCountMismatch,
+				#[codec(index = 5)]
+				MessageStorage(runtime_types::bp_runtime::storage_proof::StorageProofError),
+				#[codec(index = 6)]
+				MessageTooLarge,
+				#[codec(index = 7)]
+				OutboundLaneStorage(runtime_types::bp_runtime::storage_proof::StorageProofError),
+				#[codec(index = 8)]
+				StorageProof(runtime_types::bp_runtime::storage_proof::StorageProofError),
+				#[codec(index = 9)]
+				Other,
+			}
+		}
+		pub mod bp_runtime {
+			use super::runtime_types;
+			pub mod messages {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub struct MessageDispatchResult<_0> {
+					pub unspent_weight: ::sp_weights::Weight,
+					pub dispatch_level_result: _0,
+				}
+			}
+			pub mod storage_proof {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum StorageProofError {
+					#[codec(index = 0)]
+					UnableToGenerateTrieProof,
+					#[codec(index = 1)]
+					InvalidProof,
+					#[codec(index = 2)]
+					UnsortedEntries,
+					#[codec(index = 3)]
+					UnavailableKey,
+					#[codec(index = 4)]
+					EmptyVal,
+					#[codec(index = 5)]
+					DecodeError,
+					#[codec(index = 6)]
+					UnusedKey,
+					#[codec(index = 7)]
+					StorageRootMismatch,
+					#[codec(index = 8)]
+					StorageValueUnavailable,
+					#[codec(index = 9)]
+					DuplicateNodes,
+				}
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum BasicOperatingMode {
+				#[codec(index = 0)]
+				Normal,
+				#[codec(index = 1)]
+				Halted,
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum OwnedBridgeModuleError {
+				#[codec(index = 0)]
+				Halted,
+			}
+		}
+		pub mod moonbeam_runtime {
+			use super::runtime_types;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct BridgeRejectObsoleteHeadersAndMessages;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct Runtime;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum RuntimeCall {
+				#[codec(index = 30)]
+				Utility(runtime_types::pallet_utility::pallet::Call),
+				#[codec(index = 130)]
+				BridgeKusamaGrandpa(runtime_types::pallet_bridge_grandpa::pallet::Call),
+				#[codec(index = 131)]
+				BridgeKusamaParachains(runtime_types::pallet_bridge_parachains::pallet::Call),
+				#[codec(index = 132)]
+				BridgeKusamaMessages(runtime_types::pallet_bridge_messages::pallet::Call),
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum RuntimeError {
+				#[codec(index = 30)]
+				Utility(runtime_types::pallet_utility::pallet::Error),
+				#[codec(index = 130)]
+				BridgeKusamaGrandpa(runtime_types::pallet_bridge_grandpa::pallet::Error),
+				#[codec(index = 131)]
+				BridgeKusamaParachains(runtime_types::pallet_bridge_parachains::pallet::Error),
+				#[codec(index = 132)]
+				BridgeKusamaMessages(runtime_types::pallet_bridge_messages::pallet::Error),
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum RuntimeEvent {
+				#[codec(index = 30)]
+				Utility(runtime_types::pallet_utility::pallet::Event),
+				#[codec(index = 130)]
+				BridgeKusamaGrandpa(runtime_types::pallet_bridge_grandpa::pallet::Event),
+				#[codec(index = 131)]
+				BridgeKusamaParachains(runtime_types::pallet_bridge_parachains::pallet::Event),
+				#[codec(index = 132)]
+				BridgeKusamaMessages(runtime_types::pallet_bridge_messages::pallet::Event),
+			}
+		}
+		pub mod frame_metadata_hash_extension {
+			use super::runtime_types;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct CheckMetadataHash {
+				pub mode: runtime_types::frame_metadata_hash_extension::Mode,
+			}
+			#[derive(
+				::codec::Decode,
+				::codec::DecodeWithMemTracking,
+				::codec::Encode,
+				Clone,
+				Debug,
+				Eq,
+				PartialEq,
+				scale_info::TypeInfo,
+			)]
+			pub enum Mode {
+				#[codec(index = 0)]
+				Disabled,
+				#[codec(index = 1)]
+				Enabled,
+			}
+		}
+		pub mod frame_system {
+			use super::runtime_types;
+			pub mod extensions {
+				use super::runtime_types;
+				pub mod check_genesis {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub struct CheckGenesis;
+				}
+				pub mod check_mortality {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub struct CheckMortality(pub ::sp_runtime::generic::Era);
+				}
+				pub mod check_non_zero_sender {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub struct CheckNonZeroSender;
+				}
+				pub mod check_nonce {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub struct CheckNonce(#[codec(compact)] pub ::core::primitive::u32);
+				}
+				pub mod check_spec_version {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub struct CheckSpecVersion;
+				}
+				pub mod check_tx_version {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub struct CheckTxVersion;
+				}
+				pub mod check_weight {
+					use super::runtime_types;
+					#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+					pub struct CheckWeight;
+				}
+			}
+		}
+		pub mod pallet_bridge_grandpa {
+			use super::runtime_types;
+			pub mod pallet {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum Call {
+					#[codec(index = 0)]
+					submit_finality_proof {
+						finality_target: ::subxt::ext::subxt_core::alloc::boxed::Box<
+							::sp_runtime::generic::Header<
+								::core::primitive::u32,
+								::sp_runtime::traits::BlakeTwo256,
+							>,
+						>,
+						justification: ::bp_header_chain::justification::GrandpaJustification<
+							::sp_runtime::generic::Header<
+								::core::primitive::u32,
+								::sp_runtime::traits::BlakeTwo256,
+							>,
+						>,
+					},
+					#[codec(index = 1)]
+					initialize {
+						init_data: ::bp_header_chain::InitializationData<
+							::sp_runtime::generic::Header<
+								::core::primitive::u32,
+								::sp_runtime::traits::BlakeTwo256,
+							>,
+						>,
+					},
+					#[codec(index = 2)]
+					set_owner {
+						new_owner: ::core::option::Option<::bp_ethereum_common::AccountId20>,
+					},
+					#[codec(index = 3)]
+					set_operating_mode {
+						operating_mode: runtime_types::bp_runtime::BasicOperatingMode,
+					},
+					#[codec(index = 4)]
+					submit_finality_proof_ex {
+						finality_target: ::subxt::ext::subxt_core::alloc::boxed::Box<
+							::sp_runtime::generic::Header<
+								::core::primitive::u32,
+								::sp_runtime::traits::BlakeTwo256,
+							>,
+						>,
+						justification: ::bp_header_chain::justification::GrandpaJustification<
+							::sp_runtime::generic::Header<
+								::core::primitive::u32,
+								::sp_runtime::traits::BlakeTwo256,
+							>,
+						>,
+						current_set_id: ::core::primitive::u64,
+						is_free_execution_expected: ::core::primitive::bool,
+					},
+					#[codec(index = 5)]
+					force_set_pallet_state {
+						new_current_set_id: ::core::primitive::u64,
+						new_authorities: ::subxt::ext::subxt_core::alloc::vec::Vec<(
+							runtime_types::sp_consensus_grandpa::app::Public,
+							::core::primitive::u64,
+						)>,
+						new_best_header: ::subxt::ext::subxt_core::alloc::boxed::Box<
+							::sp_runtime::generic::Header<
+								::core::primitive::u32,
+								::sp_runtime::traits::BlakeTwo256,
+							>,
+						>,
+					},
+				}
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum Error {
+					#[codec(index = 0)]
+					InvalidJustification,
+					#[codec(index = 1)]
+					InvalidAuthoritySet,
+					#[codec(index = 2)]
+					OldHeader,
+					#[codec(index = 3)]
+					UnsupportedScheduledChange,
+					#[codec(index = 4)]
+					NotInitialized,
+					#[codec(index = 5)]
+					AlreadyInitialized,
+					#[codec(index = 6)]
+					TooManyAuthoritiesInSet,
+					#[codec(index = 7)]
+					BridgeModule(runtime_types::bp_runtime::OwnedBridgeModuleError),
+					#[codec(index = 8)]
+					InvalidAuthoritySetId,
+					#[codec(index = 9)]
+					FreeHeadersLimitExceded,
+					#[codec(index = 10)]
+					BelowFreeHeaderInterval,
+					#[codec(index = 11)]
+					HeaderOverflowLimits,
+				}
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum Event {
+					#[codec(index = 0)]
+					UpdatedBestFinalizedHeader {
+						number: ::core::primitive::u32,
+						hash: ::subxt::ext::subxt_core::utils::H256,
+						grandpa_info: runtime_types::bp_header_chain::HeaderFinalityInfo<
+							::bp_header_chain::justification::GrandpaJustification<
+								::sp_runtime::generic::Header<
+									::core::primitive::u32,
+									::sp_runtime::traits::BlakeTwo256,
+								>,
+							>,
+							runtime_types::bp_header_chain::AuthoritySet,
+						>,
+					},
+				}
+			}
+		}
+		pub mod pallet_bridge_messages {
+			use super::runtime_types;
+			pub mod lanes_manager {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum LanesManagerError {
+					#[codec(index = 0)]
+					InboundLaneAlreadyExists,
+					#[codec(index = 1)]
+					OutboundLaneAlreadyExists,
+					#[codec(index = 2)]
+					UnknownInboundLane,
+					#[codec(index = 3)]
+					UnknownOutboundLane,
+					#[codec(index = 4)]
+					ClosedInboundLane,
+					#[codec(index = 5)]
+					ClosedOutboundLane,
+					#[codec(index = 6)]
+					LaneDispatcherInactive,
+				}
+			}
+			pub mod outbound_lane {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum ReceptionConfirmationError {
+					#[codec(index = 0)]
+					FailedToConfirmFutureMessages,
+					#[codec(index = 1)]
+					EmptyUnrewardedRelayerEntry,
+					#[codec(index = 2)]
+					NonConsecutiveUnrewardedRelayerEntries,
+					#[codec(index = 3)]
+					TryingToConfirmMoreMessagesThanExpected,
+				}
+			}
+			pub mod pallet {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum Call {
+					#[codec(index = 0)]
+					set_owner {
+						new_owner: ::core::option::Option<::bp_ethereum_common::AccountId20>,
+					},
+					#[codec(index = 1)]
+					set_operating_mode {
+						operating_mode: runtime_types::bp_messages::MessagesOperatingMode,
+					},
+					#[codec(index = 2)]
+					receive_messages_proof {
+						relayer_id_at_bridged_chain: ::bp_ethereum_common::AccountId20,
+						proof: ::subxt::ext::subxt_core::alloc::boxed::Box<
+							::bp_messages::target_chain::FromBridgedChainMessagesProof<
+								::subxt::ext::subxt_core::utils::H256,
+								::bp_messages::HashedLaneId,
+							>,
+						>,
+						messages_count: ::core::primitive::u32,
+						dispatch_weight: ::sp_weights::Weight,
+					},
+					#[codec(index = 3)]
+					receive_messages_delivery_proof {
+						proof: ::bp_messages::source_chain::FromBridgedChainMessagesDeliveryProof<
+							::subxt::ext::subxt_core::utils::H256,
+							::bp_messages::HashedLaneId,
+						>,
+						relayers_state: ::bp_messages::UnrewardedRelayersState,
+					},
+				}
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum Error {
+					#[codec(index = 0)]
+                    NotOperatingNormally,
+                    #[codec(index = 1)]
+                    LanesManager(
+                        runtime_types::pallet_bridge_messages::lanes_manager::LanesManagerError,
+                    ),
+                    #[codec(index = 2)]
+                    MessageRejectedByPallet(
+                        runtime_types::bp_messages::VerificationError,
+                    ),
+                    #[codec(index = 3)]
+                    TooManyMessagesInTheProof,
+                    #[codec(index = 4)]
+                    InvalidMessagesProof,
+                    #[codec(index = 5)]
+                    InvalidMessagesDeliveryProof,
+                    #[codec(index = 6)]
+                    InvalidUnrewardedRelayersState,
+                    #[codec(index = 7)]
+                    InsufficientDispatchWeight,
+                    #[codec(index = 8)]
+                    ReceptionConfirmation(
+                        runtime_types::pallet_bridge_messages::outbound_lane::ReceptionConfirmationError,
+                    ),
+                    #[codec(index = 9)]
+                    BridgeModule(runtime_types::bp_runtime::OwnedBridgeModuleError),
+                }
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum Event {
+					#[codec(index = 0)]
+                    MessageAccepted {
+                        lane_id: ::bp_messages::HashedLaneId,
+                        nonce: ::core::primitive::u64,
+                    },
+                    #[codec(index = 1)]
+                    MessagesReceived(
+                        runtime_types::bp_messages::ReceivedMessages<
+                            runtime_types::pallet_xcm_bridge::dispatcher::XcmBlobMessageDispatchResult,
+                            ::bp_messages::HashedLaneId,
+                        >,
+                    ),
+                    #[codec(index = 2)]
+                    MessagesDelivered {
+                        lane_id: ::bp_messages::HashedLaneId,
+                        messages: runtime_types::bp_messages::DeliveredMessages,
+                    },
+                }
+			}
+		}
+		pub mod pallet_bridge_parachains {
+			use super::runtime_types;
+			pub mod pallet {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum Call {
+					#[codec(index = 0)]
+					submit_parachain_heads {
+						at_relay_block:
+							(::core::primitive::u32, ::subxt::ext::subxt_core::utils::H256),
+						parachains: ::subxt::ext::subxt_core::alloc::vec::Vec<(
+							::bp_polkadot_core::parachains::ParaId,
+							::subxt::ext::subxt_core::utils::H256,
+						)>,
+						parachain_heads_proof: ::bp_polkadot_core::parachains::ParaHeadsProof,
+					},
+					#[codec(index = 1)]
+					set_owner {
+						new_owner: ::core::option::Option<::bp_ethereum_common::AccountId20>,
+					},
+					#[codec(index = 2)]
+					set_operating_mode {
+						operating_mode: runtime_types::bp_runtime::BasicOperatingMode,
+					},
+					#[codec(index = 3)]
+					submit_parachain_heads_ex {
+						at_relay_block:
+							(::core::primitive::u32, ::subxt::ext::subxt_core::utils::H256),
+						parachains: ::subxt::ext::subxt_core::alloc::vec::Vec<(
+							::bp_polkadot_core::parachains::ParaId,
+							::subxt::ext::subxt_core::utils::H256,
+						)>,
+						parachain_heads_proof: ::bp_polkadot_core::parachains::ParaHeadsProof,
+						is_free_execution_expected: ::core::primitive::bool,
+					},
+				}
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum Error {
+					#[codec(index = 0)]
+					UnknownRelayChainBlock,
+					#[codec(index = 1)]
+					InvalidRelayChainBlockNumber,
+					#[codec(index = 2)]
+					HeaderChainStorageProof(runtime_types::bp_header_chain::HeaderChainError),
+					#[codec(index = 3)]
+					BridgeModule(runtime_types::bp_runtime::OwnedBridgeModuleError),
+				}
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum Event {
+					#[codec(index = 0)]
+					UntrackedParachainRejected { parachain: ::bp_polkadot_core::parachains::ParaId },
+					#[codec(index = 1)]
+					MissingParachainHead { parachain: ::bp_polkadot_core::parachains::ParaId },
+					#[codec(index = 2)]
+					IncorrectParachainHeadHash {
+						parachain: ::bp_polkadot_core::parachains::ParaId,
+						parachain_head_hash: ::subxt::ext::subxt_core::utils::H256,
+						actual_parachain_head_hash: ::subxt::ext::subxt_core::utils::H256,
+					},
+					#[codec(index = 3)]
+					RejectedObsoleteParachainHead {
+						parachain: ::bp_polkadot_core::parachains::ParaId,
+						parachain_head_hash: ::subxt::ext::subxt_core::utils::H256,
+					},
+					#[codec(index = 4)]
+					RejectedLargeParachainHead {
+						parachain: ::bp_polkadot_core::parachains::ParaId,
+						parachain_head_hash: ::subxt::ext::subxt_core::utils::H256,
+						parachain_head_size: ::core::primitive::u32,
+					},
+					#[codec(index = 5)]
+					UpdatedParachainHead {
+						parachain: ::bp_polkadot_core::parachains::ParaId,
+						parachain_head_hash: ::subxt::ext::subxt_core::utils::H256,
+					},
+				}
+			}
+		}
+		pub mod pallet_transaction_payment {
+			use super::runtime_types;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct ChargeTransactionPayment(#[codec(compact)] pub ::core::primitive::u128);
+		}
+		pub mod pallet_utility {
+			use super::runtime_types;
+			pub mod pallet {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum Call {
+					#[codec(index = 2)]
+					batch_all {
+						calls: ::subxt::ext::subxt_core::alloc::vec::Vec<
+							runtime_types::moonbeam_runtime::RuntimeCall,
+						>,
+					},
+				}
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum Error {
+					#[codec(index = 0)]
+					TooManyCalls,
+				}
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum Event {
+					#[codec(index = 0)]
+					BatchInterrupted {
+						index: ::core::primitive::u32,
+						error: runtime_types::sp_runtime::DispatchError,
+					},
+					#[codec(index = 1)]
+					BatchCompleted,
+					#[codec(index = 2)]
+					BatchCompletedWithErrors,
+					#[codec(index = 3)]
+					ItemCompleted,
+					#[codec(index = 4)]
+					ItemFailed { error: runtime_types::sp_runtime::DispatchError },
+					#[codec(index = 5)]
+					DispatchedAs {
+						result:
+							::core::result::Result<(), runtime_types::sp_runtime::DispatchError>,
+					},
+					#[codec(index = 6)]
+					IfElseMainSuccess,
+					#[codec(index = 7)]
+					IfElseFallbackCalled { main_error: runtime_types::sp_runtime::DispatchError },
+				}
+			}
+		}
+		pub mod pallet_xcm_bridge {
+			use super::runtime_types;
+			pub mod dispatcher {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum XcmBlobMessageDispatchResult {
+					#[codec(index = 0)]
+					InvalidPayload,
+					#[codec(index = 1)]
+					Dispatched,
+					#[codec(index = 2)]
+					NotDispatched,
+				}
+			}
+		}
+		pub mod sp_arithmetic {
+			use super::runtime_types;
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum ArithmeticError {
+				#[codec(index = 0)]
+				Underflow,
+				#[codec(index = 1)]
+				Overflow,
+				#[codec(index = 2)]
+				DivisionByZero,
+			}
+		}
+		pub mod sp_consensus_grandpa {
+			use super::runtime_types;
+			pub mod app {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub struct Public(pub [::core::primitive::u8; 32usize]);
+			}
+		}
+		pub mod sp_runtime {
+			use super::runtime_types;
+			pub mod proving_trie {
+				use super::runtime_types;
+				#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+				pub enum TrieError {
+					#[codec(index = 0)]
+					InvalidStateRoot,
+					#[codec(index = 1)]
+					IncompleteDatabase,
+					#[codec(index = 2)]
+					ValueAtIncompleteKey,
+					#[codec(index = 3)]
+					DecoderError,
+					#[codec(index = 4)]
+					InvalidHash,
+					#[codec(index = 5)]
+					DuplicateKey,
+					#[codec(index = 6)]
+					ExtraneousNode,
+					#[codec(index = 7)]
+					ExtraneousValue,
+					#[codec(index = 8)]
+					ExtraneousHashReference,
+					#[codec(index = 9)]
+					InvalidChildReference,
+					#[codec(index = 10)]
+					ValueMismatch,
+					#[codec(index = 11)]
+					IncompleteProof,
+					#[codec(index = 12)]
+					RootMismatch,
+					#[codec(index = 13)]
+					DecodeError,
+				}
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum DispatchError {
+				#[codec(index = 0)]
+				Other,
+				#[codec(index = 1)]
+				CannotLookup,
+				#[codec(index = 2)]
+				BadOrigin,
+				#[codec(index = 3)]
+				Module(runtime_types::sp_runtime::ModuleError),
+				#[codec(index = 4)]
+				ConsumerRemaining,
+				#[codec(index = 5)]
+				NoProviders,
+				#[codec(index = 6)]
+				TooManyConsumers,
+				#[codec(index = 7)]
+				Token(runtime_types::sp_runtime::TokenError),
+				#[codec(index = 8)]
+				Arithmetic(runtime_types::sp_arithmetic::ArithmeticError),
+				#[codec(index = 9)]
+				Transactional(runtime_types::sp_runtime::TransactionalError),
+				#[codec(index = 10)]
+				Exhausted,
+				#[codec(index = 11)]
+				Corruption,
+				#[codec(index = 12)]
+				Unavailable,
+				#[codec(index = 13)]
+				RootNotAllowed,
+				#[codec(index = 14)]
+				Trie(runtime_types::sp_runtime::proving_trie::TrieError),
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub struct ModuleError {
+				pub index: ::core::primitive::u8,
+				pub error: [::core::primitive::u8; 4usize],
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum TokenError {
+				#[codec(index = 0)]
+				FundsUnavailable,
+				#[codec(index = 1)]
+				OnlyProvider,
+				#[codec(index = 2)]
+				BelowMinimum,
+				#[codec(index = 3)]
+				CannotCreate,
+				#[codec(index = 4)]
+				UnknownAsset,
+				#[codec(index = 5)]
+				Frozen,
+				#[codec(index = 6)]
+				Unsupported,
+				#[codec(index = 7)]
+				CannotCreateHold,
+				#[codec(index = 8)]
+				NotExpendable,
+				#[codec(index = 9)]
+				Blocked,
+			}
+			#[derive(::codec::Decode, ::codec::Encode, Clone, Debug, PartialEq)]
+			pub enum TransactionalError {
+				#[codec(index = 0)]
+				LimitReached,
+				#[codec(index = 1)]
+				NoLayer,
+			}
+		}
+	}
+}
+/// `spec_version` of the runtime and the RFC-78 hash of its metadata, this code has been
+/// generated from. It is used by the `CheckMetadataHash` transaction extension.
+pub const METADATA_HASH: Option<(u32, [u8; 32])> = None;
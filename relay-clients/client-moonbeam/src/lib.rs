@@ -0,0 +1,197 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types used to connect to the Moonbeam parachain.
+
+pub mod codegen_runtime;
+pub mod runtime_apis;
+
+use bp_messages::{InboundMessageDetails, MessageNonce, MessagePayload, OutboundMessageDetails};
+use bp_moonbeam::AVERAGE_BLOCK_INTERVAL;
+use bp_polkadot_core::{SuffixedCommonTransactionExtension, SuffixedCommonTransactionExtensionExt};
+use relay_common_client::{
+	ChainWithMetadataHash, ChainWithTransactionExtension, ExplicitParams, SupportedRuntime,
+	TransactionExtensionParams,
+};
+use relay_ethereum_common_client::{AccountId20, EthereumPair, EthereumSignature};
+use relay_substrate_client::{
+	calls::UtilityCall as MockUtilityCall, Chain, ChainWithBalances, ChainWithMessages,
+	ChainWithRuntimeVersion, ChainWithTransactions, ChainWithUtilityPallet,
+	Error as SubstrateError, MockedRuntimeUtilityPallet, SignParam, SimpleRuntimeVersion,
+	UnderlyingChainProvider, UnsignedTransaction,
+};
+use sp_core::storage::StorageKey;
+use sp_runtime::OpaqueExtrinsic;
+use std::time::Duration;
+
+pub use codegen_runtime::api::runtime_types;
+use runtime_types::frame_metadata_hash_extension::Mode;
+
+use bp_runtime::extensions::{
+	BridgeRejectObsoleteHeadersAndMessages, GenericTransactionExtensionSchema,
+};
+
+pub type CheckMetadataHash = GenericTransactionExtensionSchema<Mode, Option<[u8; 32]>>;
+pub type StorageWeightReclaim = GenericTransactionExtensionSchema<(), ()>;
+
+pub type TransactionExtension = SuffixedCommonTransactionExtension<(
+	BridgeRejectObsoleteHeadersAndMessages,
+	CheckMetadataHash,
+	StorageWeightReclaim,
+)>;
+
+pub type RuntimeCall = runtime_types::moonbeam_runtime::RuntimeCall;
+pub type BridgeMessagesCall = runtime_types::pallet_bridge_messages::pallet::Call;
+pub type BridgeGrandpaCall = runtime_types::pallet_bridge_grandpa::pallet::Call;
+pub type BridgeParachainCall = runtime_types::pallet_bridge_parachains::pallet::Call;
+type UtilityCall = runtime_types::pallet_utility::pallet::Call;
+
+/// Moonbeam chain definition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Moonbeam;
+
+impl UnderlyingChainProvider for Moonbeam {
+	type Chain = bp_moonbeam::Moonbeam;
+}
+
+impl Chain for Moonbeam {
+	const NAME: &'static str = "Moonbeam";
+	const BEST_FINALIZED_HEADER_ID_METHOD: &'static str =
+		runtime_apis::moonbeam_finality_api::best_finalized::NAME;
+	const FREE_HEADERS_INTERVAL_METHOD: &'static str =
+		runtime_apis::moonbeam_finality_api::free_headers_interval::NAME;
+	const AVERAGE_BLOCK_INTERVAL: Duration = AVERAGE_BLOCK_INTERVAL;
+
+	type SignedBlock = bp_moonbeam::SignedBlock;
+	type Call = RuntimeCall;
+}
+
+impl ChainWithBalances for Moonbeam {
+	fn account_info_storage_key(account_id: &Self::AccountId) -> StorageKey {
+		bp_moonbeam::AccountInfoStorageMapKeyProvider::final_key(account_id)
+	}
+}
+
+impl From<MockUtilityCall<RuntimeCall>> for RuntimeCall {
+	fn from(value: MockUtilityCall<RuntimeCall>) -> RuntimeCall {
+		match value {
+			MockUtilityCall::batch_all(calls) =>
+				RuntimeCall::Utility(UtilityCall::batch_all { calls }),
+		}
+	}
+}
+
+impl ChainWithUtilityPallet for Moonbeam {
+	type UtilityPallet = MockedRuntimeUtilityPallet<RuntimeCall>;
+}
+
+impl ChainWithTransactions for Moonbeam {
+	type AccountKeyPair = EthereumPair;
+	type SignedTransaction = OpaqueExtrinsic;
+
+	fn sign_transaction(
+		param: SignParam<Self>,
+		unsigned: UnsignedTransaction<Self>,
+	) -> Result<Self::SignedTransaction, SubstrateError> {
+		relay_common_client::sign_transaction(SUPPORTED_RUNTIMES, param, unsigned)
+	}
+}
+
+impl ChainWithMetadataHash for Moonbeam {
+	const METADATA_HASH: Option<(u32, [u8; 32])> = codegen_runtime::METADATA_HASH;
+}
+
+const SUPPORTED_RUNTIMES: &[SupportedRuntime<Moonbeam>] = &[SupportedRuntime {
+	version: RUNTIME_VERSION,
+	sign: relay_common_client::sign_opaque_transaction,
+}];
+
+impl ChainWithTransactionExtension for Moonbeam {
+	type Address = AccountId20;
+	type Signature = EthereumSignature;
+	type TransactionExtension = TransactionExtension;
+
+	fn transaction_extension(params: TransactionExtensionParams<Self>) -> TransactionExtension {
+		let metadata_hash_mode =
+			if params.metadata_hash.is_some() { Mode::Enabled } else { Mode::Disabled };
+		TransactionExtension::from_params(
+			params.spec_version,
+			params.transaction_version,
+			params.era,
+			params.genesis_hash,
+			params.nonce,
+			params.tip,
+			(((), metadata_hash_mode, ()), ((), params.metadata_hash, ())),
+		)
+	}
+
+	fn explicit_params(
+		extension: &TransactionExtension,
+	) -> Result<ExplicitParams<Self>, SubstrateError> {
+		let (_, _, _, _, era, nonce, _, tip, (bridge_reject_obsolete, metadata_hash_mode, _)) =
+			extension.payload.clone();
+		Ok(ExplicitParams {
+			era,
+			nonce: nonce.0,
+			tip: tip.0,
+			metadata_hash_check: Some(metadata_hash_mode == Mode::Enabled),
+			bridge_extensions: vec![(
+				"BridgeRejectObsoleteHeadersAndMessages",
+				format!("{bridge_reject_obsolete:?}"),
+			)],
+		})
+	}
+
+	fn signer_account(address: AccountId20) -> Option<AccountId20> {
+		Some(address)
+	}
+}
+
+impl ChainWithMessages for Moonbeam {
+	const TO_CHAIN_MESSAGE_DETAILS_METHOD: &'static str =
+		runtime_apis::to_moonbeam_outbound_lane_api::message_details::NAME;
+	const FROM_CHAIN_MESSAGE_DETAILS_METHOD: &'static str =
+		runtime_apis::from_moonbeam_inbound_lane_api::message_details::NAME;
+}
+
+// The relay calls bridge runtime APIs of the chain with arguments and decodes their outputs, using
+// types below. Make sure they match types of APIs, generated from the bridged runtime metadata.
+const _: fn(
+	runtime_apis::moonbeam_finality_api::best_finalized::Output,
+) -> Option<bp_runtime::HeaderIdOf<bp_moonbeam::Moonbeam>> = |output| output;
+const _: fn(
+	runtime_apis::moonbeam_finality_api::free_headers_interval::Output,
+) -> Option<bp_runtime::BlockNumberOf<bp_moonbeam::Moonbeam>> = |output| output;
+const _: fn(
+	(bp_messages::HashedLaneId, MessageNonce, MessageNonce),
+) -> runtime_apis::to_moonbeam_outbound_lane_api::message_details::Input = |input| input;
+const _: fn(
+	runtime_apis::to_moonbeam_outbound_lane_api::message_details::Output,
+) -> Vec<OutboundMessageDetails> = |output| output;
+const _: fn(
+	(bp_messages::HashedLaneId, Vec<(MessagePayload, OutboundMessageDetails)>),
+) -> runtime_apis::from_moonbeam_inbound_lane_api::message_details::Input = |input| input;
+const _: fn(
+	runtime_apis::from_moonbeam_inbound_lane_api::message_details::Output,
+) -> Vec<InboundMessageDetails> = |output| output;
+
+impl ChainWithRuntimeVersion for Moonbeam {
+	const RUNTIME_VERSION: Option<SimpleRuntimeVersion> = Some(RUNTIME_VERSION);
+}
+
+/// Version of the runtime that the `codegen_runtime` has been generated for.
+const RUNTIME_VERSION: SimpleRuntimeVersion =
+	SimpleRuntimeVersion { spec_version: 3_900, transaction_version: 3 };
@@ -17,20 +17,21 @@
 //! Types used to connect to the Polkadot Bulletin chain.
 
 mod codegen_runtime;
+pub mod runtime_apis;
 
-use bp_polkadot_bulletin::POLKADOT_BULLETIN_SYNCED_HEADERS_GRANDPA_INFO_METHOD;
-use codec::Encode;
+use bp_messages::{InboundMessageDetails, MessageNonce, MessagePayload, OutboundMessageDetails};
+use codec::{Compact, Decode, Encode};
+use frame_support::Blake2_128Concat;
+use relay_common_client::{
+	ChainWithTransactionExtension, ExplicitParams, TransactionExtensionParams,
+};
 use relay_substrate_client::{
 	Chain, ChainWithBalances, ChainWithGrandpa, ChainWithMessages, ChainWithRuntimeVersion,
 	ChainWithTransactions, Error as SubstrateError, SignParam, SimpleRuntimeVersion,
 	UnderlyingChainProvider, UnsignedTransaction,
 };
-use sp_core::{storage::StorageKey, Pair};
-use sp_runtime::{
-	generic::SignedPayload,
-	traits::{FakeDispatchable, IdentifyAccount},
-	MultiAddress,
-};
+use sp_core::storage::{StorageData, StorageKey};
+use sp_runtime::{generic::Era, MultiAddress, MultiSignature};
 use sp_session::MembershipProof;
 use std::time::Duration;
 
@@ -70,9 +71,9 @@ impl UnderlyingChainProvider for PolkadotBulletin {
 impl Chain for PolkadotBulletin {
 	const NAME: &'static str = "PolkadotBulletin";
 	const BEST_FINALIZED_HEADER_ID_METHOD: &'static str =
-		bp_polkadot_bulletin::BEST_FINALIZED_POLKADOT_BULLETIN_HEADER_METHOD;
+		runtime_apis::polkadot_bulletin_finality_api::best_finalized::NAME;
 	const FREE_HEADERS_INTERVAL_METHOD: &'static str =
-		bp_polkadot_bulletin::FREE_HEADERS_INTERVAL_FOR_POLKADOT_BULLETIN_METHOD;
+		runtime_apis::polkadot_bulletin_finality_api::free_headers_interval::NAME;
 	const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_secs(6);
 
 	type SignedBlock = bp_polkadot_bulletin::SignedBlock;
@@ -81,25 +82,89 @@ impl Chain for PolkadotBulletin {
 
 impl ChainWithGrandpa for PolkadotBulletin {
 	const SYNCED_HEADERS_GRANDPA_INFO_METHOD: &'static str =
-		POLKADOT_BULLETIN_SYNCED_HEADERS_GRANDPA_INFO_METHOD;
+		runtime_apis::polkadot_bulletin_finality_api::synced_headers_grandpa_info::NAME;
 
 	type KeyOwnerProof = MembershipProof;
 }
 
 impl ChainWithMessages for PolkadotBulletin {
 	const TO_CHAIN_MESSAGE_DETAILS_METHOD: &'static str =
-		bp_polkadot_bulletin::TO_POLKADOT_BULLETIN_MESSAGE_DETAILS_METHOD;
+		runtime_apis::to_polkadot_bulletin_outbound_lane_api::message_details::NAME;
 	const FROM_CHAIN_MESSAGE_DETAILS_METHOD: &'static str =
-		bp_polkadot_bulletin::FROM_POLKADOT_BULLETIN_MESSAGE_DETAILS_METHOD;
+		runtime_apis::from_polkadot_bulletin_inbound_lane_api::message_details::NAME;
 }
 
+// The relay calls bridge runtime APIs of the chain with arguments and decodes their outputs, using
+// types below. Make sure they match types of APIs, generated from the bridged runtime metadata.
+const _: fn(
+	runtime_apis::polkadot_bulletin_finality_api::best_finalized::Output,
+) -> Option<bp_runtime::HeaderIdOf<bp_polkadot_bulletin::PolkadotBulletin>> = |output| output;
+const _: fn(
+	runtime_apis::polkadot_bulletin_finality_api::free_headers_interval::Output,
+) -> Option<bp_runtime::BlockNumberOf<bp_polkadot_bulletin::PolkadotBulletin>> = |output| output;
+const _: fn(
+	runtime_apis::polkadot_bulletin_finality_api::synced_headers_grandpa_info::Output,
+) -> Vec<
+	bp_header_chain::StoredHeaderGrandpaInfo<
+		bp_runtime::HeaderOf<bp_polkadot_bulletin::PolkadotBulletin>,
+	>,
+> = |output| output;
+const _: fn(
+	(bp_messages::LegacyLaneId, MessageNonce, MessageNonce),
+) -> runtime_apis::to_polkadot_bulletin_outbound_lane_api::message_details::Input = |input| input;
+const _: fn(
+	runtime_apis::to_polkadot_bulletin_outbound_lane_api::message_details::Output,
+) -> Vec<OutboundMessageDetails> = |output| output;
+const _: fn(
+	(bp_messages::LegacyLaneId, Vec<(MessagePayload, OutboundMessageDetails)>),
+) -> runtime_apis::from_polkadot_bulletin_inbound_lane_api::message_details::Input = |input| input;
+const _: fn(
+	runtime_apis::from_polkadot_bulletin_inbound_lane_api::message_details::Output,
+) -> Vec<InboundMessageDetails> = |output| output;
+
+/// Polkadot Bulletin chain has no balances and its transactions are fee-less, so the relay is not
+/// spending any funds there. Bridge transactions are only accepted from relayers, registered at the
+/// `RelayerSet` pallet, so the relayer authorization (see [`is_relayer_authorized`]) is what needs
+/// to be tracked instead of the relayer balance.
 impl ChainWithBalances for PolkadotBulletin {
 	fn account_info_storage_key(_account_id: &Self::AccountId) -> StorageKey {
-		// no balances at this chain
+		// the empty key marks the chain without balances (see `relay_common_client::has_balances`)
 		StorageKey(vec![])
 	}
 }
 
+/// Returns storage key of the `RelayerSet::Relayers` entry of given account.
+pub fn relayer_storage_key(account_id: &bp_polkadot_bulletin::AccountId) -> StorageKey {
+	bp_runtime::storage_map_final_key::<Blake2_128Concat>(
+		"RelayerSet",
+		"Relayers",
+		&account_id.encode(),
+	)
+}
+
+/// Returns true if the relayer with given `RelayerSet::Relayers` entry may submit bridge
+/// transactions in the block with given number.
+///
+/// Relayers may only submit bridge transactions starting from the `min_bridge_tx_block` of their
+/// entry. Accounts without the entry are not relayers at all.
+pub fn is_relayer_authorized(
+	maybe_raw_relayer: Option<StorageData>,
+	block_number: bp_polkadot_bulletin::BlockNumber,
+) -> Result<bool, SubstrateError> {
+	let Some(raw_relayer) = maybe_raw_relayer else { return Ok(false) };
+	let relayer =
+		runtime_types::pallet_relayer_set::Relayer::<bp_polkadot_bulletin::BlockNumber>::decode(
+			&mut &raw_relayer.0[..],
+		)
+		.map_err(|e| {
+			SubstrateError::Custom(format!(
+				"Failed to decode {} relayer entry: {e:?}",
+				PolkadotBulletin::NAME
+			))
+		})?;
+	Ok(block_number >= relayer.min_bridge_tx_block)
+}
+
 impl ChainWithTransactions for PolkadotBulletin {
 	type AccountKeyPair = sp_core::sr25519::Pair;
 	type SignedTransaction = bp_polkadot_bulletin::UncheckedExtrinsic<
@@ -111,27 +176,54 @@ impl ChainWithTransactions for PolkadotBulletin {
 		param: SignParam<Self>,
 		unsigned: UnsignedTransaction<Self>,
 	) -> Result<Self::SignedTransaction, SubstrateError> {
-		let raw_payload = SignedPayload::new(
-			FakeDispatchable::from(unsigned.call),
-			bp_polkadot_bulletin::TransactionExtension::from_params(
-				param.spec_version,
-				param.transaction_version,
-				unsigned.era,
-				param.genesis_hash,
-				unsigned.nonce,
-			),
-		)?;
-
-		let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload));
-		let signer: sp_runtime::MultiSigner = param.signer.public().into();
-		let (call, extra, _) = raw_payload.deconstruct();
-
-		Ok(Self::SignedTransaction::new_signed(
-			call.deconstruct(),
-			signer.into_account().into(),
-			signature.into(),
-			extra,
-		))
+		relay_common_client::sign_transaction_with_extension(param, unsigned, None)
+	}
+}
+
+impl ChainWithTransactionExtension for PolkadotBulletin {
+	type Address = Address;
+	type Signature = MultiSignature;
+	type TransactionExtension = bp_polkadot_bulletin::TransactionExtension;
+
+	fn transaction_extension(
+		params: TransactionExtensionParams<Self>,
+	) -> bp_polkadot_bulletin::TransactionExtension {
+		bp_polkadot_bulletin::TransactionExtension::from_params(
+			params.spec_version,
+			params.transaction_version,
+			params.era,
+			params.genesis_hash,
+			params.nonce,
+		)
+	}
+
+	fn explicit_params(
+		extension: &bp_polkadot_bulletin::TransactionExtension,
+	) -> Result<ExplicitParams<Self>, SubstrateError> {
+		// the transaction extension type doesn't expose its parameters, but only
+		// `CheckMortality`, `CheckNonce` and `BridgeRejectObsoleteHeadersAndMessages` extensions
+		// have explicit parameters, so they are decoded directly
+		let (era, nonce, bridge_reject_obsolete): (Era, Compact<bp_polkadot_bulletin::Nonce>, ()) =
+			Decode::decode(&mut &extension.encode()[..]).map_err(|e| {
+				SubstrateError::Custom(format!(
+					"Failed to decode {} transaction extension: {e:?}",
+					PolkadotBulletin::NAME
+				))
+			})?;
+		Ok(ExplicitParams {
+			era,
+			nonce: nonce.0,
+			tip: 0,
+			metadata_hash_check: None,
+			bridge_extensions: vec![(
+				"BridgeRejectObsoleteHeadersAndMessages",
+				format!("{bridge_reject_obsolete:?}"),
+			)],
+		})
+	}
+
+	fn signer_account(address: Address) -> Option<bp_polkadot_bulletin::AccountId> {
+		relay_common_client::multi_address_account(address)
 	}
 }
 
@@ -139,3 +231,21 @@ impl ChainWithRuntimeVersion for PolkadotBulletin {
 	const RUNTIME_VERSION: Option<SimpleRuntimeVersion> =
 		Some(SimpleRuntimeVersion { spec_version: 100, transaction_version: 1 });
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn relayer_authorization_respects_min_bridge_tx_block() {
+		let relayer = Some(StorageData(
+			runtime_types::pallet_relayer_set::Relayer { min_bridge_tx_block: 100u32 }.encode(),
+		));
+
+		assert!(!is_relayer_authorized(None, 100).unwrap());
+		assert!(!is_relayer_authorized(relayer.clone(), 99).unwrap());
+		assert!(is_relayer_authorized(relayer.clone(), 100).unwrap());
+		assert!(is_relayer_authorized(relayer, 101).unwrap());
+		assert!(is_relayer_authorized(Some(StorageData(vec![1])), 100).is_err());
+	}
+}
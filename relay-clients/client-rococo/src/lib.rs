@@ -17,21 +17,19 @@
 //! Types used to connect to the Rococo-Substrate chain.
 
 pub mod codegen_runtime;
+pub mod runtime_apis;
 
 use bp_polkadot_core::SuffixedCommonTransactionExtensionExt;
-use bp_rococo::ROCOCO_SYNCED_HEADERS_GRANDPA_INFO_METHOD;
-use codec::Encode;
+use relay_common_client::{
+	ChainWithTransactionExtension, ExplicitParams, TransactionExtensionParams,
+};
 use relay_substrate_client::{
 	Chain, ChainWithBalances, ChainWithGrandpa, ChainWithRuntimeVersion, ChainWithTransactions,
 	Error as SubstrateError, RelayChain, SignParam, SimpleRuntimeVersion, UnderlyingChainProvider,
 	UnsignedTransaction,
 };
-use sp_core::{storage::StorageKey, Pair};
-use sp_runtime::{
-	generic::SignedPayload,
-	traits::{FakeDispatchable, IdentifyAccount},
-	MultiAddress,
-};
+use sp_core::storage::StorageKey;
+use sp_runtime::{MultiAddress, MultiSignature};
 use sp_session::MembershipProof;
 use std::time::Duration;
 
@@ -61,9 +59,9 @@ impl UnderlyingChainProvider for Rococo {
 impl Chain for Rococo {
 	const NAME: &'static str = "Rococo";
 	const BEST_FINALIZED_HEADER_ID_METHOD: &'static str =
-		bp_rococo::BEST_FINALIZED_ROCOCO_HEADER_METHOD;
+		runtime_apis::rococo_finality_api::best_finalized::NAME;
 	const FREE_HEADERS_INTERVAL_METHOD: &'static str =
-		bp_rococo::FREE_HEADERS_INTERVAL_FOR_ROCOCO_METHOD;
+		runtime_apis::rococo_finality_api::free_headers_interval::NAME;
 	const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_secs(6);
 
 	type SignedBlock = bp_rococo::SignedBlock;
@@ -72,11 +70,25 @@ impl Chain for Rococo {
 
 impl ChainWithGrandpa for Rococo {
 	const SYNCED_HEADERS_GRANDPA_INFO_METHOD: &'static str =
-		ROCOCO_SYNCED_HEADERS_GRANDPA_INFO_METHOD;
+		runtime_apis::rococo_finality_api::synced_headers_grandpa_info::NAME;
 
 	type KeyOwnerProof = MembershipProof;
 }
 
+// The relay calls bridge runtime APIs of the chain with arguments and decodes their outputs, using
+// types below. Make sure they match types of APIs, generated from the bridged runtime metadata.
+const _: fn(
+	runtime_apis::rococo_finality_api::best_finalized::Output,
+) -> Option<bp_runtime::HeaderIdOf<bp_rococo::Rococo>> = |output| output;
+const _: fn(
+	runtime_apis::rococo_finality_api::free_headers_interval::Output,
+) -> Option<bp_runtime::BlockNumberOf<bp_rococo::Rococo>> = |output| output;
+const _: fn(
+	runtime_apis::rococo_finality_api::synced_headers_grandpa_info::Output,
+) -> Vec<
+	bp_header_chain::StoredHeaderGrandpaInfo<bp_runtime::HeaderOf<bp_rococo::Rococo>>,
+> = |output| output;
+
 impl ChainWithBalances for Rococo {
 	fn account_info_storage_key(account_id: &Self::AccountId) -> StorageKey {
 		bp_rococo::AccountInfoStorageMapKeyProvider::final_key(account_id)
@@ -98,29 +110,44 @@ impl ChainWithTransactions for Rococo {
 		param: SignParam<Self>,
 		unsigned: UnsignedTransaction<Self>,
 	) -> Result<Self::SignedTransaction, SubstrateError> {
-		let raw_payload = SignedPayload::new(
-			FakeDispatchable::from(unsigned.call),
-			bp_rococo::TransactionExtension::from_params(
-				param.spec_version,
-				param.transaction_version,
-				unsigned.era,
-				param.genesis_hash,
-				unsigned.nonce,
-				unsigned.tip,
-				((), ()),
-			),
-		)?;
-
-		let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload));
-		let signer: sp_runtime::MultiSigner = param.signer.public().into();
-		let (call, extra, _) = raw_payload.deconstruct();
-
-		Ok(Self::SignedTransaction::new_signed(
-			call.deconstruct(),
-			signer.into_account().into(),
-			signature.into(),
-			extra,
-		))
+		relay_common_client::sign_transaction_with_extension(param, unsigned, None)
+	}
+}
+
+impl ChainWithTransactionExtension for Rococo {
+	type Address = Address;
+	type Signature = MultiSignature;
+	type TransactionExtension = bp_rococo::TransactionExtension;
+
+	fn transaction_extension(
+		params: TransactionExtensionParams<Self>,
+	) -> bp_rococo::TransactionExtension {
+		bp_rococo::TransactionExtension::from_params(
+			params.spec_version,
+			params.transaction_version,
+			params.era,
+			params.genesis_hash,
+			params.nonce,
+			params.tip,
+			((), ()),
+		)
+	}
+
+	fn explicit_params(
+		extension: &bp_rococo::TransactionExtension,
+	) -> Result<ExplicitParams<Self>, SubstrateError> {
+		let (_, _, _, _, era, nonce, _, tip, _) = extension.payload.clone();
+		Ok(ExplicitParams {
+			era,
+			nonce: nonce.0,
+			tip: tip.0,
+			metadata_hash_check: None,
+			bridge_extensions: Vec::new(),
+		})
+	}
+
+	fn signer_account(address: Address) -> Option<bp_rococo::AccountId> {
+		relay_common_client::multi_address_account(address)
 	}
 }
 
@@ -17,21 +17,19 @@
 //! Types used to connect to the Westend chain.
 
 pub mod codegen_runtime;
+pub mod runtime_apis;
 
 use bp_polkadot_core::SuffixedCommonTransactionExtensionExt;
-use bp_westend::WESTEND_SYNCED_HEADERS_GRANDPA_INFO_METHOD;
-use codec::Encode;
+use relay_common_client::{
+	ChainWithTransactionExtension, ExplicitParams, TransactionExtensionParams,
+};
 use relay_substrate_client::{
 	Chain, ChainWithBalances, ChainWithGrandpa, ChainWithRuntimeVersion, ChainWithTransactions,
 	Error as SubstrateError, RelayChain, SignParam, SimpleRuntimeVersion, UnderlyingChainProvider,
 	UnsignedTransaction,
 };
-use sp_core::{storage::StorageKey, Pair};
-use sp_runtime::{
-	generic::SignedPayload,
-	traits::{FakeDispatchable, IdentifyAccount},
-	MultiAddress,
-};
+use sp_core::storage::StorageKey;
+use sp_runtime::{MultiAddress, MultiSignature};
 use sp_session::MembershipProof;
 use std::time::Duration;
 
@@ -61,9 +59,9 @@ impl UnderlyingChainProvider for Westend {
 impl Chain for Westend {
 	const NAME: &'static str = "Westend";
 	const BEST_FINALIZED_HEADER_ID_METHOD: &'static str =
-		bp_westend::BEST_FINALIZED_WESTEND_HEADER_METHOD;
+		runtime_apis::westend_finality_api::best_finalized::NAME;
 	const FREE_HEADERS_INTERVAL_METHOD: &'static str =
-		bp_westend::FREE_HEADERS_INTERVAL_FOR_WESTEND_METHOD;
+		runtime_apis::westend_finality_api::free_headers_interval::NAME;
 	const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_secs(6);
 
 	type SignedBlock = bp_westend::SignedBlock;
@@ -72,11 +70,25 @@ impl Chain for Westend {
 
 impl ChainWithGrandpa for Westend {
 	const SYNCED_HEADERS_GRANDPA_INFO_METHOD: &'static str =
-		WESTEND_SYNCED_HEADERS_GRANDPA_INFO_METHOD;
+		runtime_apis::westend_finality_api::synced_headers_grandpa_info::NAME;
 
 	type KeyOwnerProof = MembershipProof;
 }
 
+// The relay calls bridge runtime APIs of the chain with arguments and decodes their outputs, using
+// types below. Make sure they match types of APIs, generated from the bridged runtime metadata.
+const _: fn(
+	runtime_apis::westend_finality_api::best_finalized::Output,
+) -> Option<bp_runtime::HeaderIdOf<bp_westend::Westend>> = |output| output;
+const _: fn(
+	runtime_apis::westend_finality_api::free_headers_interval::Output,
+) -> Option<bp_runtime::BlockNumberOf<bp_westend::Westend>> = |output| output;
+const _: fn(
+	runtime_apis::westend_finality_api::synced_headers_grandpa_info::Output,
+) -> Vec<
+	bp_header_chain::StoredHeaderGrandpaInfo<bp_runtime::HeaderOf<bp_westend::Westend>>,
+> = |output| output;
+
 impl RelayChain for Westend {
 	const PARAS_PALLET_NAME: &'static str = bp_westend::PARAS_PALLET_NAME;
 	const WITH_CHAIN_BRIDGE_PARACHAINS_PALLET_NAME: &'static str =
@@ -98,29 +110,44 @@ impl ChainWithTransactions for Westend {
 		param: SignParam<Self>,
 		unsigned: UnsignedTransaction<Self>,
 	) -> Result<Self::SignedTransaction, SubstrateError> {
-		let raw_payload = SignedPayload::new(
-			FakeDispatchable::from(unsigned.call),
-			bp_westend::TransactionExtension::from_params(
-				param.spec_version,
-				param.transaction_version,
-				unsigned.era,
-				param.genesis_hash,
-				unsigned.nonce,
-				unsigned.tip,
-				((), ()),
-			),
-		)?;
-
-		let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload));
-		let signer: sp_runtime::MultiSigner = param.signer.public().into();
-		let (call, extra, _) = raw_payload.deconstruct();
-
-		Ok(Self::SignedTransaction::new_signed(
-			call.deconstruct(),
-			signer.into_account().into(),
-			signature.into(),
-			extra,
-		))
+		relay_common_client::sign_transaction_with_extension(param, unsigned, None)
+	}
+}
+
+impl ChainWithTransactionExtension for Westend {
+	type Address = Address;
+	type Signature = MultiSignature;
+	type TransactionExtension = bp_westend::TransactionExtension;
+
+	fn transaction_extension(
+		params: TransactionExtensionParams<Self>,
+	) -> bp_westend::TransactionExtension {
+		bp_westend::TransactionExtension::from_params(
+			params.spec_version,
+			params.transaction_version,
+			params.era,
+			params.genesis_hash,
+			params.nonce,
+			params.tip,
+			((), ()),
+		)
+	}
+
+	fn explicit_params(
+		extension: &bp_westend::TransactionExtension,
+	) -> Result<ExplicitParams<Self>, SubstrateError> {
+		let (_, _, _, _, era, nonce, _, tip, _) = extension.payload.clone();
+		Ok(ExplicitParams {
+			era,
+			nonce: nonce.0,
+			tip: tip.0,
+			metadata_hash_check: None,
+			bridge_extensions: Vec::new(),
+		})
+	}
+
+	fn signer_account(address: Address) -> Option<bp_westend::AccountId> {
+		relay_common_client::multi_address_account(address)
 	}
 }
 
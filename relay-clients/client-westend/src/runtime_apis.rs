@@ -0,0 +1,60 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Autogenerated runtime API
+//! THIS FILE WAS AUTOGENERATED USING parity-bridges-common::runtime-codegen
+//! EXECUTED COMMAND: runtime-codegen runtime-apis --from-node-url
+//! wss://rococo-bridge-hub-rpc.polkadot.io:443 --chain Westend
+
+/// `WestendFinalityApi` runtime API.
+pub mod westend_finality_api {
+	/// `WestendFinalityApi::best_finalized` runtime API method.
+	pub mod best_finalized {
+		/// Name of the method, used in the `state_call` RPC.
+		pub const NAME: &str = "WestendFinalityApi_best_finalized";
+		/// Method arguments.
+		pub type Input = ();
+		/// Method output.
+		pub type Output = ::core::option::Option<
+			::bp_runtime::HeaderId<::subxt::ext::subxt_core::utils::H256, ::core::primitive::u32>,
+		>;
+	}
+	/// `WestendFinalityApi::free_headers_interval` runtime API method.
+	pub mod free_headers_interval {
+		/// Name of the method, used in the `state_call` RPC.
+		pub const NAME: &str = "WestendFinalityApi_free_headers_interval";
+		/// Method arguments.
+		pub type Input = ();
+		/// Method output.
+		pub type Output = ::core::option::Option<::core::primitive::u32>;
+	}
+	/// `WestendFinalityApi::synced_headers_grandpa_info` runtime API method.
+	pub mod synced_headers_grandpa_info {
+		/// Name of the method, used in the `state_call` RPC.
+		pub const NAME: &str = "WestendFinalityApi_synced_headers_grandpa_info";
+		/// Method arguments.
+		pub type Input = ();
+		/// Method output.
+		pub type Output = ::subxt::ext::subxt_core::alloc::vec::Vec<
+			::bp_header_chain::StoredHeaderGrandpaInfo<
+				::sp_runtime::generic::Header<
+					::core::primitive::u32,
+					::sp_runtime::traits::BlakeTwo256,
+				>,
+			>,
+		>;
+	}
+}
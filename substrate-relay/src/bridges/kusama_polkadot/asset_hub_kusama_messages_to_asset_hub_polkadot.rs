@@ -0,0 +1,68 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! AssetHubKusama-to-AssetHubPolkadot messages sync entrypoint.
+
+use relay_asset_hub_kusama_client::AssetHubKusama;
+use relay_asset_hub_polkadot_client::AssetHubPolkadot;
+use substrate_relay_helper::{
+	cli::bridge::{CliBridgeBase, MessagesCliBridge},
+	messages::SubstrateMessageLane,
+};
+
+pub struct AssetHubKusamaToAssetHubPolkadotMessagesCliBridge {}
+
+impl CliBridgeBase for AssetHubKusamaToAssetHubPolkadotMessagesCliBridge {
+	type Source = AssetHubKusama;
+	type Target = AssetHubPolkadot;
+}
+
+impl MessagesCliBridge for AssetHubKusamaToAssetHubPolkadotMessagesCliBridge {
+	type MessagesLane = AssetHubKusamaMessagesToAssetHubPolkadotMessageLane;
+}
+
+substrate_relay_helper::generate_receive_message_proof_call_builder!(
+	AssetHubKusamaMessagesToAssetHubPolkadotMessageLane,
+	AssetHubKusamaMessagesToAssetHubPolkadotMessageLaneReceiveMessagesProofCallBuilder,
+	relay_asset_hub_polkadot_client::RuntimeCall::BridgeKusamaMessages,
+	relay_asset_hub_polkadot_client::BridgeMessagesCall::receive_messages_proof
+);
+
+substrate_relay_helper::generate_receive_message_delivery_proof_call_builder!(
+	AssetHubKusamaMessagesToAssetHubPolkadotMessageLane,
+	AssetHubKusamaMessagesToAssetHubPolkadotMessageLaneReceiveMessagesDeliveryProofCallBuilder,
+	relay_asset_hub_kusama_client::RuntimeCall::BridgePolkadotMessages,
+	relay_asset_hub_kusama_client::BridgeMessagesCall::receive_messages_delivery_proof
+);
+
+/// Description of AssetHubKusama -> AssetHubPolkadot messages bridge.
+#[derive(Clone, Debug)]
+pub struct AssetHubKusamaMessagesToAssetHubPolkadotMessageLane;
+
+impl SubstrateMessageLane for AssetHubKusamaMessagesToAssetHubPolkadotMessageLane {
+	type SourceChain = AssetHubKusama;
+	type TargetChain = AssetHubPolkadot;
+
+	type LaneId = bp_messages::HashedLaneId;
+
+	type ReceiveMessagesProofCallBuilder =
+		AssetHubKusamaMessagesToAssetHubPolkadotMessageLaneReceiveMessagesProofCallBuilder;
+	type ReceiveMessagesDeliveryProofCallBuilder =
+		AssetHubKusamaMessagesToAssetHubPolkadotMessageLaneReceiveMessagesDeliveryProofCallBuilder;
+
+	type SourceBatchCallBuilder = ();
+	type TargetBatchCallBuilder = ();
+}
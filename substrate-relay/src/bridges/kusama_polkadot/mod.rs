@@ -14,8 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Declaration of all bridges between Kusama Bridge Hub and Polkadot Bridge Hub.
+//! Declaration of all bridges between Kusama and Polkadot Bridge Hubs and Asset Hubs.
 
+pub mod asset_hub_kusama_messages_to_asset_hub_polkadot;
+pub mod asset_hub_kusama_parachains_to_bridge_hub_polkadot;
+pub mod asset_hub_polkadot_messages_to_asset_hub_kusama;
+pub mod asset_hub_polkadot_parachains_to_bridge_hub_kusama;
 pub mod bridge_hub_kusama_messages_to_bridge_hub_polkadot;
 pub mod bridge_hub_kusama_parachains_to_bridge_hub_polkadot;
 pub mod bridge_hub_polkadot_messages_to_bridge_hub_kusama;
@@ -17,6 +17,7 @@
 //! Declaration of all bridges that the relay is able to serve.
 
 pub mod kusama_polkadot;
+pub mod moonriver_moonbeam;
 pub mod polkadot_bulletin;
 pub mod rococo_bulletin;
 pub mod rococo_westend;
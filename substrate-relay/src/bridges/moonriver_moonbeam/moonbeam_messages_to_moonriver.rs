@@ -0,0 +1,68 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Moonbeam-to-Moonriver messages sync entrypoint.
+
+use relay_moonbeam_client::Moonbeam;
+use relay_moonriver_client::Moonriver;
+use substrate_relay_helper::{
+	cli::bridge::{CliBridgeBase, MessagesCliBridge},
+	messages::SubstrateMessageLane,
+};
+
+pub struct MoonbeamToMoonriverMessagesCliBridge {}
+
+impl CliBridgeBase for MoonbeamToMoonriverMessagesCliBridge {
+	type Source = Moonbeam;
+	type Target = Moonriver;
+}
+
+impl MessagesCliBridge for MoonbeamToMoonriverMessagesCliBridge {
+	type MessagesLane = MoonbeamMessagesToMoonriverMessageLane;
+}
+
+substrate_relay_helper::generate_receive_message_proof_call_builder!(
+	MoonbeamMessagesToMoonriverMessageLane,
+	MoonbeamMessagesToMoonriverMessageLaneReceiveMessagesProofCallBuilder,
+	relay_moonriver_client::RuntimeCall::BridgePolkadotMessages,
+	relay_moonriver_client::BridgeMessagesCall::receive_messages_proof
+);
+
+substrate_relay_helper::generate_receive_message_delivery_proof_call_builder!(
+	MoonbeamMessagesToMoonriverMessageLane,
+	MoonbeamMessagesToMoonriverMessageLaneReceiveMessagesDeliveryProofCallBuilder,
+	relay_moonbeam_client::RuntimeCall::BridgeKusamaMessages,
+	relay_moonbeam_client::BridgeMessagesCall::receive_messages_delivery_proof
+);
+
+/// Description of Moonbeam -> Moonriver messages bridge.
+#[derive(Clone, Debug)]
+pub struct MoonbeamMessagesToMoonriverMessageLane;
+
+impl SubstrateMessageLane for MoonbeamMessagesToMoonriverMessageLane {
+	type SourceChain = Moonbeam;
+	type TargetChain = Moonriver;
+
+	type LaneId = bp_messages::HashedLaneId;
+
+	type ReceiveMessagesProofCallBuilder =
+		MoonbeamMessagesToMoonriverMessageLaneReceiveMessagesProofCallBuilder;
+	type ReceiveMessagesDeliveryProofCallBuilder =
+		MoonbeamMessagesToMoonriverMessageLaneReceiveMessagesDeliveryProofCallBuilder;
+
+	type SourceBatchCallBuilder = ();
+	type TargetBatchCallBuilder = ();
+}
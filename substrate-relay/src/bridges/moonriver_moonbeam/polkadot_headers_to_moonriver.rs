@@ -0,0 +1,80 @@
+// Copyright 2022 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Polkadot-to-Moonriver headers sync entrypoint.
+
+use substrate_relay_helper::cli::bridge::{
+	CliBridgeBase, RelayToRelayEquivocationDetectionCliBridge, RelayToRelayHeadersCliBridge,
+};
+
+use async_trait::async_trait;
+use substrate_relay_helper::{
+	equivocation::SubstrateEquivocationDetectionPipeline,
+	finality::SubstrateFinalitySyncPipeline,
+	finality_base::{engine::Grandpa as GrandpaFinalityEngine, SubstrateFinalityPipeline},
+};
+
+/// Description of Polkadot -> Moonriver finalized headers bridge.
+#[derive(Clone, Debug)]
+pub struct PolkadotFinalityToMoonriver;
+
+substrate_relay_helper::generate_submit_finality_proof_ex_call_builder!(
+	PolkadotFinalityToMoonriver,
+	SubmitFinalityProofCallBuilder,
+	relay_moonriver_client::RuntimeCall::BridgePolkadotGrandpa,
+	relay_moonriver_client::BridgeGrandpaCall::submit_finality_proof_ex
+);
+
+substrate_relay_helper::generate_report_equivocation_call_builder!(
+	PolkadotFinalityToMoonriver,
+	ReportEquivocationCallBuilder,
+	relay_polkadot_client::RuntimeCall::Grandpa,
+	relay_polkadot_client::GrandpaCall::report_equivocation
+);
+
+#[async_trait]
+impl SubstrateFinalityPipeline for PolkadotFinalityToMoonriver {
+	type SourceChain = relay_polkadot_client::Polkadot;
+	type TargetChain = relay_moonriver_client::Moonriver;
+
+	type FinalityEngine = GrandpaFinalityEngine<Self::SourceChain>;
+}
+
+#[async_trait]
+impl SubstrateFinalitySyncPipeline for PolkadotFinalityToMoonriver {
+	type SubmitFinalityProofCallBuilder = SubmitFinalityProofCallBuilder;
+}
+
+#[async_trait]
+impl SubstrateEquivocationDetectionPipeline for PolkadotFinalityToMoonriver {
+	type ReportEquivocationCallBuilder = ReportEquivocationCallBuilder;
+}
+
+/// `Polkadot` to `Moonriver` bridge definition.
+pub struct PolkadotToMoonriverCliBridge {}
+
+impl CliBridgeBase for PolkadotToMoonriverCliBridge {
+	type Source = relay_polkadot_client::Polkadot;
+	type Target = relay_moonriver_client::Moonriver;
+}
+
+impl RelayToRelayHeadersCliBridge for PolkadotToMoonriverCliBridge {
+	type Finality = PolkadotFinalityToMoonriver;
+}
+
+impl RelayToRelayEquivocationDetectionCliBridge for PolkadotToMoonriverCliBridge {
+	type Equivocation = PolkadotFinalityToMoonriver;
+}
@@ -21,3 +21,157 @@ pub mod polkadot_bulletin_headers_to_bridge_hub_polkadot;
 pub mod polkadot_bulletin_messages_to_bridge_hub_polkadot;
 pub mod polkadot_headers_to_polkadot_bulletin;
 pub mod polkadot_parachains_to_polkadot_bulletin;
+
+use crate::cli::LOG_TARGET;
+
+use async_trait::async_trait;
+use codec::{Decode, Encode};
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use prometheus::IntGauge;
+use relay_bridge_hub_polkadot_client::{
+	BridgeHubPolkadot, BridgePolkadotBulletinGrandpaCall, BridgePolkadotBulletinMessagesCall,
+	RuntimeCall,
+};
+use relay_polkadot_bulletin_client::PolkadotBulletin;
+use relay_substrate_client::{AccountIdOf, Chain, Client};
+use relay_utils::metrics::{Metric, MetricsParams, PrometheusError, Registry, StandaloneMetric};
+use sp_core::{storage::StorageKey, OpaqueMetadata};
+use sp_runtime::traits::Header as HeaderT;
+use std::time::Duration;
+
+/// Names of `BridgeHubPolkadot` pallets that are serving the Polkadot Bulletin bridge, paired with
+/// calls that the relay is submitting to them.
+///
+/// Calls must be constructed the same way as in the Polkadot Bulletin call builders.
+fn bulletin_pallet_calls() -> [(&'static str, RuntimeCall); 2] {
+	[
+		(
+			"BridgePolkadotBulletinGrandpa",
+			// TODO: https://github.com/paritytech/parity-bridges-common/issues/2547 - use BridgePolkadotBulletinGrandpa
+			RuntimeCall::BridgeKusamaGrandpa(BridgePolkadotBulletinGrandpaCall::set_owner {
+				new_owner: None,
+			}),
+		),
+		(
+			"BridgePolkadotBulletinMessages",
+			// TODO: https://github.com/paritytech/parity-bridges-common/issues/2547 - use BridgePolkadotBulletinMessages
+			RuntimeCall::BridgeKusamaMessages(BridgePolkadotBulletinMessagesCall::set_owner {
+				new_owner: None,
+			}),
+		),
+	]
+}
+
+/// Ensure that the Polkadot Bulletin bridge transactions are dispatched by the Polkadot Bulletin
+/// bridge pallets of the `BridgeHubPolkadot` runtime.
+///
+/// Otherwise the relay would be submitting transactions that are either rejected or, worse, are
+/// dispatched by pallets of other bridges, deployed at the same indices.
+pub async fn ensure_bulletin_pallets_at_bridge_hub_polkadot(
+	client: &impl Client<BridgeHubPolkadot>,
+) -> anyhow::Result<()> {
+	let at = client.best_finalized_header_hash().await?;
+	let metadata: OpaqueMetadata = client.state_call(at, "Metadata_metadata".into(), ()).await?;
+	let pallets = pallets(&metadata)?;
+	for (expected_pallet, call) in bulletin_pallet_calls() {
+		let index = call.encode()[0];
+		let actual_pallet = pallets.iter().find(|(i, _)| *i == index).map(|(_, name)| name);
+		if actual_pallet.map(|name| name.as_str()) != Some(expected_pallet) {
+			anyhow::bail!(
+				"Polkadot Bulletin bridge calls are dispatched by the {} pallet with index {index}, \
+				but {} runtime has {} there. Refusing to serve the Polkadot Bulletin bridge",
+				expected_pallet,
+				BridgeHubPolkadot::NAME,
+				actual_pallet.map_or("no pallet", |name| name.as_str()),
+			)
+		}
+	}
+
+	Ok(())
+}
+
+/// Returns indices and names of all pallets from the encoded runtime metadata.
+fn pallets(metadata: &[u8]) -> anyhow::Result<Vec<(u8, String)>> {
+	let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+		.map_err(|e| anyhow::format_err!("Failed to decode runtime metadata: {e:?}"))?;
+	Ok(match metadata.1 {
+		RuntimeMetadata::V14(metadata) =>
+			metadata.pallets.into_iter().map(|pallet| (pallet.index, pallet.name)).collect(),
+		RuntimeMetadata::V15(metadata) =>
+			metadata.pallets.into_iter().map(|pallet| (pallet.index, pallet.name)).collect(),
+		metadata => anyhow::bail!("Unsupported runtime metadata version: V{}", metadata.version()),
+	})
+}
+
+/// Register the relayer authorization metric of the relayer account at the Polkadot Bulletin chain.
+///
+/// The chain has no balances, so instead of the relayer balance, we are tracking whether the
+/// relayer is allowed to submit bridge transactions.
+pub fn add_relayer_authorization_metric(
+	client: impl Client<PolkadotBulletin>,
+	metrics: &MetricsParams,
+	relayer: &AccountIdOf<PolkadotBulletin>,
+) -> anyhow::Result<()> {
+	RelayerAuthorizationMetric {
+		client,
+		storage_key: relay_polkadot_bulletin_client::relayer_storage_key(relayer),
+		authorized: IntGauge::new(
+			format!("at_{}_relay_authorization", PolkadotBulletin::NAME),
+			format!(
+				"Whether the relay account may submit bridge transactions at the {}",
+				PolkadotBulletin::NAME
+			),
+		)?,
+	}
+	.register_and_spawn(&metrics.registry)?;
+	Ok(())
+}
+
+/// Relayer authorization metric. It is `1` if the relayer account may submit bridge transactions
+/// in the next block and `0` otherwise.
+#[derive(Clone)]
+struct RelayerAuthorizationMetric<Clnt> {
+	client: Clnt,
+	/// Key of the `RelayerSet::Relayers` entry of the relayer account.
+	storage_key: StorageKey,
+	authorized: IntGauge,
+}
+
+impl<Clnt: Client<PolkadotBulletin>> RelayerAuthorizationMetric<Clnt> {
+	/// Read the relayer authorization at the best block.
+	async fn read(&self) -> anyhow::Result<bool> {
+		let best_header = self.client.best_header().await?;
+		let relayer = self
+			.client
+			.raw_storage_value(best_header.hash(), self.storage_key.clone())
+			.await?;
+		Ok(relay_polkadot_bulletin_client::is_relayer_authorized(
+			relayer,
+			*best_header.number() + 1,
+		)?)
+	}
+}
+
+impl<Clnt: Client<PolkadotBulletin>> Metric for RelayerAuthorizationMetric<Clnt> {
+	fn register(&self, registry: &Registry) -> Result<(), PrometheusError> {
+		registry.register(Box::new(self.authorized.clone()))
+	}
+}
+
+#[async_trait]
+impl<Clnt: Client<PolkadotBulletin>> StandaloneMetric for RelayerAuthorizationMetric<Clnt> {
+	fn update_interval(&self) -> Duration {
+		PolkadotBulletin::AVERAGE_BLOCK_INTERVAL * 10
+	}
+
+	async fn update(&self) {
+		match self.read().await {
+			Ok(authorized) => self.authorized.set(authorized as i64),
+			Err(e) => log::trace!(
+				target: LOG_TARGET,
+				"Failed to read relayer authorization at {}: {e:?}",
+				PolkadotBulletin::NAME,
+			),
+		}
+	}
+}
@@ -0,0 +1,234 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use clap::{Parser, ValueEnum};
+use codec::{DecodeAll, Encode};
+use relay_common_client::{
+	decode_transaction, ChainWithTransactionExtension, DecodedTransaction, UncheckedExtrinsicOf,
+	VerifyParam,
+};
+use relay_substrate_client::{AccountIdOf, CallOf, ChainWithRuntimeVersion};
+use serde::Serialize;
+use sp_core::H256;
+use sp_runtime::generic::Era;
+use std::fmt::Debug;
+use strum::{EnumString, VariantNames};
+
+/// Decode signed transaction and verify its signature.
+///
+/// The transaction is decoded using the runtime code that the chain client has been generated
+/// for. The decoded transaction is printed in the JSON format.
+#[derive(Parser)]
+pub struct DecodeExtrinsic {
+	/// Chain that the transaction has been signed for.
+	#[arg(long, value_enum, ignore_case = true)]
+	chain: DecodeExtrinsicChain,
+	/// Hash of the chain genesis block.
+	#[arg(long)]
+	genesis_hash: H256,
+	/// Hash of the block where the transaction era starts. Required to verify signature of the
+	/// mortal transaction.
+	#[arg(long)]
+	era_block_hash: Option<H256>,
+	/// Spec version of the runtime that the transaction has been signed for. Defaults to the
+	/// version that the chain client has been generated for.
+	#[arg(long)]
+	spec_version: Option<u32>,
+	/// Transaction version of the runtime that the transaction has been signed for. Defaults to
+	/// the version that the chain client has been generated for.
+	#[arg(long)]
+	transaction_version: Option<u32>,
+	/// Metadata hash of the runtime. Required to verify signature of the transaction with the
+	/// `CheckMetadataHash` transaction extension enabled.
+	#[arg(long)]
+	metadata_hash: Option<H256>,
+	/// Hex-encoded signed transaction.
+	extrinsic: String,
+}
+
+#[derive(Clone, Copy, Debug, EnumString, VariantNames, ValueEnum)]
+#[strum(serialize_all = "kebab_case")]
+/// Chain that the transaction has been signed for.
+pub enum DecodeExtrinsicChain {
+	Kusama,
+	Polkadot,
+	Rococo,
+	Westend,
+	PolkadotBulletin,
+	BridgeHubKusama,
+	BridgeHubPolkadot,
+	BridgeHubRococo,
+	BridgeHubWestend,
+	AssetHubKusama,
+	AssetHubPolkadot,
+	AssetHubRococo,
+	AssetHubWestend,
+	Moonbeam,
+	Moonriver,
+}
+
+/// Decoded transaction, printed by the command.
+#[derive(Debug, Serialize)]
+struct DecodedExtrinsic {
+	/// Hex-encoded account that has signed the transaction.
+	signer: String,
+	/// Hex-encoded transaction signature.
+	signature: String,
+	/// True if the signature matches the reconstructed signed payload.
+	signature_valid: bool,
+	/// Transaction nonce.
+	nonce: u64,
+	/// Transaction era.
+	era: DecodedEra,
+	/// Transaction tip.
+	tip: u128,
+	/// Mode of the `CheckMetadataHash` transaction extension, if the chain supports it.
+	metadata_hash_mode: Option<&'static str>,
+	/// Explicit parameters of the bridge transaction extensions.
+	bridge_extensions: Vec<DecodedExtension>,
+	/// Hex-encoded transaction call.
+	call_data: String,
+	/// Transaction call.
+	call: String,
+}
+
+/// Decoded transaction era.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum DecodedEra {
+	Immortal,
+	Mortal { period: u64, phase: u64 },
+}
+
+/// Decoded transaction extension.
+#[derive(Debug, Serialize)]
+struct DecodedExtension {
+	/// Extension name.
+	name: &'static str,
+	/// Explicit extension parameters.
+	params: String,
+}
+
+impl<AccountId: Encode, Call: Encode + Debug> From<DecodedTransaction<AccountId, Call>>
+	for DecodedExtrinsic
+{
+	fn from(transaction: DecodedTransaction<AccountId, Call>) -> Self {
+		DecodedExtrinsic {
+			signer: hex_string(&transaction.signer.encode()),
+			signature: hex_string(&transaction.signature),
+			signature_valid: transaction.signature_valid,
+			nonce: transaction.nonce,
+			era: match transaction.era {
+				Era::Immortal => DecodedEra::Immortal,
+				Era::Mortal(period, phase) => DecodedEra::Mortal { period, phase },
+			},
+			tip: transaction.tip,
+			metadata_hash_mode: transaction.metadata_hash_check.map(|enabled| {
+				if enabled {
+					"Enabled"
+				} else {
+					"Disabled"
+				}
+			}),
+			bridge_extensions: transaction
+				.bridge_extensions
+				.into_iter()
+				.map(|(name, params)| DecodedExtension { name, params })
+				.collect(),
+			call_data: hex_string(&transaction.call.encode()),
+			call: format!("{:?}", transaction.call),
+		}
+	}
+}
+
+impl DecodeExtrinsic {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		let extrinsic = hex::decode(self.extrinsic.trim_start_matches("0x"))
+			.map_err(|e| anyhow::format_err!("Invalid hex-encoded extrinsic: {e}"))?;
+		let decoded = match self.chain {
+			DecodeExtrinsicChain::Kusama =>
+				self.decode::<relay_kusama_client::Kusama>(&extrinsic)?,
+			DecodeExtrinsicChain::Polkadot =>
+				self.decode::<relay_polkadot_client::Polkadot>(&extrinsic)?,
+			DecodeExtrinsicChain::Rococo =>
+				self.decode::<relay_rococo_client::Rococo>(&extrinsic)?,
+			DecodeExtrinsicChain::Westend =>
+				self.decode::<relay_westend_client::Westend>(&extrinsic)?,
+			DecodeExtrinsicChain::PolkadotBulletin =>
+				self.decode::<relay_polkadot_bulletin_client::PolkadotBulletin>(&extrinsic)?,
+			DecodeExtrinsicChain::BridgeHubKusama =>
+				self.decode::<relay_bridge_hub_kusama_client::BridgeHubKusama>(&extrinsic)?,
+			DecodeExtrinsicChain::BridgeHubPolkadot =>
+				self.decode::<relay_bridge_hub_polkadot_client::BridgeHubPolkadot>(&extrinsic)?,
+			DecodeExtrinsicChain::BridgeHubRococo =>
+				self.decode::<relay_bridge_hub_rococo_client::BridgeHubRococo>(&extrinsic)?,
+			DecodeExtrinsicChain::BridgeHubWestend =>
+				self.decode::<relay_bridge_hub_westend_client::BridgeHubWestend>(&extrinsic)?,
+			DecodeExtrinsicChain::AssetHubKusama =>
+				self.decode::<relay_asset_hub_kusama_client::AssetHubKusama>(&extrinsic)?,
+			DecodeExtrinsicChain::AssetHubPolkadot =>
+				self.decode::<relay_asset_hub_polkadot_client::AssetHubPolkadot>(&extrinsic)?,
+			DecodeExtrinsicChain::AssetHubRococo =>
+				self.decode::<relay_asset_hub_rococo_client::AssetHubRococo>(&extrinsic)?,
+			DecodeExtrinsicChain::AssetHubWestend =>
+				self.decode::<relay_asset_hub_westend_client::AssetHubWestend>(&extrinsic)?,
+			DecodeExtrinsicChain::Moonbeam =>
+				self.decode::<relay_moonbeam_client::Moonbeam>(&extrinsic)?,
+			DecodeExtrinsicChain::Moonriver =>
+				self.decode::<relay_moonriver_client::Moonriver>(&extrinsic)?,
+		};
+
+		println!("{}", serde_json::to_string_pretty(&decoded)?);
+		Ok(())
+	}
+
+	/// Decode transaction of the chain `C` and verify its signature.
+	fn decode<C>(&self, extrinsic: &[u8]) -> anyhow::Result<DecodedExtrinsic>
+	where
+		C: ChainWithTransactionExtension<Hash = H256> + ChainWithRuntimeVersion,
+		UncheckedExtrinsicOf<C>: DecodeAll,
+		AccountIdOf<C>: Encode,
+		CallOf<C>: Encode + Debug,
+	{
+		Ok(decode_transaction::<C>(extrinsic, self.verify_param::<C>()?)?.into())
+	}
+
+	/// Returns parameters that are required to verify the transaction signature.
+	fn verify_param<C: ChainWithRuntimeVersion>(&self) -> anyhow::Result<VerifyParam<H256>> {
+		let spec_version = self.spec_version.or(C::RUNTIME_VERSION.map(|v| v.spec_version));
+		let transaction_version =
+			self.transaction_version.or(C::RUNTIME_VERSION.map(|v| v.transaction_version));
+		match (spec_version, transaction_version) {
+			(Some(spec_version), Some(transaction_version)) => Ok(VerifyParam {
+				spec_version,
+				transaction_version,
+				genesis_hash: self.genesis_hash,
+				era_block_hash: self.era_block_hash,
+				metadata_hash: self.metadata_hash.map(|hash| hash.0),
+			}),
+			_ => Err(anyhow::format_err!(
+				"Runtime version of {} is unknown. Use --spec-version and --transaction-version",
+				C::NAME,
+			)),
+		}
+	}
+}
+
+/// Returns `0x`-prefixed hex-encoded bytes.
+fn hex_string(bytes: &[u8]) -> String {
+	format!("0x{}", hex::encode(bytes))
+}
@@ -19,6 +19,10 @@ use crate::bridges::{
 		kusama_headers_to_bridge_hub_polkadot::KusamaToBridgeHubPolkadotCliBridge,
 		polkadot_headers_to_bridge_hub_kusama::PolkadotToBridgeHubKusamaCliBridge,
 	},
+	moonriver_moonbeam::{
+		kusama_headers_to_moonbeam::KusamaToMoonbeamCliBridge,
+		polkadot_headers_to_moonriver::PolkadotToMoonriverCliBridge,
+	},
 	rococo_westend::{
 		rococo_headers_to_bridge_hub_westend::RococoToBridgeHubWestendCliBridge,
 		westend_headers_to_bridge_hub_rococo::WestendToBridgeHubRococoCliBridge,
@@ -49,12 +53,16 @@ pub enum DetectEquivocationsBridge {
 	PolkadotToBridgeHubKusama,
 	RococoToBridgeHubWestend,
 	WestendToBridgeHubRococo,
+	KusamaToMoonbeam,
+	PolkadotToMoonriver,
 }
 
 impl EquivocationsDetector for KusamaToBridgeHubPolkadotCliBridge {}
 impl EquivocationsDetector for PolkadotToBridgeHubKusamaCliBridge {}
 impl EquivocationsDetector for RococoToBridgeHubWestendCliBridge {}
 impl EquivocationsDetector for WestendToBridgeHubRococoCliBridge {}
+impl EquivocationsDetector for KusamaToMoonbeamCliBridge {}
+impl EquivocationsDetector for PolkadotToMoonriverCliBridge {}
 
 impl DetectEquivocations {
 	/// Run the command.
@@ -68,6 +76,10 @@ impl DetectEquivocations {
 				RococoToBridgeHubWestendCliBridge::start(self.params),
 			DetectEquivocationsBridge::WestendToBridgeHubRococo =>
 				WestendToBridgeHubRococoCliBridge::start(self.params),
+			DetectEquivocationsBridge::KusamaToMoonbeam =>
+				KusamaToMoonbeamCliBridge::start(self.params),
+			DetectEquivocationsBridge::PolkadotToMoonriver =>
+				PolkadotToMoonriverCliBridge::start(self.params),
 		}
 		.await
 	}
@@ -0,0 +1,864 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Scheduling of messages relays of multiple lanes.
+//!
+//! Every lane is served by its own messages relay. The scheduler periodically reads outbound
+//! lanes at source chains and starts or stops lane relays, so that:
+//!
+//! - paused lanes are never relayed;
+//! - lanes that have delivered their hourly budget are stopped until the budget is available again;
+//! - lanes with pending messages stop all lanes of the same direction with lower priority.
+//!
+//! Lane relays of opposite directions are configured separately, using the `source` option.
+//! Configuration without this option applies to the lane relays of both directions.
+//!
+//! Every lane relay both delivers messages and confirms their delivery, so the stopped lane
+//! relay doesn't confirm deliveries either. Messages that have been delivered before the lane
+//! relay has been stopped are confirmed (and relayers are rewarded) when it is started again.
+//!
+//! Deliveries are counted when they are confirmed at the source chain, so lanes may slightly
+//! exceed their budgets.
+//!
+//! Messages relay registers its metrics every time it is started. Registering them twice in the
+//! same registry fails, so every start gets its own registry. Metrics of the running relay are
+//! exposed through the relay registry, along with the status of the lane relay, under the
+//! per-lane prefix.
+
+use super::LOG_TARGET;
+
+use async_std::task::JoinHandle;
+use async_trait::async_trait;
+use bp_messages::{MessageNonce, OutboundLaneData, OutboundMessageDetails};
+use clap::Parser;
+use futures::{future::BoxFuture, FutureExt};
+use prometheus::{
+	core::{Collector, Desc},
+	proto::MetricFamily,
+	IntCounter, IntGauge, Registry,
+};
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BalanceOf, Chain, ChainWithRuntimeVersion, Client,
+};
+use relay_utils::metrics::MetricsParams;
+use sp_core::Pair;
+use std::{
+	collections::{BTreeMap, VecDeque},
+	str::FromStr,
+	sync::{Arc, Mutex},
+	time::{Duration, Instant},
+};
+use substrate_relay_helper::{
+	messages::{MessagesRelayLimits, MessagesRelayParams, SubstrateMessageLane},
+	on_demand::OnDemandRelay,
+	TransactionParams,
+};
+
+/// Interval between lane schedule updates.
+const SCHEDULE_UPDATE_INTERVAL: Duration = Duration::from_secs(30);
+/// Window of lane delivery budgets.
+const BUDGET_WINDOW: Duration = Duration::from_secs(60 * 60);
+/// Maximal number of pending messages, weighed at every update.
+const MAX_MESSAGES_TO_WEIGH: MessageNonce = 1024;
+
+/// Per-lane messages relay parameters.
+#[derive(Debug, PartialEq, Parser)]
+pub struct LaneScheduleParams {
+	/// Configuration of the lane in the `<lane>:<option>[,<option>...]` format. Supported options
+	/// are `source=<chain>` (configuration only applies to the lane relay from given chain,
+	/// by default it applies to relays of both directions), `priority=<number>` (lanes with
+	/// pending messages stop lanes with lower priority, default is 0),
+	/// `max-messages-per-hour=<number>`, `max-weight-per-hour=<ref time>` and `paused`. May be
+	/// repeated.
+	#[arg(long)]
+	pub lane_config: Vec<LaneConfig>,
+}
+
+/// Configuration of the lane relay.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LaneConfig {
+	/// Encoded lane identifier.
+	pub lane: Vec<u8>,
+	/// Name of the source chain of the lane relay. If `None`, configuration applies to relays of
+	/// both directions.
+	pub source: Option<String>,
+	/// Lane priority.
+	pub priority: u32,
+	/// Maximal number of messages, delivered within an hour.
+	pub max_messages_per_hour: Option<MessageNonce>,
+	/// Maximal dispatch weight (ref time) of messages, delivered within an hour.
+	pub max_weight_per_hour: Option<u64>,
+	/// True if the lane must not be relayed.
+	pub paused: bool,
+}
+
+impl FromStr for LaneConfig {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (lane, options) = s.split_once(':').unwrap_or((s, ""));
+		let mut config = LaneConfig {
+			lane: hex::decode(lane.trim_start_matches("0x"))
+				.map_err(|e| format!("Invalid lane {lane}: {e}"))?,
+			..Default::default()
+		};
+		for option in options.split(',').filter(|option| !option.is_empty()) {
+			let (name, value) = option.split_once('=').unwrap_or((option, ""));
+			let invalid_value =
+				|e: std::num::ParseIntError| format!("Invalid value of lane option {name}: {e}");
+			match name {
+				"source" if !value.is_empty() => config.source = Some(value.into()),
+				"priority" => config.priority = value.parse().map_err(invalid_value)?,
+				"max-messages-per-hour" =>
+					config.max_messages_per_hour = Some(value.parse().map_err(invalid_value)?),
+				"max-weight-per-hour" =>
+					config.max_weight_per_hour = Some(value.parse().map_err(invalid_value)?),
+				"paused" if value.is_empty() => config.paused = true,
+				_ => return Err(format!("Unknown lane option: {option}")),
+			}
+		}
+		Ok(config)
+	}
+}
+
+impl LaneConfig {
+	/// Returns true if configuration applies to the relay of given lane from given source chain.
+	fn applies_to(&self, source: &str, lane: &[u8]) -> bool {
+		self.lane == lane && self.source.as_deref().map_or(true, |s| s == source)
+	}
+}
+
+/// State of the lane, read from the source chain.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct LaneState {
+	/// Number of messages that are not yet delivered.
+	pending_messages: MessageNonce,
+	/// Number of messages, delivered since previous read.
+	delivered_messages: MessageNonce,
+	/// Dispatch weight (ref time) of messages, delivered since previous read.
+	delivered_weight: u64,
+}
+
+/// Status of the lane relay, selected by the scheduler.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum LaneStatus {
+	/// Lane is relayed.
+	Active,
+	/// Lane is paused by its configuration.
+	Paused,
+	/// Lane has delivered its hourly budget.
+	OverBudget,
+	/// Lane of the same direction with higher priority has pending messages.
+	Preempted,
+}
+
+/// Schedule of the lane relay.
+struct LaneSchedule {
+	/// Lane relays with the same direction compete for priority.
+	direction: String,
+	/// Lane configuration.
+	config: LaneConfig,
+	/// True if lane has pending messages.
+	has_pending_messages: bool,
+	/// Messages and their weight, delivered within the budget window.
+	deliveries: VecDeque<(Instant, MessageNonce, u64)>,
+}
+
+impl LaneSchedule {
+	/// Update schedule with the lane state.
+	fn update(&mut self, now: Instant, state: LaneState) {
+		self.has_pending_messages = state.pending_messages != 0;
+		if state.delivered_messages != 0 {
+			self.deliveries
+				.push_back((now, state.delivered_messages, state.delivered_weight));
+		}
+		while let Some((delivered_at, _, _)) = self.deliveries.front() {
+			if now.duration_since(*delivered_at) < BUDGET_WINDOW {
+				break
+			}
+			self.deliveries.pop_front();
+		}
+	}
+
+	/// Returns true if lane may deliver more messages within the budget window.
+	fn is_within_budget(&self) -> bool {
+		let (messages, weight) = self.deliveries.iter().fold(
+			(0, 0u64),
+			|(messages, weight), (_, delivered_messages, delivered_weight)| {
+				(messages + delivered_messages, weight.saturating_add(*delivered_weight))
+			},
+		);
+		self.config.max_messages_per_hour.map_or(true, |max| messages < max) &&
+			self.config.max_weight_per_hour.map_or(true, |max| weight < max)
+	}
+}
+
+/// Scheduler of lane relays.
+struct LaneScheduler {
+	lanes: Vec<LaneSchedule>,
+}
+
+impl LaneScheduler {
+	/// Create scheduler for lanes with given directions, source chains and encoded identifiers.
+	///
+	/// Configuration of the lane relay from given source chain takes precedence over the
+	/// configuration of relays of both directions.
+	fn new<'a>(
+		lanes: impl IntoIterator<Item = (String, &'a str, &'a [u8])>,
+		configs: &[LaneConfig],
+	) -> Self {
+		LaneScheduler {
+			lanes: lanes
+				.into_iter()
+				.map(|(direction, source, lane)| LaneSchedule {
+					direction,
+					config: configs
+						.iter()
+						.find(|config| config.source.is_some() && config.applies_to(source, lane))
+						.or_else(|| configs.iter().find(|config| config.applies_to(source, lane)))
+						.cloned()
+						.unwrap_or_else(|| LaneConfig {
+							lane: lane.to_vec(),
+							..Default::default()
+						}),
+					has_pending_messages: false,
+					deliveries: VecDeque::new(),
+				})
+				.collect(),
+		}
+	}
+
+	/// Update schedule of the lane relay with given index.
+	fn update(&mut self, index: usize, now: Instant, state: LaneState) {
+		self.lanes[index].update(now, state);
+	}
+
+	/// Returns status of every lane relay.
+	fn statuses(&self) -> Vec<LaneStatus> {
+		let is_eligible = |lane: &LaneSchedule| !lane.config.paused && lane.is_within_budget();
+		let top_priority = |direction: &str| {
+			self.lanes
+				.iter()
+				.filter(|lane| lane.direction == direction)
+				.filter(|lane| lane.has_pending_messages && is_eligible(lane))
+				.map(|lane| lane.config.priority)
+				.max()
+		};
+
+		self.lanes
+			.iter()
+			.map(|lane| {
+				if lane.config.paused {
+					LaneStatus::Paused
+				} else if !lane.is_within_budget() {
+					LaneStatus::OverBudget
+				} else if top_priority(&lane.direction)
+					.map_or(false, |priority| lane.config.priority < priority)
+				{
+					LaneStatus::Preempted
+				} else {
+					LaneStatus::Active
+				}
+			})
+			.collect()
+	}
+}
+
+/// Reader of the lane state.
+#[async_trait]
+trait LaneProbe: Send + Sync {
+	/// Read the lane state.
+	async fn read(&mut self) -> anyhow::Result<LaneState>;
+}
+
+/// Reader of the outbound lane state at the source chain.
+struct OutboundLaneProbe<P: SubstrateMessageLane, SourceClnt> {
+	client: SourceClnt,
+	lane_id: P::LaneId,
+	/// Latest nonce of the delivered message at the previous read.
+	latest_received_nonce: Option<MessageNonce>,
+	/// Dispatch weight (ref time) of pending messages.
+	weights: BTreeMap<MessageNonce, u64>,
+}
+
+#[async_trait]
+impl<P, SourceClnt> LaneProbe for OutboundLaneProbe<P, SourceClnt>
+where
+	P: SubstrateMessageLane,
+	SourceClnt: Client<P::SourceChain>,
+{
+	async fn read(&mut self) -> anyhow::Result<LaneState> {
+		let at = self.client.best_finalized_header_hash().await?;
+		let lane_data: Option<OutboundLaneData> = self
+			.client
+			.storage_value(
+				at,
+				bp_messages::storage_keys::outbound_lane_data_key(
+					<P::TargetChain as bp_messages::ChainWithMessages>::WITH_CHAIN_MESSAGES_PALLET_NAME,
+					&self.lane_id,
+				),
+			)
+			.await?;
+		let Some(lane_data) = lane_data else { return Ok(LaneState::default()) };
+
+		// weigh pending messages while they are still stored at the source chain
+		let begin = std::cmp::max(
+			lane_data.latest_received_nonce + 1,
+			self.weights.last_key_value().map_or(0, |(nonce, _)| nonce + 1),
+		);
+		let end = std::cmp::min(
+			lane_data.latest_generated_nonce,
+			begin.saturating_add(MAX_MESSAGES_TO_WEIGH - 1),
+		);
+		if begin <= end {
+			let details: Vec<OutboundMessageDetails> = self
+				.client
+				.state_call(
+					at,
+					P::TargetChain::TO_CHAIN_MESSAGE_DETAILS_METHOD.into(),
+					(self.lane_id, begin, end),
+				)
+				.await?;
+			self.weights.extend(
+				details
+					.into_iter()
+					.map(|details| (details.nonce, details.dispatch_weight.ref_time())),
+			);
+		}
+
+		let latest_received_nonce = lane_data.latest_received_nonce;
+		let (delivered_messages, delivered_weight) =
+			match self.latest_received_nonce.replace(latest_received_nonce) {
+				Some(previous) if latest_received_nonce > previous => (
+					latest_received_nonce - previous,
+					self.weights
+						.range(previous + 1..=latest_received_nonce)
+						.fold(0u64, |total, (_, weight)| total.saturating_add(*weight)),
+				),
+				_ => (0, 0),
+			};
+		self.weights = self.weights.split_off(&(latest_received_nonce + 1));
+
+		Ok(LaneState {
+			pending_messages: lane_data
+				.latest_generated_nonce
+				.saturating_sub(latest_received_nonce),
+			delivered_messages,
+			delivered_weight,
+		})
+	}
+}
+
+/// Metrics of the lane relay.
+#[derive(Clone)]
+struct LaneRelayMetrics {
+	/// 1 if the lane relay is running and 0 otherwise.
+	is_running: IntGauge,
+	/// Number of lane relay starts.
+	starts: IntCounter,
+	/// Registry of the running lane relay.
+	relay_registry: Arc<Mutex<Option<Registry>>>,
+}
+
+impl LaneRelayMetrics {
+	/// Create metrics of the lane relay with given prefix.
+	fn new(prefix: &str) -> anyhow::Result<Self> {
+		Ok(LaneRelayMetrics {
+			is_running: IntGauge::new(
+				format!("{prefix}_relay_is_running"),
+				"Whether the messages relay of the lane is running",
+			)?,
+			starts: IntCounter::new(
+				format!("{prefix}_relay_starts"),
+				"Number of messages relay starts of the lane",
+			)?,
+			relay_registry: Arc::new(Mutex::new(None)),
+		})
+	}
+
+	/// Returns metrics parameters of the started lane relay.
+	fn started(&self) -> MetricsParams {
+		let params = MetricsParams::disabled();
+		*self.relay_registry.lock().unwrap_or_else(|e| e.into_inner()) =
+			Some(params.registry.clone());
+		self.is_running.set(1);
+		self.starts.inc();
+		params
+	}
+
+	/// Forget metrics of the stopped lane relay.
+	fn stopped(&self) {
+		*self.relay_registry.lock().unwrap_or_else(|e| e.into_inner()) = None;
+		self.is_running.set(0);
+	}
+}
+
+impl Collector for LaneRelayMetrics {
+	fn desc(&self) -> Vec<&Desc> {
+		self.is_running.desc().into_iter().chain(self.starts.desc()).collect()
+	}
+
+	fn collect(&self) -> Vec<MetricFamily> {
+		let relay_registry = self.relay_registry.lock().unwrap_or_else(|e| e.into_inner()).clone();
+		self.is_running
+			.collect()
+			.into_iter()
+			.chain(self.starts.collect())
+			.chain(relay_registry.map(|registry| registry.gather()).unwrap_or_default())
+			.collect()
+	}
+}
+
+/// Messages relay of the lane, that may be started and stopped by the scheduler.
+pub struct LaneRelay {
+	/// Lane relay name, used in logs.
+	name: String,
+	/// Lane relays with the same direction compete for priority.
+	direction: String,
+	/// Name of the source chain.
+	source: &'static str,
+	/// Encoded lane identifier.
+	lane: Vec<u8>,
+	/// Reader of the lane state.
+	probe: Box<dyn LaneProbe>,
+	/// Metrics of the lane relay.
+	metrics: LaneRelayMetrics,
+	/// Start messages relay of the lane with given metrics parameters.
+	start: Box<dyn Fn(MetricsParams) -> BoxFuture<'static, anyhow::Result<()>> + Send + Sync>,
+}
+
+/// Parameters of messages relays of lanes with the same direction.
+///
+/// Messages relay parameters of the lane are created from them every time the lane relay is
+/// started, with the metrics parameters of this start.
+pub struct LaneRelayParams<P: SubstrateMessageLane, SourceClnt, TargetClnt> {
+	/// Client of the source chain.
+	pub source_client: SourceClnt,
+	/// Source chain transaction params.
+	pub source_transaction_params: TransactionParams<AccountKeyPairOf<P::SourceChain>>,
+	/// Client of the target chain.
+	pub target_client: TargetClnt,
+	/// Target chain transaction params.
+	pub target_transaction_params: TransactionParams<AccountKeyPairOf<P::TargetChain>>,
+	/// Optional on-demand source to target headers relay.
+	pub source_to_target_headers_relay:
+		Option<Arc<dyn OnDemandRelay<P::SourceChain, P::TargetChain>>>,
+	/// Optional on-demand target to source headers relay.
+	pub target_to_source_headers_relay:
+		Option<Arc<dyn OnDemandRelay<P::TargetChain, P::SourceChain>>>,
+	/// Messages relay limits. If not provided, the relay tries to determine it automatically,
+	/// using `TransactionPayment` pallet runtime API.
+	pub limits: Option<MessagesRelayLimits>,
+}
+
+impl<P, SourceClnt, TargetClnt> Clone for LaneRelayParams<P, SourceClnt, TargetClnt>
+where
+	P: SubstrateMessageLane,
+	SourceClnt: Clone,
+	TargetClnt: Clone,
+{
+	fn clone(&self) -> Self {
+		LaneRelayParams {
+			source_client: self.source_client.clone(),
+			source_transaction_params: self.source_transaction_params.clone(),
+			target_client: self.target_client.clone(),
+			target_transaction_params: self.target_transaction_params.clone(),
+			source_to_target_headers_relay: self.source_to_target_headers_relay.clone(),
+			target_to_source_headers_relay: self.target_to_source_headers_relay.clone(),
+			limits: self.limits.clone(),
+		}
+	}
+}
+
+impl<P, SourceClnt, TargetClnt> LaneRelayParams<P, SourceClnt, TargetClnt>
+where
+	P: SubstrateMessageLane,
+	SourceClnt: Client<P::SourceChain>,
+	TargetClnt: Client<P::TargetChain>,
+	AccountIdOf<P::SourceChain>: From<<AccountKeyPairOf<P::SourceChain> as Pair>::Public>,
+	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
+	BalanceOf<P::SourceChain>: TryFrom<BalanceOf<P::TargetChain>>,
+{
+	/// Create relays of lanes with given encoded identifiers.
+	pub fn lane_relays(
+		&self,
+		lanes: impl IntoIterator<Item = impl AsRef<[u8]>>,
+	) -> anyhow::Result<Vec<LaneRelay>> {
+		lanes
+			.into_iter()
+			.map(|lane| LaneRelay::new(lane.as_ref(), self.clone()))
+			.collect()
+	}
+
+	/// Returns messages relay parameters of the lane with given identifier.
+	fn messages_relay_params(
+		self,
+		lane_id: P::LaneId,
+		metrics_params: MetricsParams,
+	) -> MessagesRelayParams<P, SourceClnt, TargetClnt> {
+		MessagesRelayParams {
+			source_client: self.source_client,
+			source_transaction_params: self.source_transaction_params,
+			target_client: self.target_client,
+			target_transaction_params: self.target_transaction_params,
+			source_to_target_headers_relay: self.source_to_target_headers_relay,
+			target_to_source_headers_relay: self.target_to_source_headers_relay,
+			lane_id,
+			limits: self.limits,
+			metrics_params,
+		}
+	}
+}
+
+impl LaneRelay {
+	/// Create relay of the lane with given encoded identifier.
+	fn new<P, SourceClnt, TargetClnt>(
+		lane: &[u8],
+		params: LaneRelayParams<P, SourceClnt, TargetClnt>,
+	) -> anyhow::Result<Self>
+	where
+		P: SubstrateMessageLane,
+		SourceClnt: Client<P::SourceChain>,
+		TargetClnt: Client<P::TargetChain>,
+		AccountIdOf<P::SourceChain>: From<<AccountKeyPairOf<P::SourceChain> as Pair>::Public>,
+		AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TargetChain> as Pair>::Public>,
+		BalanceOf<P::SourceChain>: TryFrom<BalanceOf<P::TargetChain>>,
+	{
+		let lane_id: P::LaneId = codec::DecodeAll::decode_all(&mut &lane[..]).map_err(|_| {
+			anyhow::format_err!(
+				"Invalid {} -> {} lane: 0x{}",
+				P::SourceChain::NAME,
+				P::TargetChain::NAME,
+				hex::encode(lane),
+			)
+		})?;
+		Ok(LaneRelay {
+			name: format!(
+				"{} -> {} lane 0x{}",
+				P::SourceChain::NAME,
+				P::TargetChain::NAME,
+				hex::encode(lane),
+			),
+			direction: format!("{} -> {}", P::SourceChain::NAME, P::TargetChain::NAME),
+			source: P::SourceChain::NAME,
+			lane: lane.to_vec(),
+			probe: Box::new(OutboundLaneProbe::<P, _> {
+				client: params.source_client.clone(),
+				lane_id,
+				latest_received_nonce: None,
+				weights: BTreeMap::new(),
+			}),
+			metrics: LaneRelayMetrics::new(&format!(
+				"{}_to_{}_MessageLane_{}",
+				P::SourceChain::NAME,
+				P::TargetChain::NAME,
+				hex::encode(lane),
+			))?,
+			start: Box::new(move |metrics_params| {
+				substrate_relay_helper::messages::run::<P, _, _>(
+					params.clone().messages_relay_params(lane_id, metrics_params),
+				)
+				.map(|result| result.map_err(|e| anyhow::format_err!("{}", e)))
+				.boxed()
+			}),
+		})
+	}
+}
+
+/// Run messages relays of given lanes, starting and stopping them according to the lane
+/// configurations.
+///
+/// Metrics of lane relays are registered in the registry of given metrics parameters. Returns
+/// when any of lane relays fails.
+pub async fn run_lane_relays(
+	mut relays: Vec<LaneRelay>,
+	configs: Vec<LaneConfig>,
+	metrics_params: &MetricsParams,
+) -> anyhow::Result<()> {
+	if let Some(config) = configs
+		.iter()
+		.find(|config| !relays.iter().any(|relay| config.applies_to(relay.source, &relay.lane)))
+	{
+		anyhow::bail!(
+			"Lane 0x{}{} is configured, but it is not relayed",
+			hex::encode(&config.lane),
+			config
+				.source
+				.as_ref()
+				.map(|source| format!(" from {source}"))
+				.unwrap_or_default(),
+		)
+	}
+	for relay in &relays {
+		metrics_params.registry.register(Box::new(relay.metrics.clone()))?;
+	}
+
+	let mut scheduler = LaneScheduler::new(
+		relays
+			.iter()
+			.map(|relay| (relay.direction.clone(), relay.source, relay.lane.as_slice())),
+		&configs,
+	);
+	let mut running: Vec<Option<JoinHandle<anyhow::Result<()>>>> =
+		relays.iter().map(|_| None).collect();
+	loop {
+		let now = Instant::now();
+		for (index, relay) in relays.iter_mut().enumerate() {
+			match relay.probe.read().await {
+				Ok(state) => scheduler.update(index, now, state),
+				Err(e) => log::warn!(
+					target: LOG_TARGET,
+					"Failed to read state of {}: {e:?}. Keeping its schedule",
+					relay.name,
+				),
+			}
+		}
+
+		for (index, status) in scheduler.statuses().into_iter().enumerate() {
+			let relay = &relays[index];
+			if let Some(result) = running[index].as_mut().and_then(|task| task.now_or_never()) {
+				return result.and_then(|_| {
+					Err(anyhow::format_err!("Messages relay of {} has stopped", relay.name))
+				})
+			}
+
+			match (status, running[index].take()) {
+				(LaneStatus::Active, None) => {
+					log::info!(target: LOG_TARGET, "Starting messages relay of {}", relay.name);
+					running[index] =
+						Some(async_std::task::spawn((relay.start)(relay.metrics.started())));
+				},
+				(LaneStatus::Active, task) => running[index] = task,
+				(status, Some(task)) => {
+					log::info!(
+						target: LOG_TARGET,
+						"Stopping messages relay of {}: {status:?}",
+						relay.name,
+					);
+					task.cancel().await;
+					relay.metrics.stopped();
+				},
+				(_, None) => (),
+			}
+		}
+
+		async_std::task::sleep(SCHEDULE_UPDATE_INTERVAL).await;
+	}
+}
+
+/// Abort the relay when the runtime of the chain is upgraded.
+pub async fn start_version_guard<C: ChainWithRuntimeVersion>(
+	client: &impl Client<C>,
+) -> anyhow::Result<()> {
+	if client.can_start_version_guard() {
+		relay_substrate_client::guard::abort_on_spec_version_change(
+			client.clone(),
+			client.simple_runtime_version().await?.spec_version,
+		);
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn scheduler(lanes: &[(&str, &[u8])], configs: &[LaneConfig]) -> LaneScheduler {
+		LaneScheduler::new(
+			lanes.iter().map(|(direction, lane)| {
+				let source = direction.split(" -> ").next().unwrap();
+				(direction.to_string(), source, *lane)
+			}),
+			configs,
+		)
+	}
+
+	fn pending(pending_messages: MessageNonce) -> LaneState {
+		LaneState { pending_messages, ..Default::default() }
+	}
+
+	#[test]
+	fn parses_lane_config() {
+		assert_eq!(
+			LaneConfig::from_str(
+				"0x00000001:source=BridgeHubKusama,priority=10,max-messages-per-hour=100,paused"
+			),
+			Ok(LaneConfig {
+				lane: vec![0, 0, 0, 1],
+				source: Some("BridgeHubKusama".into()),
+				priority: 10,
+				max_messages_per_hour: Some(100),
+				max_weight_per_hour: None,
+				paused: true,
+			}),
+		);
+		assert_eq!(
+			LaneConfig::from_str("00000002:max-weight-per-hour=5000000000"),
+			Ok(LaneConfig {
+				lane: vec![0, 0, 0, 2],
+				max_weight_per_hour: Some(5_000_000_000),
+				..Default::default()
+			}),
+		);
+		assert_eq!(
+			LaneConfig::from_str("00000003"),
+			Ok(LaneConfig { lane: vec![0, 0, 0, 3], ..Default::default() }),
+		);
+		assert!(LaneConfig::from_str("00000001:priority").is_err());
+		assert!(LaneConfig::from_str("00000001:source").is_err());
+		assert!(LaneConfig::from_str("00000001:unknown=1").is_err());
+		assert!(LaneConfig::from_str("not-a-lane").is_err());
+	}
+
+	#[test]
+	fn lanes_with_pending_messages_preempt_lanes_with_lower_priority() {
+		let configs = [LaneConfig { lane: vec![1], priority: 1, ..Default::default() }];
+		let mut scheduler =
+			scheduler(&[("A -> B", &[1]), ("A -> B", &[2]), ("B -> A", &[3])], &configs);
+		let now = Instant::now();
+
+		// no pending messages at the system lane
+		scheduler.update(1, now, pending(10));
+		scheduler.update(2, now, pending(10));
+		assert_eq!(scheduler.statuses(), vec![LaneStatus::Active; 3]);
+
+		// system lane has pending messages => lane of the same direction is preempted
+		scheduler.update(0, now, pending(1));
+		assert_eq!(
+			scheduler.statuses(),
+			vec![LaneStatus::Active, LaneStatus::Preempted, LaneStatus::Active],
+		);
+	}
+
+	#[test]
+	fn lanes_are_stopped_when_budget_is_exhausted() {
+		let configs = [
+			LaneConfig { lane: vec![1], max_messages_per_hour: Some(10), ..Default::default() },
+			LaneConfig { lane: vec![2], max_weight_per_hour: Some(1_000), ..Default::default() },
+		];
+		let mut scheduler = scheduler(&[("A -> B", &[1]), ("A -> B", &[2])], &configs);
+		let now = Instant::now();
+
+		scheduler.update(
+			0,
+			now,
+			LaneState { pending_messages: 1, delivered_messages: 9, delivered_weight: 1_000 },
+		);
+		scheduler.update(
+			1,
+			now,
+			LaneState { pending_messages: 1, delivered_messages: 1, delivered_weight: 999 },
+		);
+		assert_eq!(scheduler.statuses(), vec![LaneStatus::Active, LaneStatus::Active]);
+
+		scheduler.update(
+			0,
+			now,
+			LaneState { pending_messages: 1, delivered_messages: 1, delivered_weight: 0 },
+		);
+		scheduler.update(
+			1,
+			now,
+			LaneState { pending_messages: 1, delivered_messages: 1, delivered_weight: 1 },
+		);
+		assert_eq!(scheduler.statuses(), vec![LaneStatus::OverBudget, LaneStatus::OverBudget]);
+
+		// budget is available again when deliveries leave the window
+		let later = now + BUDGET_WINDOW;
+		scheduler.update(0, later, pending(1));
+		scheduler.update(1, later, pending(1));
+		assert_eq!(scheduler.statuses(), vec![LaneStatus::Active, LaneStatus::Active]);
+	}
+
+	#[test]
+	fn lane_configs_apply_to_given_direction() {
+		let configs = [
+			LaneConfig { lane: vec![1], paused: true, ..Default::default() },
+			LaneConfig { lane: vec![1], source: Some("B".into()), ..Default::default() },
+			LaneConfig {
+				lane: vec![2],
+				source: Some("A".into()),
+				paused: true,
+				..Default::default()
+			},
+		];
+		let scheduler = scheduler(
+			&[("A -> B", &[1]), ("B -> A", &[1]), ("A -> B", &[2]), ("B -> A", &[2])],
+			&configs,
+		);
+
+		// configuration of the direction takes precedence over configuration of both directions
+		assert_eq!(
+			scheduler.statuses(),
+			vec![LaneStatus::Paused, LaneStatus::Active, LaneStatus::Paused, LaneStatus::Active],
+		);
+	}
+
+	#[test]
+	fn paused_lanes_never_preempt_other_lanes() {
+		let configs =
+			[LaneConfig { lane: vec![1], priority: 1, paused: true, ..Default::default() }];
+		let mut scheduler = scheduler(&[("A -> B", &[1]), ("A -> B", &[2])], &configs);
+		let now = Instant::now();
+
+		scheduler.update(0, now, pending(1));
+		scheduler.update(1, now, pending(1));
+		assert_eq!(scheduler.statuses(), vec![LaneStatus::Paused, LaneStatus::Active]);
+	}
+
+	#[test]
+	fn lane_relay_metrics_expose_metrics_of_running_relay() {
+		let registry = Registry::new();
+		let metrics = LaneRelayMetrics::new("A_to_B_MessageLane_00000001").unwrap();
+		registry.register(Box::new(metrics.clone())).unwrap();
+		let names = || {
+			registry
+				.gather()
+				.iter()
+				.map(|family| family.get_name().to_owned())
+				.collect::<Vec<_>>()
+		};
+
+		// relay metrics are registered in the new registry at every start
+		for _ in 0..2 {
+			let params = metrics.started();
+			params
+				.registry
+				.register(Box::new(
+					IntGauge::new("A_to_B_MessageLane_00000001_nonce", "-").unwrap(),
+				))
+				.unwrap();
+			assert_eq!(
+				names(),
+				vec![
+					"A_to_B_MessageLane_00000001_nonce",
+					"A_to_B_MessageLane_00000001_relay_is_running",
+					"A_to_B_MessageLane_00000001_relay_starts",
+				],
+			);
+
+			metrics.stopped();
+			assert_eq!(
+				names(),
+				vec![
+					"A_to_B_MessageLane_00000001_relay_is_running",
+					"A_to_B_MessageLane_00000001_relay_starts",
+				],
+			);
+		}
+		assert_eq!(metrics.starts.get(), 2);
+	}
+}
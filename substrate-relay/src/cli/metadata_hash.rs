@@ -0,0 +1,187 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Verification of metadata hashes of chains, that the relay signs transactions to with the
+//! `CheckMetadataHash` transaction extension enabled.
+
+use clap::ValueEnum;
+use codec::{Decode, Encode};
+use jsonrpsee::{core::client::ClientT, rpc_params, ws_client::WsClientBuilder};
+use relay_common_client::{ChainWithMetadataHash, TokenInfo, METADATA_HASH_VERSION};
+use serde_json::{Map, Value};
+use std::str::FromStr;
+
+/// Chains that support the `CheckMetadataHash` transaction extension.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum MetadataHashChain {
+	BridgeHubKusama,
+	BridgeHubPolkadot,
+	BridgeHubRococo,
+	BridgeHubWestend,
+	AssetHubRococo,
+	AssetHubWestend,
+	AssetHubKusama,
+	AssetHubPolkadot,
+	Moonriver,
+	Moonbeam,
+}
+
+/// The `--check-metadata-hash` argument value: `<chain>=<node-uri>`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetadataHashCheck {
+	/// Chain to sign transactions with the `CheckMetadataHash` transaction extension enabled.
+	pub chain: MetadataHashChain,
+	/// URI of the chain node, used to fetch the metadata of the live runtime.
+	pub uri: String,
+}
+
+impl FromStr for MetadataHashCheck {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (chain, uri) = s
+			.split_once('=')
+			.ok_or_else(|| format!("Expected `<chain>=<node-uri>`, got `{s}`"))?;
+		Ok(MetadataHashCheck { chain: MetadataHashChain::from_str(chain, true)?, uri: uri.into() })
+	}
+}
+
+impl MetadataHashCheck {
+	/// Fetch metadata of the live runtime from the node and compare its hash with the hash,
+	/// computed by the `runtime-codegen` (or its version with the version of the generated code).
+	///
+	/// If the check passes, all further transactions to the chain are signed with the
+	/// `CheckMetadataHash` transaction extension enabled.
+	pub async fn verify(&self) -> anyhow::Result<()> {
+		let uri = self.uri.clone();
+		// `jsonrpsee` client requires `tokio` runtime and the relay runs on `async-std`
+		let (raw_metadata, token) =
+			async_std::task::spawn_blocking(move || -> anyhow::Result<_> {
+				tokio::runtime::Builder::new_current_thread()
+					.enable_all()
+					.build()?
+					.block_on(fetch_metadata(&uri))
+			})
+			.await?;
+
+		match self.chain {
+			MetadataHashChain::BridgeHubKusama =>
+				verify::<relay_bridge_hub_kusama_client::BridgeHubKusama>(&raw_metadata, &token),
+			MetadataHashChain::BridgeHubPolkadot =>
+				verify::<relay_bridge_hub_polkadot_client::BridgeHubPolkadot>(&raw_metadata, &token),
+			MetadataHashChain::BridgeHubRococo =>
+				verify::<relay_bridge_hub_rococo_client::BridgeHubRococo>(&raw_metadata, &token),
+			MetadataHashChain::BridgeHubWestend =>
+				verify::<relay_bridge_hub_westend_client::BridgeHubWestend>(&raw_metadata, &token),
+			MetadataHashChain::AssetHubRococo =>
+				verify::<relay_asset_hub_rococo_client::AssetHubRococo>(&raw_metadata, &token),
+			MetadataHashChain::AssetHubWestend =>
+				verify::<relay_asset_hub_westend_client::AssetHubWestend>(&raw_metadata, &token),
+			MetadataHashChain::AssetHubKusama =>
+				verify::<relay_asset_hub_kusama_client::AssetHubKusama>(&raw_metadata, &token),
+			MetadataHashChain::AssetHubPolkadot =>
+				verify::<relay_asset_hub_polkadot_client::AssetHubPolkadot>(&raw_metadata, &token),
+			MetadataHashChain::Moonriver =>
+				verify::<relay_moonriver_client::Moonriver>(&raw_metadata, &token),
+			MetadataHashChain::Moonbeam =>
+				verify::<relay_moonbeam_client::Moonbeam>(&raw_metadata, &token),
+		}
+	}
+}
+
+/// Verify metadata hash of the chain.
+fn verify<C: ChainWithMetadataHash>(raw_metadata: &[u8], token: &TokenInfo) -> anyhow::Result<()> {
+	relay_common_client::verify_metadata_hash::<C>(raw_metadata, token).map_err(Into::into)
+}
+
+/// Fetch raw metadata of the live runtime and the native token of the chain from the node.
+async fn fetch_metadata(uri: &str) -> anyhow::Result<(Vec<u8>, TokenInfo)> {
+	let client = WsClientBuilder::default()
+		.build(uri)
+		.await
+		.map_err(|e| anyhow::format_err!("Failed to connect to the node at {uri}: {e}"))?;
+
+	let encoded_metadata: String = client
+		.request(
+			"state_call",
+			rpc_params![
+				"Metadata_metadata_at_version",
+				format!("0x{}", hex::encode(METADATA_HASH_VERSION.encode()))
+			],
+		)
+		.await?;
+	let raw_metadata = Option::<Vec<u8>>::decode(
+		&mut &hex::decode(encoded_metadata.trim_start_matches("0x"))?[..],
+	)?
+	.ok_or_else(|| {
+		anyhow::format_err!("The node at {uri} doesn't provide V{METADATA_HASH_VERSION} metadata")
+	})?;
+
+	let properties: Map<String, Value> = client.request("system_properties", rpc_params![]).await?;
+	let token = token_info(&properties).ok_or_else(|| {
+		anyhow::format_err!("The node at {uri} doesn't provide native token info")
+	})?;
+
+	Ok((raw_metadata, token))
+}
+
+/// Read native token info from the `system_properties` RPC response. Multi-token chains report
+/// the native token first.
+fn token_info(properties: &Map<String, Value>) -> Option<TokenInfo> {
+	let first = |value: &Value| match value {
+		Value::Array(values) => values.first().cloned(),
+		value => Some(value.clone()),
+	};
+	let symbol = first(properties.get("tokenSymbol")?)?.as_str()?.to_owned();
+	let decimals = first(properties.get("tokenDecimals")?)?.as_u64()?.try_into().ok()?;
+	Some(TokenInfo { symbol, decimals })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_metadata_hash_check() {
+		assert_eq!(
+			"bridge-hub-polkadot=wss://polkadot-bridge-hub-rpc.polkadot.io"
+				.parse::<MetadataHashCheck>(),
+			Ok(MetadataHashCheck {
+				chain: MetadataHashChain::BridgeHubPolkadot,
+				uri: "wss://polkadot-bridge-hub-rpc.polkadot.io".into(),
+			}),
+		);
+		assert!("bridge-hub-polkadot".parse::<MetadataHashCheck>().is_err());
+		assert!("unknown=ws://localhost:9944".parse::<MetadataHashCheck>().is_err());
+	}
+
+	#[test]
+	fn reads_token_info() {
+		let properties = |json: &str| serde_json::from_str::<Map<String, Value>>(json).unwrap();
+
+		let token =
+			token_info(&properties(r#"{"ss58Format":0,"tokenDecimals":10,"tokenSymbol":"DOT"}"#))
+				.unwrap();
+		assert_eq!((token.symbol.as_str(), token.decimals), ("DOT", 10));
+
+		let token =
+			token_info(&properties(r#"{"tokenDecimals":[12,12],"tokenSymbol":["KSM","KAR"]}"#))
+				.unwrap();
+		assert_eq!((token.symbol.as_str(), token.decimals), ("KSM", 12));
+
+		assert!(token_info(&properties(r#"{"ss58Format":0}"#)).is_none());
+	}
+}
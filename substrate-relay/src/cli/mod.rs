@@ -16,31 +16,89 @@
 
 //! Deal with CLI args of substrate-to-substrate relay.
 
+use crate::rpc_recording::{RpcRecording, RpcRecordingMode};
+use metadata_hash::MetadataHashCheck;
+
 use async_std::prelude::*;
-use clap::Parser;
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand};
 use futures::{select, FutureExt};
 use signal_hook::consts::*;
 use signal_hook_async_std::Signals;
+use std::path::PathBuf;
 
 mod chain_schema;
+mod decode_extrinsic;
 mod detect_equivocations;
 mod init_bridge;
+mod lane_schedule;
+mod metadata_hash;
+mod query_storage;
+mod relay_asset_hubs;
 mod relay_headers;
 mod relay_headers_and_messages;
+mod relay_mandatory_headers;
 mod relay_messages;
 mod relay_parachains;
+mod relay_parachains_by_id;
 
 /// The target that will be used when publishing logs related to this pallet.
 pub const LOG_TARGET: &str = "bridge";
 
 /// Parse relay CLI args.
-pub fn parse_args() -> Command {
-	Command::parse()
+///
+/// If RPC recording or replaying is enabled, starts local RPC servers and replaces node URIs
+/// with URIs of these servers.
+pub fn parse_args() -> Cli {
+	let cli = Cli::parse();
+	let mode = match (cli.record_rpc.clone(), cli.replay_rpc.clone()) {
+		(Some(dir), _) => RpcRecordingMode::Record(dir),
+		(None, Some(dir)) => RpcRecordingMode::Replay(dir),
+		(None, None) => return cli,
+	};
+
+	match RpcRecording::start(mode, std::env::args_os()) {
+		Ok((rpc_recording, args)) =>
+			Cli { rpc_recording: Some(rpc_recording), ..Cli::parse_from(args) },
+		Err(e) => Cli::command()
+			.error(ErrorKind::Io, format!("Failed to start RPC recording: {e}"))
+			.exit(),
+	}
 }
 
 /// Substrate-to-Substrate bridge utilities.
 #[derive(Parser)]
 #[command(about = "Substrate-to-Substrate relay", version)]
+pub struct Cli {
+	/// Sign transactions to given chain with the `CheckMetadataHash` transaction extension
+	/// enabled: `<chain>=<node-uri>`. The hash of the runtime metadata, fetched from the node, is
+	/// used. The relay fails to start if it differs from the hash, computed by the
+	/// `runtime-codegen`, or, if the runtime code has been generated without the hash, if the node
+	/// runs other runtime version. May be repeated.
+	#[arg(long, global = true)]
+	check_metadata_hash: Vec<MetadataHashCheck>,
+	/// Record all RPC requests, responses and subscription notifications of every chain client
+	/// to the `<chain>.jsonl` file in given directory.
+	#[arg(long, global = true, conflicts_with = "replay_rpc")]
+	record_rpc: Option<PathBuf>,
+	/// Do not connect to chain nodes. Instead, answer RPC requests of every chain client with
+	/// responses, recorded with `--record-rpc` to given directory.
+	#[arg(long, global = true)]
+	replay_rpc: Option<PathBuf>,
+	#[command(subcommand)]
+	command: Command,
+	#[arg(skip)]
+	rpc_recording: Option<RpcRecording>,
+}
+
+impl Cli {
+	/// Run the command.
+	pub async fn run(self) {
+		self.command.run(self.check_metadata_hash, self.rpc_recording).await
+	}
+}
+
+/// Substrate-to-Substrate bridge commands.
+#[derive(Subcommand)]
 pub enum Command {
 	/// Initialize on-chain bridge pallet with current header data.
 	///
@@ -53,6 +111,16 @@ pub enum Command {
 	RelayHeaders(relay_headers::RelayHeaders),
 	/// Relay single header.
 	RelayHeader(relay_headers::RelayHeader),
+	/// Relay all mandatory headers from the range.
+	///
+	/// Mandatory headers are submitted back to back, optionally batched. Headers that are already
+	/// imported by the target chain are skipped, so the command may be restarted after failure.
+	RelayMandatoryHeaders(relay_mandatory_headers::RelayMandatoryHeaders),
+	/// Report mandatory headers that are still to be relayed.
+	///
+	/// Compares GRANDPA authorities set ids at the source chain and the target bridge pallet,
+	/// lists pending mandatory headers and estimates the weight and fee of relaying them.
+	PendingMandatoryHeaders(relay_mandatory_headers::PendingMandatoryHeaders),
 	/// Relay parachain heads.
 	RelayParachains(relay_parachains::RelayParachains),
 	/// Relay single parachain head.
@@ -79,6 +147,16 @@ pub enum Command {
 	/// Parses the source chain headers that were synchronized with the target chain looking for
 	/// equivocations. If any equivocation is found, it is reported to the source chain.
 	DetectEquivocations(detect_equivocations::DetectEquivocations),
+	/// Decode signed transaction and verify its signature.
+	///
+	/// Prints the signer, transaction extension parameters and call of the transaction in the JSON
+	/// format.
+	DecodeExtrinsic(decode_extrinsic::DecodeExtrinsic),
+	/// Read storage item of the bridge pallet.
+	///
+	/// Builds the storage key of the `<Pallet>::<Item>` item, reads the value at given block and
+	/// prints the value, decoded using the runtime code, in the JSON format.
+	QueryStorage(query_storage::QueryStorage),
 }
 
 impl Command {
@@ -111,6 +189,8 @@ impl Command {
 			Self::InitBridge(arg) => arg.run().await?,
 			Self::RelayHeaders(arg) => arg.run().await?,
 			Self::RelayHeader(arg) => arg.run().await?,
+			Self::RelayMandatoryHeaders(arg) => arg.run().await?,
+			Self::PendingMandatoryHeaders(arg) => arg.run().await?,
 			Self::RelayParachains(arg) => arg.run().await?,
 			Self::RelayParachainHead(arg) => arg.run().await?,
 			Self::RelayMessages(arg) => arg.run().await?,
@@ -118,15 +198,33 @@ impl Command {
 			Self::RelayMessagesDeliveryConfirmation(arg) => arg.run().await?,
 			Self::RelayHeadersAndMessages(arg) => arg.run().await?,
 			Self::DetectEquivocations(arg) => arg.run().await?,
+			Self::DecodeExtrinsic(arg) => arg.run().await?,
+			Self::QueryStorage(arg) => arg.run().await?,
 		}
 		Ok(())
 	}
 
 	/// Run the command.
-	pub async fn run(self) {
+	pub async fn run(
+		self,
+		check_metadata_hash: Vec<MetadataHashCheck>,
+		rpc_recording: Option<RpcRecording>,
+	) {
 		self.init_logger();
 		self.init_version();
 
+		// servers are stopped when `rpc_recording` is dropped at the end of this function
+		if let Some(ref rpc_recording) = rpc_recording {
+			rpc_recording.log_servers();
+		}
+
+		for check in check_metadata_hash {
+			if let Err(e) = check.verify().await {
+				log::error!(target: LOG_TARGET, "substrate-relay: {}", e);
+				return
+			}
+		}
+
 		let exit_signals = match Signals::new([SIGINT, SIGTERM]) {
 			Ok(signals) => signals,
 			Err(e) => {
@@ -0,0 +1,586 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use bp_messages::{LegacyLaneId, MessageNonce};
+use bp_parachains::{ImportedParaHeadsKeyProvider, ParasInfoKeyProvider};
+use bp_polkadot_core::parachains::ParaId;
+use bp_runtime::{StorageDoubleMapKeyProvider, StorageMapKeyProvider};
+use clap::{Parser, ValueEnum};
+use codec::{Decode, DecodeAll, Encode};
+use frame_support::{Blake2_128Concat, Identity, Twox64Concat};
+use relay_substrate_client::{AccountIdOf, ChainWithRuntimeVersion, Client};
+use serde::Serialize;
+use sp_core::{storage::StorageKey, H256};
+use std::{fmt::Debug, str::FromStr};
+use strum::{EnumString, VariantNames};
+use substrate_relay_helper::cli::chain_schema::SourceConnectionParams;
+
+/// Read storage item of the bridge pallet.
+///
+/// The value is decoded using the runtime code that the chain client has been generated for. The
+/// decoded value is printed in the JSON format.
+#[derive(Parser)]
+pub struct QueryStorage {
+	/// Chain to read storage of.
+	#[arg(long, value_enum, ignore_case = true)]
+	chain: QueryStorageChain,
+	#[command(flatten)]
+	source: SourceConnectionParams,
+	/// Hash of the block to read storage at. Defaults to the best finalized block.
+	#[arg(long)]
+	at: Option<H256>,
+	/// Storage item in the `<Pallet>::<Item>` form, e.g. `BridgePolkadotGrandpa::BestFinalized`.
+	item: StorageItemPath,
+	/// Keys of the map item: header hash, `ParaId`, hex-encoded `LegacyLaneId` or `HashedLaneId`,
+	/// message nonce or index in the ring buffer, depending on the item.
+	keys: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, EnumString, VariantNames, ValueEnum)]
+#[strum(serialize_all = "kebab_case")]
+/// Chain with bridge pallets.
+pub enum QueryStorageChain {
+	PolkadotBulletin,
+	BridgeHubKusama,
+	BridgeHubPolkadot,
+	BridgeHubRococo,
+	BridgeHubWestend,
+	AssetHubRococo,
+	AssetHubWestend,
+}
+
+/// Path to the storage item: pallet and item names.
+#[derive(Clone, Debug, PartialEq)]
+struct StorageItemPath {
+	pallet: String,
+	item: String,
+}
+
+impl FromStr for StorageItemPath {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.split_once("::") {
+			Some((pallet, item)) if !pallet.is_empty() && !item.is_empty() =>
+				Ok(StorageItemPath { pallet: pallet.into(), item: item.into() }),
+			_ => Err(format!("Expected storage item in the `<Pallet>::<Item>` form, got `{s}`")),
+		}
+	}
+}
+
+/// Lane identifier. Bridges are using either `LegacyLaneId` or `HashedLaneId`, but both are
+/// encoded as raw bytes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum LaneId {
+	Legacy(LegacyLaneId),
+	Hashed(H256),
+}
+
+impl Encode for LaneId {
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		match self {
+			LaneId::Legacy(lane) => lane.using_encoded(f),
+			LaneId::Hashed(lane) => lane.using_encoded(f),
+		}
+	}
+}
+
+impl FromStr for LaneId {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let lane = hex::decode(s.trim_start_matches("0x"))
+			.map_err(|e| format!("Invalid hex-encoded lane {s}: {e}"))?;
+		match lane.len() {
+			4 => Ok(LaneId::Legacy(LegacyLaneId(lane.try_into().expect("checked above; qed")))),
+			32 => Ok(LaneId::Hashed(H256::from_slice(&lane))),
+			len => Err(format!(
+				"Expected 4-byte `LegacyLaneId` or 32-byte `HashedLaneId`, got {len} bytes"
+			)),
+		}
+	}
+}
+
+/// Supported storage items of the bridge GRANDPA, parachains and messages pallets.
+#[derive(Clone, Debug, PartialEq)]
+enum StorageItem {
+	PalletOwner,
+	BasicOperatingMode,
+	MessagesOperatingMode,
+	BestFinalized,
+	CurrentAuthoritySet,
+	ImportedHeaders(H256),
+	ImportedHashes(u32),
+	ImportedHashesPointer,
+	FreeHeadersRemaining,
+	ParasInfo(ParaId),
+	ImportedParaHeads(ParaId, H256),
+	ImportedParaHashes(ParaId, u32),
+	InboundLanes(LaneId),
+	OutboundLanes(LaneId),
+	OutboundMessages(LaneId, MessageNonce),
+}
+
+impl StorageItem {
+	/// Parse storage item and its keys.
+	fn parse(path: &StorageItemPath, keys: &[String]) -> anyhow::Result<Self> {
+		let item = match (path.item.as_str(), keys) {
+			("PalletOwner", []) => StorageItem::PalletOwner,
+			// messages pallet has its own operating mode type
+			("PalletOperatingMode", []) if path.pallet.ends_with("Messages") =>
+				StorageItem::MessagesOperatingMode,
+			("PalletOperatingMode", []) => StorageItem::BasicOperatingMode,
+			("BestFinalized", []) => StorageItem::BestFinalized,
+			("CurrentAuthoritySet", []) => StorageItem::CurrentAuthoritySet,
+			("ImportedHeaders", [hash]) => StorageItem::ImportedHeaders(parse_key(hash)?),
+			("ImportedHashes", [index]) => StorageItem::ImportedHashes(parse_key(index)?),
+			("ImportedHashesPointer", []) => StorageItem::ImportedHashesPointer,
+			("FreeHeadersRemaining", []) => StorageItem::FreeHeadersRemaining,
+			("ParasInfo", [para_id]) => StorageItem::ParasInfo(ParaId(parse_key(para_id)?)),
+			("ImportedParaHeads", [para_id, hash]) =>
+				StorageItem::ImportedParaHeads(ParaId(parse_key(para_id)?), parse_key(hash)?),
+			("ImportedParaHashes", [para_id, index]) =>
+				StorageItem::ImportedParaHashes(ParaId(parse_key(para_id)?), parse_key(index)?),
+			("InboundLanes", [lane]) => StorageItem::InboundLanes(parse_key(lane)?),
+			("OutboundLanes", [lane]) => StorageItem::OutboundLanes(parse_key(lane)?),
+			("OutboundMessages", [lane, nonce]) =>
+				StorageItem::OutboundMessages(parse_key(lane)?, parse_key(nonce)?),
+			(
+				"PalletOwner" |
+				"PalletOperatingMode" |
+				"BestFinalized" |
+				"CurrentAuthoritySet" |
+				"ImportedHeaders" |
+				"ImportedHashes" |
+				"ImportedHashesPointer" |
+				"FreeHeadersRemaining" |
+				"ParasInfo" |
+				"ImportedParaHeads" |
+				"ImportedParaHashes" |
+				"InboundLanes" |
+				"OutboundLanes" |
+				"OutboundMessages",
+				_,
+			) => anyhow::bail!("Unexpected number of keys for the {}::{}", path.pallet, path.item),
+			_ => anyhow::bail!("Unsupported storage item {}::{}", path.pallet, path.item),
+		};
+		Ok(item)
+	}
+
+	/// Returns final storage key of the item in the given pallet.
+	fn storage_key(&self, pallet: &str) -> StorageKey {
+		match self {
+			StorageItem::PalletOwner => bp_runtime::storage_value_key(pallet, "PalletOwner"),
+			StorageItem::BasicOperatingMode =>
+				bp_header_chain::storage_keys::pallet_operating_mode_key(pallet),
+			StorageItem::MessagesOperatingMode =>
+				bp_messages::storage_keys::operating_mode_key(pallet),
+			StorageItem::BestFinalized => bp_header_chain::storage_keys::best_finalized_key(pallet),
+			StorageItem::CurrentAuthoritySet =>
+				bp_header_chain::storage_keys::current_authority_set_key(pallet),
+			StorageItem::ImportedHeaders(hash) =>
+				ImportedHeadersKeyProvider::final_key(pallet, hash),
+			StorageItem::ImportedHashes(index) =>
+				ImportedHashesKeyProvider::final_key(pallet, index),
+			StorageItem::ImportedHashesPointer =>
+				bp_runtime::storage_value_key(pallet, "ImportedHashesPointer"),
+			StorageItem::FreeHeadersRemaining =>
+				bp_runtime::storage_value_key(pallet, "FreeHeadersRemaining"),
+			StorageItem::ParasInfo(para_id) => ParasInfoKeyProvider::final_key(pallet, para_id),
+			StorageItem::ImportedParaHeads(para_id, hash) =>
+				ImportedParaHeadsKeyProvider::final_key(pallet, para_id, hash),
+			StorageItem::ImportedParaHashes(para_id, index) =>
+				ImportedParaHashesKeyProvider::final_key(pallet, para_id, index),
+			StorageItem::InboundLanes(lane) =>
+				bp_messages::storage_keys::inbound_lane_data_key(pallet, lane),
+			StorageItem::OutboundLanes(lane) =>
+				bp_messages::storage_keys::outbound_lane_data_key(pallet, lane),
+			StorageItem::OutboundMessages(lane, nonce) =>
+				bp_messages::storage_keys::message_key(pallet, lane, *nonce),
+		}
+	}
+
+	/// Decode value of the item using codegen types of the chain.
+	fn decode_value<C: BridgeStorageTypes>(&self, value: &[u8]) -> anyhow::Result<String> {
+		match self {
+			StorageItem::PalletOwner => decode::<AccountIdOf<C>>(value),
+			StorageItem::BasicOperatingMode => decode::<C::BasicOperatingMode>(value),
+			StorageItem::MessagesOperatingMode => decode::<C::MessagesOperatingMode>(value),
+			StorageItem::BestFinalized => decode::<C::HeaderId>(value),
+			StorageItem::CurrentAuthoritySet => decode::<C::StoredAuthoritySet>(value),
+			StorageItem::ImportedHeaders(_) => decode::<C::StoredHeaderData>(value),
+			StorageItem::ImportedHashes(_) | StorageItem::ImportedParaHashes(_, _) =>
+				decode::<H256>(value),
+			StorageItem::ImportedHashesPointer | StorageItem::FreeHeadersRemaining =>
+				decode::<u32>(value),
+			StorageItem::ParasInfo(_) => decode::<C::ParaInfo>(value),
+			StorageItem::ImportedParaHeads(_, _) => decode::<C::ParaStoredHeaderData>(value),
+			StorageItem::InboundLanes(_) => decode::<C::InboundLaneData>(value),
+			StorageItem::OutboundLanes(_) => decode::<C::OutboundLaneData>(value),
+			StorageItem::OutboundMessages(_, _) => Vec::<u8>::decode(&mut &value[..])
+				.map(|payload| hex_string(&payload))
+				.map_err(|e| anyhow::format_err!("Failed to decode message payload: {e:?}")),
+		}
+	}
+}
+
+/// Key provider of the `ImportedHeaders` map of the GRANDPA pallet.
+struct ImportedHeadersKeyProvider;
+
+impl StorageMapKeyProvider for ImportedHeadersKeyProvider {
+	const MAP_NAME: &'static str = "ImportedHeaders";
+
+	type Hasher = Identity;
+	type Key = H256;
+	type Value = bp_header_chain::StoredHeaderData<u32, H256>;
+}
+
+/// Key provider of the `ImportedHashes` map of the GRANDPA pallet.
+struct ImportedHashesKeyProvider;
+
+impl StorageMapKeyProvider for ImportedHashesKeyProvider {
+	const MAP_NAME: &'static str = "ImportedHashes";
+
+	type Hasher = Identity;
+	type Key = u32;
+	type Value = H256;
+}
+
+/// Key provider of the `ImportedParaHashes` map of the parachains pallet.
+struct ImportedParaHashesKeyProvider;
+
+impl StorageDoubleMapKeyProvider for ImportedParaHashesKeyProvider {
+	const MAP_NAME: &'static str = "ImportedParaHashes";
+
+	type Hasher1 = Blake2_128Concat;
+	type Key1 = ParaId;
+	type Hasher2 = Twox64Concat;
+	type Key2 = u32;
+	type Value = H256;
+}
+
+/// Codegen types of the bridge pallets storage items.
+trait BridgeStorageTypes: ChainWithRuntimeVersion<Hash = H256> {
+	/// `bp_runtime::BasicOperatingMode`.
+	type BasicOperatingMode: Decode + Debug;
+	/// `bp_messages::MessagesOperatingMode`.
+	type MessagesOperatingMode: Decode + Debug;
+	/// `bp_runtime::HeaderId` of the bridged chain.
+	type HeaderId: Decode + Debug;
+	/// `pallet_bridge_grandpa::storage_types::StoredAuthoritySet`.
+	type StoredAuthoritySet: Decode + Debug;
+	/// `bp_header_chain::StoredHeaderData` of the bridged chain.
+	type StoredHeaderData: Decode + Debug;
+	/// `bp_parachains::ParaInfo`.
+	type ParaInfo: Decode + Debug;
+	/// `bp_parachains::ParaStoredHeaderData`.
+	type ParaStoredHeaderData: Decode + Debug;
+	/// `bp_messages::InboundLaneData` with relayers of the bridged chain.
+	type InboundLaneData: Decode + Debug;
+	/// `bp_messages::OutboundLaneData`.
+	type OutboundLaneData: Decode + Debug;
+}
+
+/// Type that has not been generated by the `runtime-codegen` for the chain.
+#[derive(Debug)]
+struct NotGenerated;
+
+impl Decode for NotGenerated {
+	fn decode<I: codec::Input>(_: &mut I) -> Result<Self, codec::Error> {
+		Err("the type is not generated for this chain. Regenerate the runtime code".into())
+	}
+}
+
+macro_rules! impl_bridge_storage_types {
+	(
+		$chain:ty,
+		$client:ident,
+		bridged_account_id: $account_id:ty,
+		grandpa: $grandpa:tt,
+		parachains: $parachains:tt,
+		messages: $messages:tt $(,)?
+	) => {
+		impl BridgeStorageTypes for $chain {
+			type BasicOperatingMode =
+				bridge_storage_type!(true, $client, bp_runtime::BasicOperatingMode);
+			type MessagesOperatingMode =
+				bridge_storage_type!(true, $client, bp_messages::MessagesOperatingMode);
+			type HeaderId = bridge_storage_type!(
+				$grandpa, $client, bp_runtime::HeaderId<H256, u32>
+			);
+			type StoredAuthoritySet = bridge_storage_type!(
+				$grandpa,
+				$client,
+				pallet_bridge_grandpa::storage_types::StoredAuthoritySet
+			);
+			type StoredHeaderData = bridge_storage_type!(
+				$grandpa, $client, bp_header_chain::StoredHeaderData<u32, H256>
+			);
+			type ParaInfo = bridge_storage_type!($parachains, $client, bp_parachains::ParaInfo);
+			type ParaStoredHeaderData =
+				bridge_storage_type!($parachains, $client, bp_parachains::ParaStoredHeaderData);
+			type InboundLaneData =
+				bridge_storage_type!($messages, $client, bp_messages::InboundLaneData<$account_id>);
+			type OutboundLaneData =
+				bridge_storage_type!($messages, $client, bp_messages::OutboundLaneData);
+		}
+	};
+}
+
+macro_rules! bridge_storage_type {
+	(true, $client:ident, $($ty:tt)*) => {
+		$client::runtime_types::$($ty)*
+	};
+	(false, $client:ident, $($ty:tt)*) => {
+		NotGenerated
+	};
+}
+
+impl_bridge_storage_types!(
+	relay_polkadot_bulletin_client::PolkadotBulletin,
+	relay_polkadot_bulletin_client,
+	bridged_account_id: bp_polkadot_core::AccountId,
+	grandpa: true,
+	parachains: true,
+	messages: true,
+);
+impl_bridge_storage_types!(
+	relay_bridge_hub_kusama_client::BridgeHubKusama,
+	relay_bridge_hub_kusama_client,
+	bridged_account_id: bp_polkadot_core::AccountId,
+	grandpa: true,
+	parachains: true,
+	messages: true,
+);
+impl_bridge_storage_types!(
+	relay_bridge_hub_polkadot_client::BridgeHubPolkadot,
+	relay_bridge_hub_polkadot_client,
+	bridged_account_id: bp_polkadot_core::AccountId,
+	grandpa: true,
+	parachains: true,
+	messages: true,
+);
+impl_bridge_storage_types!(
+	relay_bridge_hub_rococo_client::BridgeHubRococo,
+	relay_bridge_hub_rococo_client,
+	bridged_account_id: bp_polkadot_core::AccountId,
+	grandpa: true,
+	parachains: true,
+	messages: true,
+);
+impl_bridge_storage_types!(
+	relay_bridge_hub_westend_client::BridgeHubWestend,
+	relay_bridge_hub_westend_client,
+	bridged_account_id: bp_polkadot_core::AccountId,
+	grandpa: true,
+	parachains: true,
+	messages: true,
+);
+impl_bridge_storage_types!(
+	relay_asset_hub_rococo_client::AssetHubRococo,
+	relay_asset_hub_rococo_client,
+	bridged_account_id: bp_polkadot_core::AccountId,
+	grandpa: false,
+	parachains: false,
+	messages: true,
+);
+impl_bridge_storage_types!(
+	relay_asset_hub_westend_client::AssetHubWestend,
+	relay_asset_hub_westend_client,
+	bridged_account_id: bp_polkadot_core::AccountId,
+	grandpa: false,
+	parachains: false,
+	messages: true,
+);
+
+/// Storage value, printed by the command.
+#[derive(Debug, Serialize)]
+struct QueriedStorage {
+	/// Hash of the block the storage has been read at.
+	at: H256,
+	/// Hex-encoded final storage key.
+	key: String,
+	/// Hex-encoded storage value or `None` if there's no value at the key.
+	raw_value: Option<String>,
+	/// Decoded storage value or `None` if there's no value at the key.
+	value: Option<String>,
+	/// Error, if the value could not be decoded.
+	decode_error: Option<String>,
+}
+
+impl QueryStorage {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		let queried = match self.chain {
+			QueryStorageChain::PolkadotBulletin =>
+				self.query::<relay_polkadot_bulletin_client::PolkadotBulletin>().await,
+			QueryStorageChain::BridgeHubKusama =>
+				self.query::<relay_bridge_hub_kusama_client::BridgeHubKusama>().await,
+			QueryStorageChain::BridgeHubPolkadot =>
+				self.query::<relay_bridge_hub_polkadot_client::BridgeHubPolkadot>().await,
+			QueryStorageChain::BridgeHubRococo =>
+				self.query::<relay_bridge_hub_rococo_client::BridgeHubRococo>().await,
+			QueryStorageChain::BridgeHubWestend =>
+				self.query::<relay_bridge_hub_westend_client::BridgeHubWestend>().await,
+			QueryStorageChain::AssetHubRococo =>
+				self.query::<relay_asset_hub_rococo_client::AssetHubRococo>().await,
+			QueryStorageChain::AssetHubWestend =>
+				self.query::<relay_asset_hub_westend_client::AssetHubWestend>().await,
+		}?;
+
+		println!("{}", serde_json::to_string_pretty(&queried)?);
+		Ok(())
+	}
+
+	/// Read and decode the storage value.
+	async fn query<C: BridgeStorageTypes>(self) -> anyhow::Result<QueriedStorage> {
+		let item = StorageItem::parse(&self.item, &self.keys)?;
+		let key = item.storage_key(&self.item.pallet);
+
+		let client = self.source.into_client::<C>().await?;
+		let at = match self.at {
+			Some(at) => at,
+			None => client.best_finalized_header_hash().await?,
+		};
+		let raw_value = client.raw_storage_value(at, key.clone()).await?;
+
+		let (value, decode_error) = match raw_value {
+			Some(ref raw_value) => match item.decode_value::<C>(&raw_value.0) {
+				Ok(value) => (Some(value), None),
+				Err(e) => (None, Some(e.to_string())),
+			},
+			None => (None, None),
+		};
+		Ok(QueriedStorage {
+			at,
+			key: hex_string(&key.0),
+			raw_value: raw_value.map(|raw_value| hex_string(&raw_value.0)),
+			value,
+			decode_error,
+		})
+	}
+}
+
+/// Parse storage map key argument.
+fn parse_key<T: FromStr>(key: &str) -> anyhow::Result<T>
+where
+	T::Err: Debug,
+{
+	key.parse().map_err(|e| anyhow::format_err!("Invalid storage key {key}: {e:?}"))
+}
+
+/// Decode the whole value and return its debug representation.
+fn decode<T: Decode + Debug>(value: &[u8]) -> anyhow::Result<String> {
+	T::decode_all(&mut &value[..])
+		.map(|value| format!("{value:?}"))
+		.map_err(|e| anyhow::format_err!("Failed to decode storage value: {e}"))
+}
+
+/// Returns `0x`-prefixed hex-encoded bytes.
+fn hex_string(bytes: &[u8]) -> String {
+	format!("0x{}", hex::encode(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const PALLET: &str = "BridgePolkadotMessages";
+
+	fn item(path: &str, keys: &[&str]) -> anyhow::Result<StorageItem> {
+		StorageItem::parse(
+			&path.parse().unwrap(),
+			&keys.iter().map(|key| key.to_string()).collect::<Vec<_>>(),
+		)
+	}
+
+	#[test]
+	fn parses_storage_item_path() {
+		assert_eq!(
+			"BridgePolkadotGrandpa::BestFinalized".parse(),
+			Ok(StorageItemPath {
+				pallet: "BridgePolkadotGrandpa".into(),
+				item: "BestFinalized".into()
+			}),
+		);
+		assert!("BestFinalized".parse::<StorageItemPath>().is_err());
+		assert!("BridgePolkadotGrandpa::".parse::<StorageItemPath>().is_err());
+	}
+
+	#[test]
+	fn parses_lane_ids() {
+		assert_eq!("00000002".parse::<LaneId>(), Ok(LaneId::Legacy(LegacyLaneId([0, 0, 0, 2]))),);
+		assert_eq!(
+			format!("0x{}", hex::encode([1u8; 32])).parse::<LaneId>(),
+			Ok(LaneId::Hashed(H256::repeat_byte(1))),
+		);
+		assert!("000002".parse::<LaneId>().is_err());
+	}
+
+	#[test]
+	fn selects_operating_mode_type_by_pallet() {
+		assert_eq!(
+			item("BridgePolkadotMessages::PalletOperatingMode", &[]).unwrap(),
+			StorageItem::MessagesOperatingMode,
+		);
+		assert_eq!(
+			item("BridgePolkadotGrandpa::PalletOperatingMode", &[]).unwrap(),
+			StorageItem::BasicOperatingMode,
+		);
+	}
+
+	#[test]
+	fn checks_number_of_keys() {
+		assert!(item("BridgePolkadotGrandpa::BestFinalized", &["1"]).is_err());
+		assert!(item("BridgePolkadotParachains::ImportedParaHeads", &["1000"]).is_err());
+		assert!(item("BridgePolkadotGrandpa::UnknownItem", &[]).is_err());
+	}
+
+	#[test]
+	fn builds_lane_storage_keys() {
+		let lane = "00000002".parse::<LaneId>().unwrap();
+		assert_eq!(
+			item("BridgePolkadotMessages::OutboundLanes", &["00000002"])
+				.unwrap()
+				.storage_key(PALLET),
+			bp_messages::storage_keys::outbound_lane_data_key(PALLET, &LegacyLaneId([0, 0, 0, 2])),
+		);
+		assert_eq!(
+			item("BridgePolkadotMessages::OutboundMessages", &["00000002", "42"])
+				.unwrap()
+				.storage_key(PALLET),
+			bp_messages::storage_keys::message_key(PALLET, &lane, 42),
+		);
+	}
+
+	#[test]
+	fn builds_identity_map_storage_keys() {
+		let hash = H256::repeat_byte(42);
+		let key = item("BridgePolkadotGrandpa::ImportedHeaders", &[&format!("{hash:?}")])
+			.unwrap()
+			.storage_key("BridgePolkadotGrandpa");
+
+		let prefix = bp_runtime::storage_value_key("BridgePolkadotGrandpa", "ImportedHeaders");
+		assert_eq!(key.0, [prefix.0, hash.0.to_vec()].concat());
+	}
+
+	#[test]
+	fn reports_not_generated_types() {
+		assert!(decode::<NotGenerated>(&[]).is_err());
+		assert_eq!(decode::<u32>(&42u32.encode()).unwrap(), "42");
+		assert!(decode::<u32>(&42u64.encode()).is_err());
+	}
+}
@@ -0,0 +1,328 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Complex relay of messages between Asset Hubs.
+//!
+//! Messages are exchanged by Asset Hubs, but heads of the bridged Asset Hub are tracked by the
+//! bridge parachains pallet of the BridgeHub of the same consensus. So the relay delivers
+//! messages between Asset Hubs and, on demand, heads of Asset Hubs and bridged relay chain headers
+//! to the opposite BridgeHubs.
+
+use super::{
+	lane_schedule::{run_lane_relays, start_version_guard, LaneRelayParams, LaneScheduleParams},
+	relay_headers_and_messages::{
+		BridgeHubKusamaConnectionParams, BridgeHubKusamaSigningParams,
+		BridgeHubPolkadotConnectionParams, BridgeHubPolkadotSigningParams, KusamaConnectionParams,
+		PolkadotConnectionParams,
+	},
+};
+use crate::bridges::kusama_polkadot::{
+	asset_hub_kusama_messages_to_asset_hub_polkadot::{
+		AssetHubKusamaMessagesToAssetHubPolkadotMessageLane,
+		AssetHubKusamaToAssetHubPolkadotMessagesCliBridge,
+	},
+	asset_hub_kusama_parachains_to_bridge_hub_polkadot::AssetHubKusamaToBridgeHubPolkadotParachainsCliBridge,
+	asset_hub_polkadot_messages_to_asset_hub_kusama::{
+		AssetHubPolkadotMessagesToAssetHubKusamaMessageLane,
+		AssetHubPolkadotToAssetHubKusamaMessagesCliBridge,
+	},
+	asset_hub_polkadot_parachains_to_bridge_hub_kusama::AssetHubPolkadotToBridgeHubKusamaParachainsCliBridge,
+};
+
+use async_trait::async_trait;
+use clap::Args;
+use finality_relay::HeadersToRelay;
+use relay_asset_hub_kusama_client::AssetHubKusama;
+use relay_asset_hub_polkadot_client::AssetHubPolkadot;
+use relay_bridge_hub_kusama_client::BridgeHubKusama;
+use relay_bridge_hub_polkadot_client::BridgeHubPolkadot;
+use relay_kusama_client::Kusama;
+use relay_polkadot_client::Polkadot;
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BlockNumberOf, CallOf, Chain, Client, Error as SubstrateError,
+	HeaderIdOf, Parachain,
+};
+use relay_utils::metrics::MetricsParams;
+use sp_core::Pair;
+use sp_runtime::traits::TryConvert;
+use std::{marker::PhantomData, sync::Arc};
+use substrate_relay_helper::{
+	cli::{
+		bridge::{MessagesCliBridge, ParachainToRelayHeadersCliBridge},
+		relay_headers_and_messages::HeadersAndMessagesSharedParams,
+		HexLaneId,
+	},
+	declare_chain_cli_schema,
+	finality::SubstrateFinalitySyncPipeline,
+	on_demand::{
+		headers::OnDemandHeadersRelay, parachains::OnDemandParachainsRelay, OnDemandRelay,
+	},
+	TransactionParams,
+};
+
+declare_chain_cli_schema!(AssetHubKusama, asset_hub_kusama);
+declare_chain_cli_schema!(AssetHubPolkadot, asset_hub_polkadot);
+
+/// `AssetHubKusama` <> `AssetHubPolkadot` complex relay parameters.
+#[derive(Args, Debug, PartialEq)]
+pub struct AssetHubKusamaAssetHubPolkadotHeadersAndMessages {
+	#[command(flatten)]
+	shared: HeadersAndMessagesSharedParams,
+	#[command(flatten)]
+	left: AssetHubKusamaConnectionParams,
+	#[command(flatten)]
+	left_sign: AssetHubKusamaSigningParams,
+	#[command(flatten)]
+	left_relay: KusamaConnectionParams,
+	#[command(flatten)]
+	left_bridge_hub: BridgeHubKusamaConnectionParams,
+	#[command(flatten)]
+	left_bridge_hub_sign: BridgeHubKusamaSigningParams,
+	#[command(flatten)]
+	right: AssetHubPolkadotConnectionParams,
+	#[command(flatten)]
+	right_sign: AssetHubPolkadotSigningParams,
+	#[command(flatten)]
+	right_relay: PolkadotConnectionParams,
+	#[command(flatten)]
+	right_bridge_hub: BridgeHubPolkadotConnectionParams,
+	#[command(flatten)]
+	right_bridge_hub_sign: BridgeHubPolkadotSigningParams,
+}
+
+impl AssetHubKusamaAssetHubPolkadotHeadersAndMessages {
+	/// Run the relay.
+	pub async fn run(self, lanes: LaneScheduleParams) -> anyhow::Result<()> {
+		let headers_to_relay = headers_to_relay(&self.shared);
+		let metrics_params = self.shared.prometheus_params.into_metrics_params()?;
+
+		let left = self.left.into_client::<AssetHubKusama>().await?;
+		let left_tx = TransactionParams {
+			signer: self.left_sign.to_keypair::<AssetHubKusama>()?,
+			mortality: self.left_sign.transactions_mortality()?,
+		};
+		let right = self.right.into_client::<AssetHubPolkadot>().await?;
+		let right_tx = TransactionParams {
+			signer: self.right_sign.to_keypair::<AssetHubPolkadot>()?,
+			mortality: self.right_sign.transactions_mortality()?,
+		};
+		start_version_guard(&left).await?;
+		start_version_guard(&right).await?;
+
+		let left_to_right_heads = start_on_demand_heads_relay::<
+			AssetHubKusamaToBridgeHubPolkadotParachainsCliBridge,
+			AssetHubPolkadot,
+		>(
+			self.left_relay.into_client::<Kusama>().await?,
+			self.right_bridge_hub.into_client::<BridgeHubPolkadot>().await?,
+			TransactionParams {
+				signer: self.right_bridge_hub_sign.to_keypair::<BridgeHubPolkadot>()?,
+				mortality: self.right_bridge_hub_sign.transactions_mortality()?,
+			},
+			headers_to_relay,
+			&metrics_params,
+		)
+		.await?;
+		let right_to_left_heads = start_on_demand_heads_relay::<
+			AssetHubPolkadotToBridgeHubKusamaParachainsCliBridge,
+			AssetHubKusama,
+		>(
+			self.right_relay.into_client::<Polkadot>().await?,
+			self.left_bridge_hub.into_client::<BridgeHubKusama>().await?,
+			TransactionParams {
+				signer: self.left_bridge_hub_sign.to_keypair::<BridgeHubKusama>()?,
+				mortality: self.left_bridge_hub_sign.transactions_mortality()?,
+			},
+			headers_to_relay,
+			&metrics_params,
+		)
+		.await?;
+
+		let lanes = self
+			.shared
+			.lane
+			.into_iter()
+			.map(|lane| {
+				HexLaneId::try_convert(lane)
+					.map_err(|lane| anyhow::format_err!("Invalid lane: {lane:?}"))
+			})
+			.collect::<anyhow::Result<Vec<Vec<u8>>>>()?;
+		let mut relays =
+			LaneRelayParams::<AssetHubKusamaMessagesToAssetHubPolkadotMessageLane, _, _> {
+				source_client: left.clone(),
+				source_transaction_params: left_tx.clone(),
+				target_client: right.clone(),
+				target_transaction_params: right_tx.clone(),
+				source_to_target_headers_relay: Some(left_to_right_heads.clone()),
+				target_to_source_headers_relay: Some(right_to_left_heads.clone()),
+				limits: AssetHubKusamaToAssetHubPolkadotMessagesCliBridge::maybe_messages_limits(),
+			}
+			.lane_relays(&lanes)?;
+		relays.extend(
+			LaneRelayParams::<AssetHubPolkadotMessagesToAssetHubKusamaMessageLane, _, _> {
+				source_client: right,
+				source_transaction_params: right_tx,
+				target_client: left,
+				target_transaction_params: left_tx,
+				source_to_target_headers_relay: Some(right_to_left_heads),
+				target_to_source_headers_relay: Some(left_to_right_heads),
+				limits: AssetHubPolkadotToAssetHubKusamaMessagesCliBridge::maybe_messages_limits(),
+			}
+			.lane_relays(&lanes)?,
+		);
+
+		relay_utils::relay_metrics(metrics_params.clone())
+			.expose()
+			.await
+			.map_err(|e| anyhow::format_err!("{}", e))?;
+		run_lane_relays(relays, lanes.lane_config, &metrics_params).await
+	}
+}
+
+/// Returns headers that are relayed by on-demand headers relays.
+fn headers_to_relay(shared: &HeadersAndMessagesSharedParams) -> HeadersToRelay {
+	match (shared.only_mandatory_headers, shared.only_free_headers) {
+		(_, true) => HeadersToRelay::Free,
+		(true, false) => HeadersToRelay::Mandatory,
+		_ => HeadersToRelay::All,
+	}
+}
+
+/// Start on-demand relay of the Asset Hub heads to the BridgeHub.
+///
+/// Returned relay is used by the messages relay to the `BridgedAssetHub`.
+async fn start_on_demand_heads_relay<Bridge, BridgedAssetHub>(
+	relay_client: impl Client<Bridge::SourceRelay>,
+	bridge_hub_client: impl Client<Bridge::Target>,
+	bridge_hub_transaction_params: TransactionParams<AccountKeyPairOf<Bridge::Target>>,
+	headers_to_relay: HeadersToRelay,
+	metrics_params: &MetricsParams,
+) -> anyhow::Result<Arc<dyn OnDemandRelay<Bridge::Source, BridgedAssetHub>>>
+where
+	Bridge: ParachainToRelayHeadersCliBridge,
+	Bridge::Source: Parachain,
+	BridgedAssetHub: Chain,
+	AccountIdOf<Bridge::Target>: From<<AccountKeyPairOf<Bridge::Target> as Pair>::Public>,
+{
+	Bridge::RelayFinality::start_relay_guards(
+		&bridge_hub_client,
+		bridge_hub_client.can_start_version_guard(),
+	)
+	.await?;
+
+	let relay_headers = OnDemandHeadersRelay::<Bridge::RelayFinality, _, _>::new(
+		relay_client.clone(),
+		bridge_hub_client.clone(),
+		bridge_hub_transaction_params.clone(),
+		headers_to_relay,
+		Some(metrics_params.clone()),
+	);
+	let parachain_heads = OnDemandParachainsRelay::<Bridge::ParachainFinality, _, _>::new(
+		relay_client,
+		bridge_hub_client,
+		bridge_hub_transaction_params,
+		Arc::new(relay_headers),
+	);
+	Ok(Arc::new(OnDemandHeadsAtBridgeHub::<_, Bridge::Target, _> {
+		relay: Arc::new(parachain_heads),
+		_phantom: PhantomData,
+	}))
+}
+
+/// On-demand relay of the Asset Hub heads, used by the messages relay to the bridged Asset Hub.
+///
+/// Heads are delivered to the BridgeHub and the bridged Asset Hub reads them from there. So heads
+/// can't be proved in the same batch transaction with messages.
+struct OnDemandHeadsAtBridgeHub<AssetHub: Chain, BridgeHub: Chain, BridgedAssetHub: Chain> {
+	relay: Arc<dyn OnDemandRelay<AssetHub, BridgeHub>>,
+	_phantom: PhantomData<fn() -> BridgedAssetHub>,
+}
+
+#[async_trait]
+impl<AssetHub: Chain, BridgeHub: Chain, BridgedAssetHub: Chain>
+	OnDemandRelay<AssetHub, BridgedAssetHub>
+	for OnDemandHeadsAtBridgeHub<AssetHub, BridgeHub, BridgedAssetHub>
+{
+	async fn reconnect(&self) -> Result<(), SubstrateError> {
+		self.relay.reconnect().await
+	}
+
+	async fn require_more_headers(&self, required_header: BlockNumberOf<AssetHub>) {
+		self.relay.require_more_headers(required_header).await
+	}
+
+	async fn prove_header(
+		&self,
+		_required_header: BlockNumberOf<AssetHub>,
+	) -> Result<(HeaderIdOf<AssetHub>, Vec<CallOf<BridgedAssetHub>>), SubstrateError> {
+		Err(SubstrateError::Custom(format!(
+			"{} heads are delivered to {} and can't be proved in {} transactions",
+			AssetHub::NAME,
+			BridgeHub::NAME,
+			BridgedAssetHub::NAME,
+		)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	#[derive(Parser)]
+	struct Cli {
+		#[command(flatten)]
+		params: AssetHubKusamaAssetHubPolkadotHeadersAndMessages,
+	}
+
+	#[test]
+	fn should_parse_asset_hubs_options() {
+		let cli = Cli::parse_from(vec![
+			"asset-hub-kusama-asset-hub-polkadot",
+			"--asset-hub-kusama-uri",
+			"ws://asset-hub-kusama:9944",
+			"--asset-hub-kusama-signer",
+			"//Iden",
+			"--kusama-uri",
+			"ws://kusama:9944",
+			"--bridge-hub-kusama-uri",
+			"ws://bridge-hub-kusama:9944",
+			"--bridge-hub-kusama-signer",
+			"//Ferdie",
+			"--asset-hub-polkadot-uri",
+			"ws://asset-hub-polkadot:9944",
+			"--asset-hub-polkadot-signer",
+			"//George",
+			"--polkadot-uri",
+			"ws://polkadot:9944",
+			"--bridge-hub-polkadot-uri",
+			"ws://bridge-hub-polkadot:9944",
+			"--bridge-hub-polkadot-signer",
+			"//Charlie",
+			"--lane",
+			"0000000000000000000000000000000000000000000000000000000000000001",
+			"--only-mandatory-headers",
+		]);
+
+		assert_eq!(cli.params.left.asset_hub_kusama_uri, "ws://asset-hub-kusama:9944");
+		assert_eq!(
+			cli.params.right_bridge_hub.bridge_hub_polkadot_uri,
+			"ws://bridge-hub-polkadot:9944"
+		);
+		assert_eq!(cli.params.shared.lane.len(), 1);
+		assert_eq!(headers_to_relay(&cli.params.shared), HeadersToRelay::Mandatory);
+	}
+}
@@ -22,6 +22,10 @@ use crate::bridges::{
 		kusama_headers_to_bridge_hub_polkadot::KusamaToBridgeHubPolkadotCliBridge,
 		polkadot_headers_to_bridge_hub_kusama::PolkadotToBridgeHubKusamaCliBridge,
 	},
+	moonriver_moonbeam::{
+		kusama_headers_to_moonbeam::KusamaToMoonbeamCliBridge,
+		polkadot_headers_to_moonriver::PolkadotToMoonriverCliBridge,
+	},
 	polkadot_bulletin::{
 		polkadot_bulletin_headers_to_bridge_hub_polkadot::PolkadotBulletinToBridgeHubPolkadotCliBridge,
 		polkadot_headers_to_polkadot_bulletin::PolkadotToPolkadotBulletinCliBridge,
@@ -72,6 +76,8 @@ pub enum RelayHeadersBridge {
 	PolkadotBulletinToBridgeHubPolkadot,
 	RococoToRococoBulletin,
 	RococoBulletinToBridgeHubRococo,
+	KusamaToMoonbeam,
+	PolkadotToMoonriver,
 }
 
 impl HeadersRelayer for RococoToBridgeHubWestendCliBridge {}
@@ -82,6 +88,8 @@ impl HeadersRelayer for PolkadotToPolkadotBulletinCliBridge {}
 impl HeadersRelayer for PolkadotBulletinToBridgeHubPolkadotCliBridge {}
 impl HeadersRelayer for RococoToRococoBulletinCliBridge {}
 impl HeadersRelayer for RococoBulletinToBridgeHubRococoCliBridge {}
+impl HeadersRelayer for KusamaToMoonbeamCliBridge {}
+impl HeadersRelayer for PolkadotToMoonriverCliBridge {}
 
 impl RelayHeaders {
 	/// Run the command.
@@ -103,6 +111,10 @@ impl RelayHeaders {
 				RococoToRococoBulletinCliBridge::relay_headers(self.params),
 			RelayHeadersBridge::RococoBulletinToBridgeHubRococo =>
 				RococoBulletinToBridgeHubRococoCliBridge::relay_headers(self.params),
+			RelayHeadersBridge::KusamaToMoonbeam =>
+				KusamaToMoonbeamCliBridge::relay_headers(self.params),
+			RelayHeadersBridge::PolkadotToMoonriver =>
+				PolkadotToMoonriverCliBridge::relay_headers(self.params),
 		}
 		.await
 	}
@@ -128,6 +140,10 @@ impl RelayHeader {
 				RococoToRococoBulletinCliBridge::relay_header(self.params),
 			RelayHeadersBridge::RococoBulletinToBridgeHubRococo =>
 				RococoBulletinToBridgeHubRococoCliBridge::relay_header(self.params),
+			RelayHeadersBridge::KusamaToMoonbeam =>
+				KusamaToMoonbeamCliBridge::relay_header(self.params),
+			RelayHeadersBridge::PolkadotToMoonriver =>
+				PolkadotToMoonriverCliBridge::relay_header(self.params),
 		}
 		.await
 	}
@@ -22,16 +22,33 @@
 //! 2) add `declare_chain_to_chain_bridge_schema!(...)` or
 //!    `declare_chain_to_parachain_bridge_schema` for the bridge.
 //! 3) declare a new struct for the added bridge and implement the `Full2WayBridge` trait for it.
+//!
+//! Heads of Asset Hubs are delivered to the bridged BridgeHub, not to the chain that receives
+//! messages, so the `AssetHubKusama` <> `AssetHubPolkadot` relay is composed separately, in the
+//! `relay_asset_hubs` module.
+//! The `Moonriver` <> `Moonbeam` relay is supported for runtimes that verify bridged relay chain
+//! and parachain headers on their own (with `BridgeKusama*` and `BridgePolkadot*` pallets).
+
+use super::{
+	lane_schedule::{run_lane_relays, start_version_guard, LaneRelayParams, LaneScheduleParams},
+	relay_asset_hubs::AssetHubKusamaAssetHubPolkadotHeadersAndMessages,
+};
 
 use async_trait::async_trait;
-use clap::Parser;
+use clap::{Args, Parser};
+use codec::Encode;
 
 use crate::bridges::{
 	kusama_polkadot::{
 		bridge_hub_kusama_parachains_to_bridge_hub_polkadot::BridgeHubKusamaToBridgeHubPolkadotCliBridge,
 		bridge_hub_polkadot_parachains_to_bridge_hub_kusama::BridgeHubPolkadotToBridgeHubKusamaCliBridge,
 	},
+	moonriver_moonbeam::{
+		moonbeam_parachains_to_moonriver::MoonbeamToMoonriverCliBridge,
+		moonriver_parachains_to_moonbeam::MoonriverToMoonbeamCliBridge,
+	},
 	polkadot_bulletin::{
+		add_relayer_authorization_metric, ensure_bulletin_pallets_at_bridge_hub_polkadot,
 		polkadot_bulletin_headers_to_bridge_hub_polkadot::PolkadotBulletinToBridgeHubPolkadotCliBridge,
 		polkadot_parachains_to_polkadot_bulletin::PolkadotToPolkadotBulletinCliBridge,
 	},
@@ -45,25 +62,32 @@ use crate::bridges::{
 	},
 };
 use relay_bridge_hub_rococo_client::BridgeHubRococo;
+use relay_bridge_hub_westend_client::BridgeHubWestend;
+use relay_common_client::has_balances;
 use relay_substrate_client::{
-	AccountKeyPairOf, ChainRuntimeVersion, ChainWithRuntimeVersion, ChainWithTransactions,
-	Parachain, SimpleRuntimeVersion,
+	AccountIdOf, AccountKeyPairOf, BalanceOf, Chain, ChainRuntimeVersion, ChainWithRuntimeVersion,
+	ChainWithTransactions, Parachain, SimpleRuntimeVersion,
 };
+use sp_core::Pair;
+use sp_runtime::traits::TryConvert;
 use substrate_relay_helper::{
 	cli::{
 		bridge::{
-			CliBridgeBase, MessagesCliBridge, ParachainToRelayHeadersCliBridge,
+			CliBridgeBase, MessagesCliBridge, MessagesLaneIdOf, ParachainToRelayHeadersCliBridge,
 			RelayToRelayHeadersCliBridge,
 		},
 		chain_schema::*,
 		relay_headers_and_messages::{
 			parachain_to_parachain::ParachainToParachainBridge, relay_to_parachain::*,
-			BridgeEndCommonParams, Full2WayBridge, Full2WayBridgeCommonParams,
+			BridgeEndCommonParams, Full2WayBridge, Full2WayBridgeBase, Full2WayBridgeCommonParams,
 			HeadersAndMessagesSharedParams,
 		},
+		HexLaneId,
 	},
 	declare_chain_cli_schema, declare_parachain_to_parachain_bridge_schema,
-	declare_relay_to_parachain_bridge_schema, TransactionParams,
+	declare_relay_to_parachain_bridge_schema,
+	messages::metrics::add_relay_balances_metrics,
+	TaggedAccount, TransactionParams,
 };
 
 // All supported chains.
@@ -75,6 +99,8 @@ declare_chain_cli_schema!(Kusama, kusama);
 declare_chain_cli_schema!(BridgeHubKusama, bridge_hub_kusama);
 declare_chain_cli_schema!(Polkadot, polkadot);
 declare_chain_cli_schema!(BridgeHubPolkadot, bridge_hub_polkadot);
+declare_chain_cli_schema!(Moonriver, moonriver);
+declare_chain_cli_schema!(Moonbeam, moonbeam);
 declare_chain_cli_schema!(PolkadotBulletin, polkadot_bulletin);
 declare_chain_cli_schema!(RococoBulletin, rococo_bulletin);
 // Means to override signers of different layer transactions.
@@ -116,6 +142,7 @@ declare_chain_cli_schema!(RococoParachainsToRococoBulletin, rococo_parachains_to
 // All supported bridges.
 declare_parachain_to_parachain_bridge_schema!(BridgeHubRococo, Rococo, BridgeHubWestend, Westend);
 declare_parachain_to_parachain_bridge_schema!(BridgeHubKusama, Kusama, BridgeHubPolkadot, Polkadot);
+declare_parachain_to_parachain_bridge_schema!(Moonriver, Kusama, Moonbeam, Polkadot);
 declare_relay_to_parachain_bridge_schema!(PolkadotBulletin, BridgeHubPolkadot, Polkadot);
 declare_relay_to_parachain_bridge_schema!(RococoBulletin, BridgeHubRococo, Rococo);
 
@@ -171,6 +198,32 @@ impl Full2WayBridge for BridgeHubKusamaBridgeHubPolkadotFull2WayBridge {
 	}
 }
 
+/// Moonriver <> Moonbeam complex relay.
+pub struct MoonriverMoonbeamFull2WayBridge {
+	base: <Self as Full2WayBridge>::Base,
+}
+
+#[async_trait]
+impl Full2WayBridge for MoonriverMoonbeamFull2WayBridge {
+	type Base = ParachainToParachainBridge<Self::L2R, Self::R2L>;
+	type Left = relay_moonriver_client::Moonriver;
+	type Right = relay_moonbeam_client::Moonbeam;
+	type L2R = MoonriverToMoonbeamCliBridge;
+	type R2L = MoonbeamToMoonriverCliBridge;
+
+	fn new(base: Self::Base) -> anyhow::Result<Self> {
+		Ok(Self { base })
+	}
+
+	fn base(&self) -> &Self::Base {
+		&self.base
+	}
+
+	fn mut_base(&mut self) -> &mut Self::Base {
+		&mut self.base
+	}
+}
+
 /// `PolkadotBulletin` <> `BridgeHubPolkadot` complex relay.
 pub struct PolkadotBulletinBridgeHubPolkadotFull2WayBridge {
 	base: <Self as Full2WayBridge>::Base,
@@ -185,6 +238,13 @@ impl Full2WayBridge for PolkadotBulletinBridgeHubPolkadotFull2WayBridge {
 	type R2L = PolkadotToPolkadotBulletinCliBridge;
 
 	fn new(base: Self::Base) -> anyhow::Result<Self> {
+		// the Bulletin chain has no balances, so the relayer authorization is tracked instead
+		let bulletin = &base.common().left;
+		add_relayer_authorization_metric(
+			bulletin.client.clone(),
+			&base.common().metrics_params,
+			&bulletin.tx_params.signer.public().into(),
+		)?;
 		Ok(Self { base })
 	}
 
@@ -211,6 +271,13 @@ impl Full2WayBridge for RococoBulletinBridgeHubRococoFull2WayBridge {
 	type R2L = RococoToRococoBulletinCliBridge;
 
 	fn new(base: Self::Base) -> anyhow::Result<Self> {
+		// the Bulletin chain has no balances, so the relayer authorization is tracked instead
+		let bulletin = &base.common().left;
+		add_relayer_authorization_metric(
+			bulletin.client.clone(),
+			&base.common().metrics_params,
+			&bulletin.tx_params.signer.public().into(),
+		)?;
 		Ok(Self { base })
 	}
 
@@ -223,17 +290,40 @@ impl Full2WayBridge for RococoBulletinBridgeHubRococoFull2WayBridge {
 	}
 }
 
+/// Complex relay parameters.
+#[derive(Args, Debug, PartialEq)]
+pub struct HeadersAndMessagesParams<Params: Args> {
+	#[command(flatten)]
+	bridge: Params,
+	#[command(flatten)]
+	lanes: LaneScheduleParams,
+}
+
 /// Complex headers+messages relay.
 #[derive(Debug, PartialEq, Parser)]
 pub enum RelayHeadersAndMessages {
 	/// BridgeHubKusama <> BridgeHubPolkadot relay.
-	BridgeHubKusamaBridgeHubPolkadot(BridgeHubKusamaBridgeHubPolkadotHeadersAndMessages),
+	BridgeHubKusamaBridgeHubPolkadot(
+		HeadersAndMessagesParams<BridgeHubKusamaBridgeHubPolkadotHeadersAndMessages>,
+	),
 	/// `PolkadotBulletin` <> `BridgeHubPolkadot` relay.
-	PolkadotBulletinBridgeHubPolkadot(PolkadotBulletinBridgeHubPolkadotHeadersAndMessages),
+	PolkadotBulletinBridgeHubPolkadot(
+		HeadersAndMessagesParams<PolkadotBulletinBridgeHubPolkadotHeadersAndMessages>,
+	),
 	/// `RococoBulletin` <> `BridgeHubRococo` relay.
-	RococoBulletinBridgeHubRococo(RococoBulletinBridgeHubRococoHeadersAndMessages),
+	RococoBulletinBridgeHubRococo(
+		HeadersAndMessagesParams<RococoBulletinBridgeHubRococoHeadersAndMessages>,
+	),
 	/// BridgeHubRococo <> BridgeHubWestend relay.
-	BridgeHubRococoBridgeHubWestend(BridgeHubRococoBridgeHubWestendHeadersAndMessages),
+	BridgeHubRococoBridgeHubWestend(
+		HeadersAndMessagesParams<BridgeHubRococoBridgeHubWestendHeadersAndMessages>,
+	),
+	/// Moonriver <> Moonbeam relay.
+	MoonriverMoonbeam(HeadersAndMessagesParams<MoonriverMoonbeamHeadersAndMessages>),
+	/// `AssetHubKusama` <> `AssetHubPolkadot` relay.
+	AssetHubKusamaAssetHubPolkadot(
+		HeadersAndMessagesParams<AssetHubKusamaAssetHubPolkadotHeadersAndMessages>,
+	),
 }
 
 impl RelayHeadersAndMessages {
@@ -241,28 +331,166 @@ impl RelayHeadersAndMessages {
 	pub async fn run(self) -> anyhow::Result<()> {
 		match self {
 			RelayHeadersAndMessages::BridgeHubRococoBridgeHubWestend(params) =>
-				BridgeHubRococoBridgeHubWestendFull2WayBridge::new(params.into_bridge().await?)?
-					.run()
-					.await,
+				run_bridge(
+					BridgeHubRococoBridgeHubWestendFull2WayBridge::new(
+						params.bridge.into_bridge().await?,
+					)?,
+					params.lanes,
+				)
+				.await,
 			RelayHeadersAndMessages::BridgeHubKusamaBridgeHubPolkadot(params) =>
-				BridgeHubKusamaBridgeHubPolkadotFull2WayBridge::new(params.into_bridge().await?)?
-					.run()
-					.await,
-			RelayHeadersAndMessages::PolkadotBulletinBridgeHubPolkadot(params) =>
-				PolkadotBulletinBridgeHubPolkadotFull2WayBridge::new(params.into_bridge().await?)?
-					.run()
-					.await,
+				run_bridge(
+					BridgeHubKusamaBridgeHubPolkadotFull2WayBridge::new(
+						params.bridge.into_bridge().await?,
+					)?,
+					params.lanes,
+				)
+				.await,
+			RelayHeadersAndMessages::MoonriverMoonbeam(params) =>
+				run_bridge(
+					MoonriverMoonbeamFull2WayBridge::new(params.bridge.into_bridge().await?)?,
+					params.lanes,
+				)
+				.await,
+			RelayHeadersAndMessages::PolkadotBulletinBridgeHubPolkadot(params) => {
+				let bridge = PolkadotBulletinBridgeHubPolkadotFull2WayBridge::new(
+					params.bridge.into_bridge().await?,
+				)?;
+				ensure_bulletin_pallets_at_bridge_hub_polkadot(
+					&bridge.base().common().right.client,
+				)
+				.await?;
+				run_bridge(bridge, params.lanes).await
+			},
 			RelayHeadersAndMessages::RococoBulletinBridgeHubRococo(params) =>
-				RococoBulletinBridgeHubRococoFull2WayBridge::new(params.into_bridge().await?)?
-					.run()
-					.await,
+				run_bridge(
+					RococoBulletinBridgeHubRococoFull2WayBridge::new(
+						params.bridge.into_bridge().await?,
+					)?,
+					params.lanes,
+				)
+				.await,
+			RelayHeadersAndMessages::AssetHubKusamaAssetHubPolkadot(params) =>
+				params.bridge.run(params.lanes).await,
 		}
 	}
 }
 
+/// Run the complex relay.
+///
+/// Without lane configurations, the relay is started by `Full2WayBridge::run` and messages of all
+/// lanes are relayed all the time. It has no hook to start messages relays, so with lane
+/// configurations the relay is composed of the same on-demand headers relays and metrics, but
+/// messages relays of all lanes are created by `LaneRelayParams` and started and stopped by the
+/// lane scheduler.
+///
+/// `Full2WayBridge::run` registers relayer balance metrics at both chains, so bridges with chains
+/// without balances are always started by the lane scheduler, which skips them.
+async fn run_bridge<Bridge: Full2WayBridge>(
+	mut bridge: Bridge,
+	lanes: LaneScheduleParams,
+) -> anyhow::Result<()>
+where
+	AccountIdOf<Bridge::Left>: From<<AccountKeyPairOf<Bridge::Left> as Pair>::Public>,
+	AccountIdOf<Bridge::Right>: From<<AccountKeyPairOf<Bridge::Right> as Pair>::Public>,
+	BalanceOf<Bridge::Left>: TryFrom<BalanceOf<Bridge::Right>> + Into<u128>,
+	BalanceOf<Bridge::Right>: TryFrom<BalanceOf<Bridge::Left>> + Into<u128>,
+{
+	let common = bridge.base().common();
+	let left_has_balances =
+		has_balances::<Bridge::Left>(&common.left.tx_params.signer.public().into());
+	let right_has_balances =
+		has_balances::<Bridge::Right>(&common.right.tx_params.signer.public().into());
+	if lanes.lane_config.is_empty() && left_has_balances && right_has_balances {
+		return bridge.run().await
+	}
+
+	{
+		let common = bridge.mut_base().mut_common();
+		common.left.accounts.push(TaggedAccount::Messages {
+			id: common.left.tx_params.signer.public().into(),
+			bridged_chain: Bridge::Right::NAME.to_string(),
+		});
+		common.right.accounts.push(TaggedAccount::Messages {
+			id: common.right.tx_params.signer.public().into(),
+			bridged_chain: Bridge::Left::NAME.to_string(),
+		});
+	}
+
+	let (left_to_right_headers, right_to_left_headers) =
+		bridge.mut_base().start_on_demand_headers_relayers().await?;
+	let common = bridge.base().common();
+	start_version_guard(&common.left.client).await?;
+	start_version_guard(&common.right.client).await?;
+
+	let lanes_l2r: Vec<MessagesLaneIdOf<Bridge::L2R>> = lane_ids(&common.shared.lane)?;
+	let lanes_r2l: Vec<MessagesLaneIdOf<Bridge::R2L>> = lane_ids(&common.shared.lane)?;
+	if left_has_balances {
+		add_relay_balances_metrics::<_, Bridge::Right, _>(
+			common.left.client.clone(),
+			&common.metrics_params,
+			&common.left.accounts,
+			&lanes_l2r,
+		)
+		.await?;
+	}
+	if right_has_balances {
+		add_relay_balances_metrics::<_, Bridge::Left, _>(
+			common.right.client.clone(),
+			&common.metrics_params,
+			&common.right.accounts,
+			&lanes_r2l,
+		)
+		.await?;
+	}
+
+	let mut relays = LaneRelayParams::<<Bridge::L2R as MessagesCliBridge>::MessagesLane, _, _> {
+		source_client: common.left.client.clone(),
+		source_transaction_params: common.left.tx_params.clone(),
+		target_client: common.right.client.clone(),
+		target_transaction_params: common.right.tx_params.clone(),
+		source_to_target_headers_relay: Some(left_to_right_headers.clone()),
+		target_to_source_headers_relay: Some(right_to_left_headers.clone()),
+		limits: <Bridge::L2R as MessagesCliBridge>::maybe_messages_limits(),
+	}
+	.lane_relays(lanes_l2r.iter().map(Encode::encode))?;
+	relays.extend(
+		LaneRelayParams::<<Bridge::R2L as MessagesCliBridge>::MessagesLane, _, _> {
+			source_client: common.right.client.clone(),
+			source_transaction_params: common.right.tx_params.clone(),
+			target_client: common.left.client.clone(),
+			target_transaction_params: common.left.tx_params.clone(),
+			source_to_target_headers_relay: Some(right_to_left_headers),
+			target_to_source_headers_relay: Some(left_to_right_headers),
+			limits: <Bridge::R2L as MessagesCliBridge>::maybe_messages_limits(),
+		}
+		.lane_relays(lanes_r2l.iter().map(Encode::encode))?,
+	);
+
+	relay_utils::relay_metrics(common.metrics_params.clone())
+		.expose()
+		.await
+		.map_err(|e| anyhow::format_err!("{}", e))?;
+	run_lane_relays(relays, lanes.lane_config, &common.metrics_params).await
+}
+
+/// Convert lanes, given in the command line, to lane identifiers of the bridge.
+fn lane_ids<LaneId>(lanes: &[HexLaneId]) -> anyhow::Result<Vec<LaneId>>
+where
+	HexLaneId: TryConvert<HexLaneId, LaneId>,
+{
+	lanes
+		.iter()
+		.cloned()
+		.map(HexLaneId::try_convert)
+		.collect::<Result<_, _>>()
+		.map_err(|lane| anyhow::format_err!("Invalid lane: {lane:?}"))
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::cli::lane_schedule::LaneConfig;
 	use std::str::FromStr;
 	use substrate_relay_helper::cli::{HexLaneId, PrometheusParams};
 
@@ -290,6 +518,8 @@ mod tests {
 			"ws://polkadot-alice:9944",
 			"--lane",
 			"00000000",
+			"--lane-config",
+			"00000000:priority=1,max-messages-per-hour=100",
 			"--prometheus-host",
 			"0.0.0.0",
 		]);
@@ -297,8 +527,8 @@ mod tests {
 		// then
 		assert_eq!(
 			res,
-			RelayHeadersAndMessages::BridgeHubKusamaBridgeHubPolkadot(
-				BridgeHubKusamaBridgeHubPolkadotHeadersAndMessages {
+			RelayHeadersAndMessages::BridgeHubKusamaBridgeHubPolkadot(HeadersAndMessagesParams {
+				bridge: BridgeHubKusamaBridgeHubPolkadotHeadersAndMessages {
 					shared: HeadersAndMessagesSharedParams {
 						lane: vec![HexLaneId::from_str("00000000").expect("valid laneId")],
 						only_mandatory_headers: false,
@@ -356,8 +586,18 @@ mod tests {
 							polkadot_transaction_version: None,
 						},
 					},
-				}
-			),
+				},
+				lanes: LaneScheduleParams {
+					lane_config: vec![LaneConfig {
+						lane: vec![0, 0, 0, 0],
+						source: None,
+						priority: 1,
+						max_messages_per_hour: Some(100),
+						max_weight_per_hour: None,
+						paused: false,
+					}],
+				},
+			}),
 		);
 	}
 }
@@ -0,0 +1,768 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{relay_headers::RelayHeadersBridge, LOG_TARGET};
+use crate::bridges::{
+	kusama_polkadot::{
+		kusama_headers_to_bridge_hub_polkadot::KusamaToBridgeHubPolkadotCliBridge,
+		polkadot_headers_to_bridge_hub_kusama::PolkadotToBridgeHubKusamaCliBridge,
+	},
+	moonriver_moonbeam::{
+		kusama_headers_to_moonbeam::KusamaToMoonbeamCliBridge,
+		polkadot_headers_to_moonriver::PolkadotToMoonriverCliBridge,
+	},
+	polkadot_bulletin::{
+		polkadot_bulletin_headers_to_bridge_hub_polkadot::PolkadotBulletinToBridgeHubPolkadotCliBridge,
+		polkadot_headers_to_polkadot_bulletin::PolkadotToPolkadotBulletinCliBridge,
+	},
+	rococo_bulletin::{
+		rococo_bulletin_headers_to_bridge_hub_rococo::RococoBulletinToBridgeHubRococoCliBridge,
+		rococo_headers_to_rococo_bulletin::RococoToRococoBulletinCliBridge,
+	},
+	rococo_westend::{
+		rococo_headers_to_bridge_hub_westend::RococoToBridgeHubWestendCliBridge,
+		westend_headers_to_bridge_hub_rococo::WestendToBridgeHubRococoCliBridge,
+	},
+};
+
+use async_trait::async_trait;
+use bp_header_chain::{
+	justification::{verify_and_optimize_justification, JustificationVerificationContext},
+	AuthoritySet, GrandpaConsensusLogReader,
+};
+use bp_runtime::{Chain as _, TransactionEra};
+use clap::Parser;
+use codec::Encode;
+use frame_support::{dispatch::DispatchClass, weights::Weight};
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, BalanceOf, BlockNumberOf, CallOf, Chain, Client, HashOf,
+	HeaderOf, UnsignedTransaction,
+};
+use relay_utils::{TrackedTransactionStatus, TransactionTracker};
+use serde::Serialize;
+use sp_core::Pair;
+use sp_runtime::traits::{Header as HeaderT, SaturatedConversion};
+use substrate_relay_helper::{
+	cli::{
+		bridge::RelayToRelayHeadersCliBridge,
+		chain_schema::{SourceConnectionParams, TargetConnectionParams, TargetSigningParams},
+	},
+	finality::{
+		source::SubstrateFinalitySource, SubmitFinalityProofCallBuilder,
+		SubstrateFinalitySyncPipeline,
+	},
+	finality_base::{
+		best_synced_header_id,
+		engine::{Engine, Grandpa as GrandpaFinalityEngine},
+		SubstrateFinalityPipeline,
+	},
+	BatchCallBuilder, BatchCallBuilderConstructor, UtilityPalletBatchCallBuilder,
+};
+
+/// Builder of the `submit_finality_proof` call of the bridge.
+type CallBuilderOf<Bridge> = <<Bridge as RelayToRelayHeadersCliBridge>::Finality as SubstrateFinalitySyncPipeline>::SubmitFinalityProofCallBuilder;
+
+/// Relay all mandatory headers from the range.
+#[derive(Parser)]
+pub struct RelayMandatoryHeaders {
+	/// A bridge instance to relay headers for.
+	#[arg(value_enum, ignore_case = true)]
+	bridge: RelayHeadersBridge,
+	#[command(flatten)]
+	params: RelayMandatoryHeadersParams,
+}
+
+/// Report mandatory headers that are still to be relayed.
+#[derive(Parser)]
+pub struct PendingMandatoryHeaders {
+	/// A bridge instance to report mandatory headers of.
+	#[arg(value_enum, ignore_case = true)]
+	bridge: RelayHeadersBridge,
+	#[command(flatten)]
+	params: PendingMandatoryHeadersParams,
+}
+
+/// Mandatory headers relay parameters.
+#[derive(Parser)]
+pub struct RelayMandatoryHeadersParams {
+	#[command(flatten)]
+	source: SourceConnectionParams,
+	#[command(flatten)]
+	target: TargetConnectionParams,
+	#[command(flatten)]
+	target_sign: TargetSigningParams,
+	/// Number of the first source chain header of the range.
+	#[arg(long)]
+	from: u32,
+	/// Number of the last source chain header of the range. Must be finalized.
+	#[arg(long)]
+	to: u32,
+	/// Maximal number of headers, submitted in a single `batch_all` call. Batches are also limited
+	/// by the maximal size and weight of the target chain extrinsic. Headers are submitted in
+	/// separate transactions if the target chain has no utility pallet.
+	#[arg(long, default_value = "1")]
+	batch_size: usize,
+}
+
+/// Pending mandatory headers report parameters.
+#[derive(Parser)]
+pub struct PendingMandatoryHeadersParams {
+	#[command(flatten)]
+	source: SourceConnectionParams,
+	#[command(flatten)]
+	target: TargetConnectionParams,
+}
+
+/// Mandatory header with its justification, prepared for submission to the target chain.
+struct MandatoryHeaderSubmission<Source: Chain, Target: Chain> {
+	/// Header number.
+	number: BlockNumberOf<Source>,
+	/// Header hash.
+	hash: HashOf<Source>,
+	/// Size of the optimized justification.
+	justification_size: usize,
+	/// Call to submit the header and its justification.
+	call: CallOf<Target>,
+}
+
+/// Pending mandatory headers report, printed by the command.
+#[derive(Debug, Serialize)]
+struct PendingMandatoryHeadersReport {
+	/// GRANDPA authorities set id at the best finalized source chain header.
+	source_set_id: u64,
+	/// GRANDPA authorities set id, known to the bridge pallet at the target chain.
+	target_set_id: u64,
+	/// Number of the best source chain header, known to the target chain.
+	best_synced_header: u64,
+	/// Number of the best finalized source chain header.
+	best_finalized_header: u64,
+	/// Mandatory headers that are still to be relayed.
+	mandatory_headers: Vec<PendingMandatoryHeader>,
+	/// Maximal size of the target chain extrinsic.
+	max_extrinsic_size: u32,
+	/// Maximal weight of the target chain extrinsic.
+	max_extrinsic_weight: EstimatedWeight,
+	/// True if headers may be submitted in `batch_all` calls.
+	batching: bool,
+	/// Minimal number of transactions, required to relay all mandatory headers.
+	transactions: usize,
+	/// Total size of all calls.
+	total_call_size: usize,
+	/// Total weight of all calls. `None` if the target chain can't estimate weights of calls.
+	total_weight: Option<EstimatedWeight>,
+	/// Total fee of all calls. `None` if the target chain can't estimate fees of calls.
+	total_fee: Option<u128>,
+}
+
+/// Mandatory header that is still to be relayed.
+#[derive(Debug, Serialize)]
+struct PendingMandatoryHeader {
+	/// Header number.
+	number: u64,
+	/// Hex-encoded header hash.
+	hash: String,
+	/// Set id of the authorities that have finalized the header.
+	set_id: u64,
+	/// Size of the optimized justification.
+	justification_size: usize,
+	/// Size of the call to submit the header.
+	call_size: usize,
+	/// Weight of the call to submit the header. `None` if the target chain can't estimate weights
+	/// of calls.
+	weight: Option<EstimatedWeight>,
+	/// Fee of the call to submit the header, paid before it is dispatched. The length fee only
+	/// covers the call, not the signature and extensions of the transaction. Mandatory headers
+	/// are submitted with `is_free_execution_expected`, so the fee is refunded if the header is
+	/// imported. `None` if the target chain can't estimate fees of calls.
+	fee: Option<u128>,
+	/// True if the call doesn't fit into a single target chain extrinsic. If the weight of the
+	/// call is unknown, only its size is checked.
+	exceeds_extrinsic_limits: bool,
+}
+
+/// Weight of the call.
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+struct EstimatedWeight {
+	ref_time: u64,
+	proof_size: u64,
+}
+
+impl From<Weight> for EstimatedWeight {
+	fn from(weight: Weight) -> Self {
+		EstimatedWeight { ref_time: weight.ref_time(), proof_size: weight.proof_size() }
+	}
+}
+
+/// Bridge, which mandatory headers may be relayed in batch.
+#[async_trait]
+pub trait MandatoryHeadersRelayer: RelayToRelayHeadersCliBridge
+where
+	Self::Finality: SubstrateFinalityPipeline<FinalityEngine = GrandpaFinalityEngine<Self::Source>>,
+{
+	/// Builder of the batch call at the target chain.
+	type TargetBatchCallBuilder: BatchCallBuilderConstructor<CallOf<Self::Target>>;
+
+	/// True if the target chain runtime provides the `TransactionPaymentCallApi`, so weights and
+	/// fees of calls may be estimated.
+	const TARGET_HAS_TRANSACTION_PAYMENT: bool = true;
+
+	/// Relay all mandatory headers from the range.
+	///
+	/// Headers, that are already imported by the target chain, are skipped. So if the command
+	/// fails, it may be restarted with the same arguments.
+	async fn relay_mandatory_headers(params: RelayMandatoryHeadersParams) -> anyhow::Result<()>
+	where
+		AccountIdOf<Self::Target>: From<<AccountKeyPairOf<Self::Target> as Pair>::Public>,
+	{
+		let source_client = params.source.into_client::<Self::Source>().await?;
+		let target_client = params.target.into_client::<Self::Target>().await?;
+		let target_transactions_mortality = params.target_sign.transactions_mortality()?;
+		let target_sign = params.target_sign.to_keypair::<Self::Target>()?;
+		Self::Finality::start_relay_guards(&target_client, target_client.can_start_version_guard())
+			.await?;
+
+		let batch_call_builder = match Self::TargetBatchCallBuilder::new_builder() {
+			_ if params.batch_size <= 1 => None,
+			Some(batch_call_builder) => Some(batch_call_builder),
+			None => anyhow::bail!(
+				"{} has no utility pallet. Headers may only be submitted with --batch-size 1",
+				Self::Target::NAME,
+			),
+		};
+
+		// resume from the best header, known to the target chain
+		let best_target_hash = target_client.best_header_hash().await?;
+		let best_synced_number = Self::best_synced_number(&target_client, best_target_hash).await?;
+		let from = std::cmp::max(params.from.into(), best_synced_number + 1u32.into());
+		let to: BlockNumberOf<Self::Source> = params.to.into();
+		let best_finalized_number = source_client.best_finalized_header_number().await?;
+		if to > best_finalized_number {
+			anyhow::bail!(
+				"{} header {to} is not finalized yet. Best finalized header is {best_finalized_number}",
+				Self::Source::NAME,
+			)
+		}
+		if from > to {
+			log::info!(
+				target: LOG_TARGET,
+				"All {} headers up to {to} are already imported by {}",
+				Self::Source::NAME,
+				Self::Target::NAME,
+			);
+			return Ok(())
+		}
+
+		log::info!(
+			target: LOG_TARGET,
+			"Looking for mandatory {} headers in range {from}..={to}",
+			Self::Source::NAME,
+		);
+		// every authorities set change is enacted by the mandatory header, so we are looking for
+		// blocks where the set id changes instead of reading all headers of the range
+		let mut mandatory_headers = Vec::new();
+		let mut lo = from - 1u32.into();
+		let lo_set_id = grandpa_set_id(&source_client, lo).await?;
+		let hi_set_id = grandpa_set_id(&source_client, to).await?;
+		for set_id in lo_set_id + 1..=hi_set_id {
+			lo = first_header_with_set_id(&source_client, set_id, lo, to).await?;
+			mandatory_headers.push(lo);
+		}
+		log::info!(
+			target: LOG_TARGET,
+			"Found {} mandatory {} headers in range {from}..={to}",
+			mandatory_headers.len(),
+			Self::Source::NAME,
+		);
+
+		let source = SubstrateFinalitySource::<Self::Finality, _>::new(source_client, None);
+		let mut context = GrandpaFinalityEngine::<Self::Source>::finality_verification_context(
+			&target_client,
+			best_target_hash,
+		)
+		.await?;
+		let mut calls = Vec::with_capacity(mandatory_headers.len());
+		let mut call_limits = Vec::with_capacity(mandatory_headers.len());
+		for number in &mandatory_headers {
+			let call = Self::prepare_mandatory_header(&source, *number, &mut context).await?.call;
+			let weight = match batch_call_builder {
+				Some(_) => Self::call_info(&target_client, best_target_hash, &call)
+					.await?
+					.map(|(weight, _)| weight)
+					.unwrap_or_default(),
+				None => Weight::zero(),
+			};
+			call_limits.push((call.encoded_size(), weight));
+			calls.push(call);
+		}
+
+		let batches = match batch_call_builder {
+			Some(_) => pack_calls(
+				&call_limits,
+				Self::Target::max_extrinsic_size(),
+				Self::Target::max_extrinsic_weight(),
+				params.batch_size,
+			),
+			None => vec![1; calls.len()],
+		};
+		let mut calls = calls.into_iter();
+		let mut relayed = 0;
+		for batch_len in batches {
+			let mut batch_calls = calls.by_ref().take(batch_len).collect::<Vec<_>>();
+			let batch = &mandatory_headers[relayed..relayed + batch_len];
+			let call = match batch_call_builder {
+				Some(ref batch_call_builder) if batch_calls.len() > 1 =>
+					batch_call_builder.build_batch_call(batch_calls),
+				_ => batch_calls.pop().expect("batch is never empty; qed"),
+			};
+			let tracker = target_client
+				.submit_and_watch_signed_extrinsic(&target_sign, move |best_block_id, nonce| {
+					Ok(UnsignedTransaction::new(call.into(), nonce)
+						.era(TransactionEra::new(best_block_id, target_transactions_mortality)))
+				})
+				.await?;
+			match tracker.wait().await {
+				TrackedTransactionStatus::Finalized(_) => (),
+				TrackedTransactionStatus::Lost => anyhow::bail!(
+					"Transaction with {} headers {}..={} has been lost. Restart the command to resume",
+					Self::Source::NAME,
+					batch[0],
+					batch[batch.len() - 1],
+				),
+			}
+
+			relayed += batch_len;
+			log::info!(
+				target: LOG_TARGET,
+				"Relayed {relayed}/{} mandatory {} headers. Last relayed header: {}",
+				mandatory_headers.len(),
+				Self::Source::NAME,
+				batch[batch.len() - 1],
+			);
+		}
+
+		Ok(())
+	}
+
+	/// Report mandatory headers that are still to be relayed and estimate the cost of relaying
+	/// them.
+	///
+	/// Every GRANDPA authorities set change is enacted by the mandatory header, so instead of
+	/// reading all source chain headers, we are looking for blocks where the set id changes.
+	///
+	/// Weights and fees are only reported if the target chain is able to estimate them.
+	async fn pending_mandatory_headers(
+		params: PendingMandatoryHeadersParams,
+	) -> anyhow::Result<()> {
+		let source_client = params.source.into_client::<Self::Source>().await?;
+		let target_client = params.target.into_client::<Self::Target>().await?;
+
+		let best_target_hash = target_client.best_header_hash().await?;
+		let best_synced_number = Self::best_synced_number(&target_client, best_target_hash).await?;
+		let mut context = GrandpaFinalityEngine::<Self::Source>::finality_verification_context(
+			&target_client,
+			best_target_hash,
+		)
+		.await?;
+		let target_set_id = context.authority_set_id;
+		let best_finalized_number = source_client.best_finalized_header_number().await?;
+		let source_set_id = grandpa_set_id(&source_client, best_finalized_number).await?;
+
+		let mut mandatory_headers = Vec::new();
+		let mut lo = best_synced_number;
+		for set_id in target_set_id + 1..=source_set_id {
+			lo =
+				first_header_with_set_id(&source_client, set_id, lo, best_finalized_number).await?;
+			mandatory_headers.push(lo);
+			log::info!(
+				target: LOG_TARGET,
+				"Found mandatory {} header {lo}, enacting authorities set {set_id}",
+				Self::Source::NAME,
+			);
+		}
+
+		let source = SubstrateFinalitySource::<Self::Finality, _>::new(source_client, None);
+		let max_extrinsic_size = Self::Target::max_extrinsic_size();
+		let max_extrinsic_weight = Self::Target::max_extrinsic_weight();
+		let mut pending_headers = Vec::with_capacity(mandatory_headers.len());
+		let mut calls = Vec::with_capacity(mandatory_headers.len());
+		for number in mandatory_headers {
+			let set_id = context.authority_set_id;
+			let submission = Self::prepare_mandatory_header(&source, number, &mut context).await?;
+			let call_size = submission.call.encoded_size();
+			let call_info =
+				Self::call_info(&target_client, best_target_hash, &submission.call).await?;
+			let weight = call_info.map(|(weight, _)| weight);
+
+			calls.push((call_size, weight.unwrap_or_default()));
+			pending_headers.push(PendingMandatoryHeader {
+				number: submission.number.saturated_into(),
+				hash: format!("{:?}", submission.hash),
+				set_id,
+				justification_size: submission.justification_size,
+				call_size,
+				weight: weight.map(Into::into),
+				fee: call_info.map(|(_, fee)| fee),
+				exceeds_extrinsic_limits: call_size > max_extrinsic_size as usize ||
+					weight.map_or(false, |weight| weight.any_gt(max_extrinsic_weight)),
+			});
+		}
+
+		let batching = Self::TargetBatchCallBuilder::new_builder().is_some();
+		let report = PendingMandatoryHeadersReport {
+			source_set_id,
+			target_set_id,
+			best_synced_header: best_synced_number.saturated_into(),
+			best_finalized_header: best_finalized_number.saturated_into(),
+			max_extrinsic_size,
+			max_extrinsic_weight: max_extrinsic_weight.into(),
+			batching,
+			transactions: required_transactions(
+				&calls,
+				max_extrinsic_size,
+				max_extrinsic_weight,
+				batching,
+			),
+			total_call_size: calls.iter().map(|(size, _)| size).sum(),
+			total_weight: Self::TARGET_HAS_TRANSACTION_PAYMENT.then(|| {
+				calls
+					.iter()
+					.fold(Weight::zero(), |total, (_, weight)| total.saturating_add(*weight))
+					.into()
+			}),
+			total_fee: Self::TARGET_HAS_TRANSACTION_PAYMENT.then(|| {
+				pending_headers.iter().fold(0u128, |total, header| {
+					total.saturating_add(header.fee.unwrap_or_default())
+				})
+			}),
+			mandatory_headers: pending_headers,
+		};
+
+		println!("{}", serde_json::to_string_pretty(&report)?);
+		Ok(())
+	}
+
+	/// Returns weight and fee of the call, estimated by the target chain, or `None` if the target
+	/// chain can't estimate them.
+	///
+	/// The length fee is computed for the call only. Signature and extensions of the transaction
+	/// are not known without signing it.
+	async fn call_info(
+		target_client: &impl Client<Self::Target>,
+		at: HashOf<Self::Target>,
+		call: &CallOf<Self::Target>,
+	) -> anyhow::Result<Option<(Weight, u128)>> {
+		if !Self::TARGET_HAS_TRANSACTION_PAYMENT {
+			return Ok(None)
+		}
+
+		let call_size = call.encoded_size() as u32;
+		let (weight, _, fee): (Weight, DispatchClass, BalanceOf<Self::Target>) = target_client
+			.state_call(at, "TransactionPaymentCallApi_query_call_info".into(), (call, call_size))
+			.await?;
+		Ok(Some((weight, fee.saturated_into())))
+	}
+
+	/// Returns number of the best source chain header, known to the target chain.
+	async fn best_synced_number(
+		target_client: &impl Client<Self::Target>,
+		at: HashOf<Self::Target>,
+	) -> anyhow::Result<BlockNumberOf<Self::Source>> {
+		best_synced_header_id::<Self::Source, Self::Target>(target_client, at)
+			.await?
+			.map(|id| id.number())
+			.ok_or_else(|| {
+				anyhow::format_err!(
+					"{} headers bridge at {} is not initialized",
+					Self::Source::NAME,
+					Self::Target::NAME,
+				)
+			})
+	}
+
+	/// Fetch and optimize justification of the mandatory header and build the call to submit it
+	/// to the target chain.
+	///
+	/// The `context` must be the context at the target chain, right before the header is
+	/// imported. It is updated with the context after the header is imported.
+	async fn prepare_mandatory_header<SourceClnt: Client<Self::Source>>(
+		source: &SubstrateFinalitySource<Self::Finality, SourceClnt>,
+		number: BlockNumberOf<Self::Source>,
+		context: &mut JustificationVerificationContext,
+	) -> anyhow::Result<MandatoryHeaderSubmission<Self::Source, Self::Target>> {
+		let (header, mut justification) = source.prove_block_finality(number).await?;
+		verify_and_optimize_justification(
+			(header.hash(), *header.number()),
+			context,
+			&mut justification,
+		)
+		.map_err(|e| {
+			anyhow::format_err!(
+				"Invalid justification of {} header {number}: {e:?}",
+				Self::Source::NAME,
+			)
+		})?;
+
+		let next_context = next_verification_context::<Self::Source>(&header, context)?;
+		let current_context = std::mem::replace(context, next_context);
+		Ok(MandatoryHeaderSubmission {
+			number,
+			hash: header.hash(),
+			justification_size: justification.encoded_size(),
+			call: CallBuilderOf::<Self>::build_submit_finality_proof_call(
+				header,
+				justification,
+				true,
+				current_context,
+			),
+		})
+	}
+}
+
+/// Returns GRANDPA authorities set id at given source chain header.
+async fn grandpa_set_id<C: Chain>(
+	client: &impl Client<C>,
+	number: BlockNumberOf<C>,
+) -> anyhow::Result<u64> {
+	let at = client.header_hash_by_number(number).await?;
+	Ok(client
+		.storage_value(at, bp_runtime::storage_value_key("Grandpa", "CurrentSetId"))
+		.await?
+		.unwrap_or_default())
+}
+
+/// Returns number of the first header in the `(lo, hi]` range, where the GRANDPA authorities set
+/// id is at least `set_id`.
+async fn first_header_with_set_id<C: Chain>(
+	client: &impl Client<C>,
+	set_id: u64,
+	mut lo: BlockNumberOf<C>,
+	mut hi: BlockNumberOf<C>,
+) -> anyhow::Result<BlockNumberOf<C>> {
+	while hi - lo > 1u32.into() {
+		let mid = lo + (hi - lo) / 2u32.into();
+		if grandpa_set_id(client, mid).await? >= set_id {
+			hi = mid;
+		} else {
+			lo = mid;
+		}
+	}
+	Ok(hi)
+}
+
+/// Returns minimal number of transactions, required to submit all calls.
+///
+/// If batching is supported, calls are packed into batches by `pack_calls`.
+fn required_transactions(
+	calls: &[(usize, Weight)],
+	max_extrinsic_size: u32,
+	max_extrinsic_weight: Weight,
+	batching: bool,
+) -> usize {
+	if !batching {
+		return calls.len()
+	}
+
+	pack_calls(calls, max_extrinsic_size, max_extrinsic_weight, usize::MAX).len()
+}
+
+/// Greedily pack calls with given sizes and weights into batches of at most `max_batch_len`
+/// calls, that fit into the maximal extrinsic size and weight. Returns number of calls in every
+/// batch.
+///
+/// Calls that don't fit into the extrinsic on their own are submitted in separate batches.
+fn pack_calls(
+	calls: &[(usize, Weight)],
+	max_extrinsic_size: u32,
+	max_extrinsic_weight: Weight,
+	max_batch_len: usize,
+) -> Vec<usize> {
+	let mut batches = Vec::new();
+	let mut batch: Option<(usize, usize, Weight)> = None;
+	for (size, weight) in calls {
+		batch = match batch {
+			Some((batch_len, batch_size, batch_weight))
+				if batch_len < max_batch_len &&
+					batch_size + size <= max_extrinsic_size as usize &&
+					batch_weight.saturating_add(*weight).all_lte(max_extrinsic_weight) =>
+				Some((batch_len + 1, batch_size + size, batch_weight.saturating_add(*weight))),
+			batch => {
+				batches.extend(batch.map(|(batch_len, _, _)| batch_len));
+				Some((1, *size, *weight))
+			},
+		};
+	}
+	batches.extend(batch.map(|(batch_len, _, _)| batch_len));
+	batches
+}
+
+/// Returns context, required to verify justification of the header that follows the given
+/// mandatory header.
+fn next_verification_context<C: Chain>(
+	header: &HeaderOf<C>,
+	context: &JustificationVerificationContext,
+) -> anyhow::Result<JustificationVerificationContext> {
+	let scheduled_change =
+		GrandpaConsensusLogReader::<BlockNumberOf<C>>::find_scheduled_change(header.digest())
+			.ok_or_else(|| {
+				anyhow::format_err!(
+					"{} header {} does not schedule authorities change",
+					C::NAME,
+					header.number(),
+				)
+			})?;
+	AuthoritySet::new(scheduled_change.next_authorities, context.authority_set_id + 1)
+		.try_into()
+		.map_err(|e| {
+			anyhow::format_err!(
+				"Invalid authorities set, scheduled by {} header {}: {e:?}",
+				C::NAME,
+				header.number(),
+			)
+		})
+}
+
+impl MandatoryHeadersRelayer for RococoToBridgeHubWestendCliBridge {
+	type TargetBatchCallBuilder = UtilityPalletBatchCallBuilder<Self::Target>;
+}
+impl MandatoryHeadersRelayer for WestendToBridgeHubRococoCliBridge {
+	type TargetBatchCallBuilder = UtilityPalletBatchCallBuilder<Self::Target>;
+}
+impl MandatoryHeadersRelayer for KusamaToBridgeHubPolkadotCliBridge {
+	type TargetBatchCallBuilder = UtilityPalletBatchCallBuilder<Self::Target>;
+}
+impl MandatoryHeadersRelayer for PolkadotToBridgeHubKusamaCliBridge {
+	type TargetBatchCallBuilder = UtilityPalletBatchCallBuilder<Self::Target>;
+}
+impl MandatoryHeadersRelayer for PolkadotToPolkadotBulletinCliBridge {
+	type TargetBatchCallBuilder = ();
+	const TARGET_HAS_TRANSACTION_PAYMENT: bool = false;
+}
+impl MandatoryHeadersRelayer for PolkadotBulletinToBridgeHubPolkadotCliBridge {
+	type TargetBatchCallBuilder = UtilityPalletBatchCallBuilder<Self::Target>;
+}
+impl MandatoryHeadersRelayer for RococoToRococoBulletinCliBridge {
+	type TargetBatchCallBuilder = ();
+	const TARGET_HAS_TRANSACTION_PAYMENT: bool = false;
+}
+impl MandatoryHeadersRelayer for RococoBulletinToBridgeHubRococoCliBridge {
+	type TargetBatchCallBuilder = UtilityPalletBatchCallBuilder<Self::Target>;
+}
+impl MandatoryHeadersRelayer for KusamaToMoonbeamCliBridge {
+	type TargetBatchCallBuilder = UtilityPalletBatchCallBuilder<Self::Target>;
+}
+impl MandatoryHeadersRelayer for PolkadotToMoonriverCliBridge {
+	type TargetBatchCallBuilder = UtilityPalletBatchCallBuilder<Self::Target>;
+}
+
+impl RelayMandatoryHeaders {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		match self.bridge {
+			RelayHeadersBridge::RococoToBridgeHubWestend =>
+				RococoToBridgeHubWestendCliBridge::relay_mandatory_headers(self.params),
+			RelayHeadersBridge::WestendToBridgeHubRococo =>
+				WestendToBridgeHubRococoCliBridge::relay_mandatory_headers(self.params),
+			RelayHeadersBridge::KusamaToBridgeHubPolkadot =>
+				KusamaToBridgeHubPolkadotCliBridge::relay_mandatory_headers(self.params),
+			RelayHeadersBridge::PolkadotToBridgeHubKusama =>
+				PolkadotToBridgeHubKusamaCliBridge::relay_mandatory_headers(self.params),
+			RelayHeadersBridge::PolkadotToPolkadotBulletin =>
+				PolkadotToPolkadotBulletinCliBridge::relay_mandatory_headers(self.params),
+			RelayHeadersBridge::PolkadotBulletinToBridgeHubPolkadot =>
+				PolkadotBulletinToBridgeHubPolkadotCliBridge::relay_mandatory_headers(self.params),
+			RelayHeadersBridge::RococoToRococoBulletin =>
+				RococoToRococoBulletinCliBridge::relay_mandatory_headers(self.params),
+			RelayHeadersBridge::RococoBulletinToBridgeHubRococo =>
+				RococoBulletinToBridgeHubRococoCliBridge::relay_mandatory_headers(self.params),
+			RelayHeadersBridge::KusamaToMoonbeam =>
+				KusamaToMoonbeamCliBridge::relay_mandatory_headers(self.params),
+			RelayHeadersBridge::PolkadotToMoonriver =>
+				PolkadotToMoonriverCliBridge::relay_mandatory_headers(self.params),
+		}
+		.await
+	}
+}
+
+impl PendingMandatoryHeaders {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		match self.bridge {
+			RelayHeadersBridge::RococoToBridgeHubWestend =>
+				RococoToBridgeHubWestendCliBridge::pending_mandatory_headers(self.params),
+			RelayHeadersBridge::WestendToBridgeHubRococo =>
+				WestendToBridgeHubRococoCliBridge::pending_mandatory_headers(self.params),
+			RelayHeadersBridge::KusamaToBridgeHubPolkadot =>
+				KusamaToBridgeHubPolkadotCliBridge::pending_mandatory_headers(self.params),
+			RelayHeadersBridge::PolkadotToBridgeHubKusama =>
+				PolkadotToBridgeHubKusamaCliBridge::pending_mandatory_headers(self.params),
+			RelayHeadersBridge::PolkadotToPolkadotBulletin =>
+				PolkadotToPolkadotBulletinCliBridge::pending_mandatory_headers(self.params),
+			RelayHeadersBridge::PolkadotBulletinToBridgeHubPolkadot =>
+				PolkadotBulletinToBridgeHubPolkadotCliBridge::pending_mandatory_headers(self.params),
+			RelayHeadersBridge::RococoToRococoBulletin =>
+				RococoToRococoBulletinCliBridge::pending_mandatory_headers(self.params),
+			RelayHeadersBridge::RococoBulletinToBridgeHubRococo =>
+				RococoBulletinToBridgeHubRococoCliBridge::pending_mandatory_headers(self.params),
+			RelayHeadersBridge::KusamaToMoonbeam =>
+				KusamaToMoonbeamCliBridge::pending_mandatory_headers(self.params),
+			RelayHeadersBridge::PolkadotToMoonriver =>
+				PolkadotToMoonriverCliBridge::pending_mandatory_headers(self.params),
+		}
+		.await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn required_transactions_respects_extrinsic_limits() {
+		let calls = vec![
+			(400, Weight::from_parts(100, 10)),
+			(400, Weight::from_parts(100, 10)),
+			(400, Weight::from_parts(100, 10)),
+			(100, Weight::from_parts(950, 10)),
+		];
+
+		// every call is submitted in a separate transaction without batching
+		assert_eq!(required_transactions(&calls, 1_000, Weight::from_parts(1_000, 100), false), 4);
+		// first two calls fit into single batch, the third call doesn't fit by size and the
+		// fourth call doesn't fit by weight
+		assert_eq!(required_transactions(&calls, 1_000, Weight::from_parts(1_000, 100), true), 3);
+		// all calls fit into single batch
+		assert_eq!(required_transactions(&calls, 2_000, Weight::from_parts(2_000, 100), true), 1);
+		assert_eq!(required_transactions(&[], 1_000, Weight::from_parts(1_000, 100), true), 0);
+	}
+
+	#[test]
+	fn pack_calls_respects_batch_len_and_extrinsic_limits() {
+		let calls = vec![
+			(400, Weight::from_parts(100, 10)),
+			(400, Weight::from_parts(100, 10)),
+			(400, Weight::from_parts(100, 10)),
+			(100, Weight::from_parts(950, 10)),
+			(100, Weight::from_parts(10, 10)),
+		];
+
+		assert_eq!(pack_calls(&calls, 1_000, Weight::from_parts(1_000, 100), 4), vec![2, 1, 2]);
+		assert_eq!(pack_calls(&calls, 2_000, Weight::from_parts(2_000, 100), 4), vec![4, 1]);
+		assert_eq!(pack_calls(&calls, 2_000, Weight::from_parts(2_000, 100), 1), vec![1; 5]);
+		// call that doesn't fit into the extrinsic on its own is still submitted
+		assert_eq!(pack_calls(&calls[..1], 100, Weight::from_parts(1_000, 100), 4), vec![1]);
+		assert!(pack_calls(&[], 1_000, Weight::from_parts(1_000, 100), 4).is_empty());
+	}
+}
@@ -14,15 +14,34 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
+use super::{
+	chain_schema::*,
+	lane_schedule::{
+		run_lane_relays, start_version_guard, LaneConfig, LaneRelayParams, LaneScheduleParams,
+	},
+};
+
+use async_trait::async_trait;
 use clap::{Parser, ValueEnum};
+use relay_substrate_client::{AccountIdOf, AccountKeyPairOf, BalanceOf, Chain, Client};
+use relay_utils::metrics::MetricsParams;
+use sp_core::Pair;
+use sp_runtime::traits::TryConvert;
 use strum::{EnumString, VariantNames};
 
 use crate::bridges::{
 	kusama_polkadot::{
+		asset_hub_kusama_messages_to_asset_hub_polkadot::AssetHubKusamaToAssetHubPolkadotMessagesCliBridge,
+		asset_hub_polkadot_messages_to_asset_hub_kusama::AssetHubPolkadotToAssetHubKusamaMessagesCliBridge,
 		bridge_hub_kusama_messages_to_bridge_hub_polkadot::BridgeHubKusamaToBridgeHubPolkadotMessagesCliBridge,
 		bridge_hub_polkadot_messages_to_bridge_hub_kusama::BridgeHubPolkadotToBridgeHubKusamaMessagesCliBridge,
 	},
+	moonriver_moonbeam::{
+		moonbeam_messages_to_moonriver::MoonbeamToMoonriverMessagesCliBridge,
+		moonriver_messages_to_moonbeam::MoonriverToMoonbeamMessagesCliBridge,
+	},
 	polkadot_bulletin::{
+		add_relayer_authorization_metric,
 		bridge_hub_polkadot_messages_to_polkadot_bulletin::BridgeHubPolkadotToPolkadotBulletinMessagesCliBridge,
 		polkadot_bulletin_messages_to_bridge_hub_polkadot::PolkadotBulletinToBridgeHubPolkadotMessagesCliBridge,
 	},
@@ -37,9 +56,15 @@ use crate::bridges::{
 		bridge_hub_westend_messages_to_bridge_hub_rococo::BridgeHubWestendToBridgeHubRococoMessagesCliBridge,
 	},
 };
-use substrate_relay_helper::cli::relay_messages::{
-	MessagesRelayer, RelayMessagesDeliveryConfirmationParams, RelayMessagesParams,
-	RelayMessagesRangeParams,
+use substrate_relay_helper::{
+	cli::{
+		bridge::MessagesCliBridge,
+		relay_messages::{
+			MessagesRelayer, RelayMessagesDeliveryConfirmationParams, RelayMessagesRangeParams,
+		},
+		HexLaneId, PrometheusParams,
+	},
+	TransactionParams,
 };
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, EnumString, VariantNames, ValueEnum)]
@@ -56,6 +81,10 @@ pub enum FullBridge {
 	BridgeHubRococoToRococoBulletin,
 	AssetHubRococoToBridgeHubWestend,
 	AssetHubWestendToBridgeHubRococo,
+	AssetHubKusamaToAssetHubPolkadot,
+	AssetHubPolkadotToAssetHubKusama,
+	MoonriverToMoonbeam,
+	MoonbeamToMoonriver,
 }
 
 /// Start messages relayer process.
@@ -65,7 +94,107 @@ pub struct RelayMessages {
 	#[arg(value_enum, ignore_case = true)]
 	bridge: FullBridge,
 	#[command(flatten)]
-	params: RelayMessagesParams,
+	lanes: LaneScheduleParams,
+	#[command(flatten)]
+	params: ScheduledMessagesParams,
+}
+
+/// Parameters of the scheduled lanes messages relay.
+///
+/// Without lane configurations, the relay serves the `--lane` lane all the time.
+#[derive(Parser)]
+pub struct ScheduledMessagesParams {
+	#[command(flatten)]
+	source: SourceConnectionParams,
+	#[command(flatten)]
+	source_sign: SourceSigningParams,
+	#[command(flatten)]
+	target: TargetConnectionParams,
+	#[command(flatten)]
+	target_sign: TargetSigningParams,
+	#[arg(long)]
+	lane: HexLaneId,
+	#[command(flatten)]
+	prometheus_params: PrometheusParams,
+}
+
+/// Messages relayer, that relays several lanes according to their configurations.
+#[async_trait]
+pub trait ScheduledMessagesRelayer: MessagesCliBridge
+where
+	AccountIdOf<Self::Source>: From<<AccountKeyPairOf<Self::Source> as Pair>::Public>,
+	AccountIdOf<Self::Target>: From<<AccountKeyPairOf<Self::Target> as Pair>::Public>,
+	BalanceOf<Self::Source>: TryFrom<BalanceOf<Self::Target>>,
+{
+	/// Register relayer metrics, specific to the bridged chains.
+	///
+	/// By default, no metrics are registered.
+	fn add_relayer_metrics(
+		_source_client: impl Client<Self::Source>,
+		_source_relayer: &AccountIdOf<Self::Source>,
+		_target_client: impl Client<Self::Target>,
+		_target_relayer: &AccountIdOf<Self::Target>,
+		_metrics_params: &MetricsParams,
+	) -> anyhow::Result<()> {
+		Ok(())
+	}
+
+	/// Relay messages of the `--lane` lane and of all configured lanes.
+	///
+	/// The relay is started the same way as `MessagesRelayer::relay_messages` starts it, but
+	/// messages relays of all lanes are started and stopped by the lane scheduler.
+	async fn relay_scheduled_messages(
+		params: ScheduledMessagesParams,
+		lane_configs: Vec<LaneConfig>,
+	) -> anyhow::Result<()> {
+		let source_client = params.source.into_client::<Self::Source>().await?;
+		let source_transaction_params = TransactionParams {
+			signer: params.source_sign.to_keypair::<Self::Source>()?,
+			mortality: params.source_sign.transactions_mortality()?,
+		};
+		let target_client = params.target.into_client::<Self::Target>().await?;
+		let target_transaction_params = TransactionParams {
+			signer: params.target_sign.to_keypair::<Self::Target>()?,
+			mortality: params.target_sign.transactions_mortality()?,
+		};
+		let metrics_params = params.prometheus_params.into_metrics_params()?;
+
+		let mut lanes: Vec<Vec<u8>> = vec![HexLaneId::try_convert(params.lane)
+			.map_err(|lane| anyhow::format_err!("Invalid lane: {lane:?}"))?];
+		for config in &lane_configs {
+			let is_other_direction =
+				config.source.as_ref().map_or(false, |source| source != Self::Source::NAME);
+			if !is_other_direction && !lanes.contains(&config.lane) {
+				lanes.push(config.lane.clone());
+			}
+		}
+
+		let relays = LaneRelayParams::<Self::MessagesLane, _, _> {
+			source_client: source_client.clone(),
+			source_transaction_params: source_transaction_params.clone(),
+			target_client: target_client.clone(),
+			target_transaction_params: target_transaction_params.clone(),
+			source_to_target_headers_relay: None,
+			target_to_source_headers_relay: None,
+			limits: Self::maybe_messages_limits(),
+		}
+		.lane_relays(&lanes)?;
+
+		start_version_guard(&source_client).await?;
+		start_version_guard(&target_client).await?;
+		Self::add_relayer_metrics(
+			source_client,
+			&source_transaction_params.signer.public().into(),
+			target_client,
+			&target_transaction_params.signer.public().into(),
+			&metrics_params,
+		)?;
+		relay_utils::relay_metrics(metrics_params.clone())
+			.expose()
+			.await
+			.map_err(|e| anyhow::format_err!("{}", e))?;
+		run_lane_relays(relays, lane_configs, &metrics_params).await
+	}
 }
 
 /// Relay range of messages.
@@ -98,31 +227,136 @@ impl MessagesRelayer for RococoBulletinToBridgeHubRococoMessagesCliBridge {}
 impl MessagesRelayer for BridgeHubRococoToRococoBulletinMessagesCliBridge {}
 impl MessagesRelayer for AssetHubRococoToAssetHubWestendMessagesCliBridge {}
 impl MessagesRelayer for AssetHubWestendToAssetHubRococoMessagesCliBridge {}
+impl MessagesRelayer for AssetHubKusamaToAssetHubPolkadotMessagesCliBridge {}
+impl MessagesRelayer for AssetHubPolkadotToAssetHubKusamaMessagesCliBridge {}
+impl MessagesRelayer for MoonriverToMoonbeamMessagesCliBridge {}
+impl MessagesRelayer for MoonbeamToMoonriverMessagesCliBridge {}
+
+impl ScheduledMessagesRelayer for BridgeHubRococoToBridgeHubWestendMessagesCliBridge {}
+impl ScheduledMessagesRelayer for BridgeHubWestendToBridgeHubRococoMessagesCliBridge {}
+impl ScheduledMessagesRelayer for BridgeHubKusamaToBridgeHubPolkadotMessagesCliBridge {}
+impl ScheduledMessagesRelayer for BridgeHubPolkadotToBridgeHubKusamaMessagesCliBridge {}
+impl ScheduledMessagesRelayer for PolkadotBulletinToBridgeHubPolkadotMessagesCliBridge {
+	fn add_relayer_metrics(
+		source_client: impl Client<Self::Source>,
+		source_relayer: &AccountIdOf<Self::Source>,
+		_target_client: impl Client<Self::Target>,
+		_target_relayer: &AccountIdOf<Self::Target>,
+		metrics_params: &MetricsParams,
+	) -> anyhow::Result<()> {
+		add_relayer_authorization_metric(source_client, metrics_params, source_relayer)
+	}
+}
+impl ScheduledMessagesRelayer for BridgeHubPolkadotToPolkadotBulletinMessagesCliBridge {
+	fn add_relayer_metrics(
+		_source_client: impl Client<Self::Source>,
+		_source_relayer: &AccountIdOf<Self::Source>,
+		target_client: impl Client<Self::Target>,
+		target_relayer: &AccountIdOf<Self::Target>,
+		metrics_params: &MetricsParams,
+	) -> anyhow::Result<()> {
+		add_relayer_authorization_metric(target_client, metrics_params, target_relayer)
+	}
+}
+impl ScheduledMessagesRelayer for RococoBulletinToBridgeHubRococoMessagesCliBridge {
+	fn add_relayer_metrics(
+		source_client: impl Client<Self::Source>,
+		source_relayer: &AccountIdOf<Self::Source>,
+		_target_client: impl Client<Self::Target>,
+		_target_relayer: &AccountIdOf<Self::Target>,
+		metrics_params: &MetricsParams,
+	) -> anyhow::Result<()> {
+		add_relayer_authorization_metric(source_client, metrics_params, source_relayer)
+	}
+}
+impl ScheduledMessagesRelayer for BridgeHubRococoToRococoBulletinMessagesCliBridge {
+	fn add_relayer_metrics(
+		_source_client: impl Client<Self::Source>,
+		_source_relayer: &AccountIdOf<Self::Source>,
+		target_client: impl Client<Self::Target>,
+		target_relayer: &AccountIdOf<Self::Target>,
+		metrics_params: &MetricsParams,
+	) -> anyhow::Result<()> {
+		add_relayer_authorization_metric(target_client, metrics_params, target_relayer)
+	}
+}
+impl ScheduledMessagesRelayer for AssetHubRococoToAssetHubWestendMessagesCliBridge {}
+impl ScheduledMessagesRelayer for AssetHubWestendToAssetHubRococoMessagesCliBridge {}
+impl ScheduledMessagesRelayer for AssetHubKusamaToAssetHubPolkadotMessagesCliBridge {}
+impl ScheduledMessagesRelayer for AssetHubPolkadotToAssetHubKusamaMessagesCliBridge {}
+impl ScheduledMessagesRelayer for MoonriverToMoonbeamMessagesCliBridge {}
+impl ScheduledMessagesRelayer for MoonbeamToMoonriverMessagesCliBridge {}
 
 impl RelayMessages {
 	/// Run the command.
 	pub async fn run(self) -> anyhow::Result<()> {
+		let params = self.params;
+		let lane_configs = self.lanes.lane_config;
 		match self.bridge {
 			FullBridge::BridgeHubRococoToBridgeHubWestend =>
-				BridgeHubRococoToBridgeHubWestendMessagesCliBridge::relay_messages(self.params),
+				BridgeHubRococoToBridgeHubWestendMessagesCliBridge::relay_scheduled_messages(
+					params,
+					lane_configs,
+				),
 			FullBridge::BridgeHubWestendToBridgeHubRococo =>
-				BridgeHubWestendToBridgeHubRococoMessagesCliBridge::relay_messages(self.params),
+				BridgeHubWestendToBridgeHubRococoMessagesCliBridge::relay_scheduled_messages(
+					params,
+					lane_configs,
+				),
 			FullBridge::BridgeHubKusamaToBridgeHubPolkadot =>
-				BridgeHubKusamaToBridgeHubPolkadotMessagesCliBridge::relay_messages(self.params),
+				BridgeHubKusamaToBridgeHubPolkadotMessagesCliBridge::relay_scheduled_messages(
+					params,
+					lane_configs,
+				),
 			FullBridge::BridgeHubPolkadotToBridgeHubKusama =>
-				BridgeHubPolkadotToBridgeHubKusamaMessagesCliBridge::relay_messages(self.params),
+				BridgeHubPolkadotToBridgeHubKusamaMessagesCliBridge::relay_scheduled_messages(
+					params,
+					lane_configs,
+				),
 			FullBridge::PolkadotBulletinToBridgeHubPolkadot =>
-				PolkadotBulletinToBridgeHubPolkadotMessagesCliBridge::relay_messages(self.params),
+				PolkadotBulletinToBridgeHubPolkadotMessagesCliBridge::relay_scheduled_messages(
+					params,
+					lane_configs,
+				),
 			FullBridge::BridgeHubPolkadotToPolkadotBulletin =>
-				BridgeHubPolkadotToPolkadotBulletinMessagesCliBridge::relay_messages(self.params),
+				BridgeHubPolkadotToPolkadotBulletinMessagesCliBridge::relay_scheduled_messages(
+					params,
+					lane_configs,
+				),
 			FullBridge::RococoBulletinToBridgeHubRococo =>
-				RococoBulletinToBridgeHubRococoMessagesCliBridge::relay_messages(self.params),
+				RococoBulletinToBridgeHubRococoMessagesCliBridge::relay_scheduled_messages(
+					params,
+					lane_configs,
+				),
 			FullBridge::BridgeHubRococoToRococoBulletin =>
-				BridgeHubRococoToRococoBulletinMessagesCliBridge::relay_messages(self.params),
+				BridgeHubRococoToRococoBulletinMessagesCliBridge::relay_scheduled_messages(
+					params,
+					lane_configs,
+				),
 			FullBridge::AssetHubRococoToBridgeHubWestend =>
-				AssetHubRococoToAssetHubWestendMessagesCliBridge::relay_messages(self.params),
+				AssetHubRococoToAssetHubWestendMessagesCliBridge::relay_scheduled_messages(
+					params,
+					lane_configs,
+				),
 			FullBridge::AssetHubWestendToBridgeHubRococo =>
-				AssetHubWestendToAssetHubRococoMessagesCliBridge::relay_messages(self.params),
+				AssetHubWestendToAssetHubRococoMessagesCliBridge::relay_scheduled_messages(
+					params,
+					lane_configs,
+				),
+			FullBridge::AssetHubKusamaToAssetHubPolkadot =>
+				AssetHubKusamaToAssetHubPolkadotMessagesCliBridge::relay_scheduled_messages(
+					params,
+					lane_configs,
+				),
+			FullBridge::AssetHubPolkadotToAssetHubKusama =>
+				AssetHubPolkadotToAssetHubKusamaMessagesCliBridge::relay_scheduled_messages(
+					params,
+					lane_configs,
+				),
+			FullBridge::MoonriverToMoonbeam =>
+				MoonriverToMoonbeamMessagesCliBridge::relay_scheduled_messages(params, lane_configs),
+			FullBridge::MoonbeamToMoonriver =>
+				MoonbeamToMoonriverMessagesCliBridge::relay_scheduled_messages(params, lane_configs),
 		}
 		.await
 	}
@@ -164,6 +398,14 @@ impl RelayMessagesRange {
 				AssetHubRococoToAssetHubWestendMessagesCliBridge::relay_messages_range(self.params),
 			FullBridge::AssetHubWestendToBridgeHubRococo =>
 				AssetHubWestendToAssetHubRococoMessagesCliBridge::relay_messages_range(self.params),
+			FullBridge::AssetHubKusamaToAssetHubPolkadot =>
+				AssetHubKusamaToAssetHubPolkadotMessagesCliBridge::relay_messages_range(self.params),
+			FullBridge::AssetHubPolkadotToAssetHubKusama =>
+				AssetHubPolkadotToAssetHubKusamaMessagesCliBridge::relay_messages_range(self.params),
+			FullBridge::MoonriverToMoonbeam =>
+				MoonriverToMoonbeamMessagesCliBridge::relay_messages_range(self.params),
+			FullBridge::MoonbeamToMoonriver =>
+				MoonbeamToMoonriverMessagesCliBridge::relay_messages_range(self.params),
 		}
 		.await
 	}
@@ -205,6 +447,14 @@ impl RelayMessagesDeliveryConfirmation {
 				AssetHubRococoToAssetHubWestendMessagesCliBridge::relay_messages_delivery_confirmation(self.params),
 			FullBridge::AssetHubWestendToBridgeHubRococo =>
 				AssetHubWestendToAssetHubRococoMessagesCliBridge::relay_messages_delivery_confirmation(self.params),
+			FullBridge::AssetHubKusamaToAssetHubPolkadot =>
+				AssetHubKusamaToAssetHubPolkadotMessagesCliBridge::relay_messages_delivery_confirmation(self.params),
+			FullBridge::AssetHubPolkadotToAssetHubKusama =>
+				AssetHubPolkadotToAssetHubKusamaMessagesCliBridge::relay_messages_delivery_confirmation(self.params),
+			FullBridge::MoonriverToMoonbeam =>
+				MoonriverToMoonbeamMessagesCliBridge::relay_messages_delivery_confirmation(self.params),
+			FullBridge::MoonbeamToMoonriver =>
+				MoonbeamToMoonriverMessagesCliBridge::relay_messages_delivery_confirmation(self.params),
 		}
 		.await
 	}
@@ -14,11 +14,22 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
+use super::relay_parachains_by_id::{
+	BridgedParachain, ParachainsByIdRelayer, RelayParachainsByIdParams,
+};
 use crate::bridges::{
 	kusama_polkadot::{
+		asset_hub_kusama_parachains_to_bridge_hub_polkadot::AssetHubKusamaToBridgeHubPolkadotParachainsCliBridge,
+		asset_hub_polkadot_parachains_to_bridge_hub_kusama::AssetHubPolkadotToBridgeHubKusamaParachainsCliBridge,
 		bridge_hub_kusama_parachains_to_bridge_hub_polkadot::BridgeHubKusamaToBridgeHubPolkadotCliBridge,
 		bridge_hub_polkadot_parachains_to_bridge_hub_kusama::BridgeHubPolkadotToBridgeHubKusamaCliBridge,
 	},
+	moonriver_moonbeam::{
+		moonbeam_parachains_to_bridge_hub_kusama::MoonbeamToBridgeHubKusamaParachainsCliBridge,
+		moonbeam_parachains_to_moonriver::MoonbeamToMoonriverCliBridge,
+		moonriver_parachains_to_bridge_hub_polkadot::MoonriverToBridgeHubPolkadotParachainsCliBridge,
+		moonriver_parachains_to_moonbeam::MoonriverToMoonbeamCliBridge,
+	},
 	polkadot_bulletin::polkadot_parachains_to_polkadot_bulletin::PolkadotToPolkadotBulletinCliBridge,
 	rococo_bulletin::rococo_parachains_to_rococo_bulletin::RococoToRococoBulletinCliBridge,
 	rococo_westend::{
@@ -28,7 +39,8 @@ use crate::bridges::{
 		bridge_hub_westend_parachains_to_bridge_hub_rococo::BridgeHubWestendToBridgeHubRococoCliBridge,
 	},
 };
-use clap::{Parser, ValueEnum};
+use bp_polkadot_core::parachains::ParaId;
+use clap::{ArgMatches, Args, FromArgMatches, Parser, ValueEnum};
 use strum::{EnumString, VariantNames};
 use substrate_relay_helper::cli::relay_parachains::{
 	ParachainsRelayer, RelayParachainHeadParams, RelayParachainsParams,
@@ -40,8 +52,58 @@ pub struct RelayParachains {
 	/// A bridge instance to relay parachains heads for.
 	#[arg(value_enum, ignore_case = true)]
 	bridge: RelayParachainsBridge,
+	/// Relay heads of parachains with given ids. Heads of the bridged parachain are not relayed
+	/// then, unless `--combine-with` is given. Heads are read at the best source relay chain
+	/// block, known to the target chain, and submitted to the bridge parachains pallet. May be
+	/// repeated - changed heads of all parachains are then submitted in a single transaction, if
+	/// it fits into the maximal extrinsic size. The pallet must have imported any head of every
+	/// given parachain before.
+	#[arg(long, conflicts_with = "only_free_headers")]
+	para_id: Vec<u32>,
+	/// Relay heads of the bridged parachain along with heads of parachains of other bridges
+	/// with the same source relay chain and target chain. Changed heads of all parachains are
+	/// submitted in a single transaction, if it fits into the maximal extrinsic size.
+	#[arg(long, value_enum, ignore_case = true, conflicts_with = "only_free_headers")]
+	combine_with: Vec<RelayParachainsBridge>,
 	#[command(flatten)]
-	params: RelayParachainsParams,
+	params: RelayParachainsCommandParams,
+}
+
+/// Parachain heads relay parameters.
+///
+/// Heads of the bridged parachain are relayed by the bridge pipeline. Heads of several
+/// parachains (`--para-id` or `--combine-with`) are relayed by the relay of parachains with
+/// given ids, which accepts a subset of the bridge pipeline arguments.
+enum RelayParachainsCommandParams {
+	/// Parameters of the bridge pipeline relay.
+	Pipeline(RelayParachainsParams),
+	/// Parameters of the relay of parachains with given ids.
+	ById(RelayParachainsByIdParams),
+}
+
+impl FromArgMatches for RelayParachainsCommandParams {
+	fn from_arg_matches(matches: &ArgMatches) -> Result<Self, clap::Error> {
+		if matches.contains_id("para_id") || matches.contains_id("combine_with") {
+			RelayParachainsByIdParams::from_arg_matches(matches).map(Self::ById)
+		} else {
+			RelayParachainsParams::from_arg_matches(matches).map(Self::Pipeline)
+		}
+	}
+
+	fn update_from_arg_matches(&mut self, matches: &ArgMatches) -> Result<(), clap::Error> {
+		*self = Self::from_arg_matches(matches)?;
+		Ok(())
+	}
+}
+
+impl Args for RelayParachainsCommandParams {
+	fn augment_args(cmd: clap::Command) -> clap::Command {
+		RelayParachainsParams::augment_args(cmd)
+	}
+
+	fn augment_args_for_update(cmd: clap::Command) -> clap::Command {
+		RelayParachainsParams::augment_args_for_update(cmd)
+	}
 }
 
 /// Relay single parachain head.
@@ -60,6 +122,12 @@ pub struct RelayParachainHead {
 pub enum RelayParachainsBridge {
 	BridgeHubKusamaToBridgeHubPolkadot,
 	BridgeHubPolkadotToBridgeHubKusama,
+	AssetHubKusamaToBridgeHubPolkadot,
+	AssetHubPolkadotToBridgeHubKusama,
+	MoonriverToBridgeHubPolkadot,
+	MoonbeamToBridgeHubKusama,
+	MoonriverToMoonbeam,
+	MoonbeamToMoonriver,
 	PolkadotToPolkadotBulletin,
 	RococoToRococoBulletin,
 	BridgeHubRococoToBridgeHubWestend,
@@ -74,34 +142,198 @@ impl ParachainsRelayer for AssetHubRococoToBridgeHubWestendParachainsCliBridge {
 impl ParachainsRelayer for AssetHubWestendToBridgeHubRococoParachainsCliBridge {}
 impl ParachainsRelayer for BridgeHubKusamaToBridgeHubPolkadotCliBridge {}
 impl ParachainsRelayer for BridgeHubPolkadotToBridgeHubKusamaCliBridge {}
+impl ParachainsRelayer for AssetHubKusamaToBridgeHubPolkadotParachainsCliBridge {}
+impl ParachainsRelayer for AssetHubPolkadotToBridgeHubKusamaParachainsCliBridge {}
+impl ParachainsRelayer for MoonriverToBridgeHubPolkadotParachainsCliBridge {}
+impl ParachainsRelayer for MoonbeamToBridgeHubKusamaParachainsCliBridge {}
+impl ParachainsRelayer for MoonriverToMoonbeamCliBridge {}
+impl ParachainsRelayer for MoonbeamToMoonriverCliBridge {}
 impl ParachainsRelayer for PolkadotToPolkadotBulletinCliBridge {}
 impl ParachainsRelayer for RococoToRococoBulletinCliBridge {}
 
+impl ParachainsByIdRelayer for BridgeHubRococoToBridgeHubWestendCliBridge {}
+impl ParachainsByIdRelayer for BridgeHubWestendToBridgeHubRococoCliBridge {}
+impl ParachainsByIdRelayer for AssetHubRococoToBridgeHubWestendParachainsCliBridge {}
+impl ParachainsByIdRelayer for AssetHubWestendToBridgeHubRococoParachainsCliBridge {}
+impl ParachainsByIdRelayer for BridgeHubKusamaToBridgeHubPolkadotCliBridge {}
+impl ParachainsByIdRelayer for BridgeHubPolkadotToBridgeHubKusamaCliBridge {}
+impl ParachainsByIdRelayer for AssetHubKusamaToBridgeHubPolkadotParachainsCliBridge {}
+impl ParachainsByIdRelayer for AssetHubPolkadotToBridgeHubKusamaParachainsCliBridge {}
+impl ParachainsByIdRelayer for MoonriverToBridgeHubPolkadotParachainsCliBridge {}
+impl ParachainsByIdRelayer for MoonbeamToBridgeHubKusamaParachainsCliBridge {}
+impl ParachainsByIdRelayer for MoonriverToMoonbeamCliBridge {}
+impl ParachainsByIdRelayer for MoonbeamToMoonriverCliBridge {}
+impl ParachainsByIdRelayer for PolkadotToPolkadotBulletinCliBridge {}
+impl ParachainsByIdRelayer for RococoToRococoBulletinCliBridge {}
+
 impl RelayParachains {
 	/// Run the command.
 	pub async fn run(self) -> anyhow::Result<()> {
+		let params = match self.params {
+			RelayParachainsCommandParams::Pipeline(params) => params,
+			RelayParachainsCommandParams::ById(params) => {
+				let para_ids = combined_para_ids(self.bridge, &self.combine_with, &self.para_id)?;
+				return run_by_id(self.bridge, params, para_ids).await
+			},
+		};
 		match self.bridge {
 			RelayParachainsBridge::BridgeHubRococoToBridgeHubWestend =>
-				BridgeHubRococoToBridgeHubWestendCliBridge::relay_parachains(self.params),
+				BridgeHubRococoToBridgeHubWestendCliBridge::relay_parachains(params),
 			RelayParachainsBridge::BridgeHubWestendToBridgeHubRococo =>
-				BridgeHubWestendToBridgeHubRococoCliBridge::relay_parachains(self.params),
+				BridgeHubWestendToBridgeHubRococoCliBridge::relay_parachains(params),
 			RelayParachainsBridge::AssetHubRococoToBridgeHubWestend =>
-				AssetHubRococoToBridgeHubWestendParachainsCliBridge::relay_parachains(self.params),
+				AssetHubRococoToBridgeHubWestendParachainsCliBridge::relay_parachains(params),
 			RelayParachainsBridge::AssetHubWestendToBridgeHubRococo =>
-				AssetHubWestendToBridgeHubRococoParachainsCliBridge::relay_parachains(self.params),
+				AssetHubWestendToBridgeHubRococoParachainsCliBridge::relay_parachains(params),
 			RelayParachainsBridge::BridgeHubKusamaToBridgeHubPolkadot =>
-				BridgeHubKusamaToBridgeHubPolkadotCliBridge::relay_parachains(self.params),
+				BridgeHubKusamaToBridgeHubPolkadotCliBridge::relay_parachains(params),
 			RelayParachainsBridge::BridgeHubPolkadotToBridgeHubKusama =>
-				BridgeHubPolkadotToBridgeHubKusamaCliBridge::relay_parachains(self.params),
+				BridgeHubPolkadotToBridgeHubKusamaCliBridge::relay_parachains(params),
+			RelayParachainsBridge::AssetHubKusamaToBridgeHubPolkadot =>
+				AssetHubKusamaToBridgeHubPolkadotParachainsCliBridge::relay_parachains(params),
+			RelayParachainsBridge::AssetHubPolkadotToBridgeHubKusama =>
+				AssetHubPolkadotToBridgeHubKusamaParachainsCliBridge::relay_parachains(params),
+			RelayParachainsBridge::MoonriverToBridgeHubPolkadot =>
+				MoonriverToBridgeHubPolkadotParachainsCliBridge::relay_parachains(params),
+			RelayParachainsBridge::MoonbeamToBridgeHubKusama =>
+				MoonbeamToBridgeHubKusamaParachainsCliBridge::relay_parachains(params),
+			RelayParachainsBridge::MoonriverToMoonbeam =>
+				MoonriverToMoonbeamCliBridge::relay_parachains(params),
+			RelayParachainsBridge::MoonbeamToMoonriver =>
+				MoonbeamToMoonriverCliBridge::relay_parachains(params),
 			RelayParachainsBridge::PolkadotToPolkadotBulletin =>
-				PolkadotToPolkadotBulletinCliBridge::relay_parachains(self.params),
+				PolkadotToPolkadotBulletinCliBridge::relay_parachains(params),
 			RelayParachainsBridge::RococoToRococoBulletin =>
-				RococoToRococoBulletinCliBridge::relay_parachains(self.params),
+				RococoToRococoBulletinCliBridge::relay_parachains(params),
 		}
 		.await
 	}
 }
 
+/// Relay heads of parachains with given ids, using connections and the call of given bridge.
+async fn run_by_id(
+	bridge: RelayParachainsBridge,
+	params: RelayParachainsByIdParams,
+	para_ids: Vec<ParaId>,
+) -> anyhow::Result<()> {
+	match bridge {
+		RelayParachainsBridge::BridgeHubRococoToBridgeHubWestend =>
+			BridgeHubRococoToBridgeHubWestendCliBridge::relay_parachains_by_id(params, para_ids),
+		RelayParachainsBridge::BridgeHubWestendToBridgeHubRococo =>
+			BridgeHubWestendToBridgeHubRococoCliBridge::relay_parachains_by_id(params, para_ids),
+		RelayParachainsBridge::AssetHubRococoToBridgeHubWestend =>
+			AssetHubRococoToBridgeHubWestendParachainsCliBridge::relay_parachains_by_id(
+				params, para_ids,
+			),
+		RelayParachainsBridge::AssetHubWestendToBridgeHubRococo =>
+			AssetHubWestendToBridgeHubRococoParachainsCliBridge::relay_parachains_by_id(
+				params, para_ids,
+			),
+		RelayParachainsBridge::BridgeHubKusamaToBridgeHubPolkadot =>
+			BridgeHubKusamaToBridgeHubPolkadotCliBridge::relay_parachains_by_id(params, para_ids),
+		RelayParachainsBridge::BridgeHubPolkadotToBridgeHubKusama =>
+			BridgeHubPolkadotToBridgeHubKusamaCliBridge::relay_parachains_by_id(params, para_ids),
+		RelayParachainsBridge::AssetHubKusamaToBridgeHubPolkadot =>
+			AssetHubKusamaToBridgeHubPolkadotParachainsCliBridge::relay_parachains_by_id(
+				params, para_ids,
+			),
+		RelayParachainsBridge::AssetHubPolkadotToBridgeHubKusama =>
+			AssetHubPolkadotToBridgeHubKusamaParachainsCliBridge::relay_parachains_by_id(
+				params, para_ids,
+			),
+		RelayParachainsBridge::MoonriverToBridgeHubPolkadot =>
+			MoonriverToBridgeHubPolkadotParachainsCliBridge::relay_parachains_by_id(
+				params, para_ids,
+			),
+		RelayParachainsBridge::MoonbeamToBridgeHubKusama =>
+			MoonbeamToBridgeHubKusamaParachainsCliBridge::relay_parachains_by_id(params, para_ids),
+		RelayParachainsBridge::MoonriverToMoonbeam =>
+			MoonriverToMoonbeamCliBridge::relay_parachains_by_id(params, para_ids),
+		RelayParachainsBridge::MoonbeamToMoonriver =>
+			MoonbeamToMoonriverCliBridge::relay_parachains_by_id(params, para_ids),
+		RelayParachainsBridge::PolkadotToPolkadotBulletin =>
+			PolkadotToPolkadotBulletinCliBridge::relay_parachains_by_id(params, para_ids),
+		RelayParachainsBridge::RococoToRococoBulletin =>
+			RococoToRococoBulletinCliBridge::relay_parachains_by_id(params, para_ids),
+	}
+	.await
+}
+
+/// Returns ids of parachains, which heads are relayed in combined transactions.
+///
+/// With `--combine-with`, heads of the bridged parachain and parachains of other given bridges
+/// are relayed. Heads of parachains, given by `--para-id`, are relayed as well.
+fn combined_para_ids(
+	bridge: RelayParachainsBridge,
+	combine_with: &[RelayParachainsBridge],
+	para_ids: &[u32],
+) -> anyhow::Result<Vec<ParaId>> {
+	let bridged_parachain = bridge.bridged_parachain();
+	let mut combined_para_ids = Vec::new();
+	if !combine_with.is_empty() {
+		combined_para_ids.push(bridged_parachain.para_id);
+	}
+	for other_bridge in combine_with {
+		let other_parachain = other_bridge.bridged_parachain();
+		if (other_parachain.source_relay, other_parachain.target) !=
+			(bridged_parachain.source_relay, bridged_parachain.target)
+		{
+			anyhow::bail!(
+				"Unable to combine {bridge:?} with {other_bridge:?}: the bridge relays heads of {} \
+				parachains to {}",
+				other_parachain.source_relay,
+				other_parachain.target,
+			);
+		}
+		combined_para_ids.push(other_parachain.para_id);
+	}
+	combined_para_ids.extend(para_ids.iter().copied().map(ParaId));
+
+	let mut unique_para_ids = Vec::with_capacity(combined_para_ids.len());
+	for para_id in combined_para_ids {
+		if !unique_para_ids.contains(&para_id) {
+			unique_para_ids.push(para_id);
+		}
+	}
+	Ok(unique_para_ids)
+}
+
+impl RelayParachainsBridge {
+	/// Returns parachain, which heads are relayed by the bridge pipeline.
+	fn bridged_parachain(self) -> BridgedParachain {
+		match self {
+			RelayParachainsBridge::BridgeHubRococoToBridgeHubWestend =>
+				BridgeHubRococoToBridgeHubWestendCliBridge::bridged_parachain(),
+			RelayParachainsBridge::BridgeHubWestendToBridgeHubRococo =>
+				BridgeHubWestendToBridgeHubRococoCliBridge::bridged_parachain(),
+			RelayParachainsBridge::AssetHubRococoToBridgeHubWestend =>
+				AssetHubRococoToBridgeHubWestendParachainsCliBridge::bridged_parachain(),
+			RelayParachainsBridge::AssetHubWestendToBridgeHubRococo =>
+				AssetHubWestendToBridgeHubRococoParachainsCliBridge::bridged_parachain(),
+			RelayParachainsBridge::BridgeHubKusamaToBridgeHubPolkadot =>
+				BridgeHubKusamaToBridgeHubPolkadotCliBridge::bridged_parachain(),
+			RelayParachainsBridge::BridgeHubPolkadotToBridgeHubKusama =>
+				BridgeHubPolkadotToBridgeHubKusamaCliBridge::bridged_parachain(),
+			RelayParachainsBridge::AssetHubKusamaToBridgeHubPolkadot =>
+				AssetHubKusamaToBridgeHubPolkadotParachainsCliBridge::bridged_parachain(),
+			RelayParachainsBridge::AssetHubPolkadotToBridgeHubKusama =>
+				AssetHubPolkadotToBridgeHubKusamaParachainsCliBridge::bridged_parachain(),
+			RelayParachainsBridge::MoonriverToBridgeHubPolkadot =>
+				MoonriverToBridgeHubPolkadotParachainsCliBridge::bridged_parachain(),
+			RelayParachainsBridge::MoonbeamToBridgeHubKusama =>
+				MoonbeamToBridgeHubKusamaParachainsCliBridge::bridged_parachain(),
+			RelayParachainsBridge::MoonriverToMoonbeam =>
+				MoonriverToMoonbeamCliBridge::bridged_parachain(),
+			RelayParachainsBridge::MoonbeamToMoonriver =>
+				MoonbeamToMoonriverCliBridge::bridged_parachain(),
+			RelayParachainsBridge::PolkadotToPolkadotBulletin =>
+				PolkadotToPolkadotBulletinCliBridge::bridged_parachain(),
+			RelayParachainsBridge::RococoToRococoBulletin =>
+				RococoToRococoBulletinCliBridge::bridged_parachain(),
+		}
+	}
+}
+
 impl RelayParachainHead {
 	/// Run the command.
 	pub async fn run(self) -> anyhow::Result<()> {
@@ -122,6 +354,22 @@ impl RelayParachainHead {
 				BridgeHubKusamaToBridgeHubPolkadotCliBridge::relay_parachain_head(self.params),
 			RelayParachainsBridge::BridgeHubPolkadotToBridgeHubKusama =>
 				BridgeHubPolkadotToBridgeHubKusamaCliBridge::relay_parachain_head(self.params),
+			RelayParachainsBridge::AssetHubKusamaToBridgeHubPolkadot =>
+				AssetHubKusamaToBridgeHubPolkadotParachainsCliBridge::relay_parachain_head(
+					self.params,
+				),
+			RelayParachainsBridge::AssetHubPolkadotToBridgeHubKusama =>
+				AssetHubPolkadotToBridgeHubKusamaParachainsCliBridge::relay_parachain_head(
+					self.params,
+				),
+			RelayParachainsBridge::MoonriverToBridgeHubPolkadot =>
+				MoonriverToBridgeHubPolkadotParachainsCliBridge::relay_parachain_head(self.params),
+			RelayParachainsBridge::MoonbeamToBridgeHubKusama =>
+				MoonbeamToBridgeHubKusamaParachainsCliBridge::relay_parachain_head(self.params),
+			RelayParachainsBridge::MoonriverToMoonbeam =>
+				MoonriverToMoonbeamCliBridge::relay_parachain_head(self.params),
+			RelayParachainsBridge::MoonbeamToMoonriver =>
+				MoonbeamToMoonriverCliBridge::relay_parachain_head(self.params),
 			RelayParachainsBridge::PolkadotToPolkadotBulletin =>
 				PolkadotToPolkadotBulletinCliBridge::relay_parachain_head(self.params),
 			RelayParachainsBridge::RococoToRococoBulletin =>
@@ -130,3 +378,91 @@ impl RelayParachainHead {
 		.await
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn relay_parachains(args: &[&str]) -> RelayParachains {
+		try_relay_parachains(args).unwrap()
+	}
+
+	fn try_relay_parachains(args: &[&str]) -> Result<RelayParachains, clap::Error> {
+		RelayParachains::try_parse_from(
+			[
+				"relay-parachains",
+				"bridge-hub-kusama-to-bridge-hub-polkadot",
+				"--source-uri",
+				"ws://kusama:9944",
+				"--target-uri",
+				"ws://bridge-hub-polkadot:9944",
+				"--target-signer",
+				"//Alice",
+			]
+			.iter()
+			.chain(args),
+		)
+	}
+
+	#[test]
+	fn selects_relay_by_arguments() {
+		assert!(matches!(relay_parachains(&[]).params, RelayParachainsCommandParams::Pipeline(_)));
+		assert!(matches!(
+			relay_parachains(&["--only-free-headers"]).params,
+			RelayParachainsCommandParams::Pipeline(_)
+		));
+		assert!(matches!(
+			relay_parachains(&["--para-id", "1004"]).params,
+			RelayParachainsCommandParams::ById(_)
+		));
+
+		let command = relay_parachains(&[
+			"--combine-with",
+			"asset-hub-kusama-to-bridge-hub-polkadot",
+			"--prometheus-port",
+			"9616",
+		]);
+		assert!(matches!(command.params, RelayParachainsCommandParams::ById(_)));
+		assert_eq!(command.combine_with.len(), 1);
+
+		// heads of several parachains are not relayed at free relay chain headers
+		assert!(try_relay_parachains(&["--para-id", "1004", "--only-free-headers"]).is_err());
+		assert!(try_relay_parachains(&[
+			"--combine-with",
+			"asset-hub-kusama-to-bridge-hub-polkadot",
+			"--only-free-headers",
+		])
+		.is_err());
+	}
+
+	#[test]
+	fn combines_parachains_of_bridges_with_same_chains() {
+		// BridgeHub and AssetHub Kusama parachains, then parachains with given ids
+		assert_eq!(
+			combined_para_ids(
+				RelayParachainsBridge::BridgeHubKusamaToBridgeHubPolkadot,
+				&[RelayParachainsBridge::AssetHubKusamaToBridgeHubPolkadot],
+				&[1000, 1004],
+			)
+			.unwrap(),
+			vec![ParaId(1002), ParaId(1000), ParaId(1004)],
+		);
+		// bridged parachain is only relayed when combined with other bridges
+		assert_eq!(
+			combined_para_ids(
+				RelayParachainsBridge::BridgeHubKusamaToBridgeHubPolkadot,
+				&[],
+				&[1004]
+			)
+			.unwrap(),
+			vec![ParaId(1004)],
+		);
+		// heads of Polkadot parachains can't be relayed with heads of Kusama parachains
+		assert!(combined_para_ids(
+			RelayParachainsBridge::BridgeHubKusamaToBridgeHubPolkadot,
+			&[RelayParachainsBridge::MoonbeamToBridgeHubKusama],
+			&[],
+		)
+		.is_err());
+	}
+}
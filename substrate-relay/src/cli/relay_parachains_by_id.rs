@@ -0,0 +1,441 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Relay of heads of parachains, selected by their ids.
+//!
+//! Every parachain bridge has a compile-time pipeline for a single parachain. But the call that
+//! submits parachain heads to the target chain accepts heads of any parachain, so the bridge
+//! relay chain finality and call builder may be reused to relay heads of other parachains,
+//! tracked by the bridge parachains pallet at the target chain.
+
+use super::LOG_TARGET;
+
+use async_trait::async_trait;
+use bp_parachains::{
+	parachain_head_storage_key_at_source, BestParaHeadHash, ParaInfo, ParasInfoKeyProvider,
+};
+use bp_polkadot_core::parachains::{ParaHash, ParaHead, ParaHeadsProof, ParaId};
+use bp_runtime::{Chain as _, Parachain, StorageMapKeyProvider, TransactionEra};
+use clap::Parser;
+use codec::Encode;
+use relay_substrate_client::{
+	AccountIdOf, AccountKeyPairOf, CallOf, Chain, Client, HashOf, HeaderIdOf, RelayChain,
+	UnsignedTransaction,
+};
+use relay_utils::{TrackedTransactionStatus, TransactionTracker};
+use sp_core::{storage::StorageKey, Pair};
+use std::future::Future;
+use substrate_relay_helper::{
+	cli::{
+		bridge::{CliBridgeBase, ParachainToRelayHeadersCliBridge},
+		chain_schema::{SourceConnectionParams, TargetConnectionParams, TargetSigningParams},
+		PrometheusParams,
+	},
+	finality::SubstrateFinalitySyncPipeline,
+	finality_base::best_synced_header_id,
+	parachains::{SubmitParachainHeadsCallBuilder, SubstrateParachainsPipeline},
+};
+
+/// Builder of the `submit_parachain_heads` call of the bridge.
+type CallBuilderOf<Bridge> = <<Bridge as ParachainToRelayHeadersCliBridge>::ParachainFinality as SubstrateParachainsPipeline>::SubmitParachainHeadsCallBuilder;
+
+/// Parameters of the relay of parachains with given ids.
+#[derive(Parser)]
+pub struct RelayParachainsByIdParams {
+	#[command(flatten)]
+	source: SourceConnectionParams,
+	#[command(flatten)]
+	target: TargetConnectionParams,
+	#[command(flatten)]
+	target_sign: TargetSigningParams,
+	#[command(flatten)]
+	prometheus_params: PrometheusParams,
+}
+
+/// Parachain, which heads are relayed by the bridge pipeline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BridgedParachain {
+	/// Name of the source relay chain.
+	pub source_relay: &'static str,
+	/// Name of the target chain.
+	pub target: &'static str,
+	/// Parachain id.
+	pub para_id: ParaId,
+}
+
+/// Parachain head that has been changed since it has been relayed to the target chain.
+struct ChangedParaHead {
+	/// Parachain id.
+	para_id: ParaId,
+	/// Hash of the parachain head at the source relay chain.
+	head_hash: ParaHash,
+	/// Key of the parachain head storage item at the source relay chain.
+	storage_key: StorageKey,
+}
+
+/// Bridge, which may be used to relay heads of any parachain of its source relay chain.
+#[async_trait]
+pub trait ParachainsByIdRelayer: ParachainToRelayHeadersCliBridge
+where
+	<Self as CliBridgeBase>::Source: Parachain,
+{
+	/// Returns parachain, which heads are relayed by the bridge pipeline.
+	fn bridged_parachain() -> BridgedParachain {
+		BridgedParachain {
+			source_relay: Self::SourceRelay::NAME,
+			target: Self::Target::NAME,
+			para_id: ParaId(<<Self as CliBridgeBase>::Source as Parachain>::PARACHAIN_ID),
+		}
+	}
+
+	/// Start relaying heads of parachains with given ids.
+	///
+	/// Heads are read at the best relay chain block, known to the target chain. Relay chain
+	/// headers must be relayed by a separate relay.
+	async fn relay_parachains_by_id(
+		params: RelayParachainsByIdParams,
+		para_ids: Vec<ParaId>,
+	) -> anyhow::Result<()>
+	where
+		AccountIdOf<Self::Target>: From<<AccountKeyPairOf<Self::Target> as Pair>::Public>,
+	{
+		let relay_client = params.source.into_client::<Self::SourceRelay>().await?;
+		let target_client = params.target.into_client::<Self::Target>().await?;
+		let target_transactions_mortality = params.target_sign.transactions_mortality()?;
+		let target_sign = params.target_sign.to_keypair::<Self::Target>()?;
+		Self::RelayFinality::start_relay_guards(
+			&target_client,
+			target_client.can_start_version_guard(),
+		)
+		.await?;
+		Self::ensure_parachains_tracked(&target_client, &para_ids).await?;
+		relay_utils::relay_metrics(params.prometheus_params.into_metrics_params()?)
+			.expose()
+			.await
+			.map_err(|e| anyhow::format_err!("{}", e))?;
+
+		log::info!(
+			target: LOG_TARGET,
+			"Relaying heads of {} parachains {:?} to {}",
+			Self::SourceRelay::NAME,
+			para_ids.iter().map(|para_id| para_id.0).collect::<Vec<_>>(),
+			Self::Target::NAME,
+		);
+		loop {
+			let result = Self::relay_changed_heads(
+				&relay_client,
+				&target_client,
+				&target_sign,
+				target_transactions_mortality,
+				&para_ids,
+			)
+			.await;
+			if let Err(e) = result {
+				log::error!(
+					target: LOG_TARGET,
+					"Failed to relay heads of {} parachains to {}: {e:?}",
+					Self::SourceRelay::NAME,
+					Self::Target::NAME,
+				);
+			}
+
+			async_std::task::sleep(Self::Target::AVERAGE_BLOCK_INTERVAL).await;
+		}
+	}
+
+	/// Ensure that the bridge parachains pallet at the target chain tracks parachains with given
+	/// ids.
+	///
+	/// The pallet silently ignores heads of parachains that it doesn't track and there's no
+	/// storage item that lists tracked parachains. So the parachain is considered tracked if the
+	/// pallet has already imported any of its heads.
+	async fn ensure_parachains_tracked(
+		target_client: &impl Client<Self::Target>,
+		para_ids: &[ParaId],
+	) -> anyhow::Result<()> {
+		let best_target_hash = target_client.best_header_hash().await?;
+		let mut untracked_para_ids = Vec::new();
+		for para_id in para_ids {
+			if Self::best_head_at_target(target_client, best_target_hash, *para_id)
+				.await?
+				.is_none()
+			{
+				untracked_para_ids.push(para_id.0);
+			}
+		}
+
+		if !untracked_para_ids.is_empty() {
+			anyhow::bail!(
+				"{} parachains {untracked_para_ids:?} are not tracked by the bridge parachains \
+				pallet at {}",
+				Self::SourceRelay::NAME,
+				Self::Target::NAME,
+			);
+		}
+
+		Ok(())
+	}
+
+	/// Relay heads of parachains with given ids, that have been updated at the best relay chain
+	/// block, known to the target chain.
+	///
+	/// All changed heads are submitted in a single call with the combined proof. If the call
+	/// exceeds the maximal extrinsic size, heads are submitted separately.
+	async fn relay_changed_heads(
+		relay_client: &impl Client<Self::SourceRelay>,
+		target_client: &impl Client<Self::Target>,
+		target_sign: &AccountKeyPairOf<Self::Target>,
+		target_transactions_mortality: Option<u32>,
+		para_ids: &[ParaId],
+	) -> anyhow::Result<()>
+	where
+		AccountIdOf<Self::Target>: From<<AccountKeyPairOf<Self::Target> as Pair>::Public>,
+	{
+		let best_target_hash = target_client.best_header_hash().await?;
+		let relay_block = best_synced_header_id::<Self::SourceRelay, Self::Target>(
+			target_client,
+			best_target_hash,
+		)
+		.await?
+		.ok_or_else(|| {
+			anyhow::format_err!(
+				"{} headers bridge at {} is not initialized",
+				Self::SourceRelay::NAME,
+				Self::Target::NAME,
+			)
+		})?;
+
+		let mut changed_heads = Vec::with_capacity(para_ids.len());
+		for para_id in para_ids {
+			if let Some(changed_head) = Self::changed_head(
+				relay_client,
+				target_client,
+				relay_block,
+				best_target_hash,
+				*para_id,
+			)
+			.await?
+			{
+				changed_heads.push(changed_head);
+			}
+		}
+
+		let calls = submission_calls(
+			&changed_heads,
+			Self::Target::max_extrinsic_size() as usize,
+			|heads| Self::submit_heads_call(relay_client, relay_block, heads),
+		)
+		.await?;
+		for (call, heads) in calls {
+			Self::submit_heads(
+				target_client,
+				target_sign,
+				target_transactions_mortality,
+				call,
+				heads,
+			)
+			.await?;
+		}
+
+		Ok(())
+	}
+
+	/// Returns head of the parachain with given id if it has been updated at given relay chain
+	/// block since it has been relayed to the target chain.
+	async fn changed_head(
+		relay_client: &impl Client<Self::SourceRelay>,
+		target_client: &impl Client<Self::Target>,
+		relay_block: HeaderIdOf<Self::SourceRelay>,
+		best_target_hash: HashOf<Self::Target>,
+		para_id: ParaId,
+	) -> anyhow::Result<Option<ChangedParaHead>> {
+		let storage_key =
+			parachain_head_storage_key_at_source(Self::SourceRelay::PARAS_PALLET_NAME, para_id);
+		let head: Option<ParaHead> =
+			relay_client.storage_value(relay_block.hash(), storage_key.clone()).await?;
+		let Some(head) = head else {
+			log::warn!(
+				target: LOG_TARGET,
+				"Parachain {} is not registered at {} block {}",
+				para_id.0,
+				Self::SourceRelay::NAME,
+				relay_block.number(),
+			);
+			return Ok(None)
+		};
+
+		let head_hash = head.hash();
+		match Self::best_head_at_target(target_client, best_target_hash, para_id).await? {
+			Some(best_head)
+				if best_head.head_hash == head_hash ||
+					best_head.at_relay_block_number >= relay_block.number() =>
+				Ok(None),
+			_ => Ok(Some(ChangedParaHead { para_id, head_hash, storage_key })),
+		}
+	}
+
+	/// Build the call to submit given parachain heads with the proof at given relay chain block.
+	async fn submit_heads_call(
+		relay_client: &impl Client<Self::SourceRelay>,
+		relay_block: HeaderIdOf<Self::SourceRelay>,
+		heads: &[ChangedParaHead],
+	) -> anyhow::Result<CallOf<Self::Target>> {
+		let storage_keys = heads.iter().map(|head| head.storage_key.clone()).collect();
+		let storage_proof = relay_client.prove_storage(relay_block.hash(), storage_keys).await?;
+		Ok(CallBuilderOf::<Self>::build_submit_parachain_heads_call(
+			relay_block,
+			heads.iter().map(|head| (head.para_id, head.head_hash)).collect(),
+			ParaHeadsProof { storage_proof: storage_proof.into_iter_nodes().collect() },
+			false,
+		))
+	}
+
+	/// Submit the call with given parachain heads and wait until it is finalized.
+	async fn submit_heads(
+		target_client: &impl Client<Self::Target>,
+		target_sign: &AccountKeyPairOf<Self::Target>,
+		target_transactions_mortality: Option<u32>,
+		call: CallOf<Self::Target>,
+		heads: &[ChangedParaHead],
+	) -> anyhow::Result<()>
+	where
+		AccountIdOf<Self::Target>: From<<AccountKeyPairOf<Self::Target> as Pair>::Public>,
+	{
+		let para_ids = heads.iter().map(|head| head.para_id.0).collect::<Vec<_>>();
+		log::info!(
+			target: LOG_TARGET,
+			"Relaying heads of {} parachains {para_ids:?} to {}",
+			Self::SourceRelay::NAME,
+			Self::Target::NAME,
+		);
+		let tracker = target_client
+			.submit_and_watch_signed_extrinsic(target_sign, move |best_block_id, nonce| {
+				Ok(UnsignedTransaction::new(call.into(), nonce)
+					.era(TransactionEra::new(best_block_id, target_transactions_mortality)))
+			})
+			.await?;
+		match tracker.wait().await {
+			TrackedTransactionStatus::Finalized(_) => (),
+			TrackedTransactionStatus::Lost => anyhow::bail!(
+				"Transaction with heads of {} parachains {para_ids:?} has been lost",
+				Self::SourceRelay::NAME,
+			),
+		}
+
+		// the pallet silently ignores heads that it has failed to import
+		let best_target_hash = target_client.best_header_hash().await?;
+		for head in heads {
+			let best_head =
+				Self::best_head_at_target(target_client, best_target_hash, head.para_id).await?;
+			if best_head.map(|best_head| best_head.head_hash) != Some(head.head_hash) {
+				log::warn!(
+					target: LOG_TARGET,
+					"Head of {} parachain {} has not been imported by {}",
+					Self::SourceRelay::NAME,
+					head.para_id.0,
+					Self::Target::NAME,
+				);
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Returns best head of the parachain, known to the target chain.
+	async fn best_head_at_target(
+		target_client: &impl Client<Self::Target>,
+		at: HashOf<Self::Target>,
+		para_id: ParaId,
+	) -> anyhow::Result<Option<BestParaHeadHash>> {
+		let para_info: Option<ParaInfo> = target_client
+			.storage_value(
+				at,
+				ParasInfoKeyProvider::final_key(
+					Self::SourceRelay::WITH_CHAIN_BRIDGE_PARACHAINS_PALLET_NAME,
+					&para_id,
+				),
+			)
+			.await?;
+		Ok(para_info.map(|para_info| para_info.best_head_hash))
+	}
+}
+
+/// Returns calls to submit given parachain heads, along with heads that every call submits.
+///
+/// All heads are submitted by a single call with the combined proof. If this call exceeds the
+/// maximal extrinsic size, every head is submitted by a separate call.
+async fn submission_calls<'a, Head, Call, Fut>(
+	heads: &'a [Head],
+	max_extrinsic_size: usize,
+	build_call: impl Fn(&'a [Head]) -> Fut,
+) -> anyhow::Result<Vec<(Call, &'a [Head])>>
+where
+	Call: Encode,
+	Fut: Future<Output = anyhow::Result<Call>>,
+{
+	if heads.len() > 1 {
+		let call = build_call(heads).await?;
+		let call_size = call.encoded_size();
+		if call_size <= max_extrinsic_size {
+			return Ok(vec![(call, heads)])
+		}
+
+		log::info!(
+			target: LOG_TARGET,
+			"Call with {} parachain heads has size {call_size}, exceeding maximal extrinsic size \
+			{max_extrinsic_size}. Submitting heads separately",
+			heads.len(),
+		);
+	}
+
+	let mut calls = Vec::with_capacity(heads.len());
+	for head in heads {
+		let head = std::slice::from_ref(head);
+		calls.push((build_call(head).await?, head));
+	}
+	Ok(calls)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Returns heads, submitted by every call, if every head adds 100 bytes to the call.
+	fn submitted_heads(heads: &[u32], max_extrinsic_size: usize) -> Vec<Vec<u32>> {
+		let calls =
+			async_std::task::block_on(submission_calls(heads, max_extrinsic_size, |heads| {
+				let call = vec![0u8; heads.len() * 100];
+				async move { Ok(call) }
+			}))
+			.unwrap();
+		calls.into_iter().map(|(_, heads)| heads.to_vec()).collect()
+	}
+
+	#[test]
+	fn heads_are_submitted_by_single_call_if_it_fits_into_extrinsic() {
+		assert_eq!(submitted_heads(&[1000, 1002], 1_000), vec![vec![1000, 1002]]);
+		assert_eq!(submitted_heads(&[1000], 1_000), vec![vec![1000]]);
+		assert_eq!(submitted_heads(&[], 1_000), Vec::<Vec<u32>>::new());
+	}
+
+	#[test]
+	fn heads_are_submitted_separately_if_single_call_exceeds_max_extrinsic_size() {
+		assert_eq!(
+			submitted_heads(&[1000, 1002, 1004], 250),
+			vec![vec![1000], vec![1002], vec![1004]],
+		);
+	}
+}
@@ -20,6 +20,7 @@
 
 mod bridges;
 mod cli;
+mod rpc_recording;
 
 fn main() {
 	let command = cli::parse_args();
@@ -0,0 +1,449 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Recording and replaying of the RPC traffic between the relay and chain nodes.
+//!
+//! Chain clients are connecting to nodes using `--<chain>-uri` arguments (metadata hashes are
+//! verified using `--check-metadata-hash <chain>=<uri>` nodes). When recording or
+//! replaying is enabled, every such argument is replaced with the URI of the local RPC server,
+//! started by the relay itself:
+//!
+//! - when recording, the server forwards all requests to the original node and writes every
+//!   request, response and subscription notification to the `<dir>/<chain>.jsonl` file;
+//!
+//! - when replaying, the server doesn't connect to any node. Instead, it reads the
+//!   `<dir>/<chain>.jsonl` file and answers requests with recorded responses.
+//!
+//! Nodes, specified using legacy `--<chain>-host`, `--<chain>-port`, `--<chain>-path` and
+//! `--<chain>-secure` arguments, can't be replaced, so these arguments are rejected when
+//! recording or replaying is enabled.
+//!
+//! So the replayed relay makes the same decisions as the recorded one. The relay must be started
+//! with the same command and arguments (node URIs are ignored) when replaying.
+
+use jsonrpsee::{
+	server::{Server, ServerHandle},
+	types::ErrorObjectOwned,
+	RpcModule,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+	ffi::OsString,
+	net::SocketAddr,
+	path::{Path, PathBuf},
+	time::Instant,
+};
+
+mod record;
+mod replay;
+
+/// Log target, used by the RPC recording.
+const LOG_TARGET: &str = "bridge-rpc-recording";
+
+/// Suffix of CLI arguments that contain node URIs.
+const URI_ARG_SUFFIX: &str = "-uri";
+
+/// Suffixes of legacy CLI arguments that contain parts of node addresses.
+const NODE_ADDRESS_ARG_SUFFIXES: &[&str] = &["-host", "-port", "-path", "-secure"];
+
+/// Prefixes of CLI arguments that have node address suffixes, but are not node addresses.
+const NON_NODE_ADDRESS_ARG_PREFIXES: &[&str] = &["prometheus-"];
+
+/// Name of the CLI argument that contains `<chain>=<uri>` of the node, used to verify the metadata
+/// hash of the chain.
+const METADATA_HASH_ARG: &str = "check-metadata-hash";
+
+/// RPC methods that may be called by the relay.
+const METHODS: &[&str] = &[
+	"author_pendingExtrinsics",
+	"author_submitExtrinsic",
+	"chain_getBlock",
+	"chain_getBlockHash",
+	"chain_getFinalizedHead",
+	"chain_getHeader",
+	"chain_getRuntimeVersion",
+	"payment_queryFeeDetails",
+	"payment_queryInfo",
+	"rpc_methods",
+	"state_call",
+	"state_getKeys",
+	"state_getKeysPaged",
+	"state_getMetadata",
+	"state_getReadProof",
+	"state_getRuntimeVersion",
+	"state_getStorage",
+	"state_queryStorageAt",
+	"system_accountNextIndex",
+	"system_chain",
+	"system_health",
+	"system_name",
+	"system_properties",
+	"system_syncState",
+	"system_version",
+];
+
+/// RPC subscriptions that may be used by the relay: subscribe method, notification method and
+/// unsubscribe method.
+const SUBSCRIPTIONS: &[(&str, &str, &str)] = &[
+	("author_submitAndWatchExtrinsic", "author_extrinsicUpdate", "author_unwatchExtrinsic"),
+	("beefy_subscribeJustifications", "beefy_justifications", "beefy_unsubscribeJustifications"),
+	("chain_subscribeFinalizedHeads", "chain_finalizedHead", "chain_unsubscribeFinalizedHeads"),
+	("chain_subscribeNewHeads", "chain_newHead", "chain_unsubscribeNewHeads"),
+	(
+		"grandpa_subscribeJustifications",
+		"grandpa_justifications",
+		"grandpa_unsubscribeJustifications",
+	),
+	("state_subscribeRuntimeVersion", "state_runtimeVersion", "state_unsubscribeRuntimeVersion"),
+];
+
+/// What to do with the RPC traffic of the relay.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RpcRecordingMode {
+	/// Forward requests to nodes and record the traffic to the given directory.
+	Record(PathBuf),
+	/// Answer requests with responses, recorded in the given directory.
+	Replay(PathBuf),
+}
+
+/// Single entry of the RPC recording.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum RecordedEntry {
+	/// Method call.
+	Call {
+		/// Milliseconds since the recording has started.
+		at_ms: u64,
+		/// Called method.
+		method: String,
+		/// Call parameters.
+		params: Value,
+		/// Call result.
+		result: Result<Value, RecordedError>,
+	},
+	/// New subscription.
+	Subscription {
+		/// Milliseconds since the recording has started.
+		at_ms: u64,
+		/// Subscription identifier, unique within the recording.
+		id: u64,
+		/// Subscribe method.
+		method: String,
+		/// Subscription parameters.
+		params: Value,
+		/// Error, if subscription has failed.
+		error: Option<RecordedError>,
+	},
+	/// Subscription notification.
+	Notification {
+		/// Milliseconds since the recording has started.
+		at_ms: u64,
+		/// Subscription identifier.
+		subscription: u64,
+		/// Notification payload.
+		item: Value,
+	},
+}
+
+/// Recorded RPC error.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct RecordedError {
+	code: i32,
+	message: String,
+	data: Option<Value>,
+}
+
+impl From<&ErrorObjectOwned> for RecordedError {
+	fn from(error: &ErrorObjectOwned) -> Self {
+		RecordedError {
+			code: error.code(),
+			message: error.message().into(),
+			data: error.data().and_then(|data| serde_json::from_str(data.get()).ok()),
+		}
+	}
+}
+
+impl From<RecordedError> for ErrorObjectOwned {
+	fn from(error: RecordedError) -> Self {
+		ErrorObjectOwned::owned(error.code, error.message, error.data)
+	}
+}
+
+/// Running RPC recording or replaying servers.
+///
+/// Servers are stopped when this structure is dropped.
+pub struct RpcRecording {
+	mode: RpcRecordingMode,
+	servers: Vec<(String, SocketAddr, ServerHandle)>,
+	// keep runtime alive while servers are running
+	_runtime: tokio::runtime::Runtime,
+}
+
+impl RpcRecording {
+	/// Start local RPC server for every node URI in the given command line arguments.
+	///
+	/// Returns arguments where all node URIs are replaced with URIs of local servers.
+	pub fn start(
+		mode: RpcRecordingMode,
+		args: impl IntoIterator<Item = OsString>,
+	) -> anyhow::Result<(Self, Vec<OsString>)> {
+		let runtime = tokio::runtime::Builder::new_multi_thread()
+			.worker_threads(2)
+			.enable_all()
+			.build()?;
+		if let RpcRecordingMode::Record(ref dir) = mode {
+			std::fs::create_dir_all(dir)?;
+		}
+
+		let started_at = Instant::now();
+		let mut servers = Vec::new();
+		let args = replace_node_uris(args, |chain, uri| {
+			let (address, server) = match mode {
+				RpcRecordingMode::Record(ref dir) => runtime.block_on(async {
+					let file = recording_file(dir, chain);
+					start_server(record::rpc_module(uri.into(), file, started_at).await?).await
+				})?,
+				RpcRecordingMode::Replay(ref dir) => {
+					let module = replay::rpc_module(&recording_file(dir, chain))?;
+					runtime.block_on(start_server(module))?
+				},
+			};
+			servers.push((chain.to_owned(), address, server));
+			Ok(format!("ws://{address}"))
+		})?;
+
+		Ok((RpcRecording { mode, servers, _runtime: runtime }, args))
+	}
+
+	/// Log addresses of started servers.
+	pub fn log_servers(&self) {
+		for (chain, address, _) in &self.servers {
+			match self.mode {
+				RpcRecordingMode::Record(ref dir) => log::info!(
+					target: LOG_TARGET,
+					"Recording RPC of {chain} node to {} via ws://{address}",
+					recording_file(dir, chain).display(),
+				),
+				RpcRecordingMode::Replay(ref dir) => log::info!(
+					target: LOG_TARGET,
+					"Replaying RPC of {chain} node from {} via ws://{address}",
+					recording_file(dir, chain).display(),
+				),
+			}
+		}
+	}
+}
+
+impl Drop for RpcRecording {
+	fn drop(&mut self) {
+		for (_, _, server) in &self.servers {
+			let _ = server.stop();
+		}
+	}
+}
+
+/// Return path to the file with recorded RPC traffic of given chain.
+fn recording_file(dir: &Path, chain: &str) -> PathBuf {
+	dir.join(format!("{chain}.jsonl"))
+}
+
+/// Start RPC server with given module on a random local port.
+async fn start_server<Context: Send + Sync + 'static>(
+	module: RpcModule<Context>,
+) -> anyhow::Result<(SocketAddr, ServerHandle)> {
+	let server = Server::builder().build("127.0.0.1:0").await?;
+	let address = server.local_addr()?;
+	Ok((address, server.start(module)))
+}
+
+/// Replace values of all `--<chain>-uri` and `--check-metadata-hash <chain>=<uri>` arguments
+/// using given function.
+///
+/// The function is called with the chain name (`<chain>` part of the argument) and the original
+/// URI and returns the new URI. Fails if there's a node, specified by its host and port.
+fn replace_node_uris(
+	args: impl IntoIterator<Item = OsString>,
+	mut replace: impl FnMut(&str, &str) -> anyhow::Result<String>,
+) -> anyhow::Result<Vec<OsString>> {
+	let mut args = args.into_iter();
+	let mut result = Vec::new();
+	while let Some(arg) = args.next() {
+		let Some(uri_arg) = arg.to_str().and_then(|arg| arg.strip_prefix("--")) else {
+			result.push(arg);
+			continue
+		};
+
+		let name = uri_arg.split_once('=').map_or(uri_arg, |(name, _)| name);
+		if let Some(suffix) = node_address_arg_suffix(name) {
+			anyhow::bail!(
+				"Argument --{name} is not supported when recording or replaying RPC. Use --{}{} instead",
+				name.trim_end_matches(suffix),
+				URI_ARG_SUFFIX,
+			)
+		}
+
+		match uri_arg.split_once('=') {
+			Some((METADATA_HASH_ARG, check)) => {
+				let check = replace_metadata_hash_check_uri(check, &mut replace)?;
+				result.push(format!("--{METADATA_HASH_ARG}={check}").into());
+			},
+			None if uri_arg == METADATA_HASH_ARG => {
+				result.push(arg);
+				if let Some(check) = args.next() {
+					let check = check
+						.into_string()
+						.map_err(|check| anyhow::format_err!("Invalid argument: {check:?}"))?;
+					result.push(replace_metadata_hash_check_uri(&check, &mut replace)?.into());
+				}
+			},
+			Some((name, uri)) if name.ends_with(URI_ARG_SUFFIX) => {
+				let chain = name.trim_end_matches(URI_ARG_SUFFIX);
+				result.push(format!("--{name}={}", replace(chain, uri)?).into());
+			},
+			None if uri_arg.ends_with(URI_ARG_SUFFIX) => {
+				let chain = uri_arg.trim_end_matches(URI_ARG_SUFFIX).to_owned();
+				result.push(arg);
+				if let Some(uri) = args.next() {
+					let uri = uri
+						.into_string()
+						.map_err(|uri| anyhow::format_err!("Invalid node URI: {uri:?}"))?;
+					result.push(replace(&chain, &uri)?.into());
+				}
+			},
+			_ => result.push(arg),
+		}
+	}
+	Ok(result)
+}
+
+/// Returns suffix of the legacy node address argument with given name.
+fn node_address_arg_suffix(name: &str) -> Option<&'static str> {
+	if NON_NODE_ADDRESS_ARG_PREFIXES.iter().any(|prefix| name.starts_with(prefix)) {
+		return None
+	}
+	NODE_ADDRESS_ARG_SUFFIXES.iter().copied().find(|suffix| name.ends_with(suffix))
+}
+
+/// Replace node URI in the `<chain>=<uri>` value of the `--check-metadata-hash` argument.
+fn replace_metadata_hash_check_uri(
+	check: &str,
+	replace: &mut impl FnMut(&str, &str) -> anyhow::Result<String>,
+) -> anyhow::Result<String> {
+	match check.split_once('=') {
+		Some((chain, uri)) =>
+			Ok(format!("{chain}={}", replace(&format!("{METADATA_HASH_ARG}-{chain}"), uri)?)),
+		// let the CLI parser report the error
+		None => Ok(check.into()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn args(args: &[&str]) -> Vec<OsString> {
+		args.iter().map(Into::into).collect()
+	}
+
+	#[test]
+	fn replaces_node_uris() {
+		let mut chains = Vec::new();
+		let result = replace_node_uris(
+			args(&[
+				"substrate-relay",
+				"relay-headers",
+				"--source-uri",
+				"wss://source:443",
+				"--target-uri=ws://target:9944",
+				"--target-signer",
+				"//Alice",
+				"--prometheus-host",
+				"0.0.0.0",
+				"--check-metadata-hash",
+				"bridge-hub-kusama=wss://kusama:443",
+				"--check-metadata-hash=bridge-hub-polkadot=wss://polkadot:443",
+			]),
+			|chain, uri| {
+				chains.push((chain.to_owned(), uri.to_owned()));
+				Ok(format!("ws://127.0.0.1:{}", chains.len()))
+			},
+		)
+		.unwrap();
+
+		assert_eq!(
+			result,
+			args(&[
+				"substrate-relay",
+				"relay-headers",
+				"--source-uri",
+				"ws://127.0.0.1:1",
+				"--target-uri=ws://127.0.0.1:2",
+				"--target-signer",
+				"//Alice",
+				"--prometheus-host",
+				"0.0.0.0",
+				"--check-metadata-hash",
+				"bridge-hub-kusama=ws://127.0.0.1:3",
+				"--check-metadata-hash=bridge-hub-polkadot=ws://127.0.0.1:4",
+			]),
+		);
+		assert_eq!(
+			chains,
+			vec![
+				("source".to_owned(), "wss://source:443".to_owned()),
+				("target".to_owned(), "ws://target:9944".to_owned()),
+				("check-metadata-hash-bridge-hub-kusama".to_owned(), "wss://kusama:443".to_owned()),
+				(
+					"check-metadata-hash-bridge-hub-polkadot".to_owned(),
+					"wss://polkadot:443".to_owned()
+				),
+			],
+		);
+	}
+
+	#[test]
+	fn rejects_node_host_and_port() {
+		for node_args in [&["--source-host", "source"][..], &["--target-port=9944"][..]] {
+			let result = replace_node_uris(
+				args(
+					&[
+						&["substrate-relay", "relay-headers", "--source-uri", "ws://source:9944"],
+						node_args,
+					]
+					.concat(),
+				),
+				|_, _| Ok("ws://127.0.0.1:1".into()),
+			);
+
+			assert!(result.is_err());
+		}
+	}
+
+	#[test]
+	fn recorded_entry_is_encoded_as_json_line() {
+		let entry = RecordedEntry::Call {
+			at_ms: 42,
+			method: "chain_getFinalizedHead".into(),
+			params: Value::Array(vec![]),
+			result: Ok(Value::String("0x00".into())),
+		};
+		let line = serde_json::to_string(&entry).unwrap();
+
+		assert!(!line.contains('\n'));
+		assert_eq!(serde_json::from_str::<RecordedEntry>(&line).unwrap(), entry);
+	}
+}
@@ -0,0 +1,227 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC server that forwards requests to the node and records the traffic.
+
+use super::{RecordedEntry, LOG_TARGET, METHODS, SUBSCRIPTIONS};
+
+use jsonrpsee::{
+	core::{
+		client::{ClientT, Error as ClientError, SubscriptionClientT},
+		params::ArrayParams,
+	},
+	types::{ErrorObjectOwned, Params},
+	ws_client::{WsClient, WsClientBuilder},
+	PendingSubscriptionSink, RpcModule, SubscriptionMessage,
+};
+use serde_json::Value;
+use std::{
+	fs::File,
+	io::{LineWriter, Write},
+	path::PathBuf,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc, Mutex,
+	},
+	time::Instant,
+};
+use tokio::sync::Mutex as AsyncMutex;
+
+/// Error code of errors, generated by the recording server itself.
+const RECORDER_ERROR_CODE: i32 = -32000;
+
+/// State of the recording server.
+pub(super) struct Recorder {
+	uri: String,
+	client: AsyncMutex<Option<Arc<WsClient>>>,
+	file: Mutex<LineWriter<File>>,
+	started_at: Instant,
+	next_subscription_id: AtomicU64,
+}
+
+impl Recorder {
+	/// Return connected node client, reconnecting if required.
+	async fn client(&self) -> Result<Arc<WsClient>, ErrorObjectOwned> {
+		let mut client = self.client.lock().await;
+		match *client {
+			Some(ref client) if client.is_connected() => Ok(client.clone()),
+			_ => {
+				let new_client =
+					Arc::new(WsClientBuilder::default().build(&self.uri).await.map_err(|e| {
+						error(format!("Failed to connect to the node at {}: {e}", self.uri))
+					})?);
+				*client = Some(new_client.clone());
+				Ok(new_client)
+			},
+		}
+	}
+
+	/// Return milliseconds since the recording has started.
+	fn at_ms(&self) -> u64 {
+		self.started_at.elapsed().as_millis() as u64
+	}
+
+	/// Write entry to the recording file.
+	fn record(&self, entry: RecordedEntry) {
+		let write_result =
+			serde_json::to_string(&entry).map_err(|e| e.to_string()).and_then(|line| {
+				let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+				writeln!(file, "{line}").map_err(|e| e.to_string())
+			});
+		if let Err(e) = write_result {
+			log::error!(target: LOG_TARGET, "Failed to record RPC traffic of {}: {e}", self.uri);
+		}
+	}
+
+	/// Forward method call to the node and record the result.
+	async fn call(&self, method: &str, params: Value) -> Result<Value, ErrorObjectOwned> {
+		let result = match self.client().await {
+			Ok(client) => client
+				.request::<Value, _>(method, array_params(&params))
+				.await
+				.map_err(client_error),
+			Err(e) => Err(e),
+		};
+
+		self.record(RecordedEntry::Call {
+			at_ms: self.at_ms(),
+			method: method.into(),
+			params,
+			result: result.as_ref().map(Clone::clone).map_err(Into::into),
+		});
+		result
+	}
+
+	/// Forward subscription to the node and record all notifications.
+	async fn subscribe(
+		&self,
+		subscribe_method: &str,
+		unsubscribe_method: &str,
+		params: Value,
+		pending: PendingSubscriptionSink,
+	) {
+		let id = self.next_subscription_id.fetch_add(1, Ordering::Relaxed);
+		let subscription = match self.client().await {
+			Ok(client) => client
+				.subscribe::<Value, _>(subscribe_method, array_params(&params), unsubscribe_method)
+				.await
+				.map_err(client_error),
+			Err(e) => Err(e),
+		};
+
+		self.record(RecordedEntry::Subscription {
+			at_ms: self.at_ms(),
+			id,
+			method: subscribe_method.into(),
+			params,
+			error: subscription.as_ref().err().map(Into::into),
+		});
+
+		let mut subscription = match subscription {
+			Ok(subscription) => subscription,
+			Err(e) => {
+				pending.reject(e).await;
+				return
+			},
+		};
+		let Ok(sink) = pending.accept().await else { return };
+		loop {
+			let item = tokio::select! {
+				_ = sink.closed() => return,
+				item = subscription.next() => item,
+			};
+			let Some(Ok(item)) = item else { return };
+
+			self.record(RecordedEntry::Notification {
+				at_ms: self.at_ms(),
+				subscription: id,
+				item: item.clone(),
+			});
+			let Ok(message) = SubscriptionMessage::from_json(&item) else { return };
+			if sink.send(message).await.is_err() {
+				return
+			}
+		}
+	}
+}
+
+/// Build RPC module that forwards all known methods to the node at given URI and records the
+/// traffic to the given file.
+pub(super) async fn rpc_module(
+	uri: String,
+	file: PathBuf,
+	started_at: Instant,
+) -> anyhow::Result<RpcModule<Arc<Recorder>>> {
+	let recorder = Arc::new(Recorder {
+		uri,
+		client: AsyncMutex::new(None),
+		file: Mutex::new(LineWriter::new(File::create(file)?)),
+		started_at,
+		next_subscription_id: AtomicU64::new(0),
+	});
+	// fail early if the node is not available
+	recorder.client().await.map_err(|e| anyhow::format_err!("{}", e.message()))?;
+
+	let mut module = RpcModule::new(recorder);
+	for &method in METHODS {
+		module.register_async_method(method, move |params, recorder, _| async move {
+			recorder.call(method, params_value(&params)).await
+		})?;
+	}
+	for &(subscribe_method, notification_method, unsubscribe_method) in SUBSCRIPTIONS {
+		module.register_subscription(
+			subscribe_method,
+			notification_method,
+			unsubscribe_method,
+			move |params, pending, recorder, _| async move {
+				recorder
+					.subscribe(subscribe_method, unsubscribe_method, params_value(&params), pending)
+					.await
+			},
+		)?;
+	}
+	Ok(module)
+}
+
+/// Return call parameters as JSON value.
+fn params_value(params: &Params) -> Value {
+	params.parse().unwrap_or(Value::Null)
+}
+
+/// Convert parameters, received from the relay into parameters of the node call.
+fn array_params(params: &Value) -> ArrayParams {
+	let mut array_params = ArrayParams::new();
+	if let Value::Array(params) = params {
+		for param in params {
+			// serialization of JSON value never fails
+			let _ = array_params.insert(param);
+		}
+	}
+	array_params
+}
+
+/// Convert node client error into RPC error.
+fn client_error(e: ClientError) -> ErrorObjectOwned {
+	match e {
+		ClientError::Call(e) => e,
+		e => error(e.to_string()),
+	}
+}
+
+/// Create RPC error with given message.
+fn error(message: String) -> ErrorObjectOwned {
+	ErrorObjectOwned::owned(RECORDER_ERROR_CODE, message, None::<()>)
+}
@@ -0,0 +1,386 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC server that answers requests with recorded responses.
+//!
+//! All recorded calls are kept in a single log, in the recorded order. Calls are matched with
+//! the first recorded call with the same method and parameters, that has not been replayed yet.
+//! Some parameters (e.g. signed transactions) are not reproducible, so if there is no such call,
+//! the first not yet replayed call of the same method is replayed instead. Every recorded
+//! response is replayed once. When all matching calls have been replayed, the last one is
+//! returned to all subsequent calls.
+//!
+//! Subscriptions are matched with recorded subscriptions by method, in the recorded order.
+//! Replayed calls move the shared cursor over the log and every recorded notification is sent
+//! to the subscriber once all calls, recorded before it, have been replayed. So notifications
+//! are ordered relative to calls the same way they were ordered when recording.
+
+use super::{RecordedEntry, RecordedError, LOG_TARGET, METHODS, SUBSCRIPTIONS};
+
+use jsonrpsee::{
+	types::{ErrorObjectOwned, Params},
+	PendingSubscriptionSink, RpcModule, SubscriptionMessage,
+};
+use serde_json::Value;
+use std::{
+	collections::{HashMap, VecDeque},
+	path::Path,
+	sync::{Arc, Mutex},
+};
+use tokio::sync::watch;
+
+/// Error code of errors, generated by the replaying server itself.
+const REPLAYER_ERROR_CODE: i32 = -32000;
+
+/// Recorded call result.
+type CallResult = Result<Value, RecordedError>;
+
+/// Recorded call.
+#[derive(Debug)]
+struct RecordedCall {
+	method: String,
+	params: String,
+	result: CallResult,
+	/// Whether the call has been replayed already.
+	replayed: bool,
+}
+
+/// Recorded subscription.
+#[derive(Debug, Default)]
+struct RecordedSubscription {
+	error: Option<RecordedError>,
+	/// Notifications, along with the number of calls, recorded before them.
+	notifications: Vec<(usize, Value)>,
+}
+
+/// Recorded responses.
+#[derive(Debug)]
+pub(super) struct Recording {
+	/// All recorded calls, in the recorded order.
+	calls: Vec<RecordedCall>,
+	/// Number of recorded calls up to and including the latest replayed call.
+	cursor: watch::Sender<usize>,
+	/// Subscriptions by subscribe method, that have not been replayed yet.
+	subscriptions: HashMap<String, VecDeque<RecordedSubscription>>,
+}
+
+impl Recording {
+	/// Read recording from given file.
+	fn read(file: &Path) -> anyhow::Result<Self> {
+		let content = std::fs::read_to_string(file).map_err(|e| {
+			anyhow::format_err!("Failed to read RPC recording {}: {e}", file.display())
+		})?;
+
+		let mut recording = Recording {
+			calls: Vec::new(),
+			cursor: watch::Sender::new(0),
+			subscriptions: HashMap::new(),
+		};
+		let mut subscriptions = HashMap::new();
+		for (index, line) in content.lines().enumerate().filter(|(_, line)| !line.is_empty()) {
+			let entry = serde_json::from_str(line).map_err(|e| {
+				anyhow::format_err!("Invalid entry at {}:{}: {e}", file.display(), index + 1)
+			})?;
+			match entry {
+				RecordedEntry::Call { method, params, result, .. } => {
+					recording.calls.push(RecordedCall {
+						method,
+						params: params.to_string(),
+						result,
+						replayed: false,
+					});
+				},
+				RecordedEntry::Subscription { id, method, error, .. } => {
+					subscriptions
+						.insert(id, (method, RecordedSubscription { error, ..Default::default() }));
+				},
+				RecordedEntry::Notification { subscription, item, .. } => {
+					if let Some((_, subscription)) = subscriptions.get_mut(&subscription) {
+						subscription.notifications.push((recording.calls.len(), item));
+					}
+				},
+			}
+		}
+
+		let mut subscriptions = subscriptions.into_iter().collect::<Vec<_>>();
+		subscriptions.sort_by_key(|(id, _)| *id);
+		for (_, (method, subscription)) in subscriptions {
+			recording.subscriptions.entry(method).or_default().push_back(subscription);
+		}
+
+		Ok(recording)
+	}
+
+	/// Return next recorded result of the call.
+	fn next_call_result(&mut self, method: &str, params: &Value) -> Option<CallResult> {
+		let params = params.to_string();
+		let is_same_call = |call: &RecordedCall| call.method == method && call.params == params;
+		let is_same_method = |call: &RecordedCall| call.method == method;
+
+		let not_replayed = self
+			.calls
+			.iter()
+			.position(|call| !call.replayed && is_same_call(call))
+			.or_else(|| {
+				if self.calls.iter().any(is_same_call) {
+					return None
+				}
+				log::warn!(
+					target: LOG_TARGET,
+					"No recorded {method} call with parameters {params}. Replaying next recorded call",
+				);
+				self.calls.iter().position(|call| !call.replayed && is_same_method(call))
+			});
+		let Some(index) = not_replayed else {
+			return self
+				.calls
+				.iter()
+				.rev()
+				.find(|call| is_same_call(call))
+				.or_else(|| self.calls.iter().rev().find(|call| is_same_method(call)))
+				.map(|call| call.result.clone())
+		};
+
+		self.calls[index].replayed = true;
+		self.cursor.send_if_modified(|cursor| {
+			let is_modified = *cursor <= index;
+			*cursor = (*cursor).max(index + 1);
+			is_modified
+		});
+		Some(self.calls[index].result.clone())
+	}
+
+	/// Return next recorded subscription and the receiver of the replayed calls cursor.
+	fn next_subscription(
+		&mut self,
+		method: &str,
+	) -> Option<(RecordedSubscription, watch::Receiver<usize>)> {
+		let subscription = self.subscriptions.get_mut(method)?.pop_front()?;
+		Some((subscription, self.cursor.subscribe()))
+	}
+}
+
+/// Build RPC module that answers all known methods with responses from the given recording file.
+pub(super) fn rpc_module(file: &Path) -> anyhow::Result<RpcModule<Arc<Mutex<Recording>>>> {
+	let recording = Arc::new(Mutex::new(Recording::read(file)?));
+
+	let mut module = RpcModule::new(recording);
+	for &method in METHODS {
+		module.register_method(
+			method,
+			move |params, recording, _| -> Result<_, ErrorObjectOwned> {
+				let params = params_value(&params);
+				lock(recording)
+					.next_call_result(method, &params)
+					.ok_or_else(|| error(format!("No recorded {method} calls")))?
+					.map_err(Into::into)
+			},
+		)?;
+	}
+	for &(subscribe_method, notification_method, unsubscribe_method) in SUBSCRIPTIONS {
+		module.register_subscription(
+			subscribe_method,
+			notification_method,
+			unsubscribe_method,
+			move |_, pending, recording, _| async move {
+				let subscription = lock(&recording).next_subscription(subscribe_method);
+				replay_subscription(subscribe_method, subscription, pending).await
+			},
+		)?;
+	}
+	Ok(module)
+}
+
+/// Send recorded subscription notifications to the subscriber.
+///
+/// Every notification is sent once all calls, recorded before it, have been replayed.
+async fn replay_subscription(
+	method: &str,
+	subscription: Option<(RecordedSubscription, watch::Receiver<usize>)>,
+	pending: PendingSubscriptionSink,
+) {
+	let (subscription, mut cursor) = match subscription {
+		Some((RecordedSubscription { error: Some(e), .. }, _)) => {
+			pending.reject(ErrorObjectOwned::from(e)).await;
+			return
+		},
+		Some(subscription) => subscription,
+		None => {
+			pending.reject(error(format!("No recorded {method} subscriptions"))).await;
+			return
+		},
+	};
+
+	let Ok(sink) = pending.accept().await else { return };
+	for (calls_before, item) in subscription.notifications {
+		let is_replayed = async { cursor.wait_for(|cursor| *cursor >= calls_before).await.is_ok() };
+		tokio::select! {
+			is_replayed = is_replayed => if !is_replayed { return },
+			_ = sink.closed() => return,
+		}
+
+		let Ok(message) = SubscriptionMessage::from_json(&item) else { return };
+		if sink.send(message).await.is_err() {
+			return
+		}
+	}
+
+	// keep subscription alive, as the node would do
+	sink.closed().await;
+}
+
+/// Lock the recording.
+fn lock(recording: &Mutex<Recording>) -> std::sync::MutexGuard<Recording> {
+	recording.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Return call parameters as JSON value.
+fn params_value(params: &Params) -> Value {
+	params.parse().unwrap_or(Value::Null)
+}
+
+/// Create RPC error with given message.
+fn error(message: String) -> ErrorObjectOwned {
+	ErrorObjectOwned::owned(REPLAYER_ERROR_CODE, message, None::<()>)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::io::Write;
+
+	fn recording(entries: &[RecordedEntry]) -> Recording {
+		let mut file = tempfile::NamedTempFile::new().unwrap();
+		for entry in entries {
+			writeln!(file, "{}", serde_json::to_string(entry).unwrap()).unwrap();
+		}
+		Recording::read(file.path()).unwrap()
+	}
+
+	fn call(method: &str, params: Value, result: Value) -> RecordedEntry {
+		RecordedEntry::Call { at_ms: 0, method: method.into(), params, result: Ok(result) }
+	}
+
+	#[test]
+	fn replays_calls_in_recorded_order() {
+		let params = Value::Array(vec![]);
+		let mut recording = recording(&[
+			call("chain_getFinalizedHead", params.clone(), "0x01".into()),
+			call("chain_getFinalizedHead", params.clone(), "0x02".into()),
+		]);
+
+		assert_eq!(
+			recording.next_call_result("chain_getFinalizedHead", &params),
+			Some(Ok("0x01".into())),
+		);
+		assert_eq!(
+			recording.next_call_result("chain_getFinalizedHead", &params),
+			Some(Ok("0x02".into())),
+		);
+		// last result is repeated
+		assert_eq!(
+			recording.next_call_result("chain_getFinalizedHead", &params),
+			Some(Ok("0x02".into())),
+		);
+		assert_eq!(recording.next_call_result("chain_getHeader", &params), None);
+	}
+
+	#[test]
+	fn replays_call_with_unknown_params_by_method() {
+		let mut recording = recording(&[
+			call("author_submitExtrinsic", Value::Array(vec!["0x01".into()]), "0xaa".into()),
+			call("author_submitExtrinsic", Value::Array(vec!["0x02".into()]), "0xbb".into()),
+		]);
+
+		assert_eq!(
+			recording
+				.next_call_result("author_submitExtrinsic", &Value::Array(vec!["0x01".into()])),
+			Some(Ok("0xaa".into())),
+		);
+		// the first call has been replayed already, so the next recorded call is replayed
+		assert_eq!(
+			recording
+				.next_call_result("author_submitExtrinsic", &Value::Array(vec!["0x03".into()])),
+			Some(Ok("0xbb".into())),
+		);
+		// all calls have been replayed, so the last one is repeated
+		assert_eq!(
+			recording
+				.next_call_result("author_submitExtrinsic", &Value::Array(vec!["0x04".into()])),
+			Some(Ok("0xbb".into())),
+		);
+	}
+
+	#[test]
+	fn orders_notifications_relative_to_calls() {
+		let params = Value::Array(vec![]);
+		let mut recording = recording(&[
+			call("chain_getFinalizedHead", params.clone(), "0x01".into()),
+			RecordedEntry::Subscription {
+				at_ms: 0,
+				id: 0,
+				method: "chain_subscribeFinalizedHeads".into(),
+				params: Value::Null,
+				error: None,
+			},
+			RecordedEntry::Notification { at_ms: 0, subscription: 0, item: "a".into() },
+			call("chain_getHeader", params.clone(), "0x02".into()),
+			call("chain_getFinalizedHead", params.clone(), "0x03".into()),
+			RecordedEntry::Notification { at_ms: 0, subscription: 0, item: "b".into() },
+		]);
+
+		let (subscription, cursor) =
+			recording.next_subscription("chain_subscribeFinalizedHeads").unwrap();
+		assert_eq!(subscription.notifications, vec![(1, Value::from("a")), (3, Value::from("b"))]);
+		assert_eq!(*cursor.borrow(), 0);
+
+		recording.next_call_result("chain_getFinalizedHead", &params);
+		assert_eq!(*cursor.borrow(), 1);
+		recording.next_call_result("chain_getFinalizedHead", &params);
+		assert_eq!(*cursor.borrow(), 3);
+		// replaying the skipped call doesn't move the cursor back
+		recording.next_call_result("chain_getHeader", &params);
+		assert_eq!(*cursor.borrow(), 3);
+	}
+
+	#[test]
+	fn groups_notifications_by_subscription() {
+		let mut recording = recording(&[
+			RecordedEntry::Subscription {
+				at_ms: 10,
+				id: 0,
+				method: "chain_subscribeNewHeads".into(),
+				params: Value::Null,
+				error: None,
+			},
+			RecordedEntry::Subscription {
+				at_ms: 20,
+				id: 1,
+				method: "chain_subscribeNewHeads".into(),
+				params: Value::Null,
+				error: None,
+			},
+			RecordedEntry::Notification { at_ms: 30, subscription: 1, item: "b".into() },
+			RecordedEntry::Notification { at_ms: 40, subscription: 0, item: "a".into() },
+		]);
+
+		let (first, _) = recording.next_subscription("chain_subscribeNewHeads").unwrap();
+		assert_eq!(first.notifications, vec![(0, Value::from("a"))]);
+		let (second, _) = recording.next_subscription("chain_subscribeNewHeads").unwrap();
+		assert_eq!(second.notifications, vec![(0, Value::from("b"))]);
+		assert!(recording.next_subscription("chain_subscribeNewHeads").is_none());
+	}
+}
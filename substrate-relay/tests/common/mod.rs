@@ -0,0 +1,112 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Utilities, shared by offline end-to-end relay tests.
+
+#![allow(dead_code)]
+
+use relay_mock_node::{fixtures, Header, MockChain, MockNode, Scenario, Storage};
+use relay_substrate_client::ChainWithRuntimeVersion;
+use std::{
+	process::{Child, Command, Stdio},
+	time::Duration,
+};
+
+/// Secret seed of the relayer account.
+///
+/// The raw seed is accepted by both sr25519 and ECDSA key pairs, so it works for all chains.
+pub const RELAYER_SIGNER: &str =
+	"0xe5be9a5092b81bca64be81d212e7f2f9eba183bb7a90954f7b76361f6edb5c0a";
+
+/// Max time the relay may spend on submitting the transaction to the mock node.
+const RELAY_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Running `substrate-relay` process. It is killed when dropped.
+pub struct RelayProcess(Child);
+
+impl RelayProcess {
+	/// Spawn `substrate-relay` with given arguments.
+	pub fn spawn(args: impl IntoIterator<Item = String>) -> Self {
+		let child = Command::new(env!("CARGO_BIN_EXE_substrate-relay"))
+			.args(args)
+			.stdout(Stdio::null())
+			.stderr(Stdio::null())
+			.spawn()
+			.expect("failed to spawn substrate-relay");
+		RelayProcess(child)
+	}
+}
+
+impl Drop for RelayProcess {
+	fn drop(&mut self) {
+		let _ = self.0.kill();
+		let _ = self.0.wait();
+	}
+}
+
+/// Create scenario for given chain, reporting the runtime version that is bundled with the
+/// chain client.
+pub fn scenario<C: ChainWithRuntimeVersion>(chain: MockChain) -> Scenario {
+	let scenario = Scenario::new(C::NAME, chain);
+	match C::RUNTIME_VERSION {
+		Some(version) =>
+			scenario.with_runtime_version(version.spec_version, version.transaction_version),
+		None => scenario,
+	}
+}
+
+/// Create chain with given genesis storage and a single finalized block on top of genesis.
+///
+/// The block is finalized with GRANDPA justification, signed by test authorities.
+pub fn finalized_chain(genesis_storage: Storage) -> MockChain {
+	let mut chain = MockChain::new(genesis_storage);
+	chain.import_block(Vec::new(), Default::default(), Vec::new());
+	let justification = fixtures::grandpa_justification(&chain.best().header);
+	chain.finalize(1, Some(justification)).expect("block 1 is imported above; qed");
+	chain
+}
+
+/// Return `(number, hash)` of given header.
+pub fn header_id(header: &Header) -> (u32, relay_mock_node::Hash) {
+	use sp_runtime::traits::Header as _;
+	(*header.number(), header.hash())
+}
+
+/// Assert that the relay submits the transaction containing given bytes to the node.
+pub fn assert_submitted(node: &MockNode, needle: &[u8], what: &str) {
+	let contains_needle =
+		|extrinsic: &[u8]| extrinsic.windows(needle.len()).any(|window| window == needle);
+	assert!(
+		node.wait_for_extrinsic(contains_needle, RELAY_TIMEOUT).is_some(),
+		"relay has not submitted {what} in {RELAY_TIMEOUT:?}. Submitted extrinsics: {}",
+		node.submitted_extrinsics().len(),
+	);
+}
+
+/// Assert that the relay submits the transaction containing all given byte sequences to the
+/// node.
+pub fn assert_submitted_together(node: &MockNode, needles: &[Vec<u8>], what: &str) {
+	let contains_needles = |extrinsic: &[u8]| {
+		needles
+			.iter()
+			.all(|needle| extrinsic.windows(needle.len()).any(|window| window == needle.as_slice()))
+	};
+	assert!(
+		node.wait_for_extrinsic(contains_needles, RELAY_TIMEOUT).is_some(),
+		"relay has not submitted {what} in {RELAY_TIMEOUT:?}. Submitted extrinsics: {}",
+		node.submitted_extrinsics().len(),
+	);
+}
@@ -0,0 +1,112 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline end-to-end tests of the `relay-headers` command.
+
+mod common;
+
+use bp_header_chain::ChainWithGrandpa;
+use codec::Encode;
+use common::{
+	assert_submitted, finalized_chain, header_id, scenario, RelayProcess, RELAYER_SIGNER,
+};
+use relay_mock_node::{fixtures, MockChain, MockNode};
+use relay_substrate_client::{ChainWithRuntimeVersion, UnderlyingChainProvider};
+
+/// Start source and target nodes and check that the relay submits the finalized source header
+/// to the target node.
+fn relay_headers<Source, Target>(bridge: &str)
+where
+	Source: ChainWithRuntimeVersion + UnderlyingChainProvider,
+	Source::Chain: ChainWithGrandpa,
+	Target: ChainWithRuntimeVersion,
+{
+	// source chain has block 1, finalized with justification
+	let source_chain = finalized_chain(Default::default());
+	let source_genesis = header_id(&source_chain.genesis().header);
+	let finalized_header = source_chain.best_finalized().header.clone();
+
+	// target chain knows only source genesis
+	let target_chain = MockChain::new(fixtures::grandpa_pallet_storage(
+		Source::Chain::WITH_CHAIN_GRANDPA_PALLET_NAME,
+		source_genesis,
+	));
+
+	let source = MockNode::start(scenario::<Source>(source_chain)).unwrap();
+	let target = MockNode::start(
+		scenario::<Target>(target_chain)
+			.with_state_call_response(
+				Source::BEST_FINALIZED_HEADER_ID_METHOD,
+				fixtures::best_finalized_response(Some(source_genesis)),
+			)
+			.with_state_call_response(
+				Source::FREE_HEADERS_INTERVAL_METHOD,
+				fixtures::free_headers_interval_response(),
+			),
+	)
+	.unwrap();
+
+	let _relay = RelayProcess::spawn(
+		[
+			"relay-headers",
+			bridge,
+			"--source-uri",
+			source.url(),
+			"--target-uri",
+			target.url(),
+			"--target-signer",
+			RELAYER_SIGNER,
+			"--no-prometheus",
+		]
+		.map(String::from),
+	);
+
+	assert_submitted(&target, &finalized_header.encode(), "finalized source header");
+}
+
+macro_rules! relay_headers_tests {
+	($($name:ident: $bridge:literal, $source:ty => $target:ty;)*) => {
+		$(
+			#[test]
+			fn $name() {
+				relay_headers::<$source, $target>($bridge);
+			}
+		)*
+	};
+}
+
+relay_headers_tests! {
+	rococo_to_bridge_hub_westend: "rococo-to-bridge-hub-westend",
+		relay_rococo_client::Rococo => relay_bridge_hub_westend_client::BridgeHubWestend;
+	westend_to_bridge_hub_rococo: "westend-to-bridge-hub-rococo",
+		relay_westend_client::Westend => relay_bridge_hub_rococo_client::BridgeHubRococo;
+	kusama_to_bridge_hub_polkadot: "kusama-to-bridge-hub-polkadot",
+		relay_kusama_client::Kusama => relay_bridge_hub_polkadot_client::BridgeHubPolkadot;
+	polkadot_to_bridge_hub_kusama: "polkadot-to-bridge-hub-kusama",
+		relay_polkadot_client::Polkadot => relay_bridge_hub_kusama_client::BridgeHubKusama;
+	polkadot_to_polkadot_bulletin: "polkadot-to-polkadot-bulletin",
+		relay_polkadot_client::Polkadot => relay_polkadot_bulletin_client::PolkadotBulletin;
+	polkadot_bulletin_to_bridge_hub_polkadot: "polkadot-bulletin-to-bridge-hub-polkadot",
+		relay_polkadot_bulletin_client::PolkadotBulletin => relay_bridge_hub_polkadot_client::BridgeHubPolkadot;
+	rococo_to_rococo_bulletin: "rococo-to-rococo-bulletin",
+		relay_rococo_client::Rococo => relay_polkadot_bulletin_client::PolkadotBulletin;
+	rococo_bulletin_to_bridge_hub_rococo: "rococo-bulletin-to-bridge-hub-rococo",
+		relay_polkadot_bulletin_client::PolkadotBulletin => relay_bridge_hub_rococo_client::BridgeHubRococo;
+	kusama_to_moonbeam: "kusama-to-moonbeam",
+		relay_kusama_client::Kusama => relay_moonbeam_client::Moonbeam;
+	polkadot_to_moonriver: "polkadot-to-moonriver",
+		relay_polkadot_client::Polkadot => relay_moonriver_client::Moonriver;
+}
@@ -0,0 +1,153 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline end-to-end tests of the `relay-messages` command.
+
+mod common;
+
+use common::{
+	assert_submitted, finalized_chain, header_id, scenario, RelayProcess, RELAYER_SIGNER,
+};
+use relay_mock_node::{
+	fixtures::{self, RawLaneId},
+	MockChain, MockNode,
+};
+use relay_substrate_client::{ChainWithMessages, ChainWithRuntimeVersion, UnderlyingChainProvider};
+
+/// Lane of bridges that are using `LegacyLaneId`.
+const LEGACY_LANE: &str = "00000002";
+/// Lane of bridges that are using `HashedLaneId`.
+const HASHED_LANE: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+
+/// Payload of the message, sent over the lane.
+const MESSAGE_PAYLOAD: &[u8] = b"offline end-to-end relay test message";
+
+/// Start source and target nodes and check that the relay delivers the message, sent at the
+/// finalized source block, to the target node.
+fn relay_messages<Source, Target>(bridge: &str, lane: &str)
+where
+	Source: ChainWithMessages + ChainWithRuntimeVersion + UnderlyingChainProvider,
+	Source::Chain: bp_messages::ChainWithMessages,
+	Target: ChainWithMessages + ChainWithRuntimeVersion + UnderlyingChainProvider,
+	Target::Chain: bp_messages::ChainWithMessages,
+{
+	let raw_lane = RawLaneId(hex::decode(lane).unwrap());
+	let source_pallet =
+		<Target::Chain as bp_messages::ChainWithMessages>::WITH_CHAIN_MESSAGES_PALLET_NAME;
+	let target_pallet =
+		<Source::Chain as bp_messages::ChainWithMessages>::WITH_CHAIN_MESSAGES_PALLET_NAME;
+
+	// message is sent at the source genesis and the next block is finalized
+	let source_chain = finalized_chain(fixtures::outbound_lane_storage(
+		source_pallet,
+		&raw_lane,
+		&[MESSAGE_PAYLOAD.to_vec()],
+	));
+	let source_finalized = header_id(&source_chain.best_finalized().header);
+
+	// target chain has empty inbound lane
+	let target_chain = MockChain::new(fixtures::inbound_lane_storage(target_pallet, &raw_lane));
+	let target_genesis = header_id(&target_chain.genesis().header);
+
+	let source = MockNode::start(
+		scenario::<Source>(source_chain)
+			.with_state_call(
+				Target::TO_CHAIN_MESSAGE_DETAILS_METHOD,
+				fixtures::outbound_message_details(source_pallet, raw_lane.clone()),
+			)
+			.with_state_call_response(
+				Target::BEST_FINALIZED_HEADER_ID_METHOD,
+				fixtures::best_finalized_response(Some(target_genesis)),
+			),
+	)
+	.unwrap();
+	let target = MockNode::start(
+		scenario::<Target>(target_chain)
+			.with_state_call(
+				Source::FROM_CHAIN_MESSAGE_DETAILS_METHOD,
+				fixtures::inbound_message_details(raw_lane),
+			)
+			.with_state_call_response(
+				Source::BEST_FINALIZED_HEADER_ID_METHOD,
+				fixtures::best_finalized_response(Some(source_finalized)),
+			),
+	)
+	.unwrap();
+
+	let _relay = RelayProcess::spawn(
+		[
+			"relay-messages",
+			bridge,
+			"--source-uri",
+			source.url(),
+			"--source-signer",
+			RELAYER_SIGNER,
+			"--target-uri",
+			target.url(),
+			"--target-signer",
+			RELAYER_SIGNER,
+			"--lane",
+			lane,
+			"--no-prometheus",
+		]
+		.map(String::from),
+	);
+
+	assert_submitted(&target, MESSAGE_PAYLOAD, "message delivery transaction");
+}
+
+macro_rules! relay_messages_tests {
+	($($name:ident: $bridge:literal, $lane:ident, $source:ty => $target:ty;)*) => {
+		$(
+			#[test]
+			fn $name() {
+				relay_messages::<$source, $target>($bridge, $lane);
+			}
+		)*
+	};
+}
+
+relay_messages_tests! {
+	bridge_hub_rococo_to_bridge_hub_westend: "bridge-hub-rococo-to-bridge-hub-westend", LEGACY_LANE,
+		relay_bridge_hub_rococo_client::BridgeHubRococo => relay_bridge_hub_westend_client::BridgeHubWestend;
+	bridge_hub_westend_to_bridge_hub_rococo: "bridge-hub-westend-to-bridge-hub-rococo", LEGACY_LANE,
+		relay_bridge_hub_westend_client::BridgeHubWestend => relay_bridge_hub_rococo_client::BridgeHubRococo;
+	bridge_hub_kusama_to_bridge_hub_polkadot: "bridge-hub-kusama-to-bridge-hub-polkadot", LEGACY_LANE,
+		relay_bridge_hub_kusama_client::BridgeHubKusama => relay_bridge_hub_polkadot_client::BridgeHubPolkadot;
+	bridge_hub_polkadot_to_bridge_hub_kusama: "bridge-hub-polkadot-to-bridge-hub-kusama", LEGACY_LANE,
+		relay_bridge_hub_polkadot_client::BridgeHubPolkadot => relay_bridge_hub_kusama_client::BridgeHubKusama;
+	polkadot_bulletin_to_bridge_hub_polkadot: "polkadot-bulletin-to-bridge-hub-polkadot", LEGACY_LANE,
+		relay_polkadot_bulletin_client::PolkadotBulletin => relay_bridge_hub_polkadot_client::BridgeHubPolkadot;
+	bridge_hub_polkadot_to_polkadot_bulletin: "bridge-hub-polkadot-to-polkadot-bulletin", LEGACY_LANE,
+		relay_bridge_hub_polkadot_client::BridgeHubPolkadot => relay_polkadot_bulletin_client::PolkadotBulletin;
+	rococo_bulletin_to_bridge_hub_rococo: "rococo-bulletin-to-bridge-hub-rococo", LEGACY_LANE,
+		relay_polkadot_bulletin_client::PolkadotBulletin => relay_bridge_hub_rococo_client::BridgeHubRococo;
+	bridge_hub_rococo_to_rococo_bulletin: "bridge-hub-rococo-to-rococo-bulletin", LEGACY_LANE,
+		relay_bridge_hub_rococo_client::BridgeHubRococo => relay_polkadot_bulletin_client::PolkadotBulletin;
+	// `asset-hub-*-to-bridge-hub-*` bridges relay messages between Asset Hubs
+	asset_hub_rococo_to_asset_hub_westend: "asset-hub-rococo-to-bridge-hub-westend", HASHED_LANE,
+		relay_asset_hub_rococo_client::AssetHubRococo => relay_asset_hub_westend_client::AssetHubWestend;
+	asset_hub_westend_to_asset_hub_rococo: "asset-hub-westend-to-bridge-hub-rococo", HASHED_LANE,
+		relay_asset_hub_westend_client::AssetHubWestend => relay_asset_hub_rococo_client::AssetHubRococo;
+	asset_hub_kusama_to_asset_hub_polkadot: "asset-hub-kusama-to-asset-hub-polkadot", HASHED_LANE,
+		relay_asset_hub_kusama_client::AssetHubKusama => relay_asset_hub_polkadot_client::AssetHubPolkadot;
+	asset_hub_polkadot_to_asset_hub_kusama: "asset-hub-polkadot-to-asset-hub-kusama", HASHED_LANE,
+		relay_asset_hub_polkadot_client::AssetHubPolkadot => relay_asset_hub_kusama_client::AssetHubKusama;
+	moonriver_to_moonbeam: "moonriver-to-moonbeam", HASHED_LANE,
+		relay_moonriver_client::Moonriver => relay_moonbeam_client::Moonbeam;
+	moonbeam_to_moonriver: "moonbeam-to-moonriver", HASHED_LANE,
+		relay_moonbeam_client::Moonbeam => relay_moonriver_client::Moonriver;
+}
@@ -0,0 +1,309 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline end-to-end tests of the `relay-parachains` command.
+
+mod common;
+
+use bp_header_chain::ChainWithGrandpa;
+use bp_runtime::{BasicOperatingMode, Chain as _, Parachain};
+use codec::Encode;
+use common::{
+	assert_submitted, assert_submitted_together, finalized_chain, header_id, scenario,
+	RelayProcess, RELAYER_SIGNER,
+};
+use relay_mock_node::{fixtures, Header, MockChain, MockNode};
+use relay_substrate_client::{Chain, ChainWithRuntimeVersion, RelayChain, UnderlyingChainProvider};
+use sp_runtime::{
+	generic::{Digest, DigestItem},
+	traits::Header as _,
+};
+
+/// Start source relay chain and target nodes and check that the relay submits the parachain
+/// head, finalized at the source relay chain, to the target node.
+fn relay_parachains<SourceRelay, SourcePara, Target>(bridge: &str)
+where
+	SourceRelay: RelayChain + ChainWithRuntimeVersion + UnderlyingChainProvider,
+	SourceRelay::Chain: ChainWithGrandpa,
+	SourcePara: Chain + UnderlyingChainProvider,
+	SourcePara::Chain: Parachain,
+	Target: ChainWithRuntimeVersion,
+{
+	// parachain head is stored at finalized relay chain block
+	let para_head = Header::new(
+		42,
+		Default::default(),
+		Default::default(),
+		Default::default(),
+		Default::default(),
+	);
+	let source_chain = finalized_chain(
+		[fixtures::para_head_storage(
+			SourceRelay::PARAS_PALLET_NAME,
+			SourcePara::Chain::PARACHAIN_ID,
+			&para_head,
+		)]
+		.into(),
+	);
+	let source_genesis = header_id(&source_chain.genesis().header);
+	let source_finalized = header_id(&source_chain.best_finalized().header);
+
+	// target chain knows finalized relay chain block, but it has no parachain heads
+	let mut target_storage = fixtures::grandpa_pallet_storage(
+		SourceRelay::Chain::WITH_CHAIN_GRANDPA_PALLET_NAME,
+		source_genesis,
+	);
+	target_storage.extend([fixtures::operating_mode_storage(
+		SourceRelay::WITH_CHAIN_BRIDGE_PARACHAINS_PALLET_NAME,
+		&BasicOperatingMode::Normal,
+	)]);
+	let target_chain = MockChain::new(target_storage);
+
+	let source = MockNode::start(scenario::<SourceRelay>(source_chain)).unwrap();
+	let target = MockNode::start(
+		scenario::<Target>(target_chain)
+			.with_state_call_response(
+				SourceRelay::BEST_FINALIZED_HEADER_ID_METHOD,
+				fixtures::best_finalized_response(Some(source_finalized)),
+			)
+			.with_state_call_response(
+				SourcePara::BEST_FINALIZED_HEADER_ID_METHOD,
+				fixtures::best_finalized_response(None),
+			),
+	)
+	.unwrap();
+
+	let _relay = RelayProcess::spawn(
+		[
+			"relay-parachains",
+			bridge,
+			"--source-uri",
+			source.url(),
+			"--target-uri",
+			target.url(),
+			"--target-signer",
+			RELAYER_SIGNER,
+			"--no-prometheus",
+		]
+		.map(String::from),
+	);
+
+	assert_submitted(&target, &para_head.hash().encode(), "parachain head");
+}
+
+/// Running nodes of the relay of heads of several parachains.
+///
+/// The relay is stopped before nodes.
+struct SeveralParachainsRelay {
+	_relay: RelayProcess,
+	target: MockNode,
+	_source: MockNode,
+}
+
+/// Start source relay chain node with given parachain heads, target node and the relay of the
+/// `bridge-hub-rococo-to-bridge-hub-westend` bridge with given extra arguments.
+fn relay_several_parachains(para_heads: &[(u32, Header)], args: &[&str]) -> SeveralParachainsRelay {
+	type SourceRelay = relay_rococo_client::Rococo;
+	type Target = relay_bridge_hub_westend_client::BridgeHubWestend;
+
+	// every parachain has its own head, stored at finalized relay chain block
+	let source_chain = finalized_chain(
+		para_heads
+			.iter()
+			.map(|(para_id, para_head)| {
+				fixtures::para_head_storage(SourceRelay::PARAS_PALLET_NAME, *para_id, para_head)
+			})
+			.collect(),
+	);
+	let source_genesis = header_id(&source_chain.genesis().header);
+	let source_finalized = header_id(&source_chain.best_finalized().header);
+
+	// the bridge parachains pallet tracks all parachains and has imported their genesis heads
+	let genesis_para_head = Header::new(
+		0,
+		Default::default(),
+		Default::default(),
+		Default::default(),
+		Default::default(),
+	);
+	let mut target_storage = fixtures::grandpa_pallet_storage(
+		<SourceRelay as UnderlyingChainProvider>::Chain::WITH_CHAIN_GRANDPA_PALLET_NAME,
+		source_genesis,
+	);
+	target_storage.extend([fixtures::operating_mode_storage(
+		SourceRelay::WITH_CHAIN_BRIDGE_PARACHAINS_PALLET_NAME,
+		&BasicOperatingMode::Normal,
+	)]);
+	target_storage.extend(para_heads.iter().map(|(para_id, _)| {
+		fixtures::para_info_storage(
+			SourceRelay::WITH_CHAIN_BRIDGE_PARACHAINS_PALLET_NAME,
+			*para_id,
+			&genesis_para_head,
+			0,
+		)
+	}));
+	let target_chain = MockChain::new(target_storage);
+
+	let source = MockNode::start(scenario::<SourceRelay>(source_chain)).unwrap();
+	let target = MockNode::start(scenario::<Target>(target_chain).with_state_call_response(
+		SourceRelay::BEST_FINALIZED_HEADER_ID_METHOD,
+		fixtures::best_finalized_response(Some(source_finalized)),
+	))
+	.unwrap();
+
+	let relay = RelayProcess::spawn(
+		[
+			"relay-parachains",
+			"bridge-hub-rococo-to-bridge-hub-westend",
+			"--source-uri",
+			source.url(),
+			"--target-uri",
+			target.url(),
+			"--target-signer",
+			RELAYER_SIGNER,
+			"--no-prometheus",
+		]
+		.iter()
+		.chain(args)
+		.map(|arg| arg.to_string()),
+	);
+
+	SeveralParachainsRelay { _relay: relay, target, _source: source }
+}
+
+/// Return head of the parachain with given id, which digest has given size.
+fn para_head(para_id: u32, digest_size: usize) -> (u32, Header) {
+	let digest = Digest { logs: vec![DigestItem::Other(vec![0; digest_size])] };
+	(
+		para_id,
+		Header::new(para_id, Default::default(), Default::default(), Default::default(), digest),
+	)
+}
+
+/// Return encoded hashes of given parachain heads.
+fn para_head_hashes(para_heads: &[(u32, Header)]) -> Vec<Vec<u8>> {
+	para_heads.iter().map(|(_, para_head)| para_head.hash().encode()).collect()
+}
+
+#[test]
+fn relays_parachain_head_by_para_id() {
+	// People Rococo parachain, that has no pipeline in the relay
+	let para_heads = [para_head(1004, 0)];
+	let relay = relay_several_parachains(&para_heads, &["--para-id", "1004"]);
+
+	assert_submitted(&relay.target, &para_head_hashes(&para_heads)[0], "parachain head");
+}
+
+#[test]
+fn relays_heads_of_several_parachains_in_single_transaction() {
+	// BridgeHub and AssetHub Rococo parachains
+	let para_heads = [para_head(1013, 0), para_head(1000, 0)];
+	let relay = relay_several_parachains(&para_heads, &["--para-id", "1013", "--para-id", "1000"]);
+
+	assert_submitted_together(&relay.target, &para_head_hashes(&para_heads), "parachain heads");
+}
+
+#[test]
+fn relays_heads_of_combined_bridges_in_single_transaction() {
+	// BridgeHub Rococo parachain of the bridge and AssetHub Rococo parachain of the combined bridge
+	let para_heads = [para_head(1013, 0), para_head(1000, 0)];
+	let relay = relay_several_parachains(
+		&para_heads,
+		&["--combine-with", "asset-hub-rococo-to-bridge-hub-westend"],
+	);
+
+	assert_submitted_together(&relay.target, &para_head_hashes(&para_heads), "parachain heads");
+}
+
+#[test]
+fn relays_heads_separately_if_combined_call_exceeds_max_extrinsic_size() {
+	type Target = relay_bridge_hub_westend_client::BridgeHubWestend;
+
+	// every head fits into the extrinsic, but both heads don't
+	let digest_size = Target::max_extrinsic_size() as usize / 2 + 1024;
+	let para_heads = [para_head(1013, digest_size), para_head(1000, digest_size)];
+	let relay = relay_several_parachains(
+		&para_heads,
+		&["--combine-with", "asset-hub-rococo-to-bridge-hub-westend"],
+	);
+
+	let para_head_hashes = para_head_hashes(&para_heads);
+	for para_head_hash in &para_head_hashes {
+		assert_submitted(&relay.target, para_head_hash, "parachain head");
+	}
+	let contains = |extrinsic: &[u8], needle: &[u8]| {
+		extrinsic.windows(needle.len()).any(|window| window == needle)
+	};
+	assert!(relay.target.submitted_extrinsics().iter().all(|extrinsic| !para_head_hashes
+		.iter()
+		.all(|para_head_hash| contains(extrinsic, para_head_hash))));
+}
+
+macro_rules! relay_parachains_tests {
+	($($name:ident: $bridge:literal, $source_para:ty, $source_relay:ty => $target:ty;)*) => {
+		$(
+			#[test]
+			fn $name() {
+				relay_parachains::<$source_relay, $source_para, $target>($bridge);
+			}
+		)*
+	};
+}
+
+relay_parachains_tests! {
+	bridge_hub_kusama_to_bridge_hub_polkadot: "bridge-hub-kusama-to-bridge-hub-polkadot",
+		relay_bridge_hub_kusama_client::BridgeHubKusama, relay_kusama_client::Kusama
+			=> relay_bridge_hub_polkadot_client::BridgeHubPolkadot;
+	bridge_hub_polkadot_to_bridge_hub_kusama: "bridge-hub-polkadot-to-bridge-hub-kusama",
+		relay_bridge_hub_polkadot_client::BridgeHubPolkadot, relay_polkadot_client::Polkadot
+			=> relay_bridge_hub_kusama_client::BridgeHubKusama;
+	asset_hub_kusama_to_bridge_hub_polkadot: "asset-hub-kusama-to-bridge-hub-polkadot",
+		relay_asset_hub_kusama_client::AssetHubKusama, relay_kusama_client::Kusama
+			=> relay_bridge_hub_polkadot_client::BridgeHubPolkadot;
+	asset_hub_polkadot_to_bridge_hub_kusama: "asset-hub-polkadot-to-bridge-hub-kusama",
+		relay_asset_hub_polkadot_client::AssetHubPolkadot, relay_polkadot_client::Polkadot
+			=> relay_bridge_hub_kusama_client::BridgeHubKusama;
+	moonriver_to_bridge_hub_polkadot: "moonriver-to-bridge-hub-polkadot",
+		relay_moonriver_client::Moonriver, relay_kusama_client::Kusama
+			=> relay_bridge_hub_polkadot_client::BridgeHubPolkadot;
+	moonbeam_to_bridge_hub_kusama: "moonbeam-to-bridge-hub-kusama",
+		relay_moonbeam_client::Moonbeam, relay_polkadot_client::Polkadot
+			=> relay_bridge_hub_kusama_client::BridgeHubKusama;
+	polkadot_to_polkadot_bulletin: "polkadot-to-polkadot-bulletin",
+		relay_bridge_hub_polkadot_client::BridgeHubPolkadot, relay_polkadot_client::Polkadot
+			=> relay_polkadot_bulletin_client::PolkadotBulletin;
+	rococo_to_rococo_bulletin: "rococo-to-rococo-bulletin",
+		relay_bridge_hub_rococo_client::BridgeHubRococo, relay_rococo_client::Rococo
+			=> relay_polkadot_bulletin_client::PolkadotBulletin;
+	bridge_hub_rococo_to_bridge_hub_westend: "bridge-hub-rococo-to-bridge-hub-westend",
+		relay_bridge_hub_rococo_client::BridgeHubRococo, relay_rococo_client::Rococo
+			=> relay_bridge_hub_westend_client::BridgeHubWestend;
+	bridge_hub_westend_to_bridge_hub_rococo: "bridge-hub-westend-to-bridge-hub-rococo",
+		relay_bridge_hub_westend_client::BridgeHubWestend, relay_westend_client::Westend
+			=> relay_bridge_hub_rococo_client::BridgeHubRococo;
+	asset_hub_rococo_to_bridge_hub_westend: "asset-hub-rococo-to-bridge-hub-westend",
+		relay_asset_hub_rococo_client::AssetHubRococo, relay_rococo_client::Rococo
+			=> relay_bridge_hub_westend_client::BridgeHubWestend;
+	asset_hub_westend_to_bridge_hub_rococo: "asset-hub-westend-to-bridge-hub-rococo",
+		relay_asset_hub_westend_client::AssetHubWestend, relay_westend_client::Westend
+			=> relay_bridge_hub_rococo_client::BridgeHubRococo;
+	moonriver_to_moonbeam: "moonriver-to-moonbeam",
+		relay_moonriver_client::Moonriver, relay_kusama_client::Kusama
+			=> relay_moonbeam_client::Moonbeam;
+	moonbeam_to_moonriver: "moonbeam-to-moonriver",
+		relay_moonbeam_client::Moonbeam, relay_polkadot_client::Polkadot
+			=> relay_moonriver_client::Moonriver;
+}
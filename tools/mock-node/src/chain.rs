@@ -0,0 +1,213 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Linear chain of blocks, served by the mock node.
+
+use crate::{BlockNumber, Hash, Header};
+
+use sp_consensus_grandpa::GRANDPA_ENGINE_ID;
+use sp_runtime::{
+	generic::{Block, Digest, SignedBlock},
+	traits::{BlakeTwo256, Hash as HashT, Header as HeaderT},
+	Justifications, OpaqueExtrinsic, StateVersion,
+};
+use sp_state_machine::InMemoryBackend;
+use std::collections::BTreeMap;
+
+/// Full storage of the block: key => value.
+pub type Storage = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// Signed block type, returned by the `chain_getBlock` method.
+pub type MockSignedBlock = SignedBlock<Block<Header, OpaqueExtrinsic>>;
+
+/// Block of the mock chain.
+#[derive(Clone, Debug)]
+pub struct MockBlock {
+	/// Block header.
+	pub header: Header,
+	/// Encoded block extrinsics.
+	pub extrinsics: Vec<Vec<u8>>,
+	/// Encoded GRANDPA justification of the block, if it has been finalized with justification.
+	pub justification: Option<Vec<u8>>,
+	/// Block storage (state after the block has been imported).
+	pub storage: Storage,
+}
+
+impl MockBlock {
+	/// Return block hash.
+	pub fn hash(&self) -> Hash {
+		self.header.hash()
+	}
+
+	/// Return block number.
+	pub fn number(&self) -> BlockNumber {
+		*self.header.number()
+	}
+
+	/// Convert block into the signed block, that is returned by the node RPC.
+	pub fn signed_block(&self) -> Result<MockSignedBlock, String> {
+		let extrinsics = self
+			.extrinsics
+			.iter()
+			.map(|extrinsic| {
+				OpaqueExtrinsic::from_bytes(extrinsic)
+					.map_err(|e| format!("Invalid extrinsic in block {}: {e:?}", self.number()))
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(SignedBlock {
+			block: Block { header: self.header.clone(), extrinsics },
+			justifications: self
+				.justification
+				.clone()
+				.map(|justification| Justifications::from((GRANDPA_ENGINE_ID, justification))),
+		})
+	}
+}
+
+/// Linear chain of blocks. Every block carries the full copy of its storage, which is fine for
+/// short chains of tests.
+#[derive(Clone, Debug)]
+pub struct MockChain {
+	blocks: Vec<MockBlock>,
+	best_finalized: BlockNumber,
+}
+
+impl MockChain {
+	/// Create chain with genesis block, having given storage.
+	pub fn new(genesis_storage: Storage) -> Self {
+		let genesis = build_block(Hash::zero(), 0, genesis_storage, Digest::default(), Vec::new());
+		MockChain { blocks: vec![genesis], best_finalized: 0 }
+	}
+
+	/// Return genesis block.
+	pub fn genesis(&self) -> &MockBlock {
+		&self.blocks[0]
+	}
+
+	/// Return best block.
+	pub fn best(&self) -> &MockBlock {
+		self.blocks.last().expect("chain always has genesis block; qed")
+	}
+
+	/// Return best finalized block.
+	pub fn best_finalized(&self) -> &MockBlock {
+		&self.blocks[self.best_finalized as usize]
+	}
+
+	/// Return block with given number.
+	pub fn block_by_number(&self, number: BlockNumber) -> Option<&MockBlock> {
+		self.blocks.get(number as usize)
+	}
+
+	/// Return block with given hash.
+	pub fn block_by_hash(&self, hash: Hash) -> Option<&MockBlock> {
+		self.blocks.iter().find(|block| block.hash() == hash)
+	}
+
+	/// Return block with given hash or the best block if hash is not specified.
+	pub fn block_at(&self, at: Option<Hash>) -> Result<&MockBlock, String> {
+		match at {
+			Some(hash) => self.block_by_hash(hash).ok_or_else(|| format!("Unknown block {hash:?}")),
+			None => Ok(self.best()),
+		}
+	}
+
+	/// Import new best block on top of the current best block.
+	///
+	/// The storage of new block is the storage of its parent with given changes applied (`None`
+	/// value means that the key is removed). Returns hash of the imported block.
+	pub fn import_block(
+		&mut self,
+		changes: impl IntoIterator<Item = (Vec<u8>, Option<Vec<u8>>)>,
+		digest: Digest,
+		extrinsics: Vec<Vec<u8>>,
+	) -> Hash {
+		let parent = self.best();
+		let mut storage = parent.storage.clone();
+		for (key, value) in changes {
+			match value {
+				Some(value) => storage.insert(key, value),
+				None => storage.remove(&key),
+			};
+		}
+
+		let block = build_block(parent.hash(), parent.number() + 1, storage, digest, extrinsics);
+		let hash = block.hash();
+		self.blocks.push(block);
+		hash
+	}
+
+	/// Finalize block with given number, optionally attaching the justification to it.
+	pub fn finalize(
+		&mut self,
+		number: BlockNumber,
+		justification: Option<Vec<u8>>,
+	) -> Result<Hash, String> {
+		if number < self.best_finalized {
+			return Err(format!(
+				"Cannot finalize block {number}: block {} is already finalized",
+				self.best_finalized,
+			))
+		}
+
+		let block = self
+			.blocks
+			.get_mut(number as usize)
+			.ok_or_else(|| format!("Cannot finalize unknown block {number}"))?;
+		if justification.is_some() {
+			block.justification = justification;
+		}
+		self.best_finalized = number;
+		Ok(block.hash())
+	}
+
+	/// Read storage value at given block.
+	pub fn storage(&self, at: Option<Hash>, key: &[u8]) -> Result<Option<Vec<u8>>, String> {
+		Ok(self.block_at(at)?.storage.get(key).cloned())
+	}
+
+	/// Generate storage proof of given keys at given block.
+	pub fn read_proof(&self, at: Option<Hash>, keys: Vec<Vec<u8>>) -> Result<Vec<Vec<u8>>, String> {
+		let backend = trie_backend(&self.block_at(at)?.storage);
+		sp_state_machine::prove_read(backend, keys)
+			.map(|proof| proof.into_iter_nodes().collect())
+			.map_err(|e| format!("Failed to generate storage proof: {e:?}"))
+	}
+}
+
+/// Build block with given parameters, computing state and extrinsics roots.
+fn build_block(
+	parent_hash: Hash,
+	number: BlockNumber,
+	storage: Storage,
+	digest: Digest,
+	extrinsics: Vec<Vec<u8>>,
+) -> MockBlock {
+	let state_root = *trie_backend(&storage).root();
+	let extrinsics_root = BlakeTwo256::ordered_trie_root(extrinsics.clone(), StateVersion::V1);
+	MockBlock {
+		header: Header::new(number, extrinsics_root, state_root, parent_hash, digest),
+		extrinsics,
+		justification: None,
+		storage,
+	}
+}
+
+/// Build in-memory trie backend from the block storage.
+fn trie_backend(storage: &Storage) -> InMemoryBackend<BlakeTwo256> {
+	let storage = storage.iter().map(|(key, value)| (key.clone(), Some(value.clone()))).collect();
+	InMemoryBackend::<BlakeTwo256>::from((vec![(None, storage)], StateVersion::V1))
+}
@@ -0,0 +1,224 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage entries and runtime API responses of bridge pallets, that are used to build test
+//! scenarios.
+//!
+//! GRANDPA justifications are signed by the `bp_test_utils` authorities, so the authority set
+//! that is stored in the bridge GRANDPA pallet must be the [`grandpa_authority_set`].
+
+use crate::{BlockNumber, Hash, Header, MockBlock, Storage};
+
+use bp_header_chain::AuthoritySet;
+use bp_messages::{
+	InboundLaneData, InboundMessageDetails, MessageNonce, MessagePayload, MessagesOperatingMode,
+	OutboundLaneData, OutboundMessageDetails,
+};
+use bp_parachains::{
+	parachain_head_storage_key_at_source, BestParaHeadHash, ParaInfo, ParasInfoKeyProvider,
+};
+use bp_polkadot_core::parachains::{ParaHead, ParaId};
+use bp_runtime::{BasicOperatingMode, HeaderId, StorageMapKeyProvider};
+use codec::{Decode, Encode, Output};
+use sp_runtime::traits::Header as _;
+use sp_weights::Weight;
+
+/// Dispatch weight of every message, reported by the message details runtime API.
+pub const MESSAGE_DISPATCH_WEIGHT: Weight = Weight::from_parts(1_000_000, 1_000);
+
+/// Return encoded GRANDPA justification of given header, signed by test authorities.
+pub fn grandpa_justification(header: &Header) -> Vec<u8> {
+	bp_test_utils::make_default_justification(header).encode()
+}
+
+/// Return GRANDPA authority set, that has signed justifications of [`grandpa_justification`].
+pub fn grandpa_authority_set() -> AuthoritySet {
+	AuthoritySet::new(bp_test_utils::authority_list(), bp_test_utils::TEST_GRANDPA_SET_ID)
+}
+
+/// Return storage of initialized bridge GRANDPA pallet with given best finalized header.
+pub fn grandpa_pallet_storage(pallet_name: &str, best_finalized: (BlockNumber, Hash)) -> Storage {
+	let (number, hash) = best_finalized;
+	Storage::from([
+		(
+			bp_header_chain::storage_keys::best_finalized_key(pallet_name).0,
+			HeaderId(hash, number).encode(),
+		),
+		(
+			bp_header_chain::storage_keys::current_authority_set_key(pallet_name).0,
+			grandpa_authority_set().encode(),
+		),
+		operating_mode_storage(pallet_name, &BasicOperatingMode::Normal),
+	])
+}
+
+/// Return storage entry with given operating mode of the bridge pallet.
+///
+/// All bridge pallets store their operating mode under the same storage key.
+pub fn operating_mode_storage(pallet_name: &str, mode: &impl Encode) -> (Vec<u8>, Vec<u8>) {
+	(bp_header_chain::storage_keys::pallet_operating_mode_key(pallet_name).0, mode.encode())
+}
+
+/// Return encoded response of the `<Chain>FinalityApi_best_finalized` runtime API method.
+pub fn best_finalized_response(best_finalized: Option<(BlockNumber, Hash)>) -> Vec<u8> {
+	best_finalized.map(|(number, hash)| HeaderId(hash, number)).encode()
+}
+
+/// Return encoded response of the `<Chain>FinalityApi_free_headers_interval` runtime API method,
+/// meaning that free headers are not supported.
+pub fn free_headers_interval_response() -> Vec<u8> {
+	None::<BlockNumber>.encode()
+}
+
+/// Return storage entry with the head of given parachain, stored by the relay chain `paras`
+/// pallet.
+pub fn para_head_storage(
+	paras_pallet_name: &str,
+	para_id: u32,
+	para_head: &Header,
+) -> (Vec<u8>, Vec<u8>) {
+	(
+		parachain_head_storage_key_at_source(paras_pallet_name, ParaId(para_id)).0,
+		ParaHead(para_head.encode()).encode(),
+	)
+}
+
+/// Return storage entry with the best head of given parachain, imported by the bridge parachains
+/// pallet at given relay chain block.
+pub fn para_info_storage(
+	pallet_name: &str,
+	para_id: u32,
+	para_head: &Header,
+	at_relay_block_number: BlockNumber,
+) -> (Vec<u8>, Vec<u8>) {
+	(
+		ParasInfoKeyProvider::final_key(pallet_name, &ParaId(para_id)).0,
+		ParaInfo {
+			best_head_hash: BestParaHeadHash { at_relay_block_number, head_hash: para_head.hash() },
+			next_imported_hash_position: 0,
+		}
+		.encode(),
+	)
+}
+
+/// Raw lane identifier.
+///
+/// Bridges use different lane identifier types, but all of them are encoded as raw bytes. So
+/// the mock node works with encoded lane identifiers, passed to the relay using `--lane`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawLaneId(pub Vec<u8>);
+
+impl Encode for RawLaneId {
+	fn size_hint(&self) -> usize {
+		self.0.len()
+	}
+
+	fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+		dest.write(&self.0)
+	}
+}
+
+/// Return storage of the outbound lane with given messages, stored by the messages pallet.
+///
+/// Messages have nonces `1..=messages.len()`.
+pub fn outbound_lane_storage(
+	pallet_name: &str,
+	lane: &RawLaneId,
+	messages: &[MessagePayload],
+) -> Storage {
+	let mut lane_data = OutboundLaneData::opened();
+	lane_data.latest_generated_nonce = messages.len() as MessageNonce;
+
+	let mut storage = Storage::from([
+		(
+			bp_messages::storage_keys::outbound_lane_data_key(pallet_name, lane).0,
+			lane_data.encode(),
+		),
+		messages_operating_mode_storage(pallet_name),
+	]);
+	for (nonce, payload) in (1..).zip(messages) {
+		storage.insert(
+			bp_messages::storage_keys::message_key(pallet_name, lane, nonce).0,
+			payload.encode(),
+		);
+	}
+	storage
+}
+
+/// Return storage of the empty inbound lane, stored by the messages pallet.
+pub fn inbound_lane_storage(pallet_name: &str, lane: &RawLaneId) -> Storage {
+	Storage::from([
+		(
+			bp_messages::storage_keys::inbound_lane_data_key(pallet_name, lane).0,
+			InboundLaneData::<()>::opened().encode(),
+		),
+		messages_operating_mode_storage(pallet_name),
+	])
+}
+
+/// Return storage entry with normal operating mode of the messages pallet.
+fn messages_operating_mode_storage(pallet_name: &str) -> (Vec<u8>, Vec<u8>) {
+	operating_mode_storage(pallet_name, &MessagesOperatingMode::Basic(BasicOperatingMode::Normal))
+}
+
+/// Handler of the `To<Chain>OutboundLaneApi_message_details` runtime API method.
+///
+/// It reads messages from the block storage, so the outbound lane must be created with the
+/// [`outbound_lane_storage`].
+pub fn outbound_message_details(
+	pallet_name: &'static str,
+	lane: RawLaneId,
+) -> impl Fn(&MockBlock, &[u8]) -> Result<Vec<u8>, String> + Send + Sync + 'static {
+	move |block, mut args| {
+		args = args
+			.strip_prefix(lane.0.as_slice())
+			.ok_or_else(|| "Message details are requested for unknown lane".to_string())?;
+		let (begin, end) = <(MessageNonce, MessageNonce)>::decode(&mut args)
+			.map_err(|e| format!("Invalid message details arguments: {e:?}"))?;
+
+		let details = (begin..=end)
+			.filter_map(|nonce| {
+				let key = bp_messages::storage_keys::message_key(pallet_name, &lane, nonce).0;
+				let payload = MessagePayload::decode(&mut &block.storage.get(&key)?[..]).ok()?;
+				Some(OutboundMessageDetails {
+					nonce,
+					dispatch_weight: MESSAGE_DISPATCH_WEIGHT,
+					size: payload.len() as u32,
+				})
+			})
+			.collect::<Vec<_>>();
+		Ok(details.encode())
+	}
+}
+
+/// Handler of the `From<Chain>InboundLaneApi_message_details` runtime API method.
+pub fn inbound_message_details(
+	lane: RawLaneId,
+) -> impl Fn(&MockBlock, &[u8]) -> Result<Vec<u8>, String> + Send + Sync + 'static {
+	move |_, mut args| {
+		args = args
+			.strip_prefix(lane.0.as_slice())
+			.ok_or_else(|| "Message details are requested for unknown lane".to_string())?;
+		let messages = Vec::<(MessagePayload, OutboundMessageDetails)>::decode(&mut args)
+			.map_err(|e| format!("Invalid message details arguments: {e:?}"))?;
+
+		let details = messages
+			.into_iter()
+			.map(|(_, details)| InboundMessageDetails { dispatch_weight: details.dispatch_weight })
+			.collect::<Vec<_>>();
+		Ok(details.encode())
+	}
+}
@@ -0,0 +1,277 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! In-process mock of the Substrate node, used by offline end-to-end relay tests.
+//!
+//! The mock node serves the subset of the Substrate JSON-RPC that the relay uses: headers and
+//! blocks, finality and justifications subscriptions, storage and storage proofs, runtime API
+//! calls and transaction submission. The node state is defined by the [`Scenario`]: the chain
+//! with its storage, runtime version and handlers of runtime API calls. The chain may be extended
+//! while the node is running and submitted extrinsics are recorded, so the test may check what
+//! the relay has submitted.
+//!
+//! All chains are assumed to use `bp_polkadot_core` block header. It is true for all chains that
+//! the relay supports.
+
+#![warn(missing_docs)]
+
+pub use chain::{MockBlock, MockChain, MockSignedBlock, Storage};
+pub use scenario::{ExtrinsicHandler, Scenario, StateCallHandler};
+
+pub mod fixtures;
+
+mod chain;
+mod rpc;
+mod scenario;
+
+use jsonrpsee::server::{Server, ServerHandle};
+use sp_core::Bytes;
+use sp_runtime::{generic::Digest, traits::Header as HeaderT};
+use std::{
+	sync::{Arc, Mutex, MutexGuard},
+	time::{Duration, Instant},
+};
+use tokio::sync::broadcast;
+
+/// Block header of all mock chains.
+pub type Header = bp_polkadot_core::Header;
+/// Block hash of all mock chains.
+pub type Hash = bp_polkadot_core::Hash;
+/// Block number of all mock chains.
+pub type BlockNumber = bp_polkadot_core::BlockNumber;
+
+/// Capacity of subscription channels.
+const SUBSCRIPTION_CHANNEL_CAPACITY: usize = 1024;
+/// Max size of RPC requests and responses in bytes.
+const MAX_RPC_PAYLOAD_SIZE: u32 = 15 * 1024 * 1024;
+/// Interval between checks of submitted extrinsics.
+const EXTRINSICS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Running mock node.
+///
+/// The node is stopped when this structure is dropped.
+pub struct MockNode {
+	url: String,
+	shared: Arc<Shared>,
+	server: ServerHandle,
+	// keep runtime alive while the server is running
+	_runtime: tokio::runtime::Runtime,
+}
+
+impl MockNode {
+	/// Start the mock node, listening on a random local port.
+	pub fn start(scenario: Scenario) -> Result<Self, String> {
+		let runtime = tokio::runtime::Builder::new_multi_thread()
+			.worker_threads(2)
+			.enable_all()
+			.build()
+			.map_err(|e| format!("Failed to start mock node runtime: {e}"))?;
+
+		let shared = Arc::new(Shared::new(scenario));
+		let module = rpc::rpc_module(shared.clone())
+			.map_err(|e| format!("Failed to register mock node RPC methods: {e}"))?;
+		let (url, server) = runtime.block_on(async move {
+			// same limits as the Substrate node has by default
+			let server = Server::builder()
+				.max_request_body_size(MAX_RPC_PAYLOAD_SIZE)
+				.max_response_body_size(MAX_RPC_PAYLOAD_SIZE)
+				.build("127.0.0.1:0")
+				.await
+				.map_err(|e| format!("Failed to start mock node RPC server: {e}"))?;
+			let address = server
+				.local_addr()
+				.map_err(|e| format!("Failed to read mock node RPC server address: {e}"))?;
+			Ok::<_, String>((format!("ws://{address}"), server.start(module)))
+		})?;
+
+		log::info!(target: "mock-node", "Started mock {} node at {url}", shared.spec_name());
+
+		Ok(MockNode { url, shared, server, _runtime: runtime })
+	}
+
+	/// Return websocket URL of the node.
+	pub fn url(&self) -> &str {
+		&self.url
+	}
+
+	/// Read the chain of the node.
+	pub fn with_chain<R>(&self, f: impl FnOnce(&MockChain) -> R) -> R {
+		f(&self.shared.state().scenario.chain)
+	}
+
+	/// Import new best block with given storage changes. Returns hash of the new block.
+	pub fn import_block(
+		&self,
+		changes: impl IntoIterator<Item = (Vec<u8>, Option<Vec<u8>>)>,
+		digest: Digest,
+	) -> Hash {
+		self.shared.import_block(changes, digest, Vec::new())
+	}
+
+	/// Finalize block with given number, optionally attaching GRANDPA justification to it.
+	///
+	/// The justification is also sent to the justifications subscribers.
+	pub fn finalize(
+		&self,
+		number: BlockNumber,
+		justification: Option<Vec<u8>>,
+	) -> Result<Hash, String> {
+		self.shared.finalize(number, justification)
+	}
+
+	/// Return all extrinsics, submitted to the node so far.
+	pub fn submitted_extrinsics(&self) -> Vec<Vec<u8>> {
+		self.shared.state().submitted.clone()
+	}
+
+	/// Wait until the extrinsic that matches given predicate is submitted to the node.
+	///
+	/// Returns `None` if there has been no such extrinsic within given timeout.
+	pub fn wait_for_extrinsic(
+		&self,
+		predicate: impl Fn(&[u8]) -> bool,
+		timeout: Duration,
+	) -> Option<Vec<u8>> {
+		let started_at = Instant::now();
+		loop {
+			let extrinsic = self
+				.shared
+				.state()
+				.submitted
+				.iter()
+				.find(|extrinsic| predicate(extrinsic))
+				.cloned();
+			if extrinsic.is_some() || started_at.elapsed() >= timeout {
+				return extrinsic
+			}
+
+			std::thread::sleep(EXTRINSICS_POLL_INTERVAL);
+		}
+	}
+}
+
+impl Drop for MockNode {
+	fn drop(&mut self) {
+		let _ = self.server.stop();
+	}
+}
+
+/// State, shared by the node handle and RPC methods.
+pub(crate) struct Shared {
+	state: Mutex<State>,
+	new_heads: broadcast::Sender<Header>,
+	finalized_heads: broadcast::Sender<Header>,
+	justifications: broadcast::Sender<Bytes>,
+}
+
+/// Mutable state of the node.
+pub(crate) struct State {
+	pub(crate) scenario: Scenario,
+	pub(crate) submitted: Vec<Vec<u8>>,
+}
+
+impl Shared {
+	fn new(scenario: Scenario) -> Self {
+		Shared {
+			state: Mutex::new(State { scenario, submitted: Vec::new() }),
+			new_heads: broadcast::channel(SUBSCRIPTION_CHANNEL_CAPACITY).0,
+			finalized_heads: broadcast::channel(SUBSCRIPTION_CHANNEL_CAPACITY).0,
+			justifications: broadcast::channel(SUBSCRIPTION_CHANNEL_CAPACITY).0,
+		}
+	}
+
+	/// Lock the node state.
+	pub(crate) fn state(&self) -> MutexGuard<State> {
+		// the mutex may only be poisoned if the test has already failed
+		self.state.lock().unwrap_or_else(|e| e.into_inner())
+	}
+
+	/// Return runtime name of the chain.
+	pub(crate) fn spec_name(&self) -> String {
+		self.state().scenario.spec_name.clone()
+	}
+
+	/// Subscribe to new best headers.
+	pub(crate) fn subscribe_new_heads(&self) -> broadcast::Receiver<Header> {
+		self.new_heads.subscribe()
+	}
+
+	/// Subscribe to new finalized headers.
+	pub(crate) fn subscribe_finalized_heads(&self) -> broadcast::Receiver<Header> {
+		self.finalized_heads.subscribe()
+	}
+
+	/// Subscribe to GRANDPA justifications.
+	pub(crate) fn subscribe_justifications(&self) -> broadcast::Receiver<Bytes> {
+		self.justifications.subscribe()
+	}
+
+	/// Import new best block and notify subscribers.
+	pub(crate) fn import_block(
+		&self,
+		changes: impl IntoIterator<Item = (Vec<u8>, Option<Vec<u8>>)>,
+		digest: Digest,
+		extrinsics: Vec<Vec<u8>>,
+	) -> Hash {
+		let (hash, header) = {
+			let mut state = self.state();
+			let hash = state.scenario.chain.import_block(changes, digest, extrinsics);
+			(hash, state.scenario.chain.best().header.clone())
+		};
+
+		log::debug!(target: "mock-node", "Imported block {}: {hash:?}", header.number());
+		let _ = self.new_heads.send(header);
+		hash
+	}
+
+	/// Finalize block and notify subscribers.
+	pub(crate) fn finalize(
+		&self,
+		number: BlockNumber,
+		justification: Option<Vec<u8>>,
+	) -> Result<Hash, String> {
+		let (hash, header) = {
+			let mut state = self.state();
+			let hash = state.scenario.chain.finalize(number, justification.clone())?;
+			(hash, state.scenario.chain.best_finalized().header.clone())
+		};
+
+		log::debug!(target: "mock-node", "Finalized block {number}: {hash:?}");
+		let _ = self.finalized_heads.send(header);
+		if let Some(justification) = justification {
+			let _ = self.justifications.send(Bytes(justification));
+		}
+		Ok(hash)
+	}
+
+	/// Include submitted extrinsic into the new finalized block. Returns hash of this block.
+	pub(crate) fn submit_extrinsic(&self, extrinsic: Vec<u8>) -> Result<Hash, String> {
+		let changes = {
+			let mut state = self.state();
+			state.submitted.push(extrinsic.clone());
+			match state.scenario.on_extrinsic.clone() {
+				Some(handler) => handler(&state.scenario.chain, &extrinsic),
+				None => Vec::new(),
+			}
+		};
+
+		log::debug!(target: "mock-node", "Received extrinsic: 0x{}", hex::encode(&extrinsic));
+		let hash = self.import_block(changes, Digest::default(), vec![extrinsic]);
+		let number = self.state().scenario.chain.best().number();
+		self.finalize(number, None)?;
+		Ok(hash)
+	}
+}
@@ -0,0 +1,272 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! JSON-RPC methods of the mock node.
+
+use crate::{BlockNumber, Hash, Shared};
+
+use jsonrpsee::{types::ErrorObjectOwned, PendingSubscriptionSink, RpcModule, SubscriptionMessage};
+use sc_transaction_pool_api::TransactionStatus;
+use serde::Serialize;
+use sp_core::{
+	storage::{StorageData, StorageKey},
+	Bytes,
+};
+use sp_runtime::traits::{BlakeTwo256, Hash as HashT};
+use sp_version::RuntimeVersion;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Error code of all errors, returned by the mock node.
+const MOCK_NODE_ERROR_CODE: i32 = -32000;
+
+/// Result of the RPC method.
+type RpcResult<T> = Result<T, ErrorObjectOwned>;
+
+/// Build RPC module with all methods, supported by the mock node.
+pub(crate) fn rpc_module(
+	shared: Arc<Shared>,
+) -> Result<RpcModule<Arc<Shared>>, Box<dyn std::error::Error + Send + Sync>> {
+	let mut module = RpcModule::new(shared);
+
+	// system methods
+
+	module.register_method(
+		"system_health",
+		|_, _, _| serde_json::json!({ "peers": 1, "isSyncing": false, "shouldHavePeers": true }),
+	)?;
+	module.register_method("system_properties", |_, _, _| serde_json::json!({}))?;
+	module.register_method("system_chain", |_, shared, _| shared.spec_name())?;
+	module.register_method("system_accountNextIndex", |_, shared, _| {
+		// the mock node doesn't check signatures, so the nonce is just a number of extrinsics
+		shared.state().submitted.len() as u32
+	})?;
+
+	// chain methods
+
+	module.register_method("chain_getBlockHash", |params, shared, _| -> RpcResult<_> {
+		let number = parse_block_number(params.sequence().optional_next()?)?;
+		let state = shared.state();
+		Ok(match number {
+			Some(number) => state.scenario.chain.block_by_number(number).map(|block| block.hash()),
+			None => Some(state.scenario.chain.best().hash()),
+		})
+	})?;
+	module.register_method("chain_getHeader", |params, shared, _| -> RpcResult<_> {
+		let at: Option<Hash> = params.sequence().optional_next()?;
+		let state = shared.state();
+		Ok(match at {
+			Some(hash) =>
+				state.scenario.chain.block_by_hash(hash).map(|block| block.header.clone()),
+			None => Some(state.scenario.chain.best().header.clone()),
+		})
+	})?;
+	module.register_method("chain_getBlock", |params, shared, _| -> RpcResult<_> {
+		let at: Option<Hash> = params.sequence().optional_next()?;
+		let state = shared.state();
+		let block = match at {
+			Some(hash) => state.scenario.chain.block_by_hash(hash),
+			None => Some(state.scenario.chain.best()),
+		};
+		block.map(|block| block.signed_block()).transpose().map_err(error)
+	})?;
+	module.register_method("chain_getFinalizedHead", |_, shared, _| {
+		shared.state().scenario.chain.best_finalized().hash()
+	})?;
+	module.register_subscription(
+		"chain_subscribeNewHeads",
+		"chain_newHead",
+		"chain_unsubscribeNewHeads",
+		|_, pending, shared, _| async move {
+			let best = shared.state().scenario.chain.best().header.clone();
+			let receiver = shared.subscribe_new_heads();
+			forward(pending, Some(best), receiver).await
+		},
+	)?;
+	module.register_subscription(
+		"chain_subscribeFinalizedHeads",
+		"chain_finalizedHead",
+		"chain_unsubscribeFinalizedHeads",
+		|_, pending, shared, _| async move {
+			let best_finalized = shared.state().scenario.chain.best_finalized().header.clone();
+			let receiver = shared.subscribe_finalized_heads();
+			forward(pending, Some(best_finalized), receiver).await
+		},
+	)?;
+
+	// state methods
+
+	module.register_method("state_call", |params, shared, _| -> RpcResult<_> {
+		let mut params = params.sequence();
+		let method: String = params.next()?;
+		let data: Bytes = params.next()?;
+		let at: Option<Hash> = params.optional_next()?;
+
+		let state = shared.state();
+		let block = state.scenario.chain.block_at(at).map_err(error)?;
+		let handler = state
+			.scenario
+			.state_calls
+			.get(&method)
+			.ok_or_else(|| error(format!("Unsupported runtime API method: {method}")))?;
+		handler(block, &data).map(Bytes).map_err(error)
+	})?;
+	module.register_method("state_getStorage", |params, shared, _| -> RpcResult<_> {
+		let mut params = params.sequence();
+		let key: StorageKey = params.next()?;
+		let at: Option<Hash> = params.optional_next()?;
+
+		let value = shared.state().scenario.chain.storage(at, &key.0).map_err(error)?;
+		Ok(value.map(StorageData))
+	})?;
+	module.register_method("state_getReadProof", |params, shared, _| -> RpcResult<_> {
+		let mut params = params.sequence();
+		let keys: Vec<StorageKey> = params.next()?;
+		let at: Option<Hash> = params.optional_next()?;
+
+		let state = shared.state();
+		let at = state.scenario.chain.block_at(at).map_err(error)?.hash();
+		let proof = state
+			.scenario
+			.chain
+			.read_proof(Some(at), keys.into_iter().map(|key| key.0).collect())
+			.map_err(error)?;
+		Ok(ReadProof { at, proof: proof.into_iter().map(Bytes).collect() })
+	})?;
+	module.register_method("state_getRuntimeVersion", |_, shared, _| {
+		let state = shared.state();
+		RuntimeVersion {
+			spec_name: state.scenario.spec_name.clone().into(),
+			impl_name: state.scenario.spec_name.clone().into(),
+			spec_version: state.scenario.spec_version,
+			transaction_version: state.scenario.transaction_version,
+			..Default::default()
+		}
+	})?;
+
+	// author methods
+
+	module.register_method("author_submitExtrinsic", |params, shared, _| -> RpcResult<_> {
+		let extrinsic: Bytes = params.one()?;
+		let hash = BlakeTwo256::hash(&extrinsic);
+		shared.submit_extrinsic(extrinsic.0).map_err(error)?;
+		Ok(hash)
+	})?;
+	module.register_method("author_pendingExtrinsics", |_, _, _| Vec::<Bytes>::new())?;
+	module.register_subscription(
+		"author_submitAndWatchExtrinsic",
+		"author_extrinsicUpdate",
+		"author_unwatchExtrinsic",
+		|params, pending, shared, _| async move {
+			let extrinsic: Bytes = match params.one() {
+				Ok(extrinsic) => extrinsic,
+				Err(e) => {
+					pending.reject(e).await;
+					return
+				},
+			};
+			let block_hash = match shared.submit_extrinsic(extrinsic.0) {
+				Ok(block_hash) => block_hash,
+				Err(e) => {
+					pending.reject(error(e)).await;
+					return
+				},
+			};
+
+			let Ok(sink) = pending.accept().await else { return };
+			let statuses: [TransactionStatus<Hash, Hash>; 3] = [
+				TransactionStatus::Ready,
+				TransactionStatus::InBlock((block_hash, 0)),
+				TransactionStatus::Finalized((block_hash, 0)),
+			];
+			for status in statuses {
+				let Ok(message) = SubscriptionMessage::from_json(&status) else { return };
+				if sink.send(message).await.is_err() {
+					return
+				}
+			}
+		},
+	)?;
+
+	// grandpa methods
+
+	module.register_subscription(
+		"grandpa_subscribeJustifications",
+		"grandpa_justifications",
+		"grandpa_unsubscribeJustifications",
+		|_, pending, shared, _| async move {
+			let receiver = shared.subscribe_justifications();
+			forward(pending, None, receiver).await
+		},
+	)?;
+
+	Ok(module)
+}
+
+/// Response of the `state_getReadProof` method.
+#[derive(Serialize)]
+struct ReadProof {
+	at: Hash,
+	proof: Vec<Bytes>,
+}
+
+/// Accept subscription and forward items from the broadcast channel to the subscriber, optionally
+/// starting with the given item.
+async fn forward<T: Clone + Serialize>(
+	pending: PendingSubscriptionSink,
+	first: Option<T>,
+	mut receiver: broadcast::Receiver<T>,
+) {
+	let Ok(sink) = pending.accept().await else { return };
+	let mut next = first;
+	loop {
+		if let Some(item) = next.take() {
+			let Ok(message) = SubscriptionMessage::from_json(&item) else { return };
+			if sink.send(message).await.is_err() {
+				return
+			}
+		}
+
+		next = tokio::select! {
+			_ = sink.closed() => return,
+			item = receiver.recv() => match item {
+				Ok(item) => Some(item),
+				Err(broadcast::error::RecvError::Lagged(_)) => None,
+				Err(broadcast::error::RecvError::Closed) => return,
+			},
+		};
+	}
+}
+
+/// Parse block number parameter, that may be either number or hex string.
+fn parse_block_number(number: Option<serde_json::Value>) -> RpcResult<Option<BlockNumber>> {
+	let number = match number {
+		None | Some(serde_json::Value::Null) => return Ok(None),
+		Some(serde_json::Value::Number(number)) => number.as_u64(),
+		Some(serde_json::Value::String(number)) =>
+			u64::from_str_radix(number.trim_start_matches("0x"), 16).ok(),
+		Some(_) => None,
+	};
+	number
+		.and_then(|number| BlockNumber::try_from(number).ok())
+		.map(Some)
+		.ok_or_else(|| error("Invalid block number"))
+}
+
+/// Create RPC error with given message.
+fn error(message: impl ToString) -> ErrorObjectOwned {
+	ErrorObjectOwned::owned(MOCK_NODE_ERROR_CODE, message.to_string(), None::<()>)
+}
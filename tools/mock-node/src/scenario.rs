@@ -0,0 +1,113 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Scenario, that drives the mock node.
+
+use crate::chain::{MockBlock, MockChain};
+
+use codec::Encode;
+use sp_weights::Weight;
+use std::{collections::HashMap, sync::Arc};
+
+/// Name of the runtime API method that is used by the relay to estimate transaction weight.
+pub const QUERY_INFO_METHOD: &str = "TransactionPaymentApi_query_info";
+/// Name of the runtime API method that is used by the relay to estimate transaction fee.
+pub const QUERY_FEE_DETAILS_METHOD: &str = "TransactionPaymentApi_query_fee_details";
+
+/// Handler of the `state_call` method. It is given the block at which the call is made and the
+/// SCALE-encoded call arguments. It must return the SCALE-encoded call result.
+pub type StateCallHandler = Arc<dyn Fn(&MockBlock, &[u8]) -> Result<Vec<u8>, String> + Send + Sync>;
+
+/// Handler of submitted extrinsics. It is given the chain state (before the extrinsic is included)
+/// and the encoded extrinsic. It returns storage changes of the block that includes the extrinsic.
+pub type ExtrinsicHandler =
+	Arc<dyn Fn(&MockChain, &[u8]) -> Vec<(Vec<u8>, Option<Vec<u8>>)> + Send + Sync>;
+
+/// Scenario of the mock node: initial chain, runtime version and runtime API handlers.
+///
+/// The chain may be changed after the node is started, using `MockNode` methods. Every
+/// submitted extrinsic is included into the new block that is finalized immediately.
+#[derive(Clone)]
+pub struct Scenario {
+	pub(crate) chain: MockChain,
+	pub(crate) spec_name: String,
+	pub(crate) spec_version: u32,
+	pub(crate) transaction_version: u32,
+	pub(crate) state_calls: HashMap<String, StateCallHandler>,
+	pub(crate) on_extrinsic: Option<ExtrinsicHandler>,
+}
+
+impl Scenario {
+	/// Create scenario for the chain with given runtime name.
+	///
+	/// The scenario answers `TransactionPaymentApi` calls with small weight and fee.
+	pub fn new(spec_name: impl Into<String>, chain: MockChain) -> Self {
+		Scenario {
+			chain,
+			spec_name: spec_name.into(),
+			spec_version: 1,
+			transaction_version: 1,
+			state_calls: HashMap::new(),
+			on_extrinsic: None,
+		}
+		.with_state_call_response(QUERY_INFO_METHOD, default_dispatch_info())
+		.with_state_call_response(QUERY_FEE_DETAILS_METHOD, default_fee_details())
+	}
+
+	/// Set runtime version of the chain. It must match the version that is bundled with the
+	/// relay client, otherwise the relay refuses to submit transactions.
+	pub fn with_runtime_version(mut self, spec_version: u32, transaction_version: u32) -> Self {
+		self.spec_version = spec_version;
+		self.transaction_version = transaction_version;
+		self
+	}
+
+	/// Set handler of given runtime API method.
+	pub fn with_state_call(
+		mut self,
+		method: impl Into<String>,
+		handler: impl Fn(&MockBlock, &[u8]) -> Result<Vec<u8>, String> + Send + Sync + 'static,
+	) -> Self {
+		self.state_calls.insert(method.into(), Arc::new(handler));
+		self
+	}
+
+	/// Answer given runtime API method with the same encoded response at every block.
+	pub fn with_state_call_response(self, method: impl Into<String>, response: Vec<u8>) -> Self {
+		self.with_state_call(method, move |_, _| Ok(response.clone()))
+	}
+
+	/// Set handler of submitted extrinsics.
+	pub fn with_extrinsic_handler(
+		mut self,
+		handler: impl Fn(&MockChain, &[u8]) -> Vec<(Vec<u8>, Option<Vec<u8>>)> + Send + Sync + 'static,
+	) -> Self {
+		self.on_extrinsic = Some(Arc::new(handler));
+		self
+	}
+}
+
+/// Encoded `RuntimeDispatchInfo` with small weight and fee.
+fn default_dispatch_info() -> Vec<u8> {
+	// (weight, dispatch class = Normal, partial fee)
+	(Weight::from_parts(1_000_000, 1_000), 0u8, 1_000_000_000u128).encode()
+}
+
+/// Encoded `FeeDetails` with small fee.
+fn default_fee_details() -> Vec<u8> {
+	// (Some(inclusion fee: base fee, len fee, adjusted weight fee), tip)
+	(Some((1_000_000u128, 1_000_000u128, 1_000_000u128)), 0u128).encode()
+}
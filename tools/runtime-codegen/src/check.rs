@@ -0,0 +1,307 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Comparison of the generated runtime code with the committed one.
+//!
+//! Files are compared item-by-item, so formatting and header comments are ignored.
+
+use color_eyre::eyre::{self, WrapErr};
+use quote::ToTokens;
+use std::{collections::BTreeMap, fmt, path::Path};
+use subxt_codegen::syn;
+
+/// Maximal number of changed types that are listed in the summary.
+const MAX_LISTED_TYPES: usize = 16;
+
+/// Single change of the generated code.
+#[derive(Debug, PartialEq)]
+pub enum Change {
+	/// Item has been added.
+	Added(String),
+	/// Item has been removed.
+	Removed(String),
+	/// Item has been changed.
+	Changed(String),
+}
+
+/// Differences between committed and generated runtime code.
+#[derive(Debug, Default, PartialEq)]
+pub struct CodeDiff {
+	/// Changes of `RuntimeCall` variants.
+	pub pallets: Vec<Change>,
+	/// Changes of pallet `Call` variants.
+	pub calls: Vec<Change>,
+	/// Changes of all other types.
+	pub types: Vec<Change>,
+}
+
+impl CodeDiff {
+	/// Returns true if there are no changes.
+	pub fn is_empty(&self) -> bool {
+		self.pallets.is_empty() && self.calls.is_empty() && self.types.is_empty()
+	}
+}
+
+/// Compare generated runtime code with the code from the `committed_file`.
+pub fn check_runtime(committed_file: &Path, generated: &str) -> color_eyre::Result<CodeDiff> {
+	let committed = std::fs::read_to_string(committed_file)
+		.wrap_err_with(|| format!("Error reading {}", committed_file.display()))?;
+	compare_runtime_code(&committed, generated)
+}
+
+/// Compare two versions of the runtime code.
+pub fn compare_runtime_code(old: &str, new: &str) -> color_eyre::Result<CodeDiff> {
+	let old = collect_items(old)?;
+	let new = collect_items(new)?;
+
+	let mut diff = CodeDiff::default();
+	for change in compare_maps(&old, &new, |old, new| tokens(old) != tokens(new)) {
+		let Change::Changed(ref path) = change else {
+			diff.types.push(change);
+			continue
+		};
+
+		match (&old[path], &new[path]) {
+			(syn::Item::Enum(old_enum), syn::Item::Enum(new_enum))
+				if path.ends_with("::RuntimeCall") =>
+				diff.pallets.extend(compare_runtime_calls(old_enum, new_enum)),
+			(syn::Item::Enum(old_enum), syn::Item::Enum(new_enum)) if path.ends_with("::Call") =>
+				diff.calls.extend(compare_calls(path, old_enum, new_enum)),
+			_ => diff.types.push(change),
+		}
+	}
+
+	Ok(diff)
+}
+
+/// Parse the code and collect all named items of the `runtime_types` module (and its
+/// submodules), keyed by their path.
+fn collect_items(code: &str) -> color_eyre::Result<BTreeMap<String, syn::Item>> {
+	fn collect(items: &[syn::Item], prefix: &str, result: &mut BTreeMap<String, syn::Item>) {
+		for item in items {
+			let ident = match item {
+				syn::Item::Mod(item_mod) => {
+					if let Some((_, ref items)) = item_mod.content {
+						collect(items, &join_path(prefix, &item_mod.ident), result);
+					}
+					continue
+				},
+				syn::Item::Struct(item) => &item.ident,
+				syn::Item::Enum(item) => &item.ident,
+				syn::Item::Type(item) => &item.ident,
+				syn::Item::Const(item) => &item.ident,
+				syn::Item::Fn(item) => &item.sig.ident,
+				_ => continue,
+			};
+			result.insert(join_path(prefix, ident), item.clone());
+		}
+	}
+
+	let file =
+		syn::parse_file(code).map_err(|e| eyre::eyre!("Error parsing runtime code: {:?}", e))?;
+	let mut result = BTreeMap::new();
+	collect(&file.items, "", &mut result);
+	Ok(result)
+}
+
+/// Compare variants of two `RuntimeCall` enums.
+fn compare_runtime_calls(old: &syn::ItemEnum, new: &syn::ItemEnum) -> Vec<Change> {
+	let old = variants(old);
+	let new = variants(new);
+	compare_maps(&old, &new, |old, new| tokens(*old) != tokens(*new))
+		.into_iter()
+		.map(|change| match change {
+			Change::Changed(name) => {
+				let (old_index, new_index) = (codec_index(old[&name]), codec_index(new[&name]));
+				if old_index != new_index {
+					Change::Changed(format!(
+						"{name} (index {} -> {})",
+						display_index(old_index),
+						display_index(new_index),
+					))
+				} else {
+					Change::Changed(format!("{name} (call type)"))
+				}
+			},
+			change => change,
+		})
+		.collect()
+}
+
+/// Compare variants of two pallet `Call` enums.
+fn compare_calls(path: &str, old: &syn::ItemEnum, new: &syn::ItemEnum) -> Vec<Change> {
+	let path = path.strip_prefix("api::runtime_types::").unwrap_or(path);
+	compare_maps(&variants(old), &variants(new), |old, new| tokens(*old) != tokens(*new))
+		.into_iter()
+		.map(|change| match change {
+			Change::Added(name) => Change::Added(format!("{path}::{name}")),
+			Change::Removed(name) => Change::Removed(format!("{path}::{name}")),
+			Change::Changed(name) => Change::Changed(format!("{path}::{name}")),
+		})
+		.collect()
+}
+
+/// Compare two maps, returning keys of added, removed and changed entries.
+fn compare_maps<T>(
+	old: &BTreeMap<String, T>,
+	new: &BTreeMap<String, T>,
+	is_changed: impl Fn(&T, &T) -> bool,
+) -> Vec<Change> {
+	let removed = old.keys().filter(|key| !new.contains_key(*key)).cloned().map(Change::Removed);
+	let added_or_changed = new.iter().filter_map(|(key, new_value)| match old.get(key) {
+		None => Some(Change::Added(key.clone())),
+		Some(old_value) if is_changed(old_value, new_value) => Some(Change::Changed(key.clone())),
+		Some(_) => None,
+	});
+	removed.chain(added_or_changed).collect()
+}
+
+fn variants(item: &syn::ItemEnum) -> BTreeMap<String, &syn::Variant> {
+	item.variants
+		.iter()
+		.map(|variant| (variant.ident.to_string(), variant))
+		.collect()
+}
+
+/// Returns value of the `#[codec(index = N)]` attribute of the variant.
+pub fn codec_index(variant: &syn::Variant) -> Option<u8> {
+	variant
+		.attrs
+		.iter()
+		.filter(|attr| attr.path().is_ident("codec"))
+		.filter_map(|attr| attr.parse_args::<syn::MetaNameValue>().ok())
+		.filter(|name_value| name_value.path.is_ident("index"))
+		.find_map(|name_value| match name_value.value {
+			syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(ref index), .. }) =>
+				index.base10_parse().ok(),
+			_ => None,
+		})
+}
+
+fn display_index(index: Option<u8>) -> String {
+	index.map(|index| index.to_string()).unwrap_or_else(|| "?".into())
+}
+
+fn tokens(item: &impl ToTokens) -> String {
+	item.to_token_stream().to_string()
+}
+
+fn join_path(prefix: &str, ident: &syn::Ident) -> String {
+	if prefix.is_empty() {
+		ident.to_string()
+	} else {
+		format!("{prefix}::{ident}")
+	}
+}
+
+impl fmt::Display for Change {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Added(name) => write!(f, "+ {name}"),
+			Self::Removed(name) => write!(f, "- {name}"),
+			Self::Changed(name) => write!(f, "~ {name}"),
+		}
+	}
+}
+
+impl fmt::Display for CodeDiff {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if !self.pallets.is_empty() {
+			writeln!(f, "  pallets:")?;
+			for change in &self.pallets {
+				writeln!(f, "    {change}")?;
+			}
+		}
+		if !self.calls.is_empty() {
+			writeln!(f, "  calls:")?;
+			for change in &self.calls {
+				writeln!(f, "    {change}")?;
+			}
+		}
+		if !self.types.is_empty() {
+			writeln!(f, "  types:")?;
+			for change in self.types.iter().take(MAX_LISTED_TYPES) {
+				let change = match change {
+					Change::Added(path) => Change::Added(short_type_path(path)),
+					Change::Removed(path) => Change::Removed(short_type_path(path)),
+					Change::Changed(path) => Change::Changed(short_type_path(path)),
+				};
+				writeln!(f, "    {change}")?;
+			}
+			if self.types.len() > MAX_LISTED_TYPES {
+				writeln!(f, "    ... and {} more", self.types.len() - MAX_LISTED_TYPES)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+fn short_type_path(path: &str) -> String {
+	path.strip_prefix("api::runtime_types::").unwrap_or(path).into()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const RUNTIME: &str = r#"
+		pub mod api {
+			pub mod runtime_types {
+				pub mod bridge_hub_runtime {
+					pub enum RuntimeCall {
+						#[codec(index = 0)]
+						System(runtime_types::frame_system::pallet::Call),
+						#[codec(index = 40)]
+						Utility(runtime_types::pallet_utility::pallet::Call),
+					}
+				}
+				pub mod pallet_utility {
+					pub mod pallet {
+						pub enum Call {
+							#[codec(index = 2)]
+							batch_all { calls: Vec<runtime_types::bridge_hub_runtime::RuntimeCall> },
+						}
+					}
+				}
+				pub struct Weight { pub ref_time: u64 }
+			}
+		}
+	"#;
+
+	#[test]
+	fn same_code_has_no_changes() {
+		// formatting and header comments are ignored
+		let formatted = format!("//! Header\n{}", RUNTIME.replace("\t", "    "));
+		assert!(compare_runtime_code(RUNTIME, &formatted).unwrap().is_empty());
+	}
+
+	#[test]
+	fn changes_are_detected() {
+		let changed = RUNTIME
+			.replace("#[codec(index = 40)]", "#[codec(index = 41)]")
+			.replace("#[codec(index = 2)]", "#[codec(index = 3)]")
+			.replace("pub ref_time: u64", "pub ref_time: u64, pub proof_size: u64");
+
+		assert_eq!(
+			compare_runtime_code(RUNTIME, &changed).unwrap(),
+			CodeDiff {
+				pallets: vec![Change::Changed("Utility (index 40 -> 41)".into())],
+				calls: vec![Change::Changed("pallet_utility::pallet::Call::batch_all".into())],
+				types: vec![Change::Changed("api::runtime_types::Weight".into())],
+			},
+		);
+	}
+}
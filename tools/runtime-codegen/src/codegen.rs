@@ -0,0 +1,204 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime code generation.
+
+use crate::{metadata_hash::MetadataHash, transaction_extension::generate_transaction_extension};
+
+use codec::Decode;
+use color_eyre::eyre;
+use proc_macro2::Literal;
+use quote::quote;
+use subxt_codegen::{syn, CodegenBuilder, Metadata};
+
+/// Header generic, produced by the `subxt` because of
+/// [well-known issue](https://github.com/paritytech/parity-bridges-common/issues/2669).
+const BROKEN_HEADER_GENERIC: &str = "::sp_runtime::generic::Header<::core::primitive::u32>";
+/// Header generic that shall be used instead of the `BROKEN_HEADER_GENERIC`.
+const FIXED_HEADER_GENERIC: &str =
+	"::sp_runtime::generic::Header<::core::primitive::u32, ::sp_runtime::traits::BlakeTwo256>";
+
+struct TypeSubstitute {
+	subxt_type: syn::Path,
+	substitute: syn::Path,
+}
+
+impl TypeSubstitute {
+	fn simple(subxt_type: &str) -> Self {
+		Self {
+			subxt_type: syn::parse_str::<syn::Path>(subxt_type).unwrap(),
+			substitute: syn::parse_str::<syn::Path>(&format!("::{subxt_type}")).unwrap(),
+		}
+	}
+
+	fn custom(subxt_type: &str, substitute: &str) -> Self {
+		Self {
+			subxt_type: syn::parse_str::<syn::Path>(subxt_type).unwrap(),
+			substitute: syn::parse_str::<syn::Path>(substitute).unwrap(),
+		}
+	}
+}
+
+/// Generate runtime code from the raw (SCALE-encoded `RuntimeMetadataPrefixed`) metadata.
+///
+/// The `executed_command` is mentioned in the header of generated file. The `metadata_hash` is
+/// emitted as the `METADATA_HASH` constant. The `TransactionExtension` type is generated from
+/// transaction extensions, listed in the metadata.
+pub fn generate_runtime(
+	raw_metadata: &[u8],
+	metadata_hash: Option<MetadataHash>,
+	executed_command: &str,
+) -> color_eyre::Result<String> {
+	let transaction_extension = generate_transaction_extension(raw_metadata)?;
+	let metadata = Metadata::decode(&mut &raw_metadata[..])
+		.map_err(|e| eyre::eyre!("Error decoding metadata: {:?}", e))?;
+
+	let runtime_api = codegen_builder()
+		.generate(metadata)
+		.map_err(|e| eyre::eyre!("Error generating runtime api: {:?}", e))?;
+
+	let metadata_hash = match metadata_hash {
+		Some(MetadataHash { spec_version, hash }) => {
+			let spec_version = Literal::u32_unsuffixed(spec_version);
+			let hash = hash.iter().map(|byte| Literal::u8_unsuffixed(*byte));
+			quote!(Some((#spec_version, [#(#hash),*])))
+		},
+		None => quote!(None),
+	};
+	let runtime_api = quote! {
+		#runtime_api
+
+		/// `spec_version` of the runtime and the RFC-78 hash of its metadata, this code has been
+		/// generated from. It is used by the `CheckMetadataHash` transaction extension.
+		pub const METADATA_HASH: Option<(u32, [u8; 32])> = #metadata_hash;
+
+		#transaction_extension
+	};
+
+	format_runtime(runtime_api, executed_command)
+}
+
+fn codegen_builder() -> CodegenBuilder {
+	let mut codegen_builder = CodegenBuilder::new();
+	codegen_builder.runtime_types_only();
+	codegen_builder.no_docs();
+
+	// Default module derivatives.
+	codegen_builder.disable_default_derives();
+	codegen_builder.set_additional_global_derives(vec![
+		syn::parse_quote!(::codec::Encode),
+		syn::parse_quote!(::codec::Decode),
+		syn::parse_quote!(Clone),
+		syn::parse_quote!(Debug),
+		syn::parse_quote!(PartialEq),
+	]);
+
+	// Add `TypeInfo` just for `frame_metadata_hash_extension::Meta` to be usable for
+	// `TransactionExtension`.
+	codegen_builder.add_derives_for_type(
+		syn::parse_str("frame_metadata_hash_extension::Mode").expect("valid type"),
+		vec![
+			syn::parse_quote!(Eq),
+			syn::parse_quote!(scale_info::TypeInfo),
+			syn::parse_quote!(::codec::DecodeWithMemTracking),
+		],
+		false,
+	);
+
+	// Type substitutes
+	let type_substitutes = vec![
+		TypeSubstitute::simple("sp_core::crypto::AccountId32"),
+		TypeSubstitute::custom("sp_weights::weight_v2::Weight", "::sp_weights::Weight"),
+		TypeSubstitute::custom("sp_runtime::generic::era::Era", "::sp_runtime::generic::Era"),
+		TypeSubstitute::custom(
+			"sp_runtime::generic::header::Header",
+			"::sp_runtime::generic::Header",
+		),
+		TypeSubstitute::simple("sp_runtime::traits::BlakeTwo256"),
+		TypeSubstitute::simple("sp_session::MembershipProof"),
+		TypeSubstitute::simple("sp_consensus_grandpa::EquivocationProof"),
+		TypeSubstitute::simple("bp_header_chain::justification::GrandpaJustification"),
+		TypeSubstitute::simple("bp_header_chain::InitializationData"),
+		TypeSubstitute::simple("bp_polkadot_core::parachains::ParaId"),
+		TypeSubstitute::simple("bp_polkadot_core::parachains::ParaHeadsProof"),
+		TypeSubstitute::simple("bp_messages::target_chain::FromBridgedChainMessagesProof"),
+		TypeSubstitute::simple("bp_messages::source_chain::FromBridgedChainMessagesDeliveryProof"),
+		TypeSubstitute::custom(
+			"bridge_runtime_common::messages::target::FromBridgedChainMessagesProof",
+			"::bp_messages::target_chain::FromBridgedChainMessagesProof",
+		),
+		TypeSubstitute::custom(
+			"bridge_runtime_common::messages::source::FromBridgedChainMessagesDeliveryProof",
+			"::bp_messages::source_chain::FromBridgedChainMessagesDeliveryProof",
+		),
+		TypeSubstitute::simple("bp_messages::UnrewardedRelayersState"),
+		TypeSubstitute::custom(
+			"sp_runtime::generic::digest::Digest",
+			"::sp_runtime::generic::Digest",
+		),
+		TypeSubstitute::custom("bp_messages::lane::LegacyLaneId", "::bp_messages::LegacyLaneId"),
+		TypeSubstitute::custom("bp_messages::lane::HashedLaneId", "::bp_messages::HashedLaneId"),
+		// Ethereum-compatible chains (Moonbeam, Moonriver)
+		TypeSubstitute::custom("account::AccountId20", "::bp_ethereum_common::AccountId20"),
+	];
+	for type_substitute in type_substitutes {
+		codegen_builder.set_type_substitute(type_substitute.subxt_type, type_substitute.substitute);
+	}
+
+	codegen_builder
+}
+
+/// Format generated code and prepend it with the file header.
+pub(crate) fn format_runtime(
+	runtime_api: proc_macro2::TokenStream,
+	executed_command: &str,
+) -> color_eyre::Result<String> {
+	let syn_tree = syn::parse_file(&runtime_api.to_string())
+		.map_err(|e| eyre::eyre!("Error parsing generated runtime api: {:?}", e))?;
+	let pretty_runtime_api = fix_header_generic(prettyplease::unparse(&syn_tree));
+
+	Ok(indoc::formatdoc!(
+		"// Copyright (C) Parity Technologies (UK) Ltd.
+		// This file is part of Parity Bridges Common.
+
+		// Parity Bridges Common is free software: you can redistribute it and/or modify
+		// it under the terms of the GNU General Public License as published by
+		// the Free Software Foundation, either version 3 of the License, or
+		// (at your option) any later version.
+
+		// Parity Bridges Common is distributed in the hope that it will be useful,
+		// but WITHOUT ANY WARRANTY; without even the implied warranty of
+		// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+		// GNU General Public License for more details.
+
+		// You should have received a copy of the GNU General Public License
+		// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+		//! Autogenerated runtime API
+		//! THIS FILE WAS AUTOGENERATED USING parity-bridges-common::runtime-codegen
+		//! EXECUTED COMMAND: {}
+
+		{}
+		",
+		executed_command,
+		pretty_runtime_api
+	))
+}
+
+/// Replace all occurrences of `BROKEN_HEADER_GENERIC` with `FIXED_HEADER_GENERIC`.
+fn fix_header_generic(code: String) -> String {
+	code.replace(BROKEN_HEADER_GENERIC, FIXED_HEADER_GENERIC)
+}
@@ -0,0 +1,534 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Comparison of two runtime metadata versions.
+//!
+//! It is used to review runtime upgrades: the report lists all changes of pallets, calls,
+//! transaction extensions and runtime APIs and highlights changes that affect relay clients.
+
+use crate::{
+	metadata::RuntimeMetadataSource,
+	runtime_info::{
+		ArgInfo, CallInfo, PalletInfo, RuntimeApiInfo, RuntimeInfo, TransactionExtensionInfo,
+	},
+};
+
+use color_eyre::eyre::WrapErr;
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt::Write};
+
+/// Crates of pallets, whose calls are constructed by relay clients.
+const RELAY_PALLET_CRATES: &[&str] = &[
+	"pallet_bridge_grandpa",
+	"pallet_bridge_messages",
+	"pallet_bridge_parachains",
+	"pallet_bridge_relayers",
+	"pallet_utility",
+	"pallet_sudo",
+];
+/// Runtime APIs, called by relay clients.
+const RELAY_RUNTIME_APIS: &[&str] =
+	&["Core", "AccountNonceApi", "TaggedTransactionQueue", "TransactionPaymentApi", "GrandpaApi"];
+/// Suffixes of bridge runtime APIs, declared with `decl_bridge_finality_runtime_apis` and
+/// `decl_bridge_messages_runtime_apis` macros.
+const RELAY_RUNTIME_API_SUFFIXES: &[&str] = &["FinalityApi", "OutboundLaneApi", "InboundLaneApi"];
+
+/// Compare two versions of the runtime metadata.
+#[derive(Debug, clap::Args)]
+pub struct DiffParams {
+	/// Old metadata: node URL, path to the runtime wasm blob or to the `.scale` metadata file.
+	#[clap(long, value_parser)]
+	old: RuntimeMetadataSource,
+	/// New metadata: node URL, path to the runtime wasm blob or to the `.scale` metadata file.
+	#[clap(long, value_parser)]
+	new: RuntimeMetadataSource,
+	/// Report format.
+	#[clap(long, value_enum, default_value_t = ReportFormat::Markdown)]
+	format: ReportFormat,
+}
+
+/// Format of the diff report.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ReportFormat {
+	/// Markdown tables, suitable for pasting into the review.
+	Markdown,
+	/// JSON, suitable for processing by other tools.
+	Json,
+}
+
+/// Kind of the change.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+	/// Item has been added.
+	Added,
+	/// Item has been removed.
+	Removed,
+	/// Item has been changed.
+	Changed,
+}
+
+/// Single change of the metadata.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct DiffEntry {
+	/// Changed item, e.g. `BridgeKusamaGrandpa::submit_finality_proof`.
+	pub item: String,
+	/// Kind of the change.
+	pub change: ChangeKind,
+	/// Human-readable change details.
+	pub details: String,
+	/// True if change affects types, used by relay clients.
+	pub affects_relay: bool,
+}
+
+/// Differences between two versions of the runtime metadata.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct MetadataDiff {
+	/// Changes of pallets and their indices.
+	pub pallets: Vec<DiffEntry>,
+	/// Changes of pallet calls.
+	pub calls: Vec<DiffEntry>,
+	/// Changes of transaction extensions.
+	pub transaction_extensions: Vec<DiffEntry>,
+	/// Changes of runtime APIs. `None` if any of metadata versions doesn't include runtime APIs.
+	pub runtime_apis: Option<Vec<DiffEntry>>,
+}
+
+/// Run the `diff` subcommand.
+pub fn run(params: DiffParams) -> color_eyre::Result<()> {
+	let old = RuntimeInfo::decode(&params.old.fetch()?)
+		.wrap_err_with(|| format!("Invalid old metadata at {}", params.old))?;
+	let new = RuntimeInfo::decode(&params.new.fetch()?)
+		.wrap_err_with(|| format!("Invalid new metadata at {}", params.new))?;
+	let diff = diff_runtimes(&old, &new);
+
+	match params.format {
+		ReportFormat::Markdown => print!("{}", diff.to_markdown(&params.old, &params.new)),
+		ReportFormat::Json => println!(
+			"{}",
+			serde_json::to_string_pretty(&diff).wrap_err("Error serializing diff report")?
+		),
+	}
+
+	Ok(())
+}
+
+/// Compare two versions of the runtime.
+pub fn diff_runtimes(old: &RuntimeInfo, new: &RuntimeInfo) -> MetadataDiff {
+	let mut diff = MetadataDiff::default();
+	diff_pallets(old, new, &mut diff);
+	diff.transaction_extensions = diff_transaction_extensions(old, new);
+	diff.runtime_apis = match (&old.runtime_apis, &new.runtime_apis) {
+		(Some(old_apis), Some(new_apis)) => Some(diff_runtime_apis(old_apis, new_apis)),
+		_ => None,
+	};
+	diff
+}
+
+fn diff_pallets(old: &RuntimeInfo, new: &RuntimeInfo, diff: &mut MetadataDiff) {
+	let old_pallets = by_name(&old.pallets, |pallet| &pallet.name);
+	let new_pallets = by_name(&new.pallets, |pallet| &pallet.name);
+	for (name, old_pallet) in &old_pallets {
+		if !new_pallets.contains_key(name) {
+			diff.pallets.push(DiffEntry {
+				item: name.to_string(),
+				change: ChangeKind::Removed,
+				details: format!("index {}", old_pallet.index),
+				affects_relay: is_relay_pallet(old_pallet),
+			});
+		}
+	}
+
+	for (name, new_pallet) in &new_pallets {
+		let Some(old_pallet) = old_pallets.get(name) else {
+			diff.pallets.push(DiffEntry {
+				item: name.to_string(),
+				change: ChangeKind::Added,
+				details: format!("index {}", new_pallet.index),
+				affects_relay: is_relay_pallet(new_pallet),
+			});
+			continue
+		};
+
+		let affects_relay = is_relay_pallet(old_pallet) || is_relay_pallet(new_pallet);
+		if old_pallet.index != new_pallet.index {
+			diff.pallets.push(DiffEntry {
+				item: name.to_string(),
+				change: ChangeKind::Changed,
+				details: format!("index {} -> {}", old_pallet.index, new_pallet.index),
+				affects_relay,
+			});
+		}
+		diff.calls.extend(diff_calls(name, old_pallet, new_pallet, affects_relay));
+	}
+}
+
+fn diff_calls(
+	pallet: &str,
+	old: &PalletInfo,
+	new: &PalletInfo,
+	affects_relay: bool,
+) -> Vec<DiffEntry> {
+	let old_calls = by_name(&old.calls, |call| &call.name);
+	let new_calls = by_name(&new.calls, |call| &call.name);
+	let entry = |call: &str, change, details| DiffEntry {
+		item: format!("{pallet}::{call}"),
+		change,
+		details,
+		affects_relay,
+	};
+
+	let removed = old_calls
+		.iter()
+		.filter(|(name, _)| !new_calls.contains_key(*name))
+		.map(|(name, call)| entry(name, ChangeKind::Removed, format!("index {}", call.index)));
+	let added_or_changed = new_calls.iter().filter_map(|(name, new_call)| {
+		let Some(old_call) = old_calls.get(name) else {
+			return Some(entry(name, ChangeKind::Added, format!("index {}", new_call.index)))
+		};
+		let details = call_changes(old_call, new_call);
+		(!details.is_empty()).then(|| entry(name, ChangeKind::Changed, details.join("; ")))
+	});
+	removed.chain(added_or_changed).collect()
+}
+
+fn call_changes(old: &CallInfo, new: &CallInfo) -> Vec<String> {
+	let mut changes = Vec::new();
+	if old.index != new.index {
+		changes.push(format!("index {} -> {}", old.index, new.index));
+	}
+	changes.extend(args_changes(&old.args, &new.args));
+	changes
+}
+
+fn args_changes(old: &[ArgInfo], new: &[ArgInfo]) -> Vec<String> {
+	if arg_names(old) != arg_names(new) {
+		return vec![format!(
+			"arguments `({})` -> `({})`",
+			arg_names(old).join(", "),
+			arg_names(new).join(", ")
+		)]
+	}
+
+	old.iter()
+		.zip(new)
+		.filter(|(old, new)| old.ty.layout != new.ty.layout)
+		.map(|(old, new)| {
+			if old.ty.name != new.ty.name {
+				format!("`{}`: `{}` -> `{}`", old.name, old.ty.name, new.ty.name)
+			} else {
+				format!("`{}`: `{}` layout changed", old.name, old.ty.name)
+			}
+		})
+		.collect()
+}
+
+fn diff_transaction_extensions(old: &RuntimeInfo, new: &RuntimeInfo) -> Vec<DiffEntry> {
+	let old_extensions = by_name(&old.transaction_extensions, |ext| &ext.identifier);
+	let new_extensions = by_name(&new.transaction_extensions, |ext| &ext.identifier);
+	// every change of transaction extensions breaks transactions, signed by relay clients
+	let entry = |item: &str, change, details| DiffEntry {
+		item: item.into(),
+		change,
+		details,
+		affects_relay: true,
+	};
+
+	let mut changes = Vec::new();
+	for (identifier, old_ext) in &old_extensions {
+		if !new_extensions.contains_key(identifier) {
+			changes.push(entry(identifier, ChangeKind::Removed, format!("`{}`", old_ext.ty.name)));
+		}
+	}
+	for (identifier, new_ext) in &new_extensions {
+		let Some(old_ext) = old_extensions.get(identifier) else {
+			changes.push(entry(identifier, ChangeKind::Added, format!("`{}`", new_ext.ty.name)));
+			continue
+		};
+
+		let mut details = Vec::new();
+		if old_ext.ty.layout != new_ext.ty.layout {
+			details.push(format!("type `{}` -> `{}`", old_ext.ty.name, new_ext.ty.name));
+		}
+		if old_ext.implicit.layout != new_ext.implicit.layout {
+			details.push(format!(
+				"implicit type `{}` -> `{}`",
+				old_ext.implicit.name, new_ext.implicit.name
+			));
+		}
+		if !details.is_empty() {
+			changes.push(entry(identifier, ChangeKind::Changed, details.join("; ")));
+		}
+	}
+
+	// extensions are encoded as a tuple, so order matters
+	let old_order = common_extensions(old, &new_extensions);
+	let new_order = common_extensions(new, &old_extensions);
+	if old_order != new_order {
+		changes.push(entry(
+			"(order)",
+			ChangeKind::Changed,
+			format!("{} -> {}", old_order.join(", "), new_order.join(", ")),
+		));
+	}
+
+	changes
+}
+
+/// Returns identifiers of `runtime` transaction extensions, that are also in `other`.
+fn common_extensions<'a>(
+	runtime: &'a RuntimeInfo,
+	other: &BTreeMap<&str, &TransactionExtensionInfo>,
+) -> Vec<&'a str> {
+	runtime
+		.transaction_extensions
+		.iter()
+		.map(|ext| ext.identifier.as_str())
+		.filter(|identifier| other.contains_key(identifier))
+		.collect()
+}
+
+fn diff_runtime_apis(old: &[RuntimeApiInfo], new: &[RuntimeApiInfo]) -> Vec<DiffEntry> {
+	let old_apis = by_name(old, |api| &api.name);
+	let new_apis = by_name(new, |api| &api.name);
+	let mut changes = Vec::new();
+	for (name, old_api) in &old_apis {
+		if !new_apis.contains_key(name) {
+			changes.push(DiffEntry {
+				item: name.to_string(),
+				change: ChangeKind::Removed,
+				details: format!("{} methods", old_api.methods.len()),
+				affects_relay: is_relay_runtime_api(name),
+			});
+		}
+	}
+
+	for (name, new_api) in &new_apis {
+		let affects_relay = is_relay_runtime_api(name);
+		let Some(old_api) = old_apis.get(name) else {
+			changes.push(DiffEntry {
+				item: name.to_string(),
+				change: ChangeKind::Added,
+				details: format!("{} methods", new_api.methods.len()),
+				affects_relay,
+			});
+			continue
+		};
+
+		let old_methods = by_name(&old_api.methods, |method| &method.name);
+		let new_methods = by_name(&new_api.methods, |method| &method.name);
+		let entry = |method: &str, change, details: String| DiffEntry {
+			item: format!("{name}::{method}"),
+			change,
+			details,
+			affects_relay,
+		};
+		for method in old_methods.keys().filter(|method| !new_methods.contains_key(*method)) {
+			changes.push(entry(method, ChangeKind::Removed, String::new()));
+		}
+		for (method, new_method) in &new_methods {
+			let Some(old_method) = old_methods.get(method) else {
+				changes.push(entry(method, ChangeKind::Added, String::new()));
+				continue
+			};
+
+			let mut details = args_changes(&old_method.inputs, &new_method.inputs);
+			if old_method.output.layout != new_method.output.layout {
+				details.push(format!(
+					"output `{}` -> `{}`",
+					old_method.output.name, new_method.output.name
+				));
+			}
+			if !details.is_empty() {
+				changes.push(entry(method, ChangeKind::Changed, details.join("; ")));
+			}
+		}
+	}
+
+	changes
+}
+
+fn arg_names(args: &[ArgInfo]) -> Vec<&str> {
+	args.iter().map(|arg| arg.name.as_str()).collect()
+}
+
+fn by_name<'a, T>(items: &'a [T], name: impl Fn(&'a T) -> &'a String) -> BTreeMap<&'a str, &'a T> {
+	items.iter().map(|item| (name(item).as_str(), item)).collect()
+}
+
+fn is_relay_pallet(pallet: &PalletInfo) -> bool {
+	pallet.call_type.as_ref().is_some_and(|call_type| {
+		RELAY_PALLET_CRATES
+			.iter()
+			.any(|pallet_crate| call_type.split("::").next() == Some(*pallet_crate))
+	})
+}
+
+fn is_relay_runtime_api(name: &str) -> bool {
+	RELAY_RUNTIME_APIS.contains(&name) ||
+		RELAY_RUNTIME_API_SUFFIXES.iter().any(|suffix| name.ends_with(suffix))
+}
+
+impl MetadataDiff {
+	/// Render the diff as Markdown document.
+	pub fn to_markdown(&self, old: &RuntimeMetadataSource, new: &RuntimeMetadataSource) -> String {
+		let mut all_entries = self
+			.pallets
+			.iter()
+			.chain(&self.calls)
+			.chain(&self.transaction_extensions)
+			.chain(self.runtime_apis.iter().flatten());
+		let (total, affects_relay) =
+			all_entries.by_ref().fold((0, 0), |(total, affects_relay), entry| {
+				(total + 1, affects_relay + entry.affects_relay as usize)
+			});
+
+		let mut markdown = String::new();
+		let _ = writeln!(markdown, "# Runtime metadata diff\n");
+		let _ = writeln!(markdown, "- old: `{old}`");
+		let _ = writeln!(markdown, "- new: `{new}`\n");
+		let _ = writeln!(
+			markdown,
+			"{total} change(s), {affects_relay} of them affect relay clients (marked with :warning:).\n"
+		);
+
+		write_markdown_section(&mut markdown, "Pallets", Some(&self.pallets));
+		write_markdown_section(&mut markdown, "Calls", Some(&self.calls));
+		write_markdown_section(
+			&mut markdown,
+			"Transaction extensions",
+			Some(&self.transaction_extensions),
+		);
+		write_markdown_section(&mut markdown, "Runtime APIs", self.runtime_apis.as_ref());
+		markdown
+	}
+}
+
+fn write_markdown_section(markdown: &mut String, title: &str, entries: Option<&Vec<DiffEntry>>) {
+	let _ = writeln!(markdown, "## {title}\n");
+	let entries = match entries {
+		Some(entries) if entries.is_empty() => {
+			let _ = writeln!(markdown, "No changes.\n");
+			return
+		},
+		Some(entries) => entries,
+		None => {
+			let _ = writeln!(markdown, "Not available: metadata V15 or later is required.\n");
+			return
+		},
+	};
+
+	let _ = writeln!(markdown, "| | Change | Item | Details |");
+	let _ = writeln!(markdown, "|---|---|---|---|");
+	for entry in entries {
+		let _ = writeln!(
+			markdown,
+			"| {} | {} | `{}` | {} |",
+			if entry.affects_relay { ":warning:" } else { "" },
+			match entry.change {
+				ChangeKind::Added => "added",
+				ChangeKind::Removed => "removed",
+				ChangeKind::Changed => "changed",
+			},
+			entry.item,
+			entry.details.replace('|', "\\|"),
+		);
+	}
+	let _ = writeln!(markdown);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::runtime_info::TypeInfo;
+
+	fn ty(name: &str, layout: &str) -> TypeInfo {
+		TypeInfo { name: name.into(), layout: layout.into() }
+	}
+
+	fn runtime() -> RuntimeInfo {
+		RuntimeInfo {
+			pallets: vec![
+				PalletInfo {
+					name: "System".into(),
+					index: 0,
+					call_type: Some("frame_system::pallet::Call".into()),
+					calls: vec![CallInfo {
+						name: "remark".into(),
+						index: 0,
+						args: vec![ArgInfo { name: "remark".into(), ty: ty("Vec<u8>", "Vec<u8>") }],
+					}],
+				},
+				PalletInfo {
+					name: "BridgeKusamaGrandpa".into(),
+					index: 51,
+					call_type: Some("pallet_bridge_grandpa::pallet::Call".into()),
+					calls: vec![CallInfo {
+						name: "submit_finality_proof".into(),
+						index: 0,
+						args: vec![ArgInfo {
+							name: "justification".into(),
+							ty: ty("GrandpaJustification", "{round: u64}"),
+						}],
+					}],
+				},
+			],
+			transaction_extensions: Vec::new(),
+			runtime_apis: None,
+		}
+	}
+
+	#[test]
+	fn same_runtime_has_no_changes() {
+		assert_eq!(diff_runtimes(&runtime(), &runtime()), MetadataDiff::default());
+	}
+
+	#[test]
+	fn relay_changes_are_highlighted() {
+		let mut new = runtime();
+		new.pallets[0].calls[0].index = 1;
+		new.pallets[1].index = 53;
+		new.pallets[1].calls[0].args[0].ty.layout = "{round: u64, commit: u32}".into();
+
+		assert_eq!(
+			diff_runtimes(&runtime(), &new),
+			MetadataDiff {
+				pallets: vec![DiffEntry {
+					item: "BridgeKusamaGrandpa".into(),
+					change: ChangeKind::Changed,
+					details: "index 51 -> 53".into(),
+					affects_relay: true,
+				}],
+				calls: vec![
+					DiffEntry {
+						item: "BridgeKusamaGrandpa::submit_finality_proof".into(),
+						change: ChangeKind::Changed,
+						details: "`justification`: `GrandpaJustification` layout changed".into(),
+						affects_relay: true,
+					},
+					DiffEntry {
+						item: "System::remark".into(),
+						change: ChangeKind::Changed,
+						details: "index 0 -> 1".into(),
+						affects_relay: false,
+					},
+				],
+				transaction_extensions: Vec::new(),
+				runtime_apis: None,
+			},
+		);
+	}
+}
@@ -14,183 +14,256 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
+mod check;
+mod codegen;
+mod diff;
+mod manifest;
+mod metadata;
+mod metadata_hash;
+mod prune;
+mod runtime_api;
+mod runtime_info;
+mod scaffold;
+mod transaction_extension;
+
+use crate::{
+	manifest::Manifest, metadata::RuntimeMetadataSource, metadata_hash::TokenInfo, prune::AllowList,
+};
+
 use clap::Parser as ClapParser;
-use codec::{Decode, Encode};
-use color_eyre::eyre;
-use std::{env, path::PathBuf};
-use subxt_codegen::{syn, CodegenBuilder, Metadata};
-use subxt_utils_fetchmetadata::{from_url_blocking, MetadataVersion, Url};
-use wasm_testbed::WasmTestBed;
+use color_eyre::eyre::{self, WrapErr};
+use std::{
+	env,
+	path::{Path, PathBuf},
+	process,
+};
+use subxt_utils_fetchmetadata::Url;
 
 /// Command for generating indirect runtimes code.
 #[derive(Debug, ClapParser)]
+#[clap(args_conflicts_with_subcommands = true)]
 struct Command {
+	#[clap(subcommand)]
+	subcommand: Option<Subcommand>,
 	#[clap(name = "from-node-url", long, value_parser)]
 	node_url: Option<Url>,
 	#[clap(name = "from-wasm-file", long, value_parser)]
-	wasm_file: Option<String>,
+	wasm_file: Option<PathBuf>,
+	#[clap(name = "from-metadata-file", long, value_parser)]
+	metadata_file: Option<PathBuf>,
+	/// Write generated code to the given file instead of printing it.
+	#[clap(long, value_parser, conflicts_with = "manifest")]
+	output: Option<PathBuf>,
+	/// Regenerate code for all clients, listed in the given manifest file.
+	#[clap(
+		long,
+		value_parser,
+		conflicts_with_all = ["from-node-url", "from-wasm-file", "from-metadata-file"]
+	)]
+	manifest: Option<PathBuf>,
+	/// Only regenerate code for clients with given names from the manifest.
+	#[clap(long, value_parser, requires = "manifest")]
+	client: Vec<String>,
+	/// Only generate code for given pallets (`Utility`, `Bridge*`) or calls
+	/// (`Utility::batch_all`). Codec indices of pallets and calls are kept intact. May be
+	/// repeated.
+	#[clap(long, value_parser, conflicts_with = "manifest")]
+	allow: Vec<String>,
+	/// Symbol of the chain native token. If specified along with `--decimals`, the RFC-78
+	/// metadata hash is computed and emitted as the `METADATA_HASH` constant.
+	#[clap(long, value_parser, requires = "decimals", conflicts_with = "manifest")]
+	token_symbol: Option<String>,
+	/// Number of decimals of the chain native token.
+	#[clap(long, value_parser, requires = "token_symbol", conflicts_with = "manifest")]
+	decimals: Option<u8>,
+	/// Do not write anything, but compare generated code with existing files (given by the
+	/// `--output` or listed in the `--manifest`) and fail if they differ.
+	#[clap(long)]
+	check: bool,
 }
 
-enum RuntimeMetadataSource {
-	NodeUrl(Url),
-	WasmFile(wasm_loader::Source),
+/// Additional commands of the tool.
+#[derive(Debug, clap::Subcommand)]
+enum Subcommand {
+	/// Compare two versions of the runtime metadata and report changes that may affect relay.
+	Diff(diff::DiffParams),
+	/// Generate typed bindings of bridge runtime APIs of the chain from the bridged runtime
+	/// metadata.
+	RuntimeApis(runtime_api::RuntimeApisParams),
+	/// Generate the relay client crate for the new chain from its runtime metadata.
+	Scaffold(scaffold::ScaffoldParams),
 }
 
-impl RuntimeMetadataSource {
-	fn from_command(cmd: Command) -> color_eyre::Result<Self> {
-		match (cmd.node_url, cmd.wasm_file) {
-			(Some(_), Some(_)) => Err(eyre::eyre!(
-				"Please specify one of `--from-node-url` or `--from-wasm-file` but not both"
-			)),
-			(None, None) =>
-				Err(eyre::eyre!("Please specify one of `--from-node-url` or `--from-wasm-file`")),
-			(Some(node_url), None) => Ok(Self::NodeUrl(node_url)),
-			(None, Some(source)) =>
-				Ok(Self::WasmFile(wasm_loader::Source::File(PathBuf::from(source)))),
+/// Regenerate code for all selected clients from the manifest.
+fn regenerate_from_manifest(
+	manifest_path: PathBuf,
+	clients: Vec<String>,
+	check: bool,
+) -> color_eyre::Result<()> {
+	let manifest = Manifest::load(&manifest_path)?;
+	let mut outdated = Vec::new();
+	for client in manifest.select_clients(&clients)? {
+		let metadata_source = client.metadata_source()?;
+		eprintln!(
+			"{} runtime of {} using {}",
+			if check { "Checking" } else { "Regenerating" },
+			client.name,
+			metadata_source,
+		);
+
+		let allow_list = client.allow_list();
+		let token = client.token_info()?;
+		let executed_command = std::iter::once(format!("runtime-codegen {metadata_source}"))
+			.chain(allow_list.entries().iter().map(|entry| format!("--allow {entry}")))
+			.chain(token.iter().map(|token| {
+				format!("--token-symbol {} --decimals {}", token.symbol, token.decimals)
+			}))
+			.collect::<Vec<_>>()
+			.join(" ");
+		let runtime =
+			generate_runtime(&metadata_source, &allow_list, token.as_ref(), &executed_command)?;
+		if !process_runtime(runtime, &client.output, check)? {
+			outdated.push(client.name.as_str());
 		}
-	}
-}
 
-struct TypeSubstitute {
-	subxt_type: syn::Path,
-	substitute: syn::Path,
-}
+		if let Some(ref runtime_apis) = client.runtime_apis {
+			let metadata_source = runtime_apis.metadata_source(&client.name)?;
+			eprintln!(
+				"{} runtime APIs of {} using {}",
+				if check { "Checking" } else { "Regenerating" },
+				client.name,
+				metadata_source,
+			);
 
-impl TypeSubstitute {
-	fn simple(subxt_type: &str) -> Self {
-		Self {
-			subxt_type: syn::parse_str::<syn::Path>(subxt_type).unwrap(),
-			substitute: syn::parse_str::<syn::Path>(&format!("::{subxt_type}")).unwrap(),
+			let runtime_apis_code = generate_runtime_apis(&metadata_source, &runtime_apis.chain)?;
+			if !process_runtime(runtime_apis_code, &runtime_apis.output, check)? {
+				outdated.push(client.name.as_str());
+			}
 		}
 	}
 
-	fn custom(subxt_type: &str, substitute: &str) -> Self {
-		Self {
-			subxt_type: syn::parse_str::<syn::Path>(subxt_type).unwrap(),
-			substitute: syn::parse_str::<syn::Path>(substitute).unwrap(),
-		}
+	if !outdated.is_empty() {
+		return Err(eyre::eyre!("Generated runtime code is outdated: {}", outdated.join(", ")))
 	}
-}
 
-fn print_runtime(runtime_api: proc_macro2::TokenStream) {
-	let syn_tree = syn::parse_file(&runtime_api.to_string()).unwrap();
-	let pretty_runtime_api = prettyplease::unparse(&syn_tree);
+	Ok(())
+}
 
-	indoc::printdoc!(
-		"// Copyright (C) Parity Technologies (UK) Ltd.
-		// This file is part of Parity Bridges Common.
+/// Generate runtime code from the metadata, pruned according to the `allow_list`.
+///
+/// If `token` is specified, the metadata hash is computed (over the full metadata) and emitted
+/// along with the code.
+fn generate_runtime(
+	metadata_source: &RuntimeMetadataSource,
+	allow_list: &AllowList,
+	token: Option<&TokenInfo>,
+	executed_command: &str,
+) -> color_eyre::Result<String> {
+	let mut raw_metadata = metadata_source.fetch()?;
+	let metadata_hash = token
+		.map(|token| metadata_hash::compute_metadata_hash(&raw_metadata, token))
+		.transpose()?;
+	if !allow_list.is_empty() {
+		raw_metadata = prune::prune_metadata(&raw_metadata, allow_list)?;
+	}
+	codegen::generate_runtime(&raw_metadata, metadata_hash, executed_command)
+}
 
-		// Parity Bridges Common is free software: you can redistribute it and/or modify
-		// it under the terms of the GNU General Public License as published by
-		// the Free Software Foundation, either version 3 of the License, or
-		// (at your option) any later version.
+/// Generate typed bindings of bridge runtime APIs of the `chain` from the bridged runtime metadata.
+fn generate_runtime_apis(
+	metadata_source: &RuntimeMetadataSource,
+	chain: &str,
+) -> color_eyre::Result<String> {
+	let executed_command =
+		format!("runtime-codegen runtime-apis {metadata_source} --chain {chain}");
+	runtime_api::generate_runtime_apis(&metadata_source.fetch()?, chain, &executed_command)
+}
 
-		// Parity Bridges Common is distributed in the hope that it will be useful,
-		// but WITHOUT ANY WARRANTY; without even the implied warranty of
-		// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
-		// GNU General Public License for more details.
+/// Write generated runtime code to the `output` file or, if `check` is true, compare it with the
+/// contents of this file.
+///
+/// Returns false if `check` is true and the file is outdated.
+fn process_runtime(runtime: String, output: &Path, check: bool) -> color_eyre::Result<bool> {
+	if check {
+		let diff = check::check_runtime(output, &runtime)?;
+		if !diff.is_empty() {
+			eprintln!("{} is outdated:\n{diff}", output.display());
+			return Ok(false)
+		}
 
-		// You should have received a copy of the GNU General Public License
-		// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+		return Ok(true)
+	}
 
-		//! Autogenerated runtime API
-		//! THIS FILE WAS AUTOGENERATED USING parity-bridges-common::runtime-codegen
-		//! EXECUTED COMMAND: {}
+	std::fs::write(output, runtime)
+		.wrap_err_with(|| format!("Error writing {}", output.display()))?;
+	rustfmt(output);
+	Ok(true)
+}
 
-		{}
-		",
-		env::args().collect::<Vec<String>>().join(" "),
-		pretty_runtime_api
-	);
+/// Try to format the generated file using repository `rustfmt` settings.
+///
+/// The `prettyplease` output is valid Rust code, but it doesn't follow the repository formatting
+/// rules, so `cargo fmt --check` would fail on it.
+fn rustfmt(path: &Path) {
+	let status = process::Command::new("rustfmt")
+		.args(["+nightly", "--edition", "2021"])
+		.arg(path)
+		.status();
+	match status {
+		Ok(status) if status.success() => (),
+		Ok(status) => eprintln!("rustfmt has failed on {}: {status}", path.display()),
+		Err(e) => eprintln!("Failed to start rustfmt on {}: {e}", path.display()),
+	}
 }
 
 fn main() -> color_eyre::Result<()> {
 	let args: Command = Command::parse();
-	let metadata_source = RuntimeMetadataSource::from_command(args)?;
-
-	let mut codegen_builder = CodegenBuilder::new();
-	codegen_builder.runtime_types_only();
-	codegen_builder.no_docs();
-
-	// Default module derivatives.
-	codegen_builder.disable_default_derives();
-	codegen_builder.set_additional_global_derives(vec![
-		syn::parse_quote!(::codec::Encode),
-		syn::parse_quote!(::codec::Decode),
-		syn::parse_quote!(Clone),
-		syn::parse_quote!(Debug),
-		syn::parse_quote!(PartialEq),
-	]);
-
-	// Add `TypeInfo` just for `frame_metadata_hash_extension::Meta` to be usable for
-	// `TransactionExtension`.
-	codegen_builder.add_derives_for_type(
-		syn::parse_str("frame_metadata_hash_extension::Mode").expect("valid type"),
-		vec![
-			syn::parse_quote!(Eq),
-			syn::parse_quote!(scale_info::TypeInfo),
-			syn::parse_quote!(::codec::DecodeWithMemTracking),
-		],
-		false,
-	);
-
-	// Type substitutes
-	let type_substitutes = vec![
-		TypeSubstitute::simple("sp_core::crypto::AccountId32"),
-		TypeSubstitute::custom("sp_weights::weight_v2::Weight", "::sp_weights::Weight"),
-		TypeSubstitute::custom("sp_runtime::generic::era::Era", "::sp_runtime::generic::Era"),
-		TypeSubstitute::custom(
-			"sp_runtime::generic::header::Header",
-			"::sp_runtime::generic::Header",
-		),
-		TypeSubstitute::simple("sp_runtime::traits::BlakeTwo256"),
-		TypeSubstitute::simple("sp_session::MembershipProof"),
-		TypeSubstitute::simple("sp_consensus_grandpa::EquivocationProof"),
-		TypeSubstitute::simple("bp_header_chain::justification::GrandpaJustification"),
-		TypeSubstitute::simple("bp_header_chain::InitializationData"),
-		TypeSubstitute::simple("bp_polkadot_core::parachains::ParaId"),
-		TypeSubstitute::simple("bp_polkadot_core::parachains::ParaHeadsProof"),
-		TypeSubstitute::simple("bp_messages::target_chain::FromBridgedChainMessagesProof"),
-		TypeSubstitute::simple("bp_messages::source_chain::FromBridgedChainMessagesDeliveryProof"),
-		TypeSubstitute::custom(
-			"bridge_runtime_common::messages::target::FromBridgedChainMessagesProof",
-			"::bp_messages::target_chain::FromBridgedChainMessagesProof",
-		),
-		TypeSubstitute::custom(
-			"bridge_runtime_common::messages::source::FromBridgedChainMessagesDeliveryProof",
-			"::bp_messages::source_chain::FromBridgedChainMessagesDeliveryProof",
-		),
-		TypeSubstitute::simple("bp_messages::UnrewardedRelayersState"),
-		TypeSubstitute::custom(
-			"sp_runtime::generic::digest::Digest",
-			"::sp_runtime::generic::Digest",
-		),
-		TypeSubstitute::custom("bp_messages::lane::LegacyLaneId", "::bp_messages::LegacyLaneId"),
-		TypeSubstitute::custom("bp_messages::lane::HashedLaneId", "::bp_messages::HashedLaneId"),
-	];
-	for type_substitute in type_substitutes {
-		codegen_builder.set_type_substitute(type_substitute.subxt_type, type_substitute.substitute);
-	}
-
-	// Generate the Runtime API.
-	let raw_metadata = match metadata_source {
-		RuntimeMetadataSource::NodeUrl(node_url) =>
-			from_url_blocking(node_url, MetadataVersion::Latest)
-				.map_err(|e| eyre::eyre!("Error fetching metadata from node url: {:?}", e))?,
-		RuntimeMetadataSource::WasmFile(source) => {
-			let testbed = WasmTestBed::new(&source)
-				.map_err(|e| eyre::eyre!("Error creating WasmTestBed: {:?}", e))?;
-			testbed.runtime_metadata_prefixed().encode()
+	match args.subcommand {
+		Some(Subcommand::Diff(params)) => return diff::run(params),
+		Some(Subcommand::RuntimeApis(params)) => {
+			let metadata_source = RuntimeMetadataSource::from_options(
+				params.node_url,
+				params.wasm_file,
+				params.metadata_file,
+			)?;
+			let runtime_apis = generate_runtime_apis(&metadata_source, &params.chain)?;
+			match params.output {
+				Some(output) =>
+					if !process_runtime(runtime_apis, &output, params.check)? {
+						return Err(eyre::eyre!("Generated runtime APIs code is outdated"))
+					},
+				None => print!("{runtime_apis}"),
+			}
+			return Ok(())
 		},
-	};
-	let metadata = Metadata::decode(&mut &raw_metadata[..])
-		.map_err(|e| eyre::eyre!("Error decoding metadata: {:?}", e))?;
-
-	let runtime_api = codegen_builder
-		.generate(metadata)
-		.map_err(|e| eyre::eyre!("Error generating runtime api: {:?}", e))?;
+		Some(Subcommand::Scaffold(params)) => return scaffold::run(params),
+		None => (),
+	}
+	if let Some(manifest) = args.manifest {
+		return regenerate_from_manifest(manifest, args.client, args.check)
+	}
 
-	print_runtime(runtime_api);
+	let metadata_source =
+		RuntimeMetadataSource::from_options(args.node_url, args.wasm_file, args.metadata_file)?;
+	let token = args
+		.token_symbol
+		.zip(args.decimals)
+		.map(|(symbol, decimals)| TokenInfo { symbol, decimals });
+	let runtime = generate_runtime(
+		&metadata_source,
+		&AllowList::new(args.allow),
+		token.as_ref(),
+		&env::args().collect::<Vec<String>>().join(" "),
+	)?;
+	match args.output {
+		Some(output) =>
+			if !process_runtime(runtime, &output, args.check)? {
+				return Err(eyre::eyre!("Generated runtime code is outdated"))
+			},
+		None if args.check =>
+			return Err(eyre::eyre!("Please specify `--output` or `--manifest` to use `--check`")),
+		None => print!("{runtime}"),
+	}
 
 	Ok(())
 }
@@ -0,0 +1,176 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Manifest, listing all runtimes that we need to generate code for.
+
+use crate::{metadata::RuntimeMetadataSource, metadata_hash::TokenInfo, prune::AllowList};
+
+use color_eyre::eyre::{self, WrapErr};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use subxt_utils_fetchmetadata::Url;
+
+/// Runtimes manifest.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Manifest {
+	/// All client crates that are using generated runtime code.
+	#[serde(default)]
+	pub client: Vec<ClientEntry>,
+}
+
+/// Single client crate entry of the manifest.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ClientEntry {
+	/// Name of the client crate.
+	pub name: String,
+	/// URL of the node to fetch metadata from.
+	pub node_url: Option<String>,
+	/// Path to the runtime wasm blob to read metadata from.
+	pub wasm_file: Option<PathBuf>,
+	/// Path to the file with SCALE-encoded metadata.
+	pub metadata_file: Option<PathBuf>,
+	/// Path to the generated file.
+	pub output: PathBuf,
+	/// Pallets and calls to generate code for. If empty, code for the whole runtime is generated.
+	#[serde(default)]
+	pub allow: Vec<String>,
+	/// Symbol of the chain native token, required to compute the metadata hash.
+	pub token_symbol: Option<String>,
+	/// Number of decimals of the chain native token, required to compute the metadata hash.
+	pub decimals: Option<u8>,
+	/// Typed bindings of bridge runtime APIs of the client chain.
+	pub runtime_apis: Option<RuntimeApisEntry>,
+}
+
+/// Bridge runtime APIs of the client chain, exposed by the bridged chain runtime.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RuntimeApisEntry {
+	/// Name of the client chain, used in names of bridge runtime APIs.
+	pub chain: String,
+	/// URL of the bridged chain node to fetch metadata from.
+	pub node_url: Option<String>,
+	/// Path to the bridged chain runtime wasm blob to read metadata from.
+	pub wasm_file: Option<PathBuf>,
+	/// Path to the file with SCALE-encoded metadata of the bridged chain runtime.
+	pub metadata_file: Option<PathBuf>,
+	/// Path to the generated file.
+	pub output: PathBuf,
+}
+
+impl Manifest {
+	/// Read manifest from the file.
+	///
+	/// All relative paths of the manifest are resolved against the directory where the manifest
+	/// file is located.
+	pub fn load(path: &Path) -> color_eyre::Result<Self> {
+		let contents = std::fs::read_to_string(path)
+			.wrap_err_with(|| format!("Error reading manifest {}", path.display()))?;
+		let mut manifest: Manifest = toml::from_str(&contents)
+			.wrap_err_with(|| format!("Error parsing manifest {}", path.display()))?;
+
+		let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+		for client in &mut manifest.client {
+			client.wasm_file = client.wasm_file.take().map(|p| base_dir.join(p));
+			client.metadata_file = client.metadata_file.take().map(|p| base_dir.join(p));
+			client.output = base_dir.join(&client.output);
+			if let Some(ref mut runtime_apis) = client.runtime_apis {
+				runtime_apis.wasm_file = runtime_apis.wasm_file.take().map(|p| base_dir.join(p));
+				runtime_apis.metadata_file =
+					runtime_apis.metadata_file.take().map(|p| base_dir.join(p));
+				runtime_apis.output = base_dir.join(&runtime_apis.output);
+			}
+		}
+
+		Ok(manifest)
+	}
+
+	/// Select clients with given names. If `names` is empty, all clients are returned.
+	///
+	/// The same client may be listed several times - e.g. when it carries code for both the
+	/// current and the next runtime versions. All entries of the client are returned then.
+	pub fn select_clients(&self, names: &[String]) -> color_eyre::Result<Vec<&ClientEntry>> {
+		if names.is_empty() {
+			return Ok(self.client.iter().collect())
+		}
+
+		let mut selected = Vec::new();
+		for name in names {
+			let len_before = selected.len();
+			selected.extend(self.client.iter().filter(|client| client.name == *name));
+			if selected.len() == len_before {
+				return Err(eyre::eyre!("Client {name} is not listed in the manifest"))
+			}
+		}
+		Ok(selected)
+	}
+}
+
+impl ClientEntry {
+	/// Return metadata source of this client.
+	pub fn metadata_source(&self) -> color_eyre::Result<RuntimeMetadataSource> {
+		let node_url = self
+			.node_url
+			.as_ref()
+			.map(|node_url| Url::parse(node_url))
+			.transpose()
+			.wrap_err_with(|| format!("Invalid node url of {}", self.name))?;
+		RuntimeMetadataSource::from_options(
+			node_url,
+			self.wasm_file.clone(),
+			self.metadata_file.clone(),
+		)
+		.wrap_err_with(|| format!("Invalid metadata source of {}", self.name))
+	}
+
+	/// Return native token of this client chain, if it is specified.
+	pub fn token_info(&self) -> color_eyre::Result<Option<TokenInfo>> {
+		match (&self.token_symbol, self.decimals) {
+			(Some(symbol), Some(decimals)) =>
+				Ok(Some(TokenInfo { symbol: symbol.clone(), decimals })),
+			(None, None) => Ok(None),
+			_ => Err(eyre::eyre!(
+				"Both `token-symbol` and `decimals` must be specified for {}",
+				self.name
+			)),
+		}
+	}
+
+	/// Return allow-list of this client.
+	pub fn allow_list(&self) -> AllowList {
+		AllowList::new(self.allow.clone())
+	}
+}
+
+impl RuntimeApisEntry {
+	/// Return metadata source of the bridged chain runtime of the `client`.
+	pub fn metadata_source(&self, client: &str) -> color_eyre::Result<RuntimeMetadataSource> {
+		let node_url = self
+			.node_url
+			.as_ref()
+			.map(|node_url| Url::parse(node_url))
+			.transpose()
+			.wrap_err_with(|| format!("Invalid runtime APIs node url of {client}"))?;
+		RuntimeMetadataSource::from_options(
+			node_url,
+			self.wasm_file.clone(),
+			self.metadata_file.clone(),
+		)
+		.wrap_err_with(|| format!("Invalid runtime APIs metadata source of {client}"))
+	}
+}
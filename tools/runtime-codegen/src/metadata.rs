@@ -0,0 +1,109 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Sources of the runtime metadata.
+
+use codec::Encode;
+use color_eyre::eyre;
+use std::{fmt, path::PathBuf, str::FromStr};
+use subxt_utils_fetchmetadata::{from_url_blocking, MetadataVersion, Url};
+use wasm_testbed::WasmTestBed;
+
+/// Where to take the runtime metadata from.
+#[derive(Clone, Debug)]
+pub enum RuntimeMetadataSource {
+	/// Fetch metadata from the running node.
+	NodeUrl(Url),
+	/// Read metadata from the runtime wasm blob.
+	WasmFile(PathBuf),
+	/// Read SCALE-encoded `RuntimeMetadataPrefixed` from the file.
+	MetadataFile(PathBuf),
+}
+
+impl RuntimeMetadataSource {
+	/// Select metadata source from the given options. Exactly one option must be provided.
+	pub fn from_options(
+		node_url: Option<Url>,
+		wasm_file: Option<PathBuf>,
+		metadata_file: Option<PathBuf>,
+	) -> color_eyre::Result<Self> {
+		match (node_url, wasm_file, metadata_file) {
+			(Some(node_url), None, None) => Ok(Self::NodeUrl(node_url)),
+			(None, Some(wasm_file), None) => Ok(Self::WasmFile(wasm_file)),
+			(None, None, Some(metadata_file)) => Ok(Self::MetadataFile(metadata_file)),
+			(None, None, None) => Err(eyre::eyre!(
+				"Please specify one of `--from-node-url`, `--from-wasm-file` or `--from-metadata-file`"
+			)),
+			_ => Err(eyre::eyre!(
+				"Please specify only one of `--from-node-url`, `--from-wasm-file` or `--from-metadata-file`"
+			)),
+		}
+	}
+
+	/// Read raw (SCALE-encoded `RuntimeMetadataPrefixed`) metadata from the source.
+	pub fn fetch(&self) -> color_eyre::Result<Vec<u8>> {
+		match self {
+			// metadata hash is computed over the `V15` metadata, so we ask for this version
+			Self::NodeUrl(node_url) =>
+				from_url_blocking(node_url.clone(), MetadataVersion::Version(15))
+					.map_err(|e| eyre::eyre!("Error fetching metadata from node url: {:?}", e)),
+			Self::WasmFile(wasm_file) => {
+				let source = wasm_loader::Source::File(wasm_file.clone());
+				let testbed = WasmTestBed::new(&source)
+					.map_err(|e| eyre::eyre!("Error creating WasmTestBed: {:?}", e))?;
+				Ok(testbed.runtime_metadata_prefixed().encode())
+			},
+			Self::MetadataFile(metadata_file) => std::fs::read(metadata_file).map_err(|e| {
+				eyre::eyre!("Error reading metadata file {}: {:?}", metadata_file.display(), e)
+			}),
+		}
+	}
+}
+
+impl FromStr for RuntimeMetadataSource {
+	type Err = String;
+
+	/// Parse metadata source: node URL, path to the runtime wasm blob (with `.wasm` extension) or
+	/// path to the file with SCALE-encoded metadata.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if ["ws://", "wss://", "http://", "https://"]
+			.iter()
+			.any(|scheme| s.starts_with(scheme))
+		{
+			return Url::parse(s)
+				.map(Self::NodeUrl)
+				.map_err(|e| format!("Invalid node url {s}: {e}"))
+		}
+
+		let path = PathBuf::from(s);
+		if path.extension().is_some_and(|extension| extension == "wasm") {
+			Ok(Self::WasmFile(path))
+		} else {
+			Ok(Self::MetadataFile(path))
+		}
+	}
+}
+
+impl fmt::Display for RuntimeMetadataSource {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::NodeUrl(node_url) => write!(f, "--from-node-url {node_url}"),
+			Self::WasmFile(wasm_file) => write!(f, "--from-wasm-file {}", wasm_file.display()),
+			Self::MetadataFile(metadata_file) =>
+				write!(f, "--from-metadata-file {}", metadata_file.display()),
+		}
+	}
+}
@@ -0,0 +1,91 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Computation of the [RFC-78](https://polkadot-fellows.github.io/RFCs/approved/0078-merkleized-metadata.html)
+//! metadata hash, used by the `CheckMetadataHash` transaction extension.
+
+use codec::Decode;
+use color_eyre::eyre;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use merkleized_metadata::{generate_metadata_digest, ExtraInfo};
+
+/// Metadata hash of the runtime.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MetadataHash {
+	/// Spec version of the runtime.
+	pub spec_version: u32,
+	/// The hash itself.
+	pub hash: [u8; 32],
+}
+
+/// Native token of the chain. It is not a part of the metadata, but is required to compute
+/// the hash.
+#[derive(Clone, Debug)]
+pub struct TokenInfo {
+	/// Token symbol.
+	pub symbol: String,
+	/// Number of token decimals.
+	pub decimals: u8,
+}
+
+/// Compute metadata hash using raw (SCALE-encoded `RuntimeMetadataPrefixed`) metadata.
+///
+/// The same hash is computed by the `substrate-wasm-builder` when the runtime is built with the
+/// metadata hash enabled, so it requires the `V15` metadata.
+pub fn compute_metadata_hash(
+	raw_metadata: &[u8],
+	token: &TokenInfo,
+) -> color_eyre::Result<MetadataHash> {
+	let metadata = RuntimeMetadataPrefixed::decode(&mut &raw_metadata[..])
+		.map_err(|e| eyre::eyre!("Error decoding metadata: {:?}", e))?;
+	let RuntimeMetadata::V15(ref metadata_v15) = metadata.1 else {
+		return Err(eyre::eyre!(
+			"Metadata hash may only be computed from V15 metadata. Got V{}",
+			metadata.1.version()
+		))
+	};
+
+	let system_constant = |name: &str| {
+		metadata_v15
+			.pallets
+			.iter()
+			.filter(|pallet| pallet.name == "System")
+			.flat_map(|pallet| pallet.constants.iter())
+			.find(|constant| constant.name == name)
+			.map(|constant| constant.value.clone())
+			.ok_or_else(|| eyre::eyre!("Missing System::{name} constant in the metadata"))
+	};
+	// `RuntimeVersion` starts with `spec_name`, `impl_name`, `authoring_version` and `spec_version`
+	let (spec_name, _impl_name, _authoring_version, spec_version) =
+		<(String, String, u32, u32)>::decode(&mut &system_constant("Version")?[..])
+			.map_err(|e| eyre::eyre!("Error decoding System::Version constant: {:?}", e))?;
+	let base58_prefix = u16::decode(&mut &system_constant("SS58Prefix")?[..])
+		.map_err(|e| eyre::eyre!("Error decoding System::SS58Prefix constant: {:?}", e))?;
+
+	let digest = generate_metadata_digest(
+		&metadata.1,
+		ExtraInfo {
+			spec_version,
+			spec_name,
+			base58_prefix,
+			decimals: token.decimals,
+			token_symbol: token.symbol.clone(),
+		},
+	)
+	.map_err(|e| eyre::eyre!("Error computing metadata hash: {e}"))?;
+
+	Ok(MetadataHash { spec_version, hash: digest.hash() })
+}
@@ -0,0 +1,346 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pruning of the runtime metadata.
+//!
+//! Relay clients only use a handful of runtime calls, so there's no need to generate code for
+//! all runtime types. Pruned metadata only has allowed pallets (with allowed calls) in the
+//! `RuntimeCall` and the types they (transitively) depend on. Codec indices of all remaining
+//! pallets and calls are kept unchanged.
+
+use codec::{Decode, Encode};
+use color_eyre::eyre;
+use frame_metadata::{
+	v14::RuntimeMetadataV14, v15::RuntimeMetadataV15, RuntimeMetadata, RuntimeMetadataPrefixed,
+};
+use scale_info::{
+	form::{Form, PortableForm},
+	PortableRegistry, TypeDef, Variant,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Allow-list of pallets and calls that are kept in the pruned metadata.
+///
+/// Every entry is either a pallet name (`Utility`), meaning that all calls of the pallet are kept,
+/// or a pallet call (`Utility::batch_all`). Both pallet and call names may contain `*` wildcards
+/// (`Bridge*`).
+#[derive(Clone, Debug, Default)]
+pub struct AllowList {
+	entries: Vec<String>,
+}
+
+impl AllowList {
+	/// Create allow-list from the given entries.
+	pub fn new(entries: Vec<String>) -> Self {
+		Self { entries }
+	}
+
+	/// Returns true if nothing is pruned.
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// Returns allow-list entries.
+	pub fn entries(&self) -> &[String] {
+		&self.entries
+	}
+
+	fn split(entry: &str) -> (&str, Option<&str>) {
+		match entry.split_once("::") {
+			Some((pallet, call)) => (pallet, Some(call)),
+			None => (entry, None),
+		}
+	}
+
+	fn allows_pallet(&self, pallet: &str) -> bool {
+		self.entries.iter().any(|entry| matches(Self::split(entry).0, pallet))
+	}
+
+	fn allows_call(&self, pallet: &str, call: &str) -> bool {
+		self.entries.iter().any(|entry| match Self::split(entry) {
+			(pallet_pattern, None) => matches(pallet_pattern, pallet),
+			(pallet_pattern, Some(call_pattern)) =>
+				matches(pallet_pattern, pallet) && matches(call_pattern, call),
+		})
+	}
+}
+
+/// Remove everything that is not required to express allowed pallets and calls from the raw
+/// (SCALE-encoded `RuntimeMetadataPrefixed`) metadata.
+pub fn prune_metadata(raw_metadata: &[u8], allow_list: &AllowList) -> color_eyre::Result<Vec<u8>> {
+	let mut metadata = RuntimeMetadataPrefixed::decode(&mut &raw_metadata[..])
+		.map_err(|e| eyre::eyre!("Error decoding metadata: {:?}", e))?;
+	match metadata.1 {
+		RuntimeMetadata::V14(ref mut metadata) => prune_v14(metadata, allow_list)?,
+		RuntimeMetadata::V15(ref mut metadata) => prune_v15(metadata, allow_list)?,
+		ref metadata =>
+			return Err(eyre::eyre!("Unsupported metadata version: V{}", metadata.version())),
+	}
+	Ok(metadata.encode())
+}
+
+fn prune_v14(metadata: &mut RuntimeMetadataV14, allow_list: &AllowList) -> color_eyre::Result<()> {
+	let selection = Selection::new(
+		&metadata.types,
+		metadata
+			.pallets
+			.iter()
+			.map(|p| (p.name.as_str(), p.calls.as_ref().map(|c| c.ty.id))),
+		allow_list,
+	)?;
+	metadata.pallets.retain(|pallet| selection.pallets.contains(&pallet.name));
+	for pallet in &mut metadata.pallets {
+		pallet.storage = None;
+		pallet.constants.clear();
+	}
+
+	// V14 metadata has no references to outer enums - they are found by name
+	let outer_enums = ["RuntimeCall", "RuntimeEvent", "RuntimeError"]
+		.into_iter()
+		.filter_map(|name| find_variant_type(&metadata.types, name))
+		.collect::<Vec<_>>();
+	selection.apply(&mut metadata.types, &outer_enums);
+
+	let mut roots = vec![metadata.ty.id, metadata.extrinsic.ty.id];
+	roots.extend(outer_enums);
+	for ext in &metadata.extrinsic.signed_extensions {
+		roots.extend([ext.ty.id, ext.additional_signed.id]);
+	}
+	for pallet in &metadata.pallets {
+		roots.extend(pallet.calls.as_ref().map(|c| c.ty.id));
+		roots.extend(pallet.event.as_ref().map(|e| e.ty.id));
+		roots.extend(pallet.error.as_ref().map(|e| e.ty.id));
+	}
+
+	let ids = retain_types(&mut metadata.types, roots);
+	remap(&ids, &mut metadata.ty);
+	remap(&ids, &mut metadata.extrinsic.ty);
+	for ext in &mut metadata.extrinsic.signed_extensions {
+		remap(&ids, &mut ext.ty);
+		remap(&ids, &mut ext.additional_signed);
+	}
+	for pallet in &mut metadata.pallets {
+		pallet.calls.iter_mut().for_each(|c| remap(&ids, &mut c.ty));
+		pallet.event.iter_mut().for_each(|e| remap(&ids, &mut e.ty));
+		pallet.error.iter_mut().for_each(|e| remap(&ids, &mut e.ty));
+	}
+
+	Ok(())
+}
+
+fn prune_v15(metadata: &mut RuntimeMetadataV15, allow_list: &AllowList) -> color_eyre::Result<()> {
+	let selection = Selection::new(
+		&metadata.types,
+		metadata
+			.pallets
+			.iter()
+			.map(|p| (p.name.as_str(), p.calls.as_ref().map(|c| c.ty.id))),
+		allow_list,
+	)?;
+	metadata.pallets.retain(|pallet| selection.pallets.contains(&pallet.name));
+	for pallet in &mut metadata.pallets {
+		pallet.storage = None;
+		pallet.constants.clear();
+	}
+	metadata.apis.clear();
+	metadata.custom.map.clear();
+
+	let outer_enums = [
+		metadata.outer_enums.call_enum_ty.id,
+		metadata.outer_enums.event_enum_ty.id,
+		metadata.outer_enums.error_enum_ty.id,
+	];
+	selection.apply(&mut metadata.types, &outer_enums);
+
+	let mut roots = vec![
+		metadata.ty.id,
+		metadata.extrinsic.address_ty.id,
+		metadata.extrinsic.call_ty.id,
+		metadata.extrinsic.signature_ty.id,
+		metadata.extrinsic.extra_ty.id,
+	];
+	roots.extend(outer_enums);
+	for ext in &metadata.extrinsic.signed_extensions {
+		roots.extend([ext.ty.id, ext.additional_signed.id]);
+	}
+	for pallet in &metadata.pallets {
+		roots.extend(pallet.calls.as_ref().map(|c| c.ty.id));
+		roots.extend(pallet.event.as_ref().map(|e| e.ty.id));
+		roots.extend(pallet.error.as_ref().map(|e| e.ty.id));
+	}
+
+	let ids = retain_types(&mut metadata.types, roots);
+	remap(&ids, &mut metadata.ty);
+	remap(&ids, &mut metadata.extrinsic.address_ty);
+	remap(&ids, &mut metadata.extrinsic.call_ty);
+	remap(&ids, &mut metadata.extrinsic.signature_ty);
+	remap(&ids, &mut metadata.extrinsic.extra_ty);
+	for ext in &mut metadata.extrinsic.signed_extensions {
+		remap(&ids, &mut ext.ty);
+		remap(&ids, &mut ext.additional_signed);
+	}
+	remap(&ids, &mut metadata.outer_enums.call_enum_ty);
+	remap(&ids, &mut metadata.outer_enums.event_enum_ty);
+	remap(&ids, &mut metadata.outer_enums.error_enum_ty);
+	for pallet in &mut metadata.pallets {
+		pallet.calls.iter_mut().for_each(|c| remap(&ids, &mut c.ty));
+		pallet.event.iter_mut().for_each(|e| remap(&ids, &mut e.ty));
+		pallet.error.iter_mut().for_each(|e| remap(&ids, &mut e.ty));
+	}
+
+	Ok(())
+}
+
+/// Pallets and calls, selected by the allow-list.
+struct Selection {
+	/// Names of selected pallets.
+	pallets: BTreeSet<String>,
+	/// Selected calls, keyed by the pallet `Call` type. Multiple pallet instances may share the
+	/// same `Call` type, so it contains calls, selected for any of instances.
+	calls: BTreeMap<u32, BTreeSet<String>>,
+}
+
+impl Selection {
+	fn new<'a>(
+		registry: &PortableRegistry,
+		pallets: impl Iterator<Item = (&'a str, Option<u32>)>,
+		allow_list: &AllowList,
+	) -> color_eyre::Result<Self> {
+		let mut selection = Selection { pallets: BTreeSet::new(), calls: BTreeMap::new() };
+		let mut unused_entries = allow_list
+			.entries
+			.iter()
+			.filter(|entry| !entry.contains('*'))
+			.collect::<BTreeSet<_>>();
+		for (pallet, call_type) in pallets.filter(|(pallet, _)| allow_list.allows_pallet(pallet)) {
+			selection.pallets.insert(pallet.into());
+			unused_entries.retain(|entry| AllowList::split(entry) != (pallet, None));
+
+			let Some(call_type) = call_type else { continue };
+			let calls = selection.calls.entry(call_type).or_default();
+			for call in variants(registry, call_type).iter().map(|call| call.name.as_str()) {
+				if allow_list.allows_call(pallet, call) {
+					calls.insert(call.into());
+					unused_entries.retain(|entry| AllowList::split(entry) != (pallet, Some(call)));
+				}
+			}
+		}
+
+		if !unused_entries.is_empty() {
+			return Err(eyre::eyre!(
+				"Allow-list entries are not found in the metadata: {}",
+				unused_entries.into_iter().cloned().collect::<Vec<_>>().join(", ")
+			))
+		}
+
+		Ok(selection)
+	}
+
+	/// Remove variants of pallets and calls that are not selected from the outer enums and pallet
+	/// `Call` types.
+	fn apply(&self, registry: &mut PortableRegistry, outer_enums: &[u32]) {
+		for outer_enum in outer_enums {
+			retain_variants(registry, *outer_enum, |variant| self.pallets.contains(&variant.name));
+		}
+		for (call_type, calls) in &self.calls {
+			retain_variants(registry, *call_type, |variant| calls.contains(&variant.name));
+		}
+	}
+}
+
+/// Retain given types and all types they depend on. Returns mapping of old type ids to new ids.
+fn retain_types(registry: &mut PortableRegistry, roots: Vec<u32>) -> BTreeMap<u32, u32> {
+	let roots = roots.into_iter().collect::<BTreeSet<_>>();
+	registry.retain(|id| roots.contains(&id))
+}
+
+fn remap(ids: &BTreeMap<u32, u32>, ty: &mut <PortableForm as Form>::Type) {
+	// all referenced types are retained by `retain_types`
+	*ty = ids[&ty.id].into();
+}
+
+fn variants(registry: &PortableRegistry, id: u32) -> &[Variant<PortableForm>] {
+	match registry.resolve(id).map(|ty| &ty.type_def) {
+		Some(TypeDef::Variant(variant)) => &variant.variants,
+		_ => &[],
+	}
+}
+
+fn retain_variants(
+	registry: &mut PortableRegistry,
+	id: u32,
+	filter: impl Fn(&Variant<PortableForm>) -> bool,
+) {
+	let ty = registry.types.iter_mut().find(|ty| ty.id == id);
+	if let Some(TypeDef::Variant(ref mut variant)) = ty.map(|ty| &mut ty.ty.type_def) {
+		variant.variants.retain(filter);
+	}
+}
+
+fn find_variant_type(registry: &PortableRegistry, name: &str) -> Option<u32> {
+	registry
+		.types
+		.iter()
+		.find(|ty| {
+			ty.ty.path.segments.last().map(String::as_str) == Some(name) &&
+				matches!(ty.ty.type_def, TypeDef::Variant(_))
+		})
+		.map(|ty| ty.id)
+}
+
+/// Returns true if `name` matches the `pattern`, where `*` matches any (possibly empty) sequence
+/// of characters.
+fn matches(pattern: &str, name: &str) -> bool {
+	let mut parts = pattern.split('*');
+	let first = parts.next().unwrap_or_default();
+	let Some(mut rest) = name.strip_prefix(first) else { return false };
+
+	let mut parts = parts.collect::<Vec<_>>();
+	let Some(last) = parts.pop() else { return rest.is_empty() };
+	for part in parts {
+		match rest.find(part) {
+			Some(position) => rest = &rest[position + part.len()..],
+			None => return false,
+		}
+	}
+	rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn wildcards_are_matched() {
+		assert!(matches("Utility", "Utility"));
+		assert!(!matches("Utility", "UtilityX"));
+		assert!(matches("Bridge*", "BridgeKusamaGrandpa"));
+		assert!(matches("Bridge*Grandpa", "BridgeKusamaGrandpa"));
+		assert!(!matches("Bridge*Grandpa", "BridgeKusamaMessages"));
+		assert!(matches("*", "System"));
+	}
+
+	#[test]
+	fn calls_are_allowed() {
+		let allow_list = AllowList::new(vec!["Bridge*".into(), "Utility::batch_all".into()]);
+		assert!(allow_list.allows_pallet("BridgeKusamaGrandpa"));
+		assert!(allow_list.allows_call("BridgeKusamaGrandpa", "submit_finality_proof"));
+		assert!(allow_list.allows_pallet("Utility"));
+		assert!(allow_list.allows_call("Utility", "batch_all"));
+		assert!(!allow_list.allows_call("Utility", "batch"));
+		assert!(!allow_list.allows_pallet("System"));
+	}
+}
@@ -0,0 +1,292 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Typed bindings of the bridge runtime APIs.
+//!
+//! Bridge runtime APIs of the chain are exposed by runtimes of other (bridged) chains, so
+//! bindings are generated from the metadata of the bridged runtime and are written to the
+//! separate file of the chain client.
+
+use crate::codegen::format_runtime;
+
+use codec::Decode;
+use color_eyre::eyre;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+use scale_info::{PortableRegistry, TypeDef, TypeDefPrimitive};
+use std::path::PathBuf;
+use subxt_codegen::syn;
+use subxt_utils_fetchmetadata::Url;
+
+/// Rust types, used instead of runtime types in bindings. Only types of bridge runtime APIs are
+/// listed here.
+const TYPE_SUBSTITUTES: &[(&str, &str)] = &[
+	("bp_header_chain::StoredHeaderGrandpaInfo", "::bp_header_chain::StoredHeaderGrandpaInfo"),
+	("bp_messages::InboundMessageDetails", "::bp_messages::InboundMessageDetails"),
+	("bp_messages::OutboundMessageDetails", "::bp_messages::OutboundMessageDetails"),
+	("bp_messages::lane::HashedLaneId", "::bp_messages::HashedLaneId"),
+	("bp_messages::lane::LegacyLaneId", "::bp_messages::LegacyLaneId"),
+	("bp_runtime::HeaderId", "::bp_runtime::HeaderId"),
+	("primitive_types::H256", "::subxt::ext::subxt_core::utils::H256"),
+	("sp_runtime::generic::header::Header", "::sp_runtime::generic::Header"),
+	("sp_runtime::traits::BlakeTwo256", "::sp_runtime::traits::BlakeTwo256"),
+	("sp_weights::weight_v2::Weight", "::sp_weights::Weight"),
+];
+
+/// Generate typed bindings of bridge runtime APIs of the chain.
+#[derive(Debug, clap::Args)]
+pub struct RuntimeApisParams {
+	/// URL of the bridged chain node, that exposes bridge runtime APIs of the `chain`.
+	#[clap(name = "from-node-url", long, value_parser)]
+	pub node_url: Option<Url>,
+	/// Runtime wasm blob of the bridged chain.
+	#[clap(name = "from-wasm-file", long, value_parser)]
+	pub wasm_file: Option<PathBuf>,
+	/// SCALE-encoded metadata of the bridged chain runtime.
+	#[clap(name = "from-metadata-file", long, value_parser)]
+	pub metadata_file: Option<PathBuf>,
+	/// Name of the chain, used in names of its bridge runtime APIs (e.g. `BridgeHubKusama` for
+	/// the `BridgeHubKusamaFinalityApi`).
+	#[clap(long, value_parser)]
+	pub chain: String,
+	/// Write generated code to the given file instead of printing it.
+	#[clap(long, value_parser)]
+	pub output: Option<PathBuf>,
+	/// Do not write anything, but compare generated code with the `--output` file and fail if
+	/// they differ.
+	#[clap(long, requires = "output")]
+	pub check: bool,
+}
+
+/// Generate typed bindings of bridge runtime APIs of the `chain` from the raw (SCALE-encoded
+/// `RuntimeMetadataPrefixed`) metadata of the bridged runtime.
+///
+/// For every method of `{chain}FinalityApi`, `To{chain}OutboundLaneApi` and
+/// `From{chain}InboundLaneApi`, the module with the method `NAME` and its `Input` and `Output`
+/// types is generated. Requires `V15` metadata, because earlier versions have no runtime APIs.
+pub fn generate_runtime_apis(
+	raw_metadata: &[u8],
+	chain: &str,
+	executed_command: &str,
+) -> color_eyre::Result<String> {
+	let metadata = RuntimeMetadataPrefixed::decode(&mut &raw_metadata[..])
+		.map_err(|e| eyre::eyre!("Error decoding metadata: {:?}", e))?;
+	let RuntimeMetadata::V15(ref metadata) = metadata.1 else {
+		return Err(eyre::eyre!(
+			"Runtime APIs are only available in V15 metadata. Got V{}",
+			metadata.1.version()
+		))
+	};
+
+	let bridge_apis = [
+		format!("{chain}FinalityApi"),
+		format!("To{chain}OutboundLaneApi"),
+		format!("From{chain}InboundLaneApi"),
+	];
+	let types = TypePaths { registry: &metadata.types };
+	let apis = metadata
+		.apis
+		.iter()
+		.filter(|api| bridge_apis.contains(&api.name))
+		.map(|api| {
+			let methods = api
+				.methods
+				.iter()
+				.map(|method| {
+					let full_name = format!("{}_{}", api.name, method.name);
+					let input = method
+						.inputs
+						.iter()
+						.map(|input| types.path(input.ty.id, &full_name))
+						.collect::<color_eyre::Result<Vec<_>>>()?;
+					let input = match input.as_slice() {
+						[input] => input.clone(),
+						inputs => quote!((#(#inputs),*)),
+					};
+					let output = types.path(method.output.id, &full_name)?;
+					let module = format_ident!("{}", method.name);
+					let doc = format!(" `{}::{}` runtime API method.", api.name, method.name);
+					Ok(quote! {
+						#[doc = #doc]
+						pub mod #module {
+							/// Name of the method, used in the `state_call` RPC.
+							pub const NAME: &str = #full_name;
+							/// Method arguments.
+							pub type Input = #input;
+							/// Method output.
+							pub type Output = #output;
+						}
+					})
+				})
+				.collect::<color_eyre::Result<Vec<_>>>()?;
+			let module = format_ident!("{}", to_snake_case(&api.name));
+			let doc = format!(" `{}` runtime API.", api.name);
+			Ok(quote! {
+				#[doc = #doc]
+				pub mod #module {
+					#(#methods)*
+				}
+			})
+		})
+		.collect::<color_eyre::Result<Vec<_>>>()?;
+	if apis.is_empty() {
+		return Err(eyre::eyre!(
+			"Runtime doesn't expose any of bridge runtime APIs: {}",
+			bridge_apis.join(", ")
+		))
+	}
+
+	format_runtime(quote!(#(#apis)*), executed_command)
+}
+
+/// Rust paths of metadata types.
+struct TypePaths<'a> {
+	registry: &'a PortableRegistry,
+}
+
+impl TypePaths<'_> {
+	/// Returns Rust path of the type, used by the `method`.
+	fn path(&self, id: u32, method: &str) -> color_eyre::Result<TokenStream> {
+		let ty = self
+			.registry
+			.resolve(id)
+			.ok_or_else(|| eyre::eyre!("Unknown type {id} of {method}"))?;
+		let params = ty
+			.type_params
+			.iter()
+			.filter_map(|param| param.ty.map(|param| self.path(param.id, method)))
+			.collect::<color_eyre::Result<Vec<_>>>()?;
+		let params = (!params.is_empty()).then(|| quote!(<#(#params),*>));
+
+		Ok(match ty.type_def {
+			TypeDef::Composite(_) | TypeDef::Variant(_) => {
+				let path = ty.path.segments.join("::");
+				let rust_path = match path.as_str() {
+					"Option" => "::core::option::Option",
+					"Result" => "::core::result::Result",
+					path => TYPE_SUBSTITUTES
+						.iter()
+						.find(|(runtime_type, _)| *runtime_type == path)
+						.map(|(_, rust_type)| *rust_type)
+						.ok_or_else(|| {
+							eyre::eyre!("Type {path} of {method} has no known Rust substitute")
+						})?,
+				};
+				let rust_path = syn::parse_str::<syn::Path>(rust_path)
+					.map_err(|e| eyre::eyre!("Invalid Rust path {rust_path}: {e:?}"))?;
+				quote!(#rust_path #params)
+			},
+			TypeDef::Sequence(ref seq) => {
+				let item = self.path(seq.type_param.id, method)?;
+				quote!(::subxt::ext::subxt_core::alloc::vec::Vec<#item>)
+			},
+			TypeDef::Array(ref array) => {
+				let item = self.path(array.type_param.id, method)?;
+				let len = Literal::usize_suffixed(array.len as usize);
+				quote!([#item; #len])
+			},
+			TypeDef::Tuple(ref tuple) => {
+				let fields = tuple
+					.fields
+					.iter()
+					.map(|field| self.path(field.id, method))
+					.collect::<color_eyre::Result<Vec<_>>>()?;
+				quote!((#(#fields,)*))
+			},
+			TypeDef::Primitive(ref primitive) => primitive_path(primitive),
+			TypeDef::Compact(ref compact) => {
+				let inner = self.path(compact.type_param.id, method)?;
+				quote!(::codec::Compact<#inner>)
+			},
+			TypeDef::BitSequence(_) =>
+				return Err(eyre::eyre!("Bit sequences of {method} are not supported")),
+		})
+	}
+}
+
+fn primitive_path(primitive: &TypeDefPrimitive) -> TokenStream {
+	match primitive {
+		TypeDefPrimitive::Bool => quote!(::core::primitive::bool),
+		TypeDefPrimitive::Char => quote!(::core::primitive::char),
+		TypeDefPrimitive::Str => quote!(::subxt::ext::subxt_core::alloc::string::String),
+		TypeDefPrimitive::U8 => quote!(::core::primitive::u8),
+		TypeDefPrimitive::U16 => quote!(::core::primitive::u16),
+		TypeDefPrimitive::U32 => quote!(::core::primitive::u32),
+		TypeDefPrimitive::U64 => quote!(::core::primitive::u64),
+		TypeDefPrimitive::U128 => quote!(::core::primitive::u128),
+		TypeDefPrimitive::U256 => quote!(::subxt::ext::subxt_core::utils::U256),
+		TypeDefPrimitive::I8 => quote!(::core::primitive::i8),
+		TypeDefPrimitive::I16 => quote!(::core::primitive::i16),
+		TypeDefPrimitive::I32 => quote!(::core::primitive::i32),
+		TypeDefPrimitive::I64 => quote!(::core::primitive::i64),
+		TypeDefPrimitive::I128 => quote!(::core::primitive::i128),
+		TypeDefPrimitive::I256 => quote!(::subxt::ext::subxt_core::utils::I256),
+	}
+}
+
+/// Convert `UpperCamelCase` runtime API name to the `snake_case` module name.
+pub(crate) fn to_snake_case(name: &str) -> String {
+	let mut result = String::with_capacity(name.len() + 4);
+	for (index, c) in name.chars().enumerate() {
+		if c.is_uppercase() && index != 0 {
+			result.push('_');
+		}
+		result.extend(c.to_lowercase());
+	}
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use scale_info::{meta_type, Registry};
+	use std::collections::BTreeMap;
+
+	fn type_path<T: scale_info::TypeInfo + 'static>() -> color_eyre::Result<String> {
+		let mut registry = Registry::new();
+		let id = registry.register_type(&meta_type::<T>()).id;
+		let registry = PortableRegistry::from(registry);
+		TypePaths { registry: &registry }
+			.path(id, "TestApi_method")
+			.map(|path| path.to_string().replace(' ', ""))
+	}
+
+	#[test]
+	fn api_names_are_converted_to_module_names() {
+		assert_eq!(to_snake_case("BridgeHubKusamaFinalityApi"), "bridge_hub_kusama_finality_api");
+		assert_eq!(
+			to_snake_case("ToBridgeHubKusamaOutboundLaneApi"),
+			"to_bridge_hub_kusama_outbound_lane_api"
+		);
+	}
+
+	#[test]
+	fn runtime_types_are_resolved() {
+		assert_eq!(
+			type_path::<Option<(u32, Vec<u8>, [u8; 4])>>().unwrap(),
+			"::core::option::Option<(::core::primitive::u32,\
+			::subxt::ext::subxt_core::alloc::vec::Vec<::core::primitive::u8>,\
+			[::core::primitive::u8;4usize],)>",
+		);
+	}
+
+	#[test]
+	fn unknown_runtime_types_are_rejected() {
+		let error = type_path::<BTreeMap<u32, u32>>().unwrap_err().to_string();
+		assert_eq!(error, "Type BTreeMap of TestApi_method has no known Rust substitute");
+	}
+}
@@ -0,0 +1,400 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime information, extracted from the metadata.
+//!
+//! Types are described by their name and by their layout. The layout is the structural
+//! description of the type (all fields and variants, expanded recursively), so two types
+//! are SCALE-compatible if their layouts are equal.
+
+use codec::Decode;
+use color_eyre::eyre;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use scale_info::{form::PortableForm, Field, PortableRegistry, TypeDef, TypeDefPrimitive};
+use std::collections::BTreeSet;
+
+/// Name of the outer call enum. Its layout is never expanded: changes of the outer call are
+/// reported as changes of pallets and their calls.
+const RUNTIME_CALL_TYPE_NAME: &str = "RuntimeCall";
+
+/// Runtime information, required to compare two runtime versions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeInfo {
+	/// Runtime pallets.
+	pub pallets: Vec<PalletInfo>,
+	/// Transaction extensions in the order they're included into transactions.
+	pub transaction_extensions: Vec<TransactionExtensionInfo>,
+	/// Runtime APIs. `None` if metadata doesn't include runtime APIs (metadata `V14`).
+	pub runtime_apis: Option<Vec<RuntimeApiInfo>>,
+}
+
+/// Runtime pallet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PalletInfo {
+	/// Pallet name.
+	pub name: String,
+	/// Pallet index within the `RuntimeCall`.
+	pub index: u8,
+	/// Full path of the pallet `Call` type, if pallet has calls.
+	pub call_type: Option<String>,
+	/// Pallet calls.
+	pub calls: Vec<CallInfo>,
+}
+
+/// Single pallet call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallInfo {
+	/// Call name.
+	pub name: String,
+	/// Call index within the pallet `Call`.
+	pub index: u8,
+	/// Call arguments.
+	pub args: Vec<ArgInfo>,
+}
+
+/// Named argument of call or runtime API method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArgInfo {
+	/// Argument name.
+	pub name: String,
+	/// Argument type.
+	pub ty: TypeInfo,
+}
+
+/// Transaction extension.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionExtensionInfo {
+	/// Extension identifier.
+	pub identifier: String,
+	/// Type of data, included into transaction.
+	pub ty: TypeInfo,
+	/// Type of data, that is not included into transaction, but is signed.
+	pub implicit: TypeInfo,
+}
+
+/// Runtime API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeApiInfo {
+	/// API (trait) name.
+	pub name: String,
+	/// API methods.
+	pub methods: Vec<RuntimeApiMethodInfo>,
+}
+
+/// Runtime API method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeApiMethodInfo {
+	/// Method name.
+	pub name: String,
+	/// Method inputs.
+	pub inputs: Vec<ArgInfo>,
+	/// Method output.
+	pub output: TypeInfo,
+}
+
+/// Type description.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeInfo {
+	/// Type name, e.g. `Vec<BoundedVec<u8>>`.
+	pub name: String,
+	/// Type layout.
+	pub layout: String,
+}
+
+impl RuntimeInfo {
+	/// Decode raw (SCALE-encoded `RuntimeMetadataPrefixed`) metadata.
+	pub fn decode(raw_metadata: &[u8]) -> color_eyre::Result<Self> {
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &raw_metadata[..])
+			.map_err(|e| eyre::eyre!("Error decoding metadata: {:?}", e))?;
+		Self::from_metadata(&metadata.1)
+	}
+
+	/// Extract runtime information from the decoded metadata.
+	pub fn from_metadata(metadata: &RuntimeMetadata) -> color_eyre::Result<Self> {
+		match metadata {
+			RuntimeMetadata::V14(metadata) => {
+				let types = TypeDescriber::new(&metadata.types);
+				Ok(Self {
+					pallets: metadata
+						.pallets
+						.iter()
+						.map(|pallet| {
+							types.pallet(
+								&pallet.name,
+								pallet.index,
+								pallet.calls.as_ref().map(|c| c.ty.id),
+							)
+						})
+						.collect(),
+					transaction_extensions: metadata
+						.extrinsic
+						.signed_extensions
+						.iter()
+						.map(|ext| TransactionExtensionInfo {
+							identifier: ext.identifier.clone(),
+							ty: types.describe(ext.ty.id),
+							implicit: types.describe(ext.additional_signed.id),
+						})
+						.collect(),
+					runtime_apis: None,
+				})
+			},
+			RuntimeMetadata::V15(metadata) => {
+				let types = TypeDescriber::new(&metadata.types);
+				Ok(Self {
+					pallets: metadata
+						.pallets
+						.iter()
+						.map(|pallet| {
+							types.pallet(
+								&pallet.name,
+								pallet.index,
+								pallet.calls.as_ref().map(|c| c.ty.id),
+							)
+						})
+						.collect(),
+					transaction_extensions: metadata
+						.extrinsic
+						.signed_extensions
+						.iter()
+						.map(|ext| TransactionExtensionInfo {
+							identifier: ext.identifier.clone(),
+							ty: types.describe(ext.ty.id),
+							implicit: types.describe(ext.additional_signed.id),
+						})
+						.collect(),
+					runtime_apis: Some(
+						metadata
+							.apis
+							.iter()
+							.map(|api| RuntimeApiInfo {
+								name: api.name.clone(),
+								methods: api
+									.methods
+									.iter()
+									.map(|method| RuntimeApiMethodInfo {
+										name: method.name.clone(),
+										inputs: method
+											.inputs
+											.iter()
+											.map(|input| ArgInfo {
+												name: input.name.clone(),
+												ty: types.describe(input.ty.id),
+											})
+											.collect(),
+										output: types.describe(method.output.id),
+									})
+									.collect(),
+							})
+							.collect(),
+					),
+				})
+			},
+			_ => Err(eyre::eyre!("Unsupported metadata version: V{}", metadata.version())),
+		}
+	}
+}
+
+/// Helper for describing types of the metadata registry.
+struct TypeDescriber<'a> {
+	registry: &'a PortableRegistry,
+}
+
+impl<'a> TypeDescriber<'a> {
+	fn new(registry: &'a PortableRegistry) -> Self {
+		Self { registry }
+	}
+
+	/// Describe pallet with given call type.
+	fn pallet(&self, name: &str, index: u8, call_type: Option<u32>) -> PalletInfo {
+		let calls = match call_type.and_then(|id| self.registry.resolve(id)) {
+			Some(ty) => match ty.type_def {
+				TypeDef::Variant(ref calls) => calls
+					.variants
+					.iter()
+					.map(|call| CallInfo {
+						name: call.name.clone(),
+						index: call.index,
+						args: self.named_fields(&call.fields),
+					})
+					.collect(),
+				_ => Vec::new(),
+			},
+			None => Vec::new(),
+		};
+
+		PalletInfo {
+			name: name.into(),
+			index,
+			call_type: call_type
+				.and_then(|id| self.registry.resolve(id))
+				.map(|ty| ty.path.segments.join("::")),
+			calls,
+		}
+	}
+
+	/// Describe type with given identifier.
+	fn describe(&self, id: u32) -> TypeInfo {
+		TypeInfo { name: self.name(id), layout: self.layout(id, &mut BTreeSet::new()) }
+	}
+
+	fn named_fields(&self, fields: &[Field<PortableForm>]) -> Vec<ArgInfo> {
+		fields
+			.iter()
+			.enumerate()
+			.map(|(index, field)| ArgInfo {
+				name: field.name.clone().unwrap_or_else(|| index.to_string()),
+				ty: self.describe(field.ty.id),
+			})
+			.collect()
+	}
+
+	/// Returns short name of the type (last path segment with generic arguments).
+	fn name(&self, id: u32) -> String {
+		let Some(ty) = self.registry.resolve(id) else { return format!("<unknown type {id}>") };
+		match ty.type_def {
+			TypeDef::Composite(_) | TypeDef::Variant(_) => {
+				let name =
+					ty.path.segments.last().cloned().unwrap_or_else(|| format!("<type {id}>"));
+				let params = ty
+					.type_params
+					.iter()
+					.filter_map(|param| param.ty.map(|param| self.name(param.id)))
+					.collect::<Vec<_>>();
+				if params.is_empty() {
+					name
+				} else {
+					format!("{name}<{}>", params.join(", "))
+				}
+			},
+			TypeDef::Sequence(ref seq) => format!("Vec<{}>", self.name(seq.type_param.id)),
+			TypeDef::Array(ref array) =>
+				format!("[{}; {}]", self.name(array.type_param.id), array.len),
+			TypeDef::Tuple(ref tuple) => format!(
+				"({})",
+				tuple
+					.fields
+					.iter()
+					.map(|field| self.name(field.id))
+					.collect::<Vec<_>>()
+					.join(", ")
+			),
+			TypeDef::Primitive(ref primitive) => primitive_name(primitive).into(),
+			TypeDef::Compact(ref compact) =>
+				format!("Compact<{}>", self.name(compact.type_param.id)),
+			TypeDef::BitSequence(_) => "BitVec".into(),
+		}
+	}
+
+	/// Returns layout of the type. Types that are currently being expanded (recursive types) and
+	/// the `RuntimeCall` are represented by their names.
+	fn layout(&self, id: u32, expanding: &mut BTreeSet<u32>) -> String {
+		let Some(ty) = self.registry.resolve(id) else { return format!("<unknown type {id}>") };
+		let is_runtime_call =
+			ty.path.segments.last().map(String::as_str) == Some(RUNTIME_CALL_TYPE_NAME);
+		if is_runtime_call || !expanding.insert(id) {
+			return self.name(id)
+		}
+
+		let layout = match ty.type_def {
+			TypeDef::Composite(ref composite) => self.fields_layout(&composite.fields, expanding),
+			TypeDef::Variant(ref variant) => format!(
+				"{{{}}}",
+				variant
+					.variants
+					.iter()
+					.map(|v| format!(
+						"#{} {}{}",
+						v.index,
+						v.name,
+						self.fields_layout(&v.fields, expanding)
+					))
+					.collect::<Vec<_>>()
+					.join(" | ")
+			),
+			TypeDef::Sequence(ref seq) =>
+				format!("Vec<{}>", self.layout(seq.type_param.id, expanding)),
+			TypeDef::Array(ref array) =>
+				format!("[{}; {}]", self.layout(array.type_param.id, expanding), array.len),
+			TypeDef::Tuple(ref tuple) => format!(
+				"({})",
+				tuple
+					.fields
+					.iter()
+					.map(|field| self.layout(field.id, expanding))
+					.collect::<Vec<_>>()
+					.join(", ")
+			),
+			TypeDef::Primitive(ref primitive) => primitive_name(primitive).into(),
+			TypeDef::Compact(ref compact) =>
+				format!("Compact<{}>", self.layout(compact.type_param.id, expanding)),
+			TypeDef::BitSequence(ref bits) => format!(
+				"BitVec<{}, {}>",
+				self.layout(bits.bit_store_type.id, expanding),
+				self.name(bits.bit_order_type.id)
+			),
+		};
+
+		expanding.remove(&id);
+		layout
+	}
+
+	fn fields_layout(
+		&self,
+		fields: &[Field<PortableForm>],
+		expanding: &mut BTreeSet<u32>,
+	) -> String {
+		if fields.is_empty() {
+			return String::new()
+		}
+
+		let is_named = fields.iter().all(|field| field.name.is_some());
+		let fields = fields
+			.iter()
+			.map(|field| match field.name {
+				Some(ref name) if is_named =>
+					format!("{name}: {}", self.layout(field.ty.id, expanding)),
+				_ => self.layout(field.ty.id, expanding),
+			})
+			.collect::<Vec<_>>()
+			.join(", ");
+		if is_named {
+			format!("{{{fields}}}")
+		} else {
+			format!("({fields})")
+		}
+	}
+}
+
+fn primitive_name(primitive: &TypeDefPrimitive) -> &'static str {
+	match primitive {
+		TypeDefPrimitive::Bool => "bool",
+		TypeDefPrimitive::Char => "char",
+		TypeDefPrimitive::Str => "String",
+		TypeDefPrimitive::U8 => "u8",
+		TypeDefPrimitive::U16 => "u16",
+		TypeDefPrimitive::U32 => "u32",
+		TypeDefPrimitive::U64 => "u64",
+		TypeDefPrimitive::U128 => "u128",
+		TypeDefPrimitive::U256 => "u256",
+		TypeDefPrimitive::I8 => "i8",
+		TypeDefPrimitive::I16 => "i16",
+		TypeDefPrimitive::I32 => "i32",
+		TypeDefPrimitive::I64 => "i64",
+		TypeDefPrimitive::I128 => "i128",
+		TypeDefPrimitive::I256 => "i256",
+	}
+}
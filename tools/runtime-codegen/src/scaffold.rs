@@ -0,0 +1,908 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Scaffolding of the new relay client crate.
+//!
+//! Everything that may be derived from the runtime metadata (runtime version, extrinsic limits,
+//! pallets, transaction extensions) is derived from it. Names of bridge pallets and runtime APIs
+//! are following conventions of existing bridges, so they must be reviewed before the client is
+//! used in production.
+
+use crate::{
+	codegen, metadata::RuntimeMetadataSource, runtime_api::to_snake_case,
+	runtime_info::RuntimeInfo, transaction_extension::known_extension,
+};
+
+use codec::{Compact, Decode};
+use color_eyre::eyre::{self, WrapErr};
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use scale_info::{PortableRegistry, TypeDef};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	fmt::Write,
+	path::{Path, PathBuf},
+};
+
+/// Crates that may be referenced by the generated runtime code.
+const CODEGEN_DEPENDENCIES: &[&str] = &[
+	"bp_header_chain",
+	"bp_messages",
+	"bp_polkadot_core",
+	"bp_runtime",
+	"sp_consensus_grandpa",
+	"sp_core",
+	"sp_runtime",
+	"sp_session",
+	"sp_weights",
+];
+/// Maximal size of the parachain header. The same limit is used by bridge hubs.
+const MAX_PARACHAIN_HEADER_SIZE: u32 = 4_096;
+/// Maximal number of unrewarded relayers in the messages confirmation transaction. The same
+/// limit is used by bridge hubs.
+const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: u64 = 1024;
+/// Maximal number of unconfirmed messages in the messages confirmation transaction. The same
+/// limit is used by bridge hubs.
+const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: u64 = 4096;
+
+/// Generate the relay client crate for the new chain.
+#[derive(Debug, clap::Args)]
+pub struct ScaffoldParams {
+	/// Name of the chain (e.g. `BridgeHubPaseo`). It is used as the name of the client chain type
+	/// and in names of bridge pallets and runtime APIs.
+	#[clap(long, value_parser)]
+	name: String,
+	/// Runtime metadata of the chain: node URL, path to the runtime wasm blob or to the `.scale`
+	/// metadata file.
+	#[clap(long, value_parser)]
+	metadata: RuntimeMetadataSource,
+	/// Parachain id. Required if the chain is a parachain.
+	#[clap(long, value_parser)]
+	para_id: Option<u32>,
+	/// Directory of the generated crate. Defaults to the `relay-clients/client-<name>` of the
+	/// repository.
+	#[clap(long, value_parser)]
+	output: Option<PathBuf>,
+}
+
+/// Chain information, derived from its runtime metadata.
+#[derive(Debug, Default)]
+struct ChainInfo {
+	/// Chain name in `UpperCamelCase`.
+	name: String,
+	/// Path of the `RuntimeCall` within the `runtime_types` module.
+	runtime_call: String,
+	/// Runtime `spec_version`.
+	spec_version: u32,
+	/// Runtime `transaction_version`.
+	transaction_version: u32,
+	/// Runtime state version.
+	state_version: u8,
+	/// Maximal size of the normal extrinsic.
+	max_extrinsic_size: u32,
+	/// Maximal `ref_time` and `proof_size` of the normal extrinsic.
+	max_extrinsic_weight: Option<(u64, u64)>,
+	/// Average block interval in milliseconds.
+	block_interval_ms: u64,
+	/// Parachain id, if the chain is a parachain.
+	para_id: Option<u32>,
+	/// Maximal number of GRANDPA authorities, if the chain has the GRANDPA pallet.
+	grandpa_max_authorities: Option<u32>,
+	/// True if the chain has the `pallet-balances`.
+	has_balances: bool,
+	/// True if the chain has the `pallet-utility` with the `batch_all` call.
+	has_utility: bool,
+	/// True if the chain has the `pallet-sudo`.
+	has_sudo: bool,
+	/// True if the chain has bridge messages pallets.
+	has_messages: bool,
+	/// True if the chain has the `CheckMetadataHash` transaction extension.
+	has_metadata_hash: bool,
+}
+
+/// Run the `scaffold` subcommand.
+pub fn run(params: ScaffoldParams) -> color_eyre::Result<()> {
+	let kebab_name = to_snake_case(&params.name).replace('_', "-");
+	let output = params.output.unwrap_or_else(|| {
+		Path::new(env!("CARGO_MANIFEST_DIR"))
+			.join("../../relay-clients")
+			.join(format!("client-{kebab_name}"))
+	});
+	if output.exists() {
+		return Err(eyre::eyre!("{} already exists", output.display()))
+	}
+
+	let raw_metadata = params.metadata.fetch()?;
+	let info = ChainInfo::from_metadata(&params.name, params.para_id, &raw_metadata)?;
+	let codegen_runtime = codegen::generate_runtime(
+		&raw_metadata,
+		None,
+		&format!("runtime-codegen {}", params.metadata),
+	)?;
+
+	let src = output.join("src");
+	std::fs::create_dir_all(&src).wrap_err_with(|| format!("Error creating {}", src.display()))?;
+	for (path, contents) in [
+		(output.join("Cargo.toml"), info.cargo_toml(&codegen_runtime)),
+		(src.join("codegen_runtime.rs"), codegen_runtime),
+		(src.join("primitives.rs"), info.primitives_rs()),
+		(src.join("lib.rs"), info.lib_rs()),
+	] {
+		std::fs::write(&path, contents)
+			.wrap_err_with(|| format!("Error writing {}", path.display()))?;
+		if path.extension().is_some_and(|extension| extension == "rs") {
+			crate::rustfmt(&path);
+		}
+	}
+
+	eprintln!(
+		"Generated relay-{kebab_name}-client at {}. Add it to the workspace members and to the \
+		runtimes manifest:\n\n[[client]]\nname = \"relay-{kebab_name}-client\"\n{}\n\
+		output = \"{}\"\n\nNames of bridge pallets and runtime APIs in the `primitives.rs` are \
+		following conventions of existing bridges. Review them before using the client.",
+		output.display(),
+		manifest_source(&params.metadata),
+		src.join("codegen_runtime.rs").display(),
+	);
+
+	Ok(())
+}
+
+impl ChainInfo {
+	/// Derive chain information from the raw (SCALE-encoded `RuntimeMetadataPrefixed`) metadata.
+	fn from_metadata(
+		name: &str,
+		para_id: Option<u32>,
+		raw_metadata: &[u8],
+	) -> color_eyre::Result<Self> {
+		let runtime = RuntimeInfo::decode(raw_metadata)?;
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &raw_metadata[..])
+			.map_err(|e| eyre::eyre!("Error decoding metadata: {:?}", e))?;
+		let (registry, constants, extrinsic_types) = match metadata.1 {
+			RuntimeMetadata::V14(ref metadata) => {
+				let extrinsic = metadata
+					.types
+					.resolve(metadata.extrinsic.ty.id)
+					.ok_or_else(|| eyre::eyre!("Missing extrinsic type in the metadata"))?;
+				let param = |name: &str| {
+					extrinsic
+						.type_params
+						.iter()
+						.find(|param| param.name == name)
+						.and_then(|param| param.ty)
+						.map(|ty| ty.id)
+						.ok_or_else(|| {
+							eyre::eyre!("Missing {name} parameter of the extrinsic type")
+						})
+				};
+				let constants = metadata
+					.pallets
+					.iter()
+					.flat_map(|pallet| {
+						pallet.constants.iter().map(|constant| {
+							(format!("{}::{}", pallet.name, constant.name), constant.value.clone())
+						})
+					})
+					.collect::<BTreeMap<_, _>>();
+				(
+					&metadata.types,
+					constants,
+					[param("Address")?, param("Call")?, param("Signature")?],
+				)
+			},
+			RuntimeMetadata::V15(ref metadata) => {
+				let constants = metadata
+					.pallets
+					.iter()
+					.flat_map(|pallet| {
+						pallet.constants.iter().map(|constant| {
+							(format!("{}::{}", pallet.name, constant.name), constant.value.clone())
+						})
+					})
+					.collect::<BTreeMap<_, _>>();
+				let extrinsic = &metadata.extrinsic;
+				(
+					&metadata.types,
+					constants,
+					[extrinsic.address_ty.id, extrinsic.call_ty.id, extrinsic.signature_ty.id],
+				)
+			},
+			_ => return Err(eyre::eyre!("Unsupported metadata version: V{}", metadata.1.version())),
+		};
+
+		let [address, call, signature] = extrinsic_types;
+		let path = |id: u32| {
+			registry.resolve(id).map(|ty| ty.path.segments.join("::")).unwrap_or_default()
+		};
+		if path(address) != "sp_runtime::multiaddress::MultiAddress" ||
+			!is_unit_param(registry, address, 1) ||
+			path(signature) != "sp_runtime::MultiSignature"
+		{
+			return Err(eyre::eyre!(
+				"Only chains with `MultiAddress<AccountId32, ()>` addresses and `MultiSignature` \
+				signatures are supported. Got {} and {}",
+				path(address),
+				path(signature),
+			))
+		}
+
+		let unknown_extensions = runtime
+			.transaction_extensions
+			.iter()
+			.filter_map(|ext| known_extension(ext).err())
+			.map(|ext| ext.identifier.as_str())
+			.collect::<Vec<_>>();
+		if !unknown_extensions.is_empty() {
+			return Err(eyre::eyre!(
+				"Unknown transaction extensions: {}. Add them to the known extensions of the tool",
+				unknown_extensions.join(", ")
+			))
+		}
+
+		let pallet = |name: &str| runtime.pallets.iter().find(|pallet| pallet.name == name);
+		let is_parachain = pallet("ParachainSystem").is_some();
+		match (is_parachain, para_id) {
+			(true, None) =>
+				return Err(eyre::eyre!("{name} is a parachain. Please specify its `--para-id`")),
+			(false, Some(_)) => return Err(eyre::eyre!("{name} is not a parachain")),
+			_ => (),
+		}
+
+		let (_, _, _, spec_version, _, _, transaction_version) =
+			decode_constant::<(String, String, u32, u32, u32, Vec<([u8; 8], u32)>, u32)>(
+				&constants,
+				"System::Version",
+			)?
+			.ok_or_else(|| eyre::eyre!("Missing System::Version constant in the metadata"))?;
+		// `state_version` has been added to the end of the `RuntimeVersion` later
+		let state_version = constants
+			.get("System::Version")
+			.and_then(|version| version.last().copied())
+			.filter(|state_version| *state_version <= 1)
+			.unwrap_or_default();
+		let (max_extrinsic_size, _, _) =
+			decode_constant::<(u32, u32, u32)>(&constants, "System::BlockLength")?.ok_or_else(
+				|| eyre::eyre!("Missing System::BlockLength constant in the metadata"),
+			)?;
+		type Weight = (Compact<u64>, Compact<u64>);
+		let (_, _, (_, max_extrinsic_weight, _, _)) = decode_constant::<(
+			Weight,
+			Weight,
+			(Weight, Option<Weight>, Option<Weight>, Option<Weight>),
+		)>(&constants, "System::BlockWeights")?
+		.ok_or_else(|| eyre::eyre!("Missing System::BlockWeights constant in the metadata"))?;
+		let block_interval_ms = match (
+			decode_constant::<u64>(&constants, "Aura::SlotDuration")?,
+			decode_constant::<u64>(&constants, "Babe::ExpectedBlockTime")?,
+			decode_constant::<u64>(&constants, "Timestamp::MinimumPeriod")?,
+		) {
+			(Some(slot_duration), _, _) => slot_duration,
+			(None, Some(expected_block_time), _) => expected_block_time,
+			(None, None, Some(minimum_period)) if minimum_period != 0 => minimum_period * 2,
+			_ => return Err(eyre::eyre!("Unable to find the block interval in the metadata")),
+		};
+
+		Ok(Self {
+			name: name.into(),
+			runtime_call: path(call),
+			spec_version,
+			transaction_version,
+			state_version,
+			max_extrinsic_size,
+			max_extrinsic_weight: max_extrinsic_weight
+				.map(|(ref_time, proof_size)| (ref_time.0, proof_size.0)),
+			block_interval_ms,
+			para_id,
+			grandpa_max_authorities: match pallet("Grandpa") {
+				Some(_) => Some(
+					decode_constant::<u32>(&constants, "Grandpa::MaxAuthorities")?.ok_or_else(
+						|| eyre::eyre!("Missing Grandpa::MaxAuthorities constant in the metadata"),
+					)?,
+				),
+				None => None,
+			},
+			has_balances: pallet("Balances").is_some(),
+			has_utility: pallet("Utility")
+				.is_some_and(|pallet| pallet.calls.iter().any(|call| call.name == "batch_all")),
+			has_sudo: pallet("Sudo").is_some(),
+			has_messages: runtime.pallets.iter().any(|pallet| {
+				pallet.name.starts_with("Bridge") && pallet.name.ends_with("Messages")
+			}),
+			has_metadata_hash: runtime
+				.transaction_extensions
+				.iter()
+				.any(|ext| ext.identifier == "CheckMetadataHash"),
+		})
+	}
+
+	/// Returns chain name in the `SCREAMING_SNAKE_CASE`.
+	fn upper_name(&self) -> String {
+		to_snake_case(&self.name).to_uppercase()
+	}
+
+	/// Returns the chain name without the `BridgeHub` or `AssetHub` prefix. It is used in names of
+	/// bridge pallets, deployed at bridged chains (e.g. `BridgeKusamaMessages` for the
+	/// `BridgeHubKusama`).
+	fn short_name(&self) -> &str {
+		["BridgeHub", "AssetHub"]
+			.iter()
+			.find_map(|prefix| self.name.strip_prefix(prefix))
+			.filter(|short_name| !short_name.is_empty())
+			.unwrap_or(&self.name)
+	}
+
+	/// Returns four-bytes chain identifier: initials of all words of the chain name, followed by
+	/// remaining letters of the last word (e.g. `bhpa` for the `BridgeHubPaseo`).
+	fn chain_id(&self) -> String {
+		let snake_name = to_snake_case(&self.name);
+		let words = snake_name.split('_').filter(|word| !word.is_empty()).collect::<Vec<_>>();
+		let mut chain_id = words.iter().filter_map(|word| word.chars().next()).collect::<String>();
+		chain_id.extend(words.last().into_iter().flat_map(|word| word.chars().skip(1)));
+		chain_id
+			.chars()
+			.filter(char::is_ascii)
+			.chain(std::iter::repeat('_'))
+			.take(4)
+			.collect()
+	}
+
+	/// Generate `Cargo.toml` of the client crate.
+	fn cargo_toml(&self, codegen_runtime: &str) -> String {
+		let mut crates = BTreeSet::from([
+			"bp_polkadot_core",
+			"bp_runtime",
+			"sp_core",
+			"sp_runtime",
+			"sp_weights",
+		]);
+		if self.grandpa_max_authorities.is_some() {
+			crates.extend(["bp_header_chain", "sp_session"]);
+		}
+		if self.has_messages {
+			crates.insert("bp_messages");
+		}
+		crates.extend(
+			CODEGEN_DEPENDENCIES
+				.iter()
+				.filter(|krate| codegen_runtime.contains(&format!("::{krate}::"))),
+		);
+		let dependencies = |prefix: &str| {
+			crates
+				.iter()
+				.filter(|krate| krate.starts_with(prefix))
+				.map(|krate| format!("{} = {{ workspace = true }}\n", krate.replace('_', "-")))
+				.collect::<String>()
+		};
+
+		indoc::formatdoc!(
+			r#"
+			[package]
+			name = "relay-{kebab_name}-client"
+			version = "0.1.0"
+			authors.workspace = true
+			edition.workspace = true
+			license = "GPL-3.0-or-later WITH Classpath-exception-2.0"
+			repository.workspace = true
+
+			[lints]
+			workspace = true
+
+			[dependencies]
+			codec = {{ workspace = true }}
+			scale-info = {{ workspace = true }}
+			subxt = {{ workspace = true }}
+
+			# Bridge dependencies
+
+			{bridge_dependencies}
+			relay-substrate-client = {{ workspace = true }}
+
+			# Substrate Dependencies
+
+			{substrate_dependencies}"#,
+			kebab_name = to_snake_case(&self.name).replace('_', "-"),
+			bridge_dependencies = dependencies("bp_"),
+			substrate_dependencies = dependencies("sp_"),
+		)
+	}
+
+	/// Generate `primitives.rs` of the client crate.
+	fn primitives_rs(&self) -> String {
+		let name = &self.name;
+		let upper_name = self.upper_name();
+		let short_name = self.short_name();
+		let mut imports = vec!["use bp_runtime::{Chain, ChainId};".to_string()];
+		let mut impls = Vec::new();
+		let mut constants = String::new();
+
+		let max_extrinsic_weight = match self.max_extrinsic_weight {
+			Some((ref_time, proof_size)) => format!("Weight::from_parts({ref_time}, {proof_size})"),
+			None => "Weight::MAX".into(),
+		};
+		writeln!(
+			constants,
+			"/// Maximal size of the normal extrinsic (the `System::BlockLength` runtime constant).
+			pub const MAX_EXTRINSIC_SIZE: u32 = {};
+			/// Maximal weight of the normal extrinsic (the `System::BlockWeights` runtime \
+			constant).
+			pub const MAX_EXTRINSIC_WEIGHT: Weight = {max_extrinsic_weight};",
+			self.max_extrinsic_size,
+		)
+		.expect("writing to string never fails");
+
+		if let Some(para_id) = self.para_id {
+			imports[0] = "use bp_runtime::{Chain, ChainId, Parachain};".into();
+			impls.push(format!(
+				"impl Parachain for {name} {{
+					const PARACHAIN_ID: u32 = {upper_name}_PARACHAIN_ID;
+					const MAX_HEADER_SIZE: u32 = MAX_{upper_name}_HEADER_SIZE;
+				}}"
+			));
+			writeln!(
+				constants,
+				"/// Identifier of {name} in the relay chain.
+				pub const {upper_name}_PARACHAIN_ID: u32 = {para_id};
+				/// Maximal size of the {name} header.
+				pub const MAX_{upper_name}_HEADER_SIZE: u32 = {MAX_PARACHAIN_HEADER_SIZE};"
+			)
+			.expect("writing to string never fails");
+		}
+
+		if let Some(max_authorities) = self.grandpa_max_authorities {
+			imports.push("use bp_header_chain::ChainWithGrandpa;".into());
+			impls.push(format!(
+				"impl ChainWithGrandpa for {name} {{
+					const WITH_CHAIN_GRANDPA_PALLET_NAME: &'static str = \
+					WITH_{upper_name}_GRANDPA_PALLET_NAME;
+					const MAX_AUTHORITIES_COUNT: u32 = MAX_AUTHORITIES_COUNT;
+					const REASONABLE_HEADERS_IN_JUSTIFICATION_ANCESTRY: u32 =
+						REASONABLE_HEADERS_IN_JUSTIFICATION_ANCESTRY;
+					const MAX_MANDATORY_HEADER_SIZE: u32 = MAX_MANDATORY_HEADER_SIZE;
+					const AVERAGE_HEADER_SIZE: u32 = AVERAGE_HEADER_SIZE;
+				}}"
+			));
+			writeln!(
+				constants,
+				"/// Maximal number of GRANDPA authorities (the `Grandpa::MaxAuthorities` runtime \
+				constant).
+				pub const MAX_AUTHORITIES_COUNT: u32 = {max_authorities};
+				/// Name of the With-{name} GRANDPA pallet instance that is deployed at bridged \
+				chains.
+				pub const WITH_{upper_name}_GRANDPA_PALLET_NAME: &str = \"Bridge{short_name}Grandpa\";"
+			)
+			.expect("writing to string never fails");
+		}
+
+		if self.has_messages {
+			imports.push("use bp_messages::{ChainWithMessages, MessageNonce};".into());
+			impls.push(format!(
+				"impl ChainWithMessages for {name} {{
+					const WITH_CHAIN_MESSAGES_PALLET_NAME: &'static str =
+						WITH_{upper_name}_MESSAGES_PALLET_NAME;
+					const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: MessageNonce =
+						MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX;
+					const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce =
+						MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX;
+				}}"
+			));
+			writeln!(
+				constants,
+				"/// Name of the With-{name} messages pallet instance that is deployed at bridged \
+				chains.
+				pub const WITH_{upper_name}_MESSAGES_PALLET_NAME: &str = \
+				\"Bridge{short_name}Messages\";
+				/// Maximal number of unrewarded relayers in the messages confirmation transaction.
+				pub const MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX: MessageNonce = \
+				{MAX_UNREWARDED_RELAYERS_IN_CONFIRMATION_TX};
+				/// Maximal number of unconfirmed messages in the messages confirmation transaction.
+				pub const MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX: MessageNonce = \
+				{MAX_UNCONFIRMED_MESSAGES_IN_CONFIRMATION_TX};"
+			)
+			.expect("writing to string never fails");
+		}
+
+		indoc::formatdoc!(
+			"{header}
+			//! Primitives of the {name} chain, derived from its runtime metadata.
+			//!
+			//! They may be moved to the `chains/chain-{kebab_name}` crate, if they are needed by
+			//! other crates.
+
+			pub use bp_polkadot_core::*;
+
+			{imports}
+			use sp_runtime::StateVersion;
+			use sp_weights::Weight;
+
+			/// {name} chain.
+			#[derive(Debug)]
+			pub struct {name};
+
+			impl Chain for {name} {{
+				const ID: ChainId = *b\"{chain_id}\";
+
+				type BlockNumber = BlockNumber;
+				type Hash = Hash;
+				type Hasher = Hasher;
+				type Header = Header;
+
+				type AccountId = AccountId;
+				type Balance = Balance;
+				type Nonce = Nonce;
+				type Signature = Signature;
+
+				const STATE_VERSION: StateVersion = StateVersion::V{state_version};
+
+				fn max_extrinsic_size() -> u32 {{
+					MAX_EXTRINSIC_SIZE
+				}}
+
+				fn max_extrinsic_weight() -> Weight {{
+					MAX_EXTRINSIC_WEIGHT
+				}}
+			}}
+
+			{impls}
+			{constants}
+			{methods}",
+			header = FILE_HEADER,
+			kebab_name = to_snake_case(name).replace('_', "-"),
+			imports = imports.join("\n"),
+			impls = impls.join("\n\n"),
+			chain_id = self.chain_id(),
+			state_version = self.state_version,
+			methods = self.runtime_api_methods(),
+		)
+	}
+
+	/// Returns constants with names of bridge runtime API methods of the chain. They are declared
+	/// by the `decl_bridge_finality_runtime_apis` and `decl_bridge_messages_runtime_apis` macros.
+	fn runtime_api_methods(&self) -> String {
+		let name = &self.name;
+		let upper_name = self.upper_name();
+		let mut methods = vec![
+			(
+				format!("BEST_FINALIZED_{upper_name}_HEADER_METHOD"),
+				format!("{name}FinalityApi_best_finalized"),
+			),
+			(
+				format!("FREE_HEADERS_INTERVAL_FOR_{upper_name}_METHOD"),
+				format!("{name}FinalityApi_free_headers_interval"),
+			),
+		];
+		if self.grandpa_max_authorities.is_some() {
+			methods.push((
+				format!("{upper_name}_SYNCED_HEADERS_GRANDPA_INFO_METHOD"),
+				format!("{name}FinalityApi_synced_headers_grandpa_info"),
+			));
+		}
+		if self.has_messages {
+			methods.push((
+				format!("TO_{upper_name}_MESSAGE_DETAILS_METHOD"),
+				format!("To{name}OutboundLaneApi_message_details"),
+			));
+			methods.push((
+				format!("FROM_{upper_name}_MESSAGE_DETAILS_METHOD"),
+				format!("From{name}InboundLaneApi_message_details"),
+			));
+		}
+
+		methods
+			.into_iter()
+			.map(|(constant, method)| {
+				let (api, method_name) = method.split_once('_').expect("all methods have '_'");
+				format!(
+					"/// Name of the `{api}::{method_name}` runtime method.\n\
+					pub const {constant}: &str = \"{method}\";\n"
+				)
+			})
+			.collect()
+	}
+
+	/// Generate `lib.rs` of the client crate.
+	fn lib_rs(&self) -> String {
+		let name = &self.name;
+		let upper_name = self.upper_name();
+		let mut client_traits = BTreeSet::from([
+			"Chain",
+			"ChainWithRuntimeVersion",
+			"ChainWithTransactions",
+			"Error as SubstrateError",
+			"SignParam",
+			"SimpleRuntimeVersion",
+			"UnderlyingChainProvider",
+			"UnsignedTransaction",
+		]);
+		let mut calls = String::new();
+		let mut impls = Vec::new();
+
+		if self.has_utility {
+			client_traits.extend([
+				"calls::UtilityCall as MockUtilityCall",
+				"ChainWithUtilityPallet",
+				"MockedRuntimeUtilityPallet",
+			]);
+			calls.push_str("pub type UtilityCall = runtime_types::pallet_utility::pallet::Call;\n");
+		}
+		if self.has_sudo {
+			calls.push_str("pub type SudoCall = runtime_types::pallet_sudo::pallet::Call;\n");
+		}
+		if self.grandpa_max_authorities.is_some() {
+			client_traits.insert("ChainWithGrandpa");
+			calls.push_str("pub type GrandpaCall = runtime_types::pallet_grandpa::pallet::Call;\n");
+			impls.push(format!(
+				"impl ChainWithGrandpa for {name} {{
+					const SYNCED_HEADERS_GRANDPA_INFO_METHOD: &'static str =
+						primitives::{upper_name}_SYNCED_HEADERS_GRANDPA_INFO_METHOD;
+
+					type KeyOwnerProof = sp_session::MembershipProof;
+				}}"
+			));
+		}
+		if self.has_balances {
+			client_traits.insert("ChainWithBalances");
+			impls.push(format!(
+				"impl ChainWithBalances for {name} {{
+					fn account_info_storage_key(account_id: &Self::AccountId) -> StorageKey {{
+						primitives::AccountInfoStorageMapKeyProvider::final_key(account_id)
+					}}
+				}}"
+			));
+		}
+		if self.has_utility {
+			impls.push(format!(
+				"impl From<MockUtilityCall<RuntimeCall>> for RuntimeCall {{
+					fn from(value: MockUtilityCall<RuntimeCall>) -> RuntimeCall {{
+						match value {{
+							MockUtilityCall::batch_all(calls) =>
+								RuntimeCall::Utility(UtilityCall::batch_all {{ calls }}),
+						}}
+					}}
+				}}
+
+				impl ChainWithUtilityPallet for {name} {{
+					type UtilityPallet = MockedRuntimeUtilityPallet<RuntimeCall>;
+				}}"
+			));
+		}
+		if self.has_messages {
+			client_traits.insert("ChainWithMessages");
+			calls.push_str(
+				"pub type BridgeMessagesCall = runtime_types::pallet_bridge_messages::pallet::Call;\n",
+			);
+			impls.push(format!(
+				"impl ChainWithMessages for {name} {{
+					const TO_CHAIN_MESSAGE_DETAILS_METHOD: &'static str =
+						primitives::TO_{upper_name}_MESSAGE_DETAILS_METHOD;
+					const FROM_CHAIN_MESSAGE_DETAILS_METHOD: &'static str =
+						primitives::FROM_{upper_name}_MESSAGE_DETAILS_METHOD;
+				}}"
+			));
+		}
+
+		// the metadata hash check is disabled, until the metadata hash is computed by the tool
+		let metadata_hash = if self.has_metadata_hash { "None," } else { "" };
+		let storage_key_import = if self.has_balances {
+			"use sp_core::{storage::StorageKey, Pair};"
+		} else {
+			"use sp_core::Pair;"
+		};
+
+		indoc::formatdoc!(
+			"{header}
+			//! Types used to connect to the {name} chain.
+
+			pub mod codegen_runtime;
+			pub mod primitives;
+
+			use codec::Encode;
+			use relay_substrate_client::{{{client_traits}}};
+			{storage_key_import}
+			use sp_runtime::{{
+				generic::SignedPayload,
+				traits::{{FakeDispatchable, IdentifyAccount}},
+				OpaqueExtrinsic,
+			}};
+			use std::time::Duration;
+
+			pub use codegen_runtime::api::runtime_types;
+
+			pub type RuntimeCall = runtime_types::{runtime_call};
+			{calls}
+			type UncheckedExtrinsic =
+				primitives::UncheckedExtrinsic<RuntimeCall, codegen_runtime::TransactionExtension>;
+
+			/// {name} chain definition.
+			#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+			pub struct {name};
+
+			impl UnderlyingChainProvider for {name} {{
+				type Chain = primitives::{name};
+			}}
+
+			impl Chain for {name} {{
+				const NAME: &'static str = \"{name}\";
+				const BEST_FINALIZED_HEADER_ID_METHOD: &'static str =
+					primitives::BEST_FINALIZED_{upper_name}_HEADER_METHOD;
+				const FREE_HEADERS_INTERVAL_METHOD: &'static str =
+					primitives::FREE_HEADERS_INTERVAL_FOR_{upper_name}_METHOD;
+				const AVERAGE_BLOCK_INTERVAL: Duration = Duration::from_millis({block_interval_ms});
+
+				type SignedBlock = primitives::SignedBlock;
+				type Call = RuntimeCall;
+			}}
+
+			{impls}
+			impl ChainWithTransactions for {name} {{
+				type AccountKeyPair = sp_core::sr25519::Pair;
+				type SignedTransaction = OpaqueExtrinsic;
+
+				fn sign_transaction(
+					param: SignParam<Self>,
+					unsigned: UnsignedTransaction<Self>,
+				) -> Result<Self::SignedTransaction, SubstrateError> {{
+					let raw_payload = SignedPayload::new(
+						FakeDispatchable::from(unsigned.call),
+						codegen_runtime::transaction_extension_from_params(
+							param.spec_version,
+							param.transaction_version,
+							unsigned.era,
+							param.genesis_hash,
+							unsigned.nonce,
+							unsigned.tip,
+							{metadata_hash}
+						),
+					)?;
+
+					let signature = raw_payload.using_encoded(|payload| param.signer.sign(payload));
+					let signer: sp_runtime::MultiSigner = param.signer.public().into();
+					let (call, extra, _) = raw_payload.deconstruct();
+
+					let transaction = UncheckedExtrinsic::new_signed(
+						call.deconstruct(),
+						signer.into_account().into(),
+						signature.into(),
+						extra,
+					);
+					OpaqueExtrinsic::from_bytes(&transaction.encode()).map_err(|e| {{
+						SubstrateError::Custom(format!(\"Failed to encode {{}} transaction: {{e:?}}\", Self::NAME))
+					}})
+				}}
+			}}
+
+			impl ChainWithRuntimeVersion for {name} {{
+				const RUNTIME_VERSION: Option<SimpleRuntimeVersion> = Some(RUNTIME_VERSION);
+			}}
+
+			/// Version of the runtime that the `codegen_runtime` has been generated for.
+			const RUNTIME_VERSION: SimpleRuntimeVersion = SimpleRuntimeVersion {{
+				spec_version: {spec_version},
+				transaction_version: {transaction_version},
+			}};
+			",
+			header = FILE_HEADER,
+			client_traits = client_traits.into_iter().collect::<Vec<_>>().join(", "),
+			impls = impls.join("\n\n"),
+			runtime_call = self.runtime_call,
+			block_interval_ms = self.block_interval_ms,
+			spec_version = self.spec_version,
+			transaction_version = self.transaction_version,
+		)
+	}
+}
+
+/// License header of generated files.
+const FILE_HEADER: &str = "// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+";
+
+/// Decode the `Pallet::Constant` runtime constant. Returns `None` if there's no such constant.
+fn decode_constant<T: Decode>(
+	constants: &BTreeMap<String, Vec<u8>>,
+	name: &str,
+) -> color_eyre::Result<Option<T>> {
+	constants
+		.get(name)
+		.map(|value| {
+			T::decode(&mut &value[..])
+				.map_err(|e| eyre::eyre!("Error decoding {name} constant: {:?}", e))
+		})
+		.transpose()
+}
+
+/// Returns true if the type parameter with given `index` is the `()`.
+fn is_unit_param(registry: &PortableRegistry, id: u32, index: usize) -> bool {
+	registry
+		.resolve(id)
+		.and_then(|ty| ty.type_params.get(index))
+		.and_then(|param| param.ty)
+		.and_then(|param| registry.resolve(param.id))
+		.is_some_and(
+			|param| matches!(param.type_def, TypeDef::Tuple(ref tuple) if tuple.fields.is_empty()),
+		)
+}
+
+/// Returns metadata source, formatted as the manifest entry.
+fn manifest_source(metadata_source: &RuntimeMetadataSource) -> String {
+	match metadata_source {
+		RuntimeMetadataSource::NodeUrl(node_url) => format!("node-url = \"{node_url}\""),
+		RuntimeMetadataSource::WasmFile(wasm_file) =>
+			format!("wasm-file = \"{}\"", wasm_file.display()),
+		RuntimeMetadataSource::MetadataFile(metadata_file) =>
+			format!("metadata-file = \"{}\"", metadata_file.display()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn chain_info(name: &str) -> ChainInfo {
+		ChainInfo {
+			name: name.into(),
+			runtime_call: "bridge_hub_paseo_runtime::RuntimeCall".into(),
+			para_id: Some(1002),
+			has_utility: true,
+			has_messages: true,
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn chain_names_are_derived() {
+		let info = chain_info("BridgeHubPaseo");
+		assert_eq!(info.upper_name(), "BRIDGE_HUB_PASEO");
+		assert_eq!(info.short_name(), "Paseo");
+		assert_eq!(info.chain_id(), "bhpa");
+
+		let info = chain_info("Paseo");
+		assert_eq!(info.short_name(), "Paseo");
+		assert_eq!(info.chain_id(), "pase");
+		assert_eq!(chain_info("Ab").chain_id(), "ab__");
+	}
+
+	#[test]
+	fn optional_traits_are_implemented() {
+		let info = chain_info("BridgeHubPaseo");
+		let lib_rs = info.lib_rs();
+		assert!(lib_rs.contains("impl ChainWithUtilityPallet for BridgeHubPaseo"));
+		assert!(lib_rs.contains("impl ChainWithMessages for BridgeHubPaseo"));
+		assert!(!lib_rs.contains("impl ChainWithGrandpa for BridgeHubPaseo"));
+		assert!(!lib_rs.contains("impl ChainWithBalances for BridgeHubPaseo"));
+
+		let primitives_rs = info.primitives_rs();
+		assert!(primitives_rs.contains("const PARACHAIN_ID: u32 = BRIDGE_HUB_PASEO_PARACHAIN_ID;"));
+		assert!(primitives_rs.contains("\"BridgePaseoMessages\""));
+		assert!(primitives_rs.contains("\"ToBridgeHubPaseoOutboundLaneApi_message_details\""));
+
+		let cargo_toml = info.cargo_toml("::sp_session::MembershipProof");
+		assert!(cargo_toml.contains("bp-messages = { workspace = true }"));
+		assert!(cargo_toml.contains("sp-session = { workspace = true }"));
+		assert!(!cargo_toml.contains("bp-header-chain"));
+	}
+}
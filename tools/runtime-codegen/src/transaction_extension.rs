@@ -0,0 +1,365 @@
+// Copyright 2019-2023 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Generation of the `TransactionExtension` type and its `transaction_extension_from_params`
+//! constructor from the transaction extensions, listed in the runtime metadata.
+
+use crate::runtime_info::{RuntimeInfo, TransactionExtensionInfo};
+
+use color_eyre::eyre;
+use proc_macro2::TokenStream;
+use quote::quote;
+use subxt_codegen::syn;
+
+/// Transaction extension that the generator knows how to construct.
+pub(crate) struct KnownExtension {
+	/// Names of the extension type (without generic arguments) or extension identifiers, used by
+	/// runtimes.
+	type_names: &'static [&'static str],
+	/// Schema (`bp_runtime::extensions::TransactionExtensionSchema`) of the extension.
+	schema: &'static str,
+	/// Extension payload, computed from arguments of the `transaction_extension_from_params`.
+	payload: &'static str,
+	/// Extension implicit data, computed from arguments of the
+	/// `transaction_extension_from_params`.
+	implicit: &'static str,
+}
+
+/// All transaction extensions that the generator knows how to construct.
+const KNOWN_EXTENSIONS: &[KnownExtension] = &[
+	KnownExtension {
+		type_names: &["CheckNonZeroSender"],
+		schema: "::bp_runtime::extensions::CheckNonZeroSender",
+		payload: "()",
+		implicit: "()",
+	},
+	KnownExtension {
+		type_names: &["CheckSpecVersion"],
+		schema: "::bp_runtime::extensions::CheckSpecVersion",
+		payload: "()",
+		implicit: "spec_version",
+	},
+	KnownExtension {
+		type_names: &["CheckTxVersion"],
+		schema: "::bp_runtime::extensions::CheckTxVersion",
+		payload: "()",
+		implicit: "transaction_version",
+	},
+	KnownExtension {
+		type_names: &["CheckGenesis"],
+		schema: "::bp_runtime::extensions::CheckGenesis<::bp_polkadot_core::Hash>",
+		payload: "()",
+		implicit: "genesis_hash",
+	},
+	KnownExtension {
+		type_names: &["CheckMortality"],
+		schema: "::bp_runtime::extensions::CheckEra<::bp_polkadot_core::Hash>",
+		payload: "era.frame_era()",
+		implicit: "era.signed_payload(genesis_hash)",
+	},
+	KnownExtension {
+		type_names: &["CheckNonce"],
+		schema: "::bp_runtime::extensions::CheckNonce<::bp_polkadot_core::Nonce>",
+		payload: "nonce.into()",
+		implicit: "()",
+	},
+	KnownExtension {
+		type_names: &["CheckWeight"],
+		schema: "::bp_runtime::extensions::CheckWeight",
+		payload: "()",
+		implicit: "()",
+	},
+	KnownExtension {
+		type_names: &["ChargeTransactionPayment"],
+		schema: "::bp_runtime::extensions::ChargeTransactionPayment<::bp_polkadot_core::Balance>",
+		payload: "tip.into()",
+		implicit: "()",
+	},
+	// relay always pays fees in the native token, so the asset id is always `None`
+	KnownExtension {
+		type_names: &["ChargeAssetTxPayment"],
+		schema: "::bp_runtime::extensions::GenericTransactionExtensionSchema<\
+			(::codec::Compact<::bp_polkadot_core::Balance>, Option<()>), ()>",
+		payload: "(tip.into(), None)",
+		implicit: "()",
+	},
+	KnownExtension {
+		type_names: &["CheckMetadataHash"],
+		schema: "::bp_runtime::extensions::GenericTransactionExtensionSchema<\
+			api::runtime_types::frame_metadata_hash_extension::Mode, Option<[u8; 32]>>",
+		payload: "if metadata_hash.is_some() { \
+			api::runtime_types::frame_metadata_hash_extension::Mode::Enabled \
+		} else { \
+			api::runtime_types::frame_metadata_hash_extension::Mode::Disabled \
+		}",
+		implicit: "metadata_hash",
+	},
+	KnownExtension {
+		type_names: &["BridgeRejectObsoleteHeadersAndMessages"],
+		schema: "::bp_runtime::extensions::BridgeRejectObsoleteHeadersAndMessages",
+		payload: "()",
+		implicit: "()",
+	},
+	KnownExtension {
+		type_names: &[
+			"BridgeRelayersSignedExtension",
+			"BridgeRelayersTransactionExtension",
+			"RefundBridgedParachainMessages",
+			"RefundSignedExtensionAdapter",
+			"RefundTransactionExtensionAdapter",
+		],
+		schema: "::bp_runtime::extensions::RefundBridgedParachainMessagesSchema",
+		payload: "()",
+		implicit: "()",
+	},
+	KnownExtension {
+		type_names: &["StorageWeightReclaim"],
+		schema: "::bp_runtime::extensions::GenericTransactionExtensionSchema<(), ()>",
+		payload: "()",
+		implicit: "()",
+	},
+	KnownExtension {
+		type_names: &["PrevalidateAttests"],
+		schema: "::bp_runtime::extensions::PrevalidateAttests",
+		payload: "()",
+		implicit: "()",
+	},
+];
+
+/// Number of leading extensions that form the
+/// `bp_polkadot_core::SuffixedCommonTransactionExtension`.
+const COMMON_EXTENSIONS_LEN: usize = 8;
+
+/// Generate transaction extension code from the raw (SCALE-encoded `RuntimeMetadataPrefixed`)
+/// metadata.
+///
+/// The `TRANSACTION_EXTENSIONS` constant is always generated. The `TransactionExtension` type and
+/// the `transaction_extension_from_params` function are only generated if all extensions are
+/// known to the generator - otherwise a warning is printed.
+pub fn generate_transaction_extension(raw_metadata: &[u8]) -> color_eyre::Result<TokenStream> {
+	transaction_extension_code(&RuntimeInfo::decode(raw_metadata)?.transaction_extensions)
+}
+
+/// Generate transaction extension code from the metadata transaction extensions.
+fn transaction_extension_code(
+	extensions: &[TransactionExtensionInfo],
+) -> color_eyre::Result<TokenStream> {
+	let identifiers = extensions.iter().map(|ext| ext.identifier.as_str());
+	let identifiers = quote! {
+		/// Identifiers of the runtime transaction extensions, in the order they are listed in the
+		/// metadata. `TransactionExtension` must use the same order.
+		pub const TRANSACTION_EXTENSIONS: &[&str] = &[#(#identifiers),*];
+	};
+
+	let known = match extensions.iter().map(known_extension).collect::<Result<Vec<_>, _>>() {
+		Ok(known) => known,
+		Err(unknown) => {
+			eprintln!(
+				"Unknown transaction extension {} of type {}. TransactionExtension is not generated",
+				unknown.identifier, unknown.ty.name,
+			);
+			return Ok(identifiers)
+		},
+	};
+
+	let has_metadata_hash = known.iter().any(|ext| ext.implicit == "metadata_hash");
+	let metadata_hash_arg = has_metadata_hash.then(|| quote!(metadata_hash: Option<[u8; 32]>,));
+	let (extension_type, constructor) = if is_suffixed_common(extensions) {
+		let suffix = &known[COMMON_EXTENSIONS_LEN..];
+		let schema = tuple(suffix.iter().map(|ext| ext.schema))?;
+		let payload = tuple(suffix.iter().map(|ext| ext.payload))?;
+		let implicit = tuple(suffix.iter().map(|ext| ext.implicit))?;
+		(
+			quote!(::bp_polkadot_core::SuffixedCommonTransactionExtension<#schema>),
+			quote! {
+				::bp_polkadot_core::SuffixedCommonTransactionExtensionExt::from_params(
+					spec_version,
+					transaction_version,
+					era,
+					genesis_hash,
+					nonce,
+					tip,
+					(#payload, #implicit),
+				)
+			},
+		)
+	} else {
+		let schema = tuple(known.iter().map(|ext| ext.schema))?;
+		let payload = tuple(known.iter().map(|ext| ext.payload))?;
+		let implicit = tuple(known.iter().map(|ext| ext.implicit))?;
+		(
+			quote!(::bp_runtime::extensions::GenericTransactionExtension<#schema>),
+			quote!(::bp_runtime::extensions::GenericTransactionExtension::new(
+				#payload,
+				Some(#implicit),
+			)),
+		)
+	};
+
+	Ok(quote! {
+		#identifiers
+
+		/// Transaction extension of the runtime.
+		pub type TransactionExtension = #extension_type;
+
+		/// Create the `TransactionExtension` from parameters.
+		pub fn transaction_extension_from_params(
+			spec_version: u32,
+			transaction_version: u32,
+			era: ::bp_runtime::TransactionEra<
+				::bp_polkadot_core::BlockNumber,
+				::bp_polkadot_core::Hash,
+			>,
+			genesis_hash: ::bp_polkadot_core::Hash,
+			nonce: ::bp_polkadot_core::Nonce,
+			tip: ::bp_polkadot_core::Balance,
+			#metadata_hash_arg
+		) -> TransactionExtension {
+			#constructor
+		}
+	})
+}
+
+/// Returns known extension, matching given metadata extension.
+///
+/// Extensions are matched by their type name. Wrapping extensions (like the
+/// `StorageWeightReclaim`) are using `()` as their type, so they are matched by identifier.
+pub(crate) fn known_extension(
+	ext: &TransactionExtensionInfo,
+) -> Result<&'static KnownExtension, &TransactionExtensionInfo> {
+	let type_name = ext.ty.name.split('<').next().unwrap_or_default();
+	KNOWN_EXTENSIONS
+		.iter()
+		.find(|known| {
+			known.type_names.contains(&type_name) ||
+				known.type_names.contains(&ext.identifier.as_str())
+		})
+		.ok_or(ext)
+}
+
+/// Returns true if extensions start with extensions of the
+/// `bp_polkadot_core::SuffixedCommonTransactionExtension`.
+fn is_suffixed_common(extensions: &[TransactionExtensionInfo]) -> bool {
+	const COMMON_TYPE_NAMES: [&str; COMMON_EXTENSIONS_LEN] = [
+		"CheckNonZeroSender",
+		"CheckSpecVersion",
+		"CheckTxVersion",
+		"CheckGenesis",
+		"CheckMortality",
+		"CheckNonce",
+		"CheckWeight",
+		"ChargeTransactionPayment",
+	];
+
+	extensions.len() > COMMON_EXTENSIONS_LEN &&
+		extensions
+			.iter()
+			.zip(COMMON_TYPE_NAMES)
+			.all(|(ext, type_name)| ext.ty.name.split('<').next() == Some(type_name))
+}
+
+/// Join items into the tuple. Single item is not wrapped into the tuple.
+fn tuple<'a>(items: impl Iterator<Item = &'a str>) -> color_eyre::Result<TokenStream> {
+	let items = items
+		.map(|item| {
+			syn::parse_str::<TokenStream>(item)
+				.map_err(|e| eyre::eyre!("Invalid transaction extension item {item}: {e:?}"))
+		})
+		.collect::<color_eyre::Result<Vec<_>>>()?;
+	Ok(match items.as_slice() {
+		[item] => item.clone(),
+		items => quote!((#(#items),*)),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::runtime_info::TypeInfo;
+
+	fn extension(identifier: &str, type_name: &str) -> TransactionExtensionInfo {
+		let ty = |name: &str| TypeInfo { name: name.into(), layout: String::new() };
+		TransactionExtensionInfo {
+			identifier: identifier.into(),
+			ty: ty(type_name),
+			implicit: ty("()"),
+		}
+	}
+
+	fn common_extensions() -> Vec<TransactionExtensionInfo> {
+		vec![
+			extension("CheckNonZeroSender", "CheckNonZeroSender"),
+			extension("CheckSpecVersion", "CheckSpecVersion"),
+			extension("CheckTxVersion", "CheckTxVersion"),
+			extension("CheckGenesis", "CheckGenesis"),
+			extension("CheckMortality", "CheckMortality"),
+			extension("CheckNonce", "CheckNonce"),
+			extension("CheckWeight", "CheckWeight"),
+			extension("ChargeTransactionPayment", "ChargeTransactionPayment<Runtime>"),
+		]
+	}
+
+	fn generated_type(extensions: &[TransactionExtensionInfo]) -> String {
+		let code = transaction_extension_code(extensions).unwrap().to_string();
+		let start = code.find("pub type TransactionExtension").unwrap();
+		let end = start + code[start..].find("; #").unwrap();
+		code[start..end].replace(' ', "")
+	}
+
+	#[test]
+	fn suffixed_common_extension_is_generated() {
+		let mut extensions = common_extensions();
+		extensions.push(extension(
+			"BridgeRejectObsoleteHeadersAndMessages",
+			"BridgeRejectObsoleteHeadersAndMessages",
+		));
+		extensions.push(extension("CheckMetadataHash", "CheckMetadataHash<Runtime>"));
+
+		assert_eq!(
+			generated_type(&extensions),
+			"pubtypeTransactionExtension=::bp_polkadot_core::SuffixedCommonTransactionExtension<(\
+			::bp_runtime::extensions::BridgeRejectObsoleteHeadersAndMessages,\
+			::bp_runtime::extensions::GenericTransactionExtensionSchema<\
+			api::runtime_types::frame_metadata_hash_extension::Mode,Option<[u8;32]>>)>",
+		);
+	}
+
+	#[test]
+	fn flat_extension_is_generated_for_custom_payment() {
+		let mut extensions = common_extensions();
+		extensions[7] = extension("ChargeAssetTxPayment", "ChargeAssetTxPayment<Runtime>");
+
+		let generated = generated_type(&extensions);
+		assert!(generated.starts_with(
+			"pubtypeTransactionExtension=::bp_runtime::extensions::GenericTransactionExtension<(\
+			::bp_runtime::extensions::CheckNonZeroSender,"
+		));
+		assert!(generated.ends_with(
+			"GenericTransactionExtensionSchema<(::codec::Compact<::bp_polkadot_core::Balance>,\
+			Option<()>),()>)>"
+		));
+	}
+
+	#[test]
+	fn only_identifiers_are_generated_for_unknown_extensions() {
+		let mut extensions = common_extensions();
+		extensions.push(extension("ValidateSigned", "ValidateSigned"));
+
+		let code = transaction_extension_code(&extensions).unwrap().to_string();
+		assert!(code.contains("\"ValidateSigned\""));
+		assert!(!code.contains("TransactionExtension ="));
+	}
+}